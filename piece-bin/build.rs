@@ -1,11 +1,19 @@
-use piece_lib::load_protos;
+use piece_lib::load_protos_lenient;
 use protobuf::CodedOutputStream;
 
 fn main() {
     println!("cargo:rerun-if-changed=../piece-lib/src/protos");
     println!("cargo:rerun-if-changed=../piece-lib/cards");
 
-    let cards = load_protos().expect("Failed to load cards");
+    let (cards, errors) = load_protos_lenient();
+    for error in errors.iter() {
+        println!("cargo:warning={:#}", error);
+    }
+    // Every card is still reported above, but a malformed card must not ship.
+    if !errors.is_empty() {
+        eprintln!("{} card file(s) failed to load", errors.len());
+        std::process::exit(1);
+    }
 
     if std::path::Path::new("cards_binpb").exists() {
         std::fs::remove_dir_all("cards_binpb").expect("Failed to remove directory");
@@ -0,0 +1,113 @@
+use indexmap::IndexMap;
+use piece_lib::{
+    in_play::{CardId, Database},
+    player::Owner,
+};
+
+/// Scores game states for the AI. Higher scores are better for the player being evaluated.
+/// Evaluators are shared between the threads the AI uses to play out its options.
+pub trait Evaluator: Send + Sync {
+    /// Scores the game from the perspective of `player`.
+    fn evaluate(&self, db: &Database, player: Owner) -> f64;
+
+    /// How much the card is worth having on the battlefield. Used to decide between spells without
+    /// having to play each of them out.
+    fn card_value(&self, db: &Database, card: CardId) -> f64;
+}
+
+/// The default evaluator, weighing life totals, the value of each player's permanents, and the
+/// number of cards in each player's hand.
+#[derive(Debug, Clone, Copy)]
+pub struct Heuristic {
+    pub life: f64,
+    pub board: f64,
+    pub cards: f64,
+}
+
+impl Default for Heuristic {
+    fn default() -> Self {
+        Self {
+            life: 1.0,
+            board: 1.0,
+            cards: 2.0,
+        }
+    }
+}
+
+impl Heuristic {
+    fn player_score(&self, db: &Database, player: Owner) -> f64 {
+        let board = db.battlefield[player]
+            .iter()
+            .map(|card| self.card_value(db, *card))
+            .sum::<f64>();
+
+        self.life * f64::from(db.all_players[player].life_total)
+            + self.board * board
+            + self.cards * db.hand[player].len() as f64
+    }
+}
+
+impl Evaluator for Heuristic {
+    fn evaluate(&self, db: &Database, player: Owner) -> f64 {
+        let opponents = db
+            .all_players
+            .all_players()
+            .into_iter()
+            .filter(|opponent| *opponent != player)
+            .map(|opponent| self.player_score(db, opponent))
+            .sum::<f64>();
+
+        self.player_score(db, player) - opponents
+    }
+
+    fn card_value(&self, db: &Database, card: CardId) -> f64 {
+        if card.is_land(db) {
+            return 1.0;
+        }
+
+        match (card.power(db), card.lethal_damage_remaining(db)) {
+            (Some(power), Some(toughness)) => {
+                f64::from(power.max(0) + toughness.max(0))
+                    + db[card].modified_keywords.values().sum::<u32>() as f64
+            }
+            _ => db[card].modified_cost.cmc() as f64,
+        }
+    }
+}
+
+type Constructor = Box<dyn Fn() -> Box<dyn Evaluator>>;
+
+/// Evaluators which can be selected by name, e.g. from the command line.
+pub struct Evaluators {
+    constructors: IndexMap<String, Constructor>,
+}
+
+impl Default for Evaluators {
+    fn default() -> Self {
+        let mut evaluators = Self {
+            constructors: Default::default(),
+        };
+        evaluators.register("heuristic", || Box::<Heuristic>::default());
+        evaluators
+    }
+}
+
+impl Evaluators {
+    /// Registers an evaluator under `name`, replacing any evaluator already registered with that
+    /// name.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        constructor: impl Fn() -> Box<dyn Evaluator> + 'static,
+    ) {
+        self.constructors.insert(name.into(), Box::new(constructor));
+    }
+
+    pub fn get(&self, name: &str) -> Option<Box<dyn Evaluator>> {
+        self.constructors.get(name).map(|constructor| constructor())
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.constructors.keys().map(String::as_str)
+    }
+}
@@ -1,24 +1,96 @@
+mod evaluator;
+
 use itertools::Itertools;
 
 use piece_lib::{
     battlefield::Battlefields,
-    effects::{PendingEffects, SelectionResult},
-    in_play::Database,
+    combat,
+    deck::{self, DeckStats},
+    effects::{Options, PendingEffects, SelectionResult},
+    in_play::{CardId, Database},
     player::{Owner, Player},
+    stack::Stack,
     turns::{Phase, Turn},
 };
 
+pub use evaluator::{Evaluator, Evaluators, Heuristic};
+use rand::Rng;
+use rayon::prelude::*;
+
+/// The AI keeps any hand once it would be down to this many cards.
+const MIN_HAND_SIZE: usize = 5;
+
+/// The most choices a rollout will make for a single set of pending effects before giving up on
+/// them.
+const MAX_ROLLOUT_CHOICES: usize = 100;
+
+/// The most stack entries a rollout will resolve, in case resolving keeps adding to the stack.
+const MAX_ROLLOUT_RESOLUTIONS: usize = 20;
+
 pub struct AI {
     player: Owner,
+    goldfish: bool,
+    evaluator: Box<dyn Evaluator>,
 }
 
 impl AI {
     pub fn new(player: Owner) -> Self {
-        Self { player }
+        Self {
+            player,
+            goldfish: false,
+            evaluator: Box::<Heuristic>::default(),
+        }
+    }
+
+    /// An opponent that never plays cards, attacks, or makes optional choices.
+    pub fn goldfish(player: Owner) -> Self {
+        Self {
+            player,
+            goldfish: true,
+            evaluator: Box::<Heuristic>::default(),
+        }
+    }
+
+    pub fn with_evaluator(mut self, evaluator: Box<dyn Evaluator>) -> Self {
+        self.evaluator = evaluator;
+        self
+    }
+
+    /// Mulligans the opening hand until [deck::analyze_hand] suggests keeping it, then puts cards
+    /// on the bottom of the library for the mulligans taken.
+    pub fn mulligan(&self, db: &mut Database, stats: &DeckStats, rng: &mut impl Rng) {
+        loop {
+            let hand = db.hand[self.player].iter().copied().collect_vec();
+            let faces = hand.iter().map(|card| card.faceup_face(db)).collect_vec();
+            let mulligans = Player::mulligans(db, self.player);
+            let to_bottom = Player::cards_to_bottom(db, self.player);
+
+            if self.goldfish
+                || hand.len().saturating_sub(to_bottom) <= MIN_HAND_SIZE
+                || deck::analyze_hand(&faces, stats).keep
+            {
+                for idx in deck::choose_bottom(&faces, stats, to_bottom) {
+                    Player::put_on_bottom(db, self.player, hand[idx]);
+                }
+                break;
+            }
+
+            debug!("Taking mulligan {}", mulligans + 1);
+            Player::mulligan(db, self.player, rng);
+        }
     }
 
     pub fn priority(&self, db: &mut Database, pending: &mut PendingEffects) -> PendingEffects {
-        if pending.is_empty() && db.turn.active_player() == self.player {
+        debug!(
+            "Evaluating position: {}",
+            self.evaluator.evaluate(db, self.player)
+        );
+
+        if !self.goldfish
+            && pending.is_empty()
+            && db.turn.active_player() == self.player
+            && !Player::should_auto_pass(db, self.player, &None)
+        {
             if matches!(db.turn.phase, Phase::PreCombatMainPhase)
                 && Player::can_play_land(db, self.player)
             {
@@ -49,21 +121,32 @@ impl AI {
                 let result = pending.resolve(db, None);
                 assert_eq!(result, SelectionResult::Complete);
 
-                if let Some(card) = db.hand[self.player].iter().find(|card| !card.is_land(db)) {
-                    pending.extend(Player::play_card(db, self.player, *card));
+                let candidates = db.hand[self.player]
+                    .iter()
+                    .copied()
+                    .filter(|card| !card.is_land(db) && Player::can_afford(db, self.player, *card))
+                    .collect_vec();
+                if let Some(card) = self.best_by_rollout(db, candidates) {
+                    pending.extend(Player::play_card(db, self.player, card));
                 }
             }
         }
 
         while pending.priority(db) == self.player {
-            let result = if pending.options(db).is_empty() {
+            let result = if pending.options(db).is_empty()
+                || (self.goldfish && !matches!(pending.options(db), Options::MandatoryList(_)))
+            {
                 let result = pending.resolve(db, None);
-                if result == SelectionResult::PendingChoice && pending.options(db).is_empty() {
+                if result == SelectionResult::PendingChoice
+                    && (self.goldfish || pending.options(db).is_empty())
+                {
                     debug!("Cancelling pending");
                     SelectionResult::Complete
                 } else {
                     result
                 }
+            } else if let Some(choice) = self.attack_choice(db, pending) {
+                pending.resolve(db, choice)
             } else {
                 pending.resolve(db, Some(0))
             };
@@ -88,9 +171,104 @@ impl AI {
                 pending
             }
         } else if db.turn.priority_player() == self.player {
-            return self.priority(db, pending);
+            self.priority(db, pending)
         } else {
             PendingEffects::default()
         }
     }
+
+    /// Declares only the attacks suggested by [combat::safe_attacks] while choosing attackers,
+    /// rather than attacking with everything. Returns None if the pending choice isn't choosing
+    /// attackers or their targets.
+    fn attack_choice(&self, db: &Database, pending: &PendingEffects) -> Option<Option<usize>> {
+        if self.goldfish
+            || db.turn.phase != Phase::DeclareAttackers
+            || db.turn.active_player() != self.player
+        {
+            return None;
+        }
+
+        let details = pending.option_details(db);
+        let candidates = combat::attack_candidates(db, self.player);
+        let safe = combat::safe_attacks(db, self.player);
+        if details
+            .iter()
+            .all(|detail| detail.card.is_some_and(|card| candidates.contains(&card)))
+        {
+            Some(
+                details
+                    .iter()
+                    .find(|detail| {
+                        safe.attacks
+                            .iter()
+                            .any(|(attacker, _)| detail.card == Some(*attacker))
+                    })
+                    .map(|detail| detail.index),
+            )
+        } else if details.iter().all(|detail| detail.player.is_some()) {
+            details
+                .iter()
+                .find(|detail| {
+                    safe.attacks
+                        .iter()
+                        .any(|(_, target)| detail.player == Some(*target))
+                })
+                .or(details.first())
+                .map(|detail| Some(detail.index))
+        } else {
+            None
+        }
+    }
+
+    /// Plays each candidate out on its own copy of the game in parallel, resolving it and anything
+    /// it puts on the stack with the first option for every choice, and picks the candidate which
+    /// leaves the best position. Ties go to the candidate with the highest card value.
+    fn best_by_rollout(&self, db: &Database, candidates: Vec<CardId>) -> Option<CardId> {
+        candidates
+            .into_par_iter()
+            .map(|card| {
+                (
+                    card,
+                    self.rollout(db, card),
+                    self.evaluator.card_value(db, card),
+                )
+            })
+            .max_by(|(_, l_score, l_value), (_, r_score, r_value)| {
+                l_score
+                    .total_cmp(r_score)
+                    .then_with(|| l_value.total_cmp(r_value))
+            })
+            .map(|(card, _, _)| card)
+    }
+
+    fn rollout(&self, db: &Database, card: CardId) -> f64 {
+        let mut db = db.clone();
+        let mut pending = Player::play_card(&mut db, self.player, card);
+        resolve_with_defaults(&mut db, &mut pending);
+
+        for _ in 0..MAX_ROLLOUT_RESOLUTIONS {
+            if db.stack.is_empty() {
+                break;
+            }
+
+            let mut pending = Stack::resolve_1(&mut db);
+            resolve_with_defaults(&mut db, &mut pending);
+        }
+
+        self.evaluator.evaluate(&db, self.player)
+    }
+}
+
+fn resolve_with_defaults(db: &mut Database, pending: &mut PendingEffects) {
+    for _ in 0..MAX_ROLLOUT_CHOICES {
+        let option = if pending.options(db).is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+
+        if pending.resolve(db, option) == SelectionResult::Complete {
+            break;
+        }
+    }
 }
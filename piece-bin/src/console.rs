@@ -0,0 +1,200 @@
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use egui::{ScrollArea, TextEdit};
+use itertools::Itertools;
+use piece_lib::{
+    card_query::{CardQuery, SearchCards},
+    debug,
+    in_play::Database,
+    player::Owner,
+    protogen::targets::Location,
+    record::GameRecord,
+    trace::Trace,
+    turns::Phase,
+    Cards,
+};
+
+/// The most results listed by the `search` command.
+const MAX_SEARCH_RESULTS: usize = 20;
+
+const HELP: &str = "Commands:
+  add <1|2> <hand|battlefield|graveyard|exile|library> <card name>
+  import <board, e.g. P1: 3 Forest (2 tapped), Alpine Grizzly; P2: 17 life, 2 cards in hand>
+  search <query, e.g. grizzly t:creature c:g cmc:2-3 o:trample>
+  life <1|2> <life total>
+  untap
+  phase <phase>
+  trace <start|dump <file.dot>>
+  record <finish <1|2>|dump <file.yaml>>
+  help";
+
+#[derive(Debug, Default)]
+pub struct Console {
+    pub open: bool,
+    input: String,
+    history: Vec<String>,
+}
+
+impl Console {
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        db: &mut Database,
+        cards: &Cards,
+        players: [Owner; 2],
+    ) {
+        let mut open = self.open;
+        egui::Window::new("Console")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ScrollArea::vertical()
+                    .max_height(200.0)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for line in self.history.iter() {
+                            ui.monospace(line);
+                        }
+                    });
+
+                let edit = ui.add(
+                    TextEdit::singleline(&mut self.input)
+                        .hint_text("help")
+                        .code_editor(),
+                );
+                if edit.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                    let command = std::mem::take(&mut self.input);
+                    self.history.push(format!("> {}", command));
+                    match run(db, cards, players, &command) {
+                        Ok(output) => self.history.push(output),
+                        Err(e) => self.history.push(format!("Error: {}", e)),
+                    }
+                }
+                edit.request_focus();
+            });
+        self.open = open;
+    }
+}
+
+fn run(
+    db: &mut Database,
+    cards: &Cards,
+    players: [Owner; 2],
+    command: &str,
+) -> anyhow::Result<String> {
+    let mut args = command.split_whitespace();
+    match args.next().unwrap_or_default() {
+        "add" => {
+            let player = parse_player(players, args.next())?;
+            let location = match args.next().unwrap_or_default() {
+                "hand" => Location::IN_HAND,
+                "battlefield" => Location::ON_BATTLEFIELD,
+                "graveyard" => Location::IN_GRAVEYARD,
+                "exile" => Location::IN_EXILE,
+                "library" => Location::IN_LIBRARY,
+                zone => return Err(anyhow!("Unknown zone: {}", zone)),
+            };
+            let name = args.collect::<Vec<_>>().join(" ");
+            if debug::add_card(db, cards, player, &name, location).is_some() {
+                Ok(format!("Added {}", name))
+            } else {
+                Err(anyhow!("Unknown card: {}", name))
+            }
+        }
+        "import" => {
+            let description = command
+                .trim_start()
+                .strip_prefix("import")
+                .unwrap_or_default();
+            let warnings = debug::import_board(db, cards, &players, description);
+            if warnings.is_empty() {
+                Ok("Imported board".to_string())
+            } else {
+                Ok(format!(
+                    "Imported board, skipping:\n{}",
+                    warnings.join("\n")
+                ))
+            }
+        }
+        "search" => {
+            let query = CardQuery::parse(
+                command
+                    .trim_start()
+                    .strip_prefix("search")
+                    .unwrap_or_default(),
+            )?;
+            let results = cards.search(&query).map(|(name, _)| name).collect_vec();
+            if results.is_empty() {
+                Ok("No matching cards".to_string())
+            } else {
+                Ok(format!(
+                    "{} matching cards:\n{}",
+                    results.len(),
+                    results.iter().take(MAX_SEARCH_RESULTS).join("\n")
+                ))
+            }
+        }
+        "life" => {
+            let player = parse_player(players, args.next())?;
+            let life_total = args.next().unwrap_or_default().parse()?;
+            debug::set_life_total(db, player, life_total);
+            Ok(format!("Set life total to {}", life_total))
+        }
+        "untap" => {
+            debug::untap_all(db);
+            Ok("Untapped all permanents".to_string())
+        }
+        "phase" => {
+            let phase = Phase::from_str(args.next().unwrap_or_default())?;
+            let output = format!("Skipped to {}", phase.as_ref());
+            debug::skip_to_phase(db, phase);
+            Ok(output)
+        }
+        "trace" => match args.next().unwrap_or_default() {
+            "start" => {
+                Trace::start(db);
+                Ok("Started tracing".to_string())
+            }
+            "dump" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| anyhow!("Expected a file to dump to"))?;
+                let trace = Trace::take(db).ok_or_else(|| anyhow!("Tracing is not enabled"))?;
+                std::fs::write(path, trace.to_dot())?;
+                Ok(format!("Wrote {} events to {}", trace.nodes.len(), path))
+            }
+            arg => Err(anyhow!("Unknown trace command: {}", arg)),
+        },
+        "record" => match args.next().unwrap_or_default() {
+            "finish" => {
+                let player = parse_player(players, args.next())?;
+                GameRecord::finish(db, player);
+                Ok("Finished the game".to_string())
+            }
+            "dump" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| anyhow!("Expected a file to dump to"))?;
+                let record = GameRecord::export(db)
+                    .ok_or_else(|| anyhow!("Both players must finish the game first"))?;
+                std::fs::write(path, serde_yaml::to_string(&record)?)?;
+                Ok(format!(
+                    "Wrote {} decisions to {}",
+                    record.decisions.len(),
+                    path
+                ))
+            }
+            arg => Err(anyhow!("Unknown record command: {}", arg)),
+        },
+        "help" => Ok(HELP.to_string()),
+        command => Err(anyhow!("Unknown command: {}", command)),
+    }
+}
+
+fn parse_player(players: [Owner; 2], arg: Option<&str>) -> anyhow::Result<Owner> {
+    match arg {
+        Some("1") => Ok(players[0]),
+        Some("2") => Ok(players[1]),
+        arg => Err(anyhow!("Expected player 1 or 2, got {:?}", arg)),
+    }
+}
@@ -1,8 +1,6 @@
 #[macro_use]
 extern crate tracing;
 
-mod ui;
-
 use std::{
     collections::HashMap,
     env::current_dir,
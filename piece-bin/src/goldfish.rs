@@ -0,0 +1,185 @@
+#[macro_use]
+extern crate tracing;
+
+mod ai;
+
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Context};
+use itertools::Itertools;
+use piece_lib::{
+    battlefield::Battlefields,
+    deck::{self, DeckConstraints, DeckStats},
+    effects::{PendingEffects, SelectionResult},
+    in_play::Database,
+    library::DeckDefinition,
+    load_cards,
+    player::{AllPlayers, Player},
+    Cards,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::ai::{Evaluator, Evaluators, AI};
+
+const DEFAULT_ITERATIONS: u64 = 100;
+const DEFAULT_MAX_TURNS: usize = 20;
+
+/// Upper bound on the number of priority passes in a single game, in case the pilot gets stuck.
+const MAX_STEPS: usize = 100_000;
+
+fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let Some(deck) = args.next() else {
+        return Err(anyhow!(
+            "Usage: goldfish <deck file | random> [iterations] [seed] [max turns] [evaluator]"
+        ));
+    };
+    let iterations = args
+        .next()
+        .map(|arg| arg.parse())
+        .transpose()?
+        .unwrap_or(DEFAULT_ITERATIONS);
+    let seed: u64 = args
+        .next()
+        .map(|arg| arg.parse())
+        .transpose()?
+        .unwrap_or_default();
+    let max_turns = args
+        .next()
+        .map(|arg| arg.parse())
+        .transpose()?
+        .unwrap_or(DEFAULT_MAX_TURNS);
+    let evaluators = Evaluators::default();
+    let evaluator = args.next().unwrap_or_else(|| "heuristic".to_string());
+    if evaluators.get(&evaluator).is_none() {
+        return Err(anyhow!(
+            "Unknown evaluator {}, expected one of: {}",
+            evaluator,
+            evaluators.names().join(", ")
+        ));
+    }
+
+    let cards = load_cards()?;
+    let def = if deck == "random" {
+        let def = deck::random_deck(
+            &cards,
+            &DeckConstraints::default(),
+            &mut StdRng::seed_from_u64(seed),
+        )?;
+        for (name, count) in def.cards().sorted() {
+            println!("{} {}", count, name);
+        }
+        println!();
+        def
+    } else {
+        let contents =
+            std::fs::read_to_string(&deck).with_context(|| format!("Reading deck: {}", deck))?;
+        deck::parse_decklist(&deck, &contents, &cards)?
+    };
+
+    let stats = deck::stats(&def, &cards);
+    let mut kills = BTreeMap::<usize, u64>::default();
+    let mut survived = 0;
+    for iteration in 0..iterations {
+        let pilot = evaluators.get(&evaluator).unwrap();
+        match goldfish(
+            &cards,
+            &def,
+            &stats,
+            pilot,
+            seed.wrapping_add(iteration),
+            max_turns,
+        ) {
+            Some(turn) => *kills.entry(turn).or_default() += 1,
+            None => survived += 1,
+        }
+    }
+
+    println!("Goldfished {} games (seed {})", iterations, seed);
+    let mut total = 0;
+    for (turn, count) in kills.iter() {
+        total += count;
+        println!(
+            "Turn {:>2}: {:>5} kills ({:>5.1}% by this turn)",
+            turn,
+            count,
+            total as f64 / iterations as f64 * 100.0
+        );
+    }
+    if survived != 0 {
+        println!("No kill by turn {}: {}", max_turns, survived);
+    }
+    if total != 0 {
+        let average = kills
+            .iter()
+            .map(|(turn, count)| turn * *count as usize)
+            .sum::<usize>() as f64
+            / total as f64;
+        println!("Average kill turn: {:.2}", average);
+    }
+
+    Ok(())
+}
+
+/// Plays a single game against an opponent which never acts, returning the turn on which the
+/// opponent's life total reached zero.
+fn goldfish(
+    cards: &Cards,
+    def: &DeckDefinition,
+    stats: &DeckStats,
+    evaluator: Box<dyn Evaluator>,
+    seed: u64,
+    max_turns: usize,
+) -> Option<usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let opponent = all_players.new_player("Goldfish".to_string(), 20);
+    let mut db = Database::new(all_players);
+    db.set_card_names(cards);
+
+    db.all_players[player].library = def.build_deck(&mut db, cards, player);
+    db.all_players[player].library.shuffle_with(&mut rng);
+    Player::draw_initial_hand(&mut db, player);
+
+    let pilot = AI::new(player).with_evaluator(evaluator);
+    pilot.mulligan(&mut db, stats, &mut rng);
+    let goldfish = AI::goldfish(opponent);
+
+    let mut pending = PendingEffects::default();
+    for _ in 0..MAX_STEPS {
+        let turn = db.turn.turn_count / db.turn.turns_per_round() + 1;
+        if db.all_players[opponent].life_total <= 0 {
+            return Some(turn);
+        }
+        if turn > max_turns {
+            return None;
+        }
+
+        let ai = if pending.priority(&db) == player {
+            &pilot
+        } else {
+            &goldfish
+        };
+
+        let next = ai.priority(&mut db, &mut pending);
+        if pending.is_empty() {
+            pending = next;
+        } else {
+            pending.extend(next);
+        }
+
+        if pending.is_empty() {
+            pending = Battlefields::check_sba(&mut db);
+            while !pending.wants_input(&db) {
+                if pending.resolve(&mut db, None) == SelectionResult::Complete {
+                    break;
+                }
+            }
+        }
+    }
+
+    warn!("Goldfish game with seed {} did not finish", seed);
+    None
+}
@@ -2,28 +2,44 @@
 extern crate tracing;
 
 mod ai;
+#[cfg(feature = "console")]
+mod console;
 mod load;
+mod settings;
 mod ui;
 
 use std::{fs::OpenOptions, time::Instant};
 
+use anyhow::Context;
+
 use convert_case::{Case, Casing};
 use egui::{Color32, Frame, Label, Layout, Sense, Stroke, TextEdit};
 use itertools::Itertools;
 use piece_lib::{
+    abilities::Ability,
+    apply_overrides,
     battlefield::Battlefields,
     card::replace_expanded_symbols,
-    effects::{Options, PendingEffects, SelectionResult},
+    card_metadata::CardMetadataStore,
+    card_query::{CardQuery, SearchCards},
+    checkpoint::Checkpoints,
+    combat,
+    config::Vanguard,
+    debug,
+    deck::{self, DeckStats},
+    effects::{OptionCategory, Options, PendingEffects, SelectionResult},
+    format::Format,
     in_play::{CardId, Database},
     library::DeckDefinition,
-    player::{AllPlayers, Owner, Player},
+    log::{Log, LogEntry},
+    notifications::{Notification, Notifications},
+    player::{mana_pool::FloatingMana, AllPlayers, Owner, Player},
     protogen::{keywords::Keyword, targets::Location},
-    stack::{Selected, Stack},
-    turns::Turn,
+    stack::{Selected, StackId},
+    turns::{AutoPolicy, Phase, Turn},
     Cards,
 };
 use protobuf::Enum;
-use taffy::prelude::*;
 use tantivy::{
     collector::TopDocs,
     doc,
@@ -33,7 +49,12 @@ use tantivy::{
     Index, Searcher,
 };
 
-use crate::{ai::AI, load::load_cards, ui::ManaDisplay};
+use crate::{
+    ai::{Evaluators, AI},
+    load::load_cards,
+    settings::{Keybindings, Settings},
+    ui::ManaDisplay,
+};
 
 static FONT_DATA: &[u8] = include_bytes!("../../fonts/mana.ttf");
 
@@ -50,7 +71,11 @@ struct App {
     name: Field,
 
     adding_card: Option<String>,
+    /// The user's favorites, tags, and notes for cards, shown when searching for a card to add.
+    card_metadata: CardMetadataStore,
     to_resolve: Option<PendingEffects>,
+    /// Checkpoints taken before each of player 1's actions which is still being resolved.
+    checkpoints: Checkpoints,
     organizing_stack: bool,
 
     hovered: Option<usize>,
@@ -59,6 +84,27 @@ struct App {
     selected_card: Option<CardId>,
     inspecting_card: Option<CardId>,
     hovering_target: Option<Selected>,
+    hovering_affected: Vec<CardId>,
+    browsing: Option<(Owner, Location)>,
+    browser_sort: ui::BrowserSort,
+    hold_priority: bool,
+    responding_to: Option<usize>,
+    /// The group of identical stack entries player 1 chose to resolve all at once. Priority is
+    /// passed automatically while one of them is on top of the stack.
+    resolving_group: Vec<StackId>,
+    /// The stats for player 1's deck while they are deciding whether to keep their opening hand.
+    opening_hand: Option<DeckStats>,
+    settings: Settings,
+    settings_open: bool,
+    /// Changed to reset the sizes of the panels, which egui otherwise remembers.
+    layout_generation: usize,
+    /// Whether the panel layout has changed since it was last saved.
+    layout_changed: bool,
+    /// The formats which can be chosen for the next game.
+    formats: Vec<Format>,
+
+    #[cfg(feature = "console")]
+    console: console::Console,
 }
 
 impl App {
@@ -73,6 +119,9 @@ impl App {
         searcher: Searcher,
         parser: QueryParser,
         name: Field,
+        deck_stats: DeckStats,
+        settings: Settings,
+        formats: Vec<Format>,
     ) -> Self {
         let mut fonts = egui::FontDefinitions::default();
         fonts.font_data.insert(
@@ -87,6 +136,7 @@ impl App {
             .insert(1, "symbols".to_string());
 
         cc.egui_ctx.set_fonts(fonts);
+        cc.egui_ctx.set_zoom_factor(settings.ui_scale);
         cc.egui_ctx.style_mut(|style| {
             style.visuals.dark_mode = true;
             let color = Color32::from_hex("#141414").unwrap();
@@ -112,7 +162,9 @@ impl App {
             parser,
             name,
             adding_card: None,
+            card_metadata: settings::load_card_metadata(),
             to_resolve: None,
+            checkpoints: Default::default(),
             organizing_stack: false,
             hovered: None,
             left_clicked: None,
@@ -120,6 +172,128 @@ impl App {
             selected_card: None,
             inspecting_card: None,
             hovering_target: None,
+            hovering_affected: vec![],
+            browsing: None,
+            browser_sort: Default::default(),
+            hold_priority: settings.hold_priority,
+            opening_hand: Some(deck_stats),
+            responding_to: None,
+            resolving_group: vec![],
+            settings,
+            settings_open: false,
+            layout_generation: 0,
+            layout_changed: false,
+            formats,
+
+            #[cfg(feature = "console")]
+            console: Default::default(),
+        }
+    }
+
+    /// Turns the engine's notifications into log lines, and asks the OS for attention when player
+    /// 1 is needed.
+    fn notify(&mut self, ctx: &egui::Context) {
+        Notifications::decision(&mut self.database, self.to_resolve.as_ref());
+        for notification in Notifications::drain(&mut self.database) {
+            info!("{:?}", notification);
+            let attention = match notification {
+                Notification::YourTurn(player) | Notification::ActionRequired(player) => {
+                    player == self.player1
+                }
+                Notification::LifeLow { player, .. } => player == self.player1,
+                Notification::GameOver { .. } => true,
+                _ => false,
+            };
+
+            if attention {
+                ctx.send_viewport_cmd(egui::ViewportCommand::RequestUserAttention(
+                    egui::UserAttentionType::Informational,
+                ));
+            }
+        }
+    }
+
+    /// The mana in the player's pool, less any mana chosen for a payment which hasn't finished.
+    fn floating_mana(&self, player: Owner) -> Vec<FloatingMana> {
+        match self.to_resolve.as_ref() {
+            Some(pending) => pending.floating_mana(&self.database, player),
+            None => self.database.all_players[player].mana_pool.floating(),
+        }
+    }
+
+    /// Taps each of player 1's untapped lands for mana using their first mana ability.
+    fn tap_lands(&mut self) {
+        let abilities = self.database.battlefield[self.player1]
+            .iter()
+            .copied()
+            .filter(|card| card.is_land(&self.database) && !card.tapped(&self.database))
+            .filter_map(|card| {
+                self.database[card]
+                    .abilities(&self.database)
+                    .into_iter()
+                    .position(|(_, ability)| matches!(ability, Ability::Mana(_)))
+                    .map(|index| (card, index))
+            })
+            .collect_vec();
+
+        self.checkpoints.push(&self.database, &self.to_resolve);
+        let activation = Battlefields::activate_mana_abilities(
+            &mut self.database,
+            &self.to_resolve,
+            self.player1,
+            &abilities,
+        );
+        debug!(
+            "Tapped lands for {:?}",
+            FloatingMana::display(&activation.floated)
+        );
+
+        let mut pending = activation.pending;
+        if let Some(to_resolve) = self.to_resolve.take() {
+            pending.extend(to_resolve);
+        }
+
+        maybe_organize_stack(
+            &mut self.database,
+            pending,
+            &mut self.to_resolve,
+            &mut self.organizing_stack,
+        );
+    }
+
+    /// Passes priority to the next player. Once every player has passed in succession, either the
+    /// top of the stack resolves or the game moves on to the next phase.
+    fn pass_priority(&mut self) {
+        debug!("Passing priority");
+        assert_eq!(self.database.turn.priority_player(), self.player1);
+        self.database.turn.pass_priority();
+
+        if self.database.turn.passed_full_priority_round() {
+            let resolving = !self.database.stack.is_empty();
+            let mut pending = Turn::step(&mut self.database);
+            while !pending.wants_input(&self.database) {
+                let result = pending.resolve(&mut self.database, None);
+                if result == SelectionResult::Complete {
+                    break;
+                }
+            }
+
+            if resolving && pending.is_empty() {
+                pending = Battlefields::check_sba(&mut self.database);
+                while !pending.wants_input(&self.database) {
+                    let result = pending.resolve(&mut self.database, None);
+                    if result == SelectionResult::Complete {
+                        break;
+                    }
+                }
+            }
+
+            maybe_organize_stack(
+                &mut self.database,
+                pending,
+                &mut self.to_resolve,
+                &mut self.organizing_stack,
+            );
         }
     }
 }
@@ -142,16 +316,69 @@ fn main() -> anyhow::Result<()> {
         .with_writer(non_blocking)
         .init();
 
-    let cards = load_cards()?;
+    let mut cards = load_cards()?;
+    let overrides = std::path::Path::new("overrides");
+    if overrides.is_dir() {
+        apply_overrides(&mut cards, overrides)?;
+    }
+
+    let mut settings = Settings::load();
 
-    let mut all_players = AllPlayers::default();
+    let formats = std::path::Path::new("formats");
+    let formats = if formats.is_dir() {
+        Format::load_dir(formats)?
+    } else {
+        vec![]
+    };
+    let format = match std::env::args()
+        .find_map(|arg| arg.strip_prefix("--format=").map(str::to_string))
+        .or_else(|| settings.format.clone())
+    {
+        Some(name) => Some(
+            formats
+                .iter()
+                .find(|format| format.name == name)
+                .with_context(|| format!("Unknown format: {}", name))?
+                .clone(),
+        ),
+        None => None,
+    };
+
+    let mut all_players = AllPlayers::new(
+        format
+            .as_ref()
+            .map_or_else(|| settings.game.clone(), |format| format.game.clone()),
+    );
 
-    let player1 = all_players.new_player("Player 1".to_string(), 20);
-    let player2 = all_players.new_player("Player 2".to_string(), 20);
+    let player1 = all_players.join(settings.player_name.clone(), Vanguard::default());
+    let player2 = all_players.join("Player 2".to_string(), Vanguard::default());
     all_players[player1].infinite_mana();
+    all_players[player1].auto_pass = settings.auto_pass;
 
     let mut database = Database::new(all_players);
-    let ai = AI::new(player2);
+    database.set_card_names(&cards);
+    let ai = if std::env::args().any(|arg| arg == "--goldfish") {
+        AI::goldfish(player2)
+    } else {
+        AI::new(player2)
+    };
+    let ai = match std::env::args().find_map(|arg| {
+        arg.strip_prefix("--evaluator=")
+            .map(|evaluator| evaluator.to_string())
+    }) {
+        Some(name) => {
+            let evaluators = Evaluators::default();
+            let evaluator = evaluators.get(&name).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown evaluator {}, expected one of: {}",
+                    name,
+                    evaluators.names().join(", ")
+                )
+            })?;
+            ai.with_evaluator(evaluator)
+        }
+        None => ai,
+    };
 
     let timer = Instant::now();
 
@@ -196,11 +423,46 @@ fn main() -> anyhow::Result<()> {
 
     info!("Indexed cards in {}ms", timer.elapsed().as_millis());
 
-    let mut def = DeckDefinition::default();
-    for card in cards.keys() {
-        def.add_card(card.clone(), 1);
-    }
+    let deck = std::env::args()
+        .find_map(|arg| arg.strip_prefix("--deck=").map(std::path::PathBuf::from))
+        .or_else(|| {
+            std::env::args()
+                .any(|arg| arg == "--last-deck")
+                .then(|| settings.recent_decks.first().cloned())
+                .flatten()
+        });
+    let def = match deck {
+        Some(deck) => {
+            let contents = std::fs::read_to_string(&deck)
+                .with_context(|| format!("Reading deck: {}", deck.display()))?;
+            let def = deck::parse_decklist(&deck.display().to_string(), &contents, &cards)?;
+            if let Some(format) = format.as_ref() {
+                let violations = format.check(&def, &cards);
+                if !violations.is_empty() {
+                    anyhow::bail!(
+                        "{} is not legal in {}:\n{}",
+                        deck.display(),
+                        format.name,
+                        violations.iter().join("\n")
+                    );
+                }
+            }
+            settings.used_deck(deck);
+            if let Err(e) = settings.save() {
+                warn!("Failed to save settings: {:?}", e);
+            }
+            def
+        }
+        None => {
+            let mut def = DeckDefinition::default();
+            for card in cards.keys() {
+                def.add_card(card.clone(), 1);
+            }
+            def
+        }
+    };
     database.all_players[player1].library = def.build_deck(&mut database, &cards, player1);
+    let deck_stats = deck::stats(&def, &cards);
 
     let mut def = DeckDefinition::default();
     def.add_card("Forest".to_string(), 4);
@@ -209,6 +471,11 @@ fn main() -> anyhow::Result<()> {
 
     Player::draw_initial_hand(&mut database, player1);
     Player::draw_initial_hand(&mut database, player2);
+    ai.mulligan(
+        &mut database,
+        &deck::stats(&def, &cards),
+        &mut rand::thread_rng(),
+    );
 
     let reader = index.reader()?;
     let searcher = reader.searcher();
@@ -227,7 +494,8 @@ fn main() -> anyhow::Result<()> {
         eframe::NativeOptions::default(),
         Box::new(move |cc| {
             Box::new(App::new(
-                cc, cards, database, ai, player1, player2, searcher, parser, name,
+                cc, cards, database, ai, player1, player2, searcher, parser, name, deck_stats,
+                settings, formats,
             ))
         }),
     )
@@ -238,203 +506,10 @@ fn main() -> anyhow::Result<()> {
 
 impl eframe::App for App {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        let mut tree = Taffy::default();
-
-        let player2_mana = tree
-            .new_leaf(Style {
-                display: Display::Grid,
-                size: Size::percent(1.0),
-                grid_column: Line::from_span(1),
-                grid_row: Line::from_span(1),
-                ..Default::default()
-            })
-            .unwrap();
-
-        let stack = tree
-            .new_leaf(Style {
-                display: Display::Grid,
-                size: Size::percent(1.0),
-                grid_column: Line::from_span(1),
-                grid_row: Line::from_span(1),
-                ..Default::default()
-            })
-            .unwrap();
-
-        let player1_mana = tree
-            .new_leaf(Style {
-                display: Display::Grid,
-                size: Size::percent(1.0),
-                grid_column: Line::from_span(1),
-                grid_row: Line::from_span(1),
-                ..Default::default()
-            })
-            .unwrap();
-
-        let lhs_column = tree
-            .new_with_children(
-                Style {
-                    display: Display::Grid,
-                    size: Size::percent(1.0),
-                    grid_column: Line::from_span(1),
-                    grid_row: Line::from_span(1),
-                    grid_template_rows: vec![
-                        TrackSizingFunction::from_percent(0.20),
-                        TrackSizingFunction::from_percent(0.50),
-                        TrackSizingFunction::from_percent(0.30),
-                    ],
-                    grid_template_columns: vec![TrackSizingFunction::from_percent(1.0)],
-                    ..Default::default()
-                },
-                &[player2_mana, stack, player1_mana],
-            )
-            .unwrap();
-
-        let player2_battlefield = tree
-            .new_leaf(Style {
-                display: Display::Grid,
-                size: Size::percent(1.0),
-                grid_column: Line::from_span(1),
-                grid_row: Line::from_span(1),
-                ..Default::default()
-            })
-            .unwrap();
-
-        let player1_battlefield = tree
-            .new_leaf(Style {
-                display: Display::Grid,
-                size: Size::percent(1.0),
-                grid_column: Line::from_span(1),
-                grid_row: Line::from_span(1),
-                ..Default::default()
-            })
-            .unwrap();
-
-        let player1_options = tree
-            .new_leaf(Style {
-                display: Display::Grid,
-                size: Size::percent(1.0),
-                grid_column: Line::from_span(1),
-                grid_row: Line::from_span(1),
-                ..Default::default()
-            })
-            .unwrap();
-
-        let player1_hand = tree
-            .new_leaf(Style {
-                display: Display::Grid,
-                size: Size::percent(1.0),
-                grid_column: Line::from_span(1),
-                grid_row: Line::from_span(1),
-                ..Default::default()
-            })
-            .unwrap();
-
-        let center_column = tree
-            .new_with_children(
-                Style {
-                    display: Display::Grid,
-                    size: Size::percent(1.0),
-                    grid_column: Line::from_span(1),
-                    grid_row: Line::from_span(1),
-                    grid_template_rows: vec![
-                        TrackSizingFunction::from_percent(0.40),
-                        TrackSizingFunction::from_percent(0.50),
-                        TrackSizingFunction::from_percent(0.05),
-                        TrackSizingFunction::from_percent(0.05),
-                    ],
-                    grid_template_columns: vec![TrackSizingFunction::from_percent(1.0)],
-                    ..Default::default()
-                },
-                &[
-                    player2_battlefield,
-                    player1_battlefield,
-                    player1_options,
-                    player1_hand,
-                ],
-            )
-            .unwrap();
-
-        let player2_exile = tree
-            .new_leaf(Style {
-                display: Display::Grid,
-                size: Size::percent(1.0),
-                grid_column: Line::from_span(1),
-                grid_row: Line::from_span(1),
-                ..Default::default()
-            })
-            .unwrap();
-
-        let player2_graveyard = tree
-            .new_leaf(Style {
-                display: Display::Grid,
-                size: Size::percent(1.0),
-                grid_column: Line::from_span(1),
-                grid_row: Line::from_span(1),
-                ..Default::default()
-            })
-            .unwrap();
-
-        let player1_graveyard = tree
-            .new_leaf(Style {
-                display: Display::Grid,
-                size: Size::percent(1.0),
-                grid_column: Line::from_span(1),
-                grid_row: Line::from_span(1),
-                ..Default::default()
-            })
-            .unwrap();
-
-        let player1_exile = tree
-            .new_leaf(Style {
-                display: Display::Grid,
-                size: Size::percent(1.0),
-                grid_column: Line::from_span(1),
-                grid_row: Line::from_span(1),
-                ..Default::default()
-            })
-            .unwrap();
-
-        let rhs_column = tree
-            .new_with_children(
-                Style {
-                    display: Display::Grid,
-                    size: Size::percent(1.0),
-                    grid_column: Line::from_span(1),
-                    grid_row: Line::from_span(1),
-                    grid_template_rows: vec![
-                        TrackSizingFunction::from_percent(0.15),
-                        TrackSizingFunction::from_percent(0.25),
-                        TrackSizingFunction::from_percent(0.40),
-                        TrackSizingFunction::from_percent(0.20),
-                    ],
-                    grid_template_columns: vec![TrackSizingFunction::from_percent(1.0)],
-                    ..Default::default()
-                },
-                &[
-                    player2_exile,
-                    player2_graveyard,
-                    player1_graveyard,
-                    player1_exile,
-                ],
-            )
-            .unwrap();
-
-        let root = tree
-            .new_with_children(
-                Style {
-                    display: Display::Grid,
-                    size: Size::percent(1.0),
-                    grid_template_rows: vec![TrackSizingFunction::from_percent(1.0)],
-                    grid_template_columns: vec![
-                        TrackSizingFunction::from_percent(0.15),
-                        TrackSizingFunction::from_percent(0.70),
-                        TrackSizingFunction::from_percent(0.15),
-                    ],
-                    ..Default::default()
-                },
-                &[lhs_column, center_column, rhs_column],
-            )
-            .unwrap();
+        self.notify(ctx);
+        if self.to_resolve.is_none() {
+            self.checkpoints.clear();
+        }
 
         if self.database.turn.priority_player() == self.player2 {
             debug!("Giving ai priority");
@@ -457,8 +532,37 @@ impl eframe::App for App {
             );
         }
 
+        if self.to_resolve.is_none()
+            && self.adding_card.is_none()
+            && self.opening_hand.is_none()
+            && self.database.turn.priority_player() == self.player1
+            && !self.organizing_stack
+            && self.database.all_players[self.player1].auto_pass
+            && Player::should_auto_pass(&self.database, self.player1, &None)
+        {
+            debug!("Auto-passing priority");
+            self.pass_priority();
+        }
+
+        if self.to_resolve.is_none()
+            && self.database.turn.priority_player() == self.player1
+            && !self.organizing_stack
+            && self
+                .database
+                .stack
+                .entries()
+                .last()
+                .is_some_and(|(id, _)| self.resolving_group.contains(id))
+        {
+            debug!("Passing priority to resolve identical entries");
+            self.pass_priority();
+        } else if self.database.stack.is_empty() {
+            self.resolving_group.clear();
+        }
+
         let enabled = self.to_resolve.is_none()
             && self.adding_card.is_none()
+            && self.opening_hand.is_none()
             && self.database.turn.priority_player() == self.player1;
 
         let frame = Frame {
@@ -477,57 +581,41 @@ impl eframe::App for App {
                 ui.with_layout(Layout::left_to_right(egui::Align::Min), |ui| {
                     if ui.button("Pass").clicked()
                         || (ui.is_enabled()
-                            && ctx.input(|input| input.key_released(egui::Key::Num1)))
+                            && Keybindings::released(ctx, &self.settings.keybindings.pass))
                     {
-                        debug!("Passing priority");
-                        assert_eq!(self.database.turn.priority_player(), self.player1);
-                        self.database.turn.pass_priority();
+                        self.pass_priority();
+                    }
 
-                        if self.database.turn.passed_full_priority_round() {
-                            let mut pending = Turn::step(&mut self.database);
-                            while !pending.wants_input(&self.database) {
-                                let result = pending.resolve(&mut self.database, None);
-                                if result == SelectionResult::Complete {
-                                    break;
-                                }
-                            }
+                    ui.checkbox(&mut self.hold_priority, "Hold priority");
+                    ui.checkbox(
+                        &mut self.database.all_players[self.player1].auto_pass,
+                        "Auto-pass",
+                    );
 
-                            maybe_organize_stack(
-                                &mut self.database,
-                                pending,
-                                &mut self.to_resolve,
-                                &mut self.organizing_stack,
-                            );
-                        }
+                    if ui.button("Tap lands").clicked()
+                        || (ui.is_enabled()
+                            && Keybindings::released(ctx, &self.settings.keybindings.tap_lands))
+                    {
+                        self.tap_lands();
                     }
 
                     if ui.button("(Debug) Untap all").clicked()
                         || (ui.is_enabled()
-                            && ctx.input(|input| input.key_released(egui::Key::Num2)))
+                            && Keybindings::released(ctx, &self.settings.keybindings.untap_all))
                     {
-                        for card in self
-                            .database
-                            .battlefield
-                            .battlefields
-                            .values()
-                            .flat_map(|b| b.iter())
-                            .copied()
-                            .collect_vec()
-                        {
-                            card.untap(&mut self.database);
-                        }
+                        debug::untap_all(&mut self.database);
                     }
 
                     if ui.button("(Debug) Infinite mana").clicked()
                         || (ui.is_enabled()
-                            && ctx.input(|input| input.key_released(egui::Key::Num3)))
+                            && Keybindings::released(ctx, &self.settings.keybindings.infinite_mana))
                     {
                         self.database.all_players[self.player1].infinite_mana();
                     }
 
                     if ui.button("(Debug) Draw").clicked()
                         || (ui.is_enabled()
-                            && ctx.input(|input| input.key_released(egui::Key::Num4)))
+                            && Keybindings::released(ctx, &self.settings.keybindings.draw))
                     {
                         let mut pending = Player::draw(self.player1, 1);
                         while !pending.wants_input(&self.database) {
@@ -545,12 +633,35 @@ impl eframe::App for App {
                         );
                     }
 
+                    if ui.button("(Debug) Fast-forward to my upkeep").clicked() {
+                        if let Err(e) = Turn::advance_until(
+                            &mut self.database,
+                            Phase::Upkeep,
+                            self.player1,
+                            AutoPolicy::Decline,
+                        ) {
+                            error!("Failed to fast-forward: {:?}", e);
+                        }
+                    }
+
                     if ui.button("(Debug) Add Card to Hand").clicked()
                         || (ui.is_enabled()
-                            && ctx.input(|input| input.key_released(egui::Key::Num5)))
+                            && Keybindings::released(ctx, &self.settings.keybindings.add_card))
                     {
                         self.adding_card = Some(String::default());
                     }
+
+                    if ui.button("Settings").clicked() {
+                        self.settings_open = !self.settings_open;
+                    }
+
+                    #[cfg(feature = "console")]
+                    if ui.button("(Debug) Console").clicked()
+                        || (ui.is_enabled()
+                            && Keybindings::released(ctx, &self.settings.keybindings.console))
+                    {
+                        self.console.open = !self.console.open;
+                    }
                 });
 
                 ui.with_layout(Layout::left_to_right(egui::Align::Min), |ui| {
@@ -567,160 +678,344 @@ impl eframe::App for App {
                         self.database.all_players[self.player1].life_total
                     ));
 
+                    if ui
+                        .button(format!(
+                            "Library ({})",
+                            self.database.all_players[self.player1]
+                                .library
+                                .cards()
+                                .count()
+                        ))
+                        .clicked()
+                    {
+                        self.browsing = Some((self.player1, Location::IN_LIBRARY));
+                    }
+
+                    if let Some(companion) = Player::companion(&self.database, self.player1) {
+                        if ui
+                            .add_enabled(
+                                Player::can_put_companion_in_hand(&self.database, self.player1),
+                                egui::Button::new(format!(
+                                    "Companion: {}",
+                                    companion.name(&self.database)
+                                )),
+                            )
+                            .clicked()
+                        {
+                            let mut pending =
+                                Player::put_companion_in_hand(&mut self.database, self.player1);
+                            while !pending.wants_input(&self.database) {
+                                let result = pending.resolve(&mut self.database, None);
+                                if result == SelectionResult::Complete {
+                                    break;
+                                }
+                            }
+
+                            maybe_organize_stack(
+                                &mut self.database,
+                                pending,
+                                &mut self.to_resolve,
+                                &mut self.organizing_stack,
+                            );
+                        }
+                    }
+
                     ui.separator();
                     ui.label(format!(
                         "{} ({})",
                         self.database.all_players[self.player2].name,
                         self.database.all_players[self.player2].life_total
                     ));
+
+                    if ui
+                        .button(format!(
+                            "Library ({})",
+                            self.database.all_players[self.player2]
+                                .library
+                                .cards()
+                                .count()
+                        ))
+                        .clicked()
+                    {
+                        self.browsing = Some((self.player2, Location::IN_LIBRARY));
+                    }
                 })
             });
 
+        let generation = self.layout_generation;
+        let panel_id = |name: &str| egui::Id::new((name, generation));
+        let mut layout = self.settings.layout.clone();
         egui::CentralPanel::default().frame(frame).show(ctx, |ui| {
             ui.set_enabled(enabled);
 
-            let size = ui.max_rect();
-            tree.compute_layout(
-                root,
-                Size {
-                    width: AvailableSpace::from_points(size.width()),
-                    height: AvailableSpace::from_points(size.height()),
-                },
-            )
-            .unwrap();
-
-            let row_offset = ui.next_widget_position().y;
-
-            let pos = tree.layout(player2_mana).unwrap();
-            ui.put(
-                egui::Rect::from_min_size(
-                    egui::pos2(pos.location.x, row_offset + pos.location.y),
-                    egui::vec2(pos.size.width, pos.size.height),
-                ),
-                ManaDisplay {
-                    player: self.player2,
-                    items: self.database.all_players[self.player2]
-                        .mana_pool
-                        .pools_display(),
-                },
-            );
-
-            let pos = tree.layout(stack).unwrap();
-            ui.put(
-                egui::Rect::from_min_size(
-                    egui::pos2(pos.location.x, row_offset + pos.location.y),
-                    egui::vec2(pos.size.width, pos.size.height),
-                ),
-                ui::Stack {
-                    items: self.database.stack.entries(),
-                    db: &self.database,
-                    left_clicked: &mut self.left_clicked,
-                    target: self.hovering_target.clone(),
-                },
-            );
+            let stack_column = egui::SidePanel::left(panel_id("Stack column"))
+                .frame(frame)
+                .resizable(true)
+                .default_width(layout.stack_width)
+                .show_inside(ui, |ui| {
+                    let player2_mana = egui::TopBottomPanel::top(panel_id("Opponent mana"))
+                        .frame(frame)
+                        .resizable(true)
+                        .default_height(layout.mana_height)
+                        .show_inside(ui, |ui| {
+                            ui.put(
+                                ui.max_rect(),
+                                ManaDisplay {
+                                    player: self.player2,
+                                    items: FloatingMana::display(&self.floating_mana(self.player2)),
+                                },
+                            );
+                        });
+                    layout.mana_height = player2_mana.response.rect.height();
+
+                    egui::TopBottomPanel::bottom(panel_id("Your mana"))
+                        .frame(frame)
+                        .resizable(false)
+                        .exact_height(layout.mana_height)
+                        .show_inside(ui, |ui| {
+                            ui.put(
+                                ui.max_rect(),
+                                ManaDisplay {
+                                    player: self.player1,
+                                    items: FloatingMana::display(&self.floating_mana(self.player1)),
+                                },
+                            );
+                        });
+
+                    let log = egui::TopBottomPanel::bottom(panel_id("Log"))
+                        .frame(frame)
+                        .resizable(true)
+                        .default_height(layout.log_height)
+                        .show_inside(ui, |ui| {
+                            egui::ScrollArea::vertical()
+                                .auto_shrink([false; 2])
+                                .stick_to_bottom(true)
+                                .show(ui, |ui| {
+                                    for (entry, _) in Log::attributed(&self.database) {
+                                        match entry {
+                                            LogEntry::NewTurn { player } => {
+                                                ui.strong(format!(
+                                                    "{}'s turn",
+                                                    self.database.all_players[*player].name
+                                                ));
+                                            }
+                                            // Cards may be chosen from hidden zones, e.g. when
+                                            // searching a library.
+                                            LogEntry::CardChosen { .. } => {}
+                                            entry => {
+                                                if let Some(line) = entry.describe(&self.database) {
+                                                    ui.label(line);
+                                                }
+                                            }
+                                        }
+                                    }
+                                });
+                        });
+                    layout.log_height = log.response.rect.height();
+
+                    egui::CentralPanel::default()
+                        .frame(frame)
+                        .show_inside(ui, |ui| {
+                            ui.put(
+                                ui.max_rect(),
+                                ui::Stack {
+                                    items: self.database.stack.grouped(&self.database),
+                                    left_clicked: &mut self.left_clicked,
+                                    target: self.hovering_target.clone(),
+                                },
+                            );
+                        });
 
-            if self.to_resolve.is_none()
-                && (self.left_clicked.take().is_some()
-                    || (ui.is_enabled() && ctx.input(|input| input.key_released(egui::Key::Enter))))
-            {
-                cleanup_stack(
-                    &mut self.database,
-                    &mut self.to_resolve,
-                    &mut self.organizing_stack,
-                );
-            }
+                    if self.to_resolve.is_none()
+                        && !self.database.stack.is_empty()
+                        && (self.left_clicked.take().is_some()
+                            || (ui.is_enabled()
+                                && ctx.input(|input| input.key_released(egui::Key::Enter))))
+                    {
+                        self.pass_priority();
+                    }
+                });
+            layout.stack_width = stack_column.response.rect.width();
+
+            let zones_column = egui::SidePanel::right(panel_id("Zones column"))
+                .frame(frame)
+                .resizable(true)
+                .default_width(layout.zones_width)
+                .show_inside(ui, |ui| {
+                    let mut browse = false;
+
+                    let player2_exile = egui::TopBottomPanel::top(panel_id("Opponent exile"))
+                        .frame(frame)
+                        .resizable(true)
+                        .default_height(layout.exile_height)
+                        .show_inside(ui, |ui| {
+                            let cards = self.database.exile[self.player2]
+                                .iter()
+                                .map(|card| card.name(&self.database))
+                                .cloned()
+                                .collect_vec();
+                            ui.put(
+                                ui.max_rect(),
+                                ui::Exile {
+                                    player: self.player2,
+                                    cards,
+                                    right_clicked: &mut self.right_clicked,
+                                    browse: &mut browse,
+                                },
+                            );
+                        });
+                    layout.exile_height = player2_exile.response.rect.height();
 
-            let pos = tree.layout(player1_mana).unwrap();
-            ui.put(
-                egui::Rect::from_min_size(
-                    egui::pos2(pos.location.x, row_offset + pos.location.y),
-                    egui::vec2(pos.size.width, pos.size.height),
-                ),
-                ManaDisplay {
-                    player: self.player1,
-                    items: self.database.all_players[self.player1]
-                        .mana_pool
-                        .pools_display(),
-                },
-            );
+                    if let Some(clicked) = self.right_clicked.take() {
+                        self.inspecting_card = Some(self.database.exile[self.player2][clicked]);
+                    } else if std::mem::take(&mut browse) {
+                        self.browsing = Some((self.player2, Location::IN_EXILE));
+                    }
 
-            let mut col_offset = tree.layout(lhs_column).unwrap().size.width;
+                    egui::TopBottomPanel::bottom(panel_id("Your exile"))
+                        .frame(frame)
+                        .resizable(false)
+                        .exact_height(layout.exile_height)
+                        .show_inside(ui, |ui| {
+                            let cards = self.database.exile[self.player1]
+                                .iter()
+                                .map(|card| card.name(&self.database))
+                                .cloned()
+                                .collect_vec();
+                            ui.put(
+                                ui.max_rect(),
+                                ui::Exile {
+                                    player: self.player1,
+                                    cards,
+                                    right_clicked: &mut self.right_clicked,
+                                    browse: &mut browse,
+                                },
+                            );
+                        });
 
-            let cards = self.database.battlefield[self.player2]
-                .iter()
-                .copied()
-                .enumerate()
-                .collect_vec();
-            let pos = tree.layout(player2_battlefield).unwrap();
-            ui.put(
-                egui::Rect::from_min_size(
-                    egui::pos2(col_offset + pos.location.x, row_offset + pos.location.y),
-                    egui::vec2(pos.size.width, pos.size.height),
-                ),
-                ui::Battlefield {
-                    db: &mut self.database,
-                    player: self.player2,
-                    cards,
-                    left_clicked: &mut None,
-                    right_clicked: &mut self.right_clicked,
-                    target: self.hovering_target.clone(),
-                },
-            );
+                    if let Some(clicked) = self.right_clicked.take() {
+                        self.inspecting_card = Some(self.database.exile[self.player1][clicked]);
+                    } else if std::mem::take(&mut browse) {
+                        self.browsing = Some((self.player1, Location::IN_EXILE));
+                    }
 
-            if let Some(clicked) = self.right_clicked.take() {
-                self.inspecting_card = Some(self.database.battlefield[self.player2][clicked]);
-            }
+                    let player2_graveyard =
+                        egui::TopBottomPanel::top(panel_id("Opponent graveyard"))
+                            .frame(frame)
+                            .resizable(true)
+                            .default_height(layout.opponent_graveyard_height)
+                            .show_inside(ui, |ui| {
+                                let cards = self.database.graveyard[self.player2]
+                                    .iter()
+                                    .map(|card| card.name(&self.database))
+                                    .cloned()
+                                    .collect_vec();
+                                ui.put(
+                                    ui.max_rect(),
+                                    ui::Graveyard {
+                                        player: self.player2,
+                                        cards,
+                                        right_clicked: &mut self.right_clicked,
+                                        browse: &mut browse,
+                                    },
+                                );
+                            });
+                    layout.opponent_graveyard_height = player2_graveyard.response.rect.height();
+
+                    if let Some(clicked) = self.right_clicked.take() {
+                        self.inspecting_card = Some(self.database.graveyard[self.player2][clicked]);
+                    } else if std::mem::take(&mut browse) {
+                        self.browsing = Some((self.player2, Location::IN_GRAVEYARD));
+                    }
 
-            let cards = self.database.battlefield[self.player1]
-                .iter()
-                .copied()
-                .enumerate()
-                .collect_vec();
-            let pos = tree.layout(player1_battlefield).unwrap();
-            ui.put(
-                egui::Rect::from_min_size(
-                    egui::pos2(col_offset + pos.location.x, row_offset + pos.location.y),
-                    egui::vec2(pos.size.width, pos.size.height),
-                ),
-                ui::Battlefield {
-                    db: &mut self.database,
-                    player: self.player1,
-                    cards,
-                    left_clicked: &mut self.left_clicked,
-                    right_clicked: &mut self.right_clicked,
-                    target: self.hovering_target.clone(),
-                },
-            );
+                    egui::CentralPanel::default()
+                        .frame(frame)
+                        .show_inside(ui, |ui| {
+                            let cards = self.database.graveyard[self.player1]
+                                .iter()
+                                .map(|card| card.name(&self.database))
+                                .cloned()
+                                .collect_vec();
+                            ui.put(
+                                ui.max_rect(),
+                                ui::Graveyard {
+                                    player: self.player1,
+                                    cards,
+                                    right_clicked: &mut self.right_clicked,
+                                    browse: &mut browse,
+                                },
+                            );
+                        });
 
-            if let Some(clicked) = self.left_clicked.take() {
-                self.selected_card = Some(self.database.battlefield[self.player1][clicked]);
-            } else if let Some(clicked) = self.right_clicked.take() {
-                self.inspecting_card = Some(self.database.battlefield[self.player1][clicked]);
-            }
+                    if let Some(clicked) = self.right_clicked.take() {
+                        self.inspecting_card = Some(self.database.graveyard[self.player1][clicked]);
+                    } else if std::mem::take(&mut browse) {
+                        self.browsing = Some((self.player1, Location::IN_GRAVEYARD));
+                    }
+                });
+            layout.zones_width = zones_column.response.rect.width();
+
+            let hand = egui::TopBottomPanel::bottom(panel_id("Hand"))
+                .frame(frame)
+                .resizable(true)
+                .default_height(layout.hand_height)
+                .show_inside(ui, |ui| {
+                    let cards = Player::playable_cards(&self.database, self.player1);
+                    let hand = cards.iter().map(|(card, _)| *card).collect_vec();
+                    ui.put(
+                        ui.max_rect(),
+                        ui::Hand {
+                            db: &mut self.database,
+                            owner: self.player1,
+                            cards,
+                            hovered: &mut self.hovered,
+                            left_clicked: &mut self.left_clicked,
+                            right_clicked: &mut self.right_clicked,
+                        },
+                    );
 
-            let pos = tree.layout(player1_options).unwrap();
-            ui.put(
-                egui::Rect::from_min_size(
-                    egui::pos2(col_offset + pos.location.x, row_offset + pos.location.y),
-                    egui::vec2(pos.size.width, pos.size.height),
-                ),
-                ui::Actions {
-                    db: &mut self.database,
-                    player: self.player1,
-                    card: self.selected_card,
-                    pending: &self.to_resolve,
-                    left_clicked: &mut self.left_clicked,
-                },
-            );
+                    if let Some(clicked) = self.left_clicked.take() {
+                        self.selected_card = Some(hand[clicked]);
+                    } else if let Some(clicked) = self.right_clicked.take() {
+                        self.inspecting_card = Some(hand[clicked]);
+                    }
+                });
+            layout.hand_height = hand.response.rect.height();
+
+            let actions = egui::TopBottomPanel::bottom(panel_id("Actions"))
+                .frame(frame)
+                .resizable(true)
+                .default_height(layout.actions_height)
+                .show_inside(ui, |ui| {
+                    ui.put(
+                        ui.max_rect(),
+                        ui::Actions {
+                            db: &mut self.database,
+                            player: self.player1,
+                            card: self.selected_card,
+                            pending: &self.to_resolve,
+                            left_clicked: &mut self.left_clicked,
+                        },
+                    );
+                });
+            layout.actions_height = actions.response.rect.height();
 
             if let Some(clicked) = self.left_clicked.take() {
                 let card = self.selected_card.unwrap();
                 let mut selected_ability = None;
-                if card.is_in_location(&self.database, Location::IN_HAND)
-                    && clicked == 0
+                if card.can_play_as_though_in_hand(&self.database)
+                    && (clicked == 0
+                        || (clicked == self.database[card].abilities(&self.database).len() + 1
+                            && card.faceup_face(&self.database).bestow.is_some()
+                            && card.is_in_location(&self.database, Location::IN_HAND)))
                     && Turn::can_cast(&self.database, card)
                 {
-                    let mut pending = Player::play_card(&mut self.database, self.player1, card);
+                    self.checkpoints.push(&self.database, &self.to_resolve);
+                    let mut pending = if clicked == 0 {
+                        Player::play_card(&mut self.database, self.player1, card)
+                    } else {
+                        Player::bestow(&mut self.database, self.player1, card)
+                    };
                     while !pending.wants_input(&self.database) {
                         let result = pending.resolve(&mut self.database, None);
                         if result == SelectionResult::Complete {
@@ -734,7 +1029,7 @@ impl eframe::App for App {
                         &mut self.to_resolve,
                         &mut self.organizing_stack,
                     );
-                } else if card.is_in_location(&self.database, Location::IN_HAND)
+                } else if card.can_play_as_though_in_hand(&self.database)
                     && Turn::can_cast(&self.database, card)
                 {
                     selected_ability = Some(clicked - 1);
@@ -744,6 +1039,7 @@ impl eframe::App for App {
 
                 if let Some(selected) = selected_ability {
                     if selected < self.database[card].abilities(&self.database).len() {
+                        self.checkpoints.push(&self.database, &self.to_resolve);
                         let mut pending = Battlefields::activate_ability(
                             &mut self.database,
                             &self.to_resolve,
@@ -773,124 +1069,78 @@ impl eframe::App for App {
                 }
             }
 
-            let cards = self.database.hand[self.player1]
-                .iter()
-                .copied()
-                .collect_vec();
-            let pos = tree.layout(player1_hand).unwrap();
-            ui.put(
-                egui::Rect::from_min_size(
-                    egui::pos2(col_offset + pos.location.x, row_offset + pos.location.y),
-                    egui::vec2(pos.size.width, pos.size.height),
-                ),
-                ui::Hand {
-                    db: &mut self.database,
-                    owner: self.player1,
-                    cards,
-                    hovered: &mut self.hovered,
-                    left_clicked: &mut self.left_clicked,
-                    right_clicked: &mut self.right_clicked,
-                },
-            );
-
-            if let Some(clicked) = self.left_clicked.take() {
-                self.selected_card = Some(self.database.hand[self.player1][clicked]);
-            } else if let Some(clicked) = self.right_clicked.take() {
-                self.inspecting_card = Some(self.database.hand[self.player1][clicked]);
-            }
-
-            col_offset += tree.layout(center_column).unwrap().size.width;
-
-            let cards = self.database.exile[self.player2]
-                .iter()
-                .map(|card| card.name(&self.database))
-                .cloned()
-                .collect_vec();
-            let pos = tree.layout(player2_exile).unwrap();
-            ui.put(
-                egui::Rect::from_min_size(
-                    egui::pos2(col_offset + pos.location.x, row_offset + pos.location.y),
-                    egui::vec2(pos.size.width, pos.size.height),
-                ),
-                ui::Exile {
-                    player: self.player2,
-                    cards,
-                    right_clicked: &mut self.right_clicked,
-                },
-            );
-
-            if let Some(clicked) = self.right_clicked.take() {
-                self.inspecting_card = Some(self.database.exile[self.player2][clicked]);
-            }
-
-            let cards = self.database.graveyard[self.player2]
-                .iter()
-                .map(|card| card.name(&self.database))
-                .cloned()
-                .collect_vec();
-            let pos = tree.layout(player2_graveyard).unwrap();
-            ui.put(
-                egui::Rect::from_min_size(
-                    egui::pos2(col_offset + pos.location.x, row_offset + pos.location.y),
-                    egui::vec2(pos.size.width, pos.size.height),
-                ),
-                ui::Graveyard {
-                    player: self.player2,
-                    cards,
-                    right_clicked: &mut self.right_clicked,
-                },
-            );
+            let player2_battlefield = egui::TopBottomPanel::top(panel_id("Opponent battlefield"))
+                .frame(frame)
+                .resizable(true)
+                .default_height(layout.opponent_battlefield_height)
+                .show_inside(ui, |ui| {
+                    let cards = self.database.battlefield[self.player2]
+                        .iter()
+                        .copied()
+                        .enumerate()
+                        .collect_vec();
+                    ui.put(
+                        ui.max_rect(),
+                        ui::Battlefield {
+                            db: &mut self.database,
+                            player: self.player2,
+                            cards,
+                            left_clicked: &mut None,
+                            right_clicked: &mut self.right_clicked,
+                            target: self.hovering_target.clone(),
+                            affected: &self.hovering_affected,
+                        },
+                    );
+                });
+            layout.opponent_battlefield_height = player2_battlefield.response.rect.height();
 
             if let Some(clicked) = self.right_clicked.take() {
-                self.inspecting_card = Some(self.database.graveyard[self.player2][clicked]);
+                self.inspecting_card = Some(self.database.battlefield[self.player2][clicked]);
             }
 
-            let cards = self.database.graveyard[self.player1]
-                .iter()
-                .map(|card| card.name(&self.database))
-                .cloned()
-                .collect_vec();
-            let pos = tree.layout(player1_graveyard).unwrap();
-            ui.put(
-                egui::Rect::from_min_size(
-                    egui::pos2(col_offset + pos.location.x, row_offset + pos.location.y),
-                    egui::vec2(pos.size.width, pos.size.height),
-                ),
-                ui::Graveyard {
-                    player: self.player1,
-                    cards,
-                    right_clicked: &mut self.right_clicked,
-                },
-            );
+            egui::CentralPanel::default()
+                .frame(frame)
+                .show_inside(ui, |ui| {
+                    let cards = self.database.battlefield[self.player1]
+                        .iter()
+                        .copied()
+                        .enumerate()
+                        .collect_vec();
+                    ui.put(
+                        ui.max_rect(),
+                        ui::Battlefield {
+                            db: &mut self.database,
+                            player: self.player1,
+                            cards,
+                            left_clicked: &mut self.left_clicked,
+                            right_clicked: &mut self.right_clicked,
+                            target: self.hovering_target.clone(),
+                            affected: &self.hovering_affected,
+                        },
+                    );
+                });
 
-            if let Some(clicked) = self.right_clicked.take() {
-                self.inspecting_card = Some(self.database.graveyard[self.player1][clicked]);
+            if let Some(clicked) = self.left_clicked.take() {
+                self.selected_card = Some(self.database.battlefield[self.player1][clicked]);
+            } else if let Some(clicked) = self.right_clicked.take() {
+                self.inspecting_card = Some(self.database.battlefield[self.player1][clicked]);
             }
+        });
 
-            let cards = self.database.exile[self.player1]
-                .iter()
-                .map(|card| card.name(&self.database))
-                .cloned()
-                .collect_vec();
-            let pos = tree.layout(player1_exile).unwrap();
-            ui.put(
-                egui::Rect::from_min_size(
-                    egui::pos2(col_offset + pos.location.x, row_offset + pos.location.y),
-                    egui::vec2(pos.size.width, pos.size.height),
-                ),
-                ui::Exile {
-                    player: self.player1,
-                    cards,
-                    right_clicked: &mut self.right_clicked,
-                },
-            );
-
-            if let Some(clicked) = self.right_clicked.take() {
-                self.inspecting_card = Some(self.database.exile[self.player1][clicked]);
+        // Panels are resized by dragging, so the layout is only saved once the drag is finished.
+        if layout != self.settings.layout {
+            self.settings.layout = layout;
+            self.layout_changed = true;
+        }
+        if self.layout_changed && !ctx.input(|input| input.pointer.any_down()) {
+            self.layout_changed = false;
+            if let Err(e) = Settings::save_layout(&self.settings.layout) {
+                error!("Failed to save layout: {:?}", e);
             }
-        });
+        }
 
         self.hovering_target = None;
+        self.hovering_affected.clear();
         let mut choice: Option<Option<usize>> = None;
         if let Some(resolving) = self.to_resolve.as_mut() {
             if resolving.priority(&self.database) == self.player2 {
@@ -917,95 +1167,295 @@ impl eframe::App for App {
                     .open(&mut open)
                     .show(ctx, |ui| {
                         ui.with_layout(Layout::top_down(egui::Align::Min), |ui| {
-                            let rest = match resolving.options(&self.database) {
-                                Options::MandatoryList(list) => list,
-                                Options::OptionalList(list) => {
+                            match resolving.options(&self.database) {
+                                Options::MandatoryList(_) => {}
+                                Options::OptionalList(_) => {
                                     if ui.button("None").clicked() {
                                         choice = Some(None);
                                     }
-                                    list
                                 }
-                                Options::ListWithDefault(list) => {
+                                Options::ListWithDefault(_) => {
                                     if ui.button("Default").clicked() {
                                         choice = Some(None);
                                     }
-                                    list
                                 }
                             };
 
-                            for (idx, option) in rest {
-                                let button = ui.button(option);
+                            let mut group = None;
+                            for option in resolving.option_details(&self.database) {
+                                if group != Some(option.category) {
+                                    if let Some(label) = category_label(option.category) {
+                                        ui.label(egui::RichText::new(label).strong());
+                                    }
+                                    group = Some(option.category);
+                                }
+
+                                let button = ui.button(option.description);
                                 if button.hovered() {
                                     self.hovering_target =
-                                        resolving.target_for_option(&self.database, idx);
+                                        resolving.target_for_option(&self.database, option.index);
+                                    self.hovering_affected = resolving
+                                        .affected_by_option(&self.database, option.index)
+                                        .cards;
                                 }
                                 if button.clicked() {
-                                    choice = Some(Some(idx));
+                                    choice = Some(Some(option.index));
                                 }
                             }
                         })
                     });
 
                 if !open || ctx.input(|input| input.key_released(egui::Key::Escape)) {
-                    let can_cancel =
-                        matches!(resolving.options(&self.database), Options::OptionalList(_));
-                    debug!("Can cancel {:?} = {}", resolving, can_cancel);
-                    if can_cancel {
-                        self.to_resolve = None;
+                    if let Some(restored) = self.checkpoints.cancel(&mut self.database) {
+                        debug!("Returning to checkpoint");
+                        self.to_resolve = restored.filter(|pending| !pending.is_empty());
+                    } else {
+                        let can_cancel =
+                            matches!(resolving.options(&self.database), Options::OptionalList(_));
+                        debug!("Can cancel {:?} = {}", resolving, can_cancel);
+                        if can_cancel {
+                            self.to_resolve = None;
+                        }
                     }
                 } else if let Some(choice) = choice {
-                    loop {
-                        match resolving.resolve(&mut self.database, choice) {
-                            SelectionResult::Complete => {
-                                let mut pending = Battlefields::check_sba(&mut self.database);
-                                while !pending.wants_input(&self.database) {
-                                    let result = pending.resolve(&mut self.database, None);
-                                    if result == SelectionResult::Complete {
-                                        break;
-                                    }
+                    match resolving.resolve_until_choice(&mut self.database, choice) {
+                        SelectionResult::Complete => {
+                            let mut pending = Battlefields::check_sba(&mut self.database);
+                            while !pending.wants_input(&self.database) {
+                                let result = pending.resolve(&mut self.database, None);
+                                if result == SelectionResult::Complete {
+                                    break;
                                 }
+                            }
 
-                                if pending.is_empty() {
-                                    let entries = self.database.stack.entries_unsettled();
-                                    if !self.organizing_stack && entries.len() > 1 {
-                                        *resolving = PendingEffects::organize_stack(&self.database);
-                                        self.organizing_stack = true;
-                                    } else {
-                                        debug!("Stepping priority");
-                                        self.database.turn.step_priority();
-                                        assert_eq!(
-                                            self.database.turn.priority_player(),
-                                            self.player2
-                                        );
-                                        debug!("Giving ai priority",);
-                                        let pending = self.ai.priority(
-                                            &mut self.database,
-                                            &mut PendingEffects::default(),
-                                        );
-                                        maybe_organize_stack(
-                                            &mut self.database,
-                                            pending,
-                                            &mut self.to_resolve,
-                                            &mut self.organizing_stack,
-                                        );
-                                    }
+                            if pending.is_empty() {
+                                let entries = self.database.stack.entries_unsettled();
+                                if !self.organizing_stack && entries.len() > 1 {
+                                    *resolving = PendingEffects::organize_stack(&self.database);
+                                    self.organizing_stack = true;
+                                } else if self.hold_priority && !self.database.stack.is_empty() {
+                                    debug!("Holding priority");
                                 } else {
-                                    self.to_resolve = Some(pending);
-                                }
-
-                                break;
-                            }
-                            SelectionResult::TryAgain => {
-                                if !resolving.options(&self.database).is_empty() {
-                                    break;
+                                    debug!("Passing priority");
+                                    self.database.turn.pass_priority();
+                                    assert_eq!(self.database.turn.priority_player(), self.player2);
+                                    debug!("Giving ai priority",);
+                                    let pending = self.ai.priority(
+                                        &mut self.database,
+                                        &mut PendingEffects::default(),
+                                    );
+                                    maybe_organize_stack(
+                                        &mut self.database,
+                                        pending,
+                                        &mut self.to_resolve,
+                                        &mut self.organizing_stack,
+                                    );
                                 }
+                            } else {
+                                self.to_resolve = Some(pending);
                             }
-                            SelectionResult::PendingChoice => {
-                                break;
-                            }
                         }
+                        SelectionResult::TryAgain | SelectionResult::PendingChoice => {}
+                    }
+                }
+            }
+        }
+
+        let preview = combat::combat_preview(&self.database);
+        if !preview.is_empty() {
+            egui::Window::new("Combat")
+                .frame(window_frame)
+                .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-10.0, 40.0))
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.add(ui::CombatSummary {
+                        db: &self.database,
+                        preview,
+                    });
+                });
+        } else if self.database.turn.phase == Phase::DeclareAttackers
+            && self.database.turn.active_player() == self.player1
+        {
+            let safe = combat::safe_attacks(&self.database, self.player1);
+            if !safe.attacks.is_empty() {
+                egui::Window::new("Safe attacks")
+                    .frame(window_frame)
+                    .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-10.0, 40.0))
+                    .resizable(false)
+                    .show(ctx, |ui| {
+                        for (attacker, target) in safe.attacks.iter() {
+                            ui.label(format!(
+                                "{} → {}",
+                                attacker.name(&self.database),
+                                self.database.all_players[*target].name
+                            ));
+                        }
+                    });
+            }
+        }
+
+        if let Some(stats) = self.opening_hand.as_ref() {
+            let hand = self.database.hand[self.player1]
+                .iter()
+                .copied()
+                .collect_vec();
+            let faces = hand
+                .iter()
+                .map(|card| card.faceup_face(&self.database))
+                .collect_vec();
+            let analysis = deck::analyze_hand(&faces, stats);
+            let mulligans = Player::mulligans(&self.database, self.player1);
+
+            let mut keep = false;
+            let mut mulligan = false;
+            egui::Window::new("Opening hand")
+                .frame(window_frame)
+                .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 40.0))
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(format!(
+                        "{} lands, {} early plays",
+                        analysis.lands, analysis.early_plays
+                    ));
+                    if !analysis.missing_colors.is_empty() {
+                        ui.label(format!(
+                            "Missing sources for: {}",
+                            analysis
+                                .missing_colors
+                                .iter()
+                                .map(|mana| mana.as_ref().to_case(Case::Title))
+                                .join(", ")
+                        ));
+                    }
+                    ui.label(format!(
+                        "Suggested: {} ({:.2})",
+                        if analysis.keep { "keep" } else { "mulligan" },
+                        analysis.score
+                    ));
+                    if mulligans > 0 {
+                        ui.label(format!("Keeping puts {} cards on the bottom.", mulligans));
                     }
+                    ui.horizontal(|ui| {
+                        keep = ui.button("Keep").clicked();
+                        mulligan = ui
+                            .add_enabled(mulligans < hand.len(), egui::Button::new("Mulligan"))
+                            .clicked();
+                    });
+                });
+
+            if keep {
+                for idx in deck::choose_bottom(&faces, stats, mulligans) {
+                    Player::put_on_bottom(&mut self.database, self.player1, hand[idx]);
                 }
+                self.opening_hand = None;
+            } else if mulligan {
+                Player::mulligan(&mut self.database, self.player1, &mut rand::thread_rng());
+            }
+        }
+
+        let stack_size = self.database.stack.entries().len();
+        let top_group = self
+            .database
+            .stack
+            .grouped(&self.database)
+            .pop()
+            .unwrap_or_default();
+        if stack_size > 0
+            && self.to_resolve.is_none()
+            && self.database.turn.priority_player() == self.player1
+            && self.responding_to != Some(stack_size)
+        {
+            let top = self
+                .database
+                .stack
+                .entries()
+                .values()
+                .last()
+                .unwrap()
+                .display(&self.database);
+
+            egui::Window::new("Priority")
+                .frame(window_frame)
+                .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 40.0))
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(format!("{} is waiting to resolve.", top));
+                    ui.horizontal(|ui| {
+                        if ui.button("Resolve").clicked() {
+                            self.pass_priority();
+                        }
+                        if top_group.len() > 1
+                            && ui
+                                .button(format!("Resolve all (x{})", top_group.len()))
+                                .clicked()
+                        {
+                            self.resolving_group = top_group.ids.clone();
+                            self.pass_priority();
+                        }
+                        if ui.button("Respond").clicked() {
+                            self.responding_to = Some(stack_size);
+                        }
+                    });
+                });
+        } else if stack_size == 0 {
+            self.responding_to = None;
+        }
+
+        if let Some((player, location)) = self.browsing {
+            let cards = match location {
+                Location::IN_GRAVEYARD => self.database.graveyard[player]
+                    .iter()
+                    .copied()
+                    .collect_vec(),
+                Location::IN_EXILE => self.database.exile[player].iter().copied().collect_vec(),
+                Location::IN_LIBRARY => self.database.all_players[player]
+                    .library
+                    .cards()
+                    .collect_vec(),
+                _ => vec![],
+            };
+
+            let title = format!(
+                "{} - {}",
+                self.database.all_players[player].name,
+                match location {
+                    Location::IN_GRAVEYARD => "Graveyard",
+                    Location::IN_EXILE => "Exile",
+                    _ => "Library",
+                }
+            );
+
+            let mut open = true;
+            let mut left_clicked = None;
+            let mut right_clicked = None;
+            egui::Window::new(title)
+                .frame(window_frame)
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    ui.add(ui::ZoneBrowser {
+                        db: &self.database,
+                        viewer: self.player1,
+                        cards: cards.iter().copied().enumerate().collect_vec(),
+                        sort: &mut self.browser_sort,
+                        left_clicked: &mut left_clicked,
+                        right_clicked: &mut right_clicked,
+                    });
+                });
+
+            if let Some(clicked) = left_clicked {
+                // Abilities which can be activated from this zone are offered in the actions bar.
+                self.selected_card = Some(cards[clicked]);
+            } else if let Some(clicked) = right_clicked {
+                self.inspecting_card = Some(cards[clicked]);
+            }
+
+            if !open
+                || (self.inspecting_card.is_none()
+                    && ctx.input(|input| input.key_released(egui::Key::Escape)))
+            {
+                self.browsing = None;
             }
         }
 
@@ -1027,6 +1477,25 @@ impl eframe::App for App {
             }
         }
 
+        if !self.database.turn.auto_resolved().is_empty() {
+            let mut open = true;
+            let mut dismissed = false;
+
+            egui::Window::new("Resolved automatically")
+                .frame(window_frame)
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    for auto_resolved in self.database.turn.auto_resolved() {
+                        ui.label(format!("•\t{}", auto_resolved.warning(&self.database)));
+                    }
+                    dismissed = ui.button("Dismiss").clicked();
+                });
+
+            if !open || dismissed {
+                self.database.turn.clear_auto_resolved();
+            }
+        }
+
         if self.adding_card.is_some() {
             let mut open = true;
 
@@ -1050,41 +1519,77 @@ impl eframe::App for App {
                         *adding = replace_expanded_symbols(adding);
                     }
 
-                    let query = self.parser.parse_query_lenient(adding).0;
-                    let top_docs = self
-                        .searcher
-                        .search(&query, &TopDocs::with_limit(10))
-                        .unwrap();
-
-                    let top = top_docs.get(0).map(|(_, addr)| {
-                        self.searcher
-                            .doc(*addr)
-                            .unwrap()
-                            .get_first(self.name)
-                            .unwrap()
-                            .as_text()
-                            .unwrap()
-                            .to_owned()
-                    });
+                    // Searches with filters such as `t:creature` are matched exactly, everything
+                    // else is a fuzzy search.
+                    let mut results = if adding.contains(':') {
+                        CardQuery::parse(adding)
+                            .map(|query| {
+                                self.cards
+                                    .search(&query)
+                                    .map(|(name, _)| name.clone())
+                                    .take(10)
+                                    .collect_vec()
+                            })
+                            .unwrap_or_default()
+                    } else {
+                        let query = self.parser.parse_query_lenient(adding).0;
+                        let top_docs = self
+                            .searcher
+                            .search(&query, &TopDocs::with_limit(10))
+                            .unwrap();
+
+                        top_docs
+                            .into_iter()
+                            .map(|(_, addr)| {
+                                self.searcher
+                                    .doc(addr)
+                                    .unwrap()
+                                    .get_first(self.name)
+                                    .unwrap()
+                                    .as_text()
+                                    .unwrap()
+                                    .to_owned()
+                            })
+                            .collect_vec()
+                    };
+                    // Favorites are listed first, otherwise keeping the order of the search.
+                    results.sort_by_key(|result| !self.card_metadata.is_favorite(result));
+                    let top = results.first().cloned();
 
                     let mut inspecting = None;
                     let mut clicked = None;
-                    for result in top_docs.into_iter().map(|(_, addr)| {
-                        self.searcher
-                            .doc(addr)
-                            .unwrap()
-                            .get_first(self.name)
-                            .unwrap()
-                            .as_text()
-                            .unwrap()
-                            .to_owned()
-                    }) {
-                        let label =
-                            ui.add(Label::new(format!("•\t{}", result)).sense(Sense::click()));
-                        if label.clicked() {
-                            clicked = Some(result);
-                        } else if label.clicked_by(egui::PointerButton::Secondary) {
-                            inspecting = Some(result);
+                    let mut favorited = None;
+                    for result in results {
+                        ui.horizontal(|ui| {
+                            let metadata = self.card_metadata.get(&result);
+                            let favorite = metadata.is_some_and(|metadata| metadata.favorite);
+                            if ui.selectable_label(favorite, "★").clicked() {
+                                favorited = Some(result.clone());
+                            }
+
+                            let mut label =
+                                ui.add(Label::new(format!("•\t{}", result)).sense(Sense::click()));
+                            if let Some(metadata) = metadata {
+                                if !metadata.tags.is_empty() || !metadata.notes.is_empty() {
+                                    label = label.on_hover_text(format!(
+                                        "{}\n{}",
+                                        metadata.tags.iter().join(", "),
+                                        metadata.notes
+                                    ));
+                                }
+                            }
+                            if label.clicked() {
+                                clicked = Some(result);
+                            } else if label.clicked_by(egui::PointerButton::Secondary) {
+                                inspecting = Some(result);
+                            }
+                        });
+                    }
+
+                    if let Some(favorited) = favorited {
+                        self.card_metadata.toggle_favorite(&favorited);
+                        if let Err(e) = settings::save_card_metadata(&self.card_metadata) {
+                            warn!("Failed to save card metadata: {:?}", e);
                         }
                     }
 
@@ -1120,33 +1625,130 @@ impl eframe::App for App {
                 self.adding_card = None;
             }
         }
-    }
-}
 
-fn cleanup_stack(
-    db: &mut Database,
-    to_resolve: &mut Option<PendingEffects>,
-    organizing_stack: &mut bool,
-) {
-    let mut pending = Stack::resolve_1(db);
-    while !pending.wants_input(db) {
-        let result = pending.resolve(db, None);
-        if result == SelectionResult::Complete {
-            break;
-        }
-    }
+        if self.settings_open {
+            let mut open = true;
+
+            egui::Window::new("Settings")
+                .frame(window_frame)
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    egui::Grid::new("Settings grid").show(ui, |ui| {
+                        ui.label("Player name");
+                        if ui
+                            .text_edit_singleline(&mut self.settings.player_name)
+                            .changed()
+                        {
+                            self.database.all_players[self.player1].name =
+                                self.settings.player_name.clone();
+                        }
+                        ui.end_row();
+
+                        ui.label("UI scale");
+                        if ui
+                            .add(egui::Slider::new(&mut self.settings.ui_scale, 0.5..=3.0))
+                            .changed()
+                        {
+                            ctx.set_zoom_factor(self.settings.ui_scale);
+                        }
+                        ui.end_row();
+
+                        ui.label("Hold priority");
+                        ui.checkbox(&mut self.hold_priority, "");
+                        ui.end_row();
+
+                        ui.label("Auto-pass");
+                        ui.checkbox(&mut self.database.all_players[self.player1].auto_pass, "");
+                        ui.end_row();
+
+                        ui.label("Format (next game)");
+                        egui::ComboBox::from_id_source("Format")
+                            .selected_text(self.settings.format.as_deref().unwrap_or("None"))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut self.settings.format, None, "None");
+                                for format in self.formats.iter() {
+                                    ui.selectable_value(
+                                        &mut self.settings.format,
+                                        Some(format.name.clone()),
+                                        &format.name,
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        let keybindings = &mut self.settings.keybindings;
+                        for (label, binding) in [
+                            ("Pass", &mut keybindings.pass),
+                            ("Tap lands", &mut keybindings.tap_lands),
+                            ("Untap all", &mut keybindings.untap_all),
+                            ("Infinite mana", &mut keybindings.infinite_mana),
+                            ("Draw", &mut keybindings.draw),
+                            ("Add card", &mut keybindings.add_card),
+                            ("Console", &mut keybindings.console),
+                        ] {
+                            ui.label(label);
+                            let valid = egui::Key::from_name(binding).is_some();
+                            ui.add(TextEdit::singleline(binding).text_color(if valid {
+                                Color32::WHITE
+                            } else {
+                                Color32::RED
+                            }));
+                            ui.end_row();
+                        }
+                    });
+
+                    if !self.settings.recent_decks.is_empty() {
+                        ui.separator();
+                        ui.label("Recent decks (load with --deck=<file> or --last-deck)");
+                        for deck in self.settings.recent_decks.iter() {
+                            ui.label(deck.display().to_string());
+                        }
+                    }
+
+                    ui.separator();
+                    if ui.button("Reset layout").clicked() {
+                        self.settings.layout = Default::default();
+                        self.layout_generation += 1;
+                        self.layout_changed = true;
+                    }
+                    if ui.button("Save").clicked() {
+                        self.settings.hold_priority = self.hold_priority;
+                        self.settings.auto_pass = self.database.all_players[self.player1].auto_pass;
+                        if let Err(e) = self.settings.save() {
+                            error!("Failed to save settings: {:?}", e);
+                        }
+                    }
+                });
 
-    if pending.is_empty() {
-        pending = Battlefields::check_sba(db);
-        while !pending.wants_input(db) {
-            let result = pending.resolve(db, None);
-            if result == SelectionResult::Complete {
-                break;
+            if !open {
+                self.settings_open = false;
             }
         }
+
+        #[cfg(feature = "console")]
+        if self.console.open {
+            self.console.show(
+                ctx,
+                &mut self.database,
+                &self.cards,
+                [self.player1, self.player2],
+            );
+        }
     }
+}
 
-    maybe_organize_stack(db, pending, to_resolve, organizing_stack);
+fn category_label(category: OptionCategory) -> Option<&'static str> {
+    match category {
+        OptionCategory::Card(Location::ON_BATTLEFIELD) => Some("Battlefield"),
+        OptionCategory::Card(Location::IN_HAND) => Some("Hand"),
+        OptionCategory::Card(Location::IN_LIBRARY) => Some("Library"),
+        OptionCategory::Card(Location::IN_GRAVEYARD) => Some("Graveyard"),
+        OptionCategory::Card(Location::IN_EXILE) => Some("Exile"),
+        OptionCategory::Card(Location::IN_STACK) | OptionCategory::Stack => Some("Stack"),
+        OptionCategory::Player => Some("Players"),
+        OptionCategory::Ability => Some("Abilities"),
+        _ => None,
+    }
 }
 
 fn maybe_organize_stack(
@@ -0,0 +1,90 @@
+//! Converts card definitions written in the legacy text-proto format into the YAML format used by
+//! piece-lib, so collections of older cards can still be loaded.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+use piece_lib::protogen::card::Card;
+use protobuf::text_format::parse_from_str;
+
+const DEFAULT_OUTPUT: &str = "experimental/yaml";
+
+const EXTENSIONS: &[&str] = &["textproto", "txtpb", "pbtxt"];
+
+fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let Some(input) = args.next() else {
+        return Err(anyhow!(
+            "Usage: migrate <card file | directory> [output directory]"
+        ));
+    };
+    let output = PathBuf::from(args.next().unwrap_or_else(|| DEFAULT_OUTPUT.to_string()));
+
+    let mut files = vec![];
+    collect_files(Path::new(&input), &mut files)?;
+    files.sort();
+
+    let mut failed = 0;
+    for file in files.iter() {
+        match migrate(file, &output) {
+            Ok(path) => println!("{} -> {}", file.display(), path.display()),
+            Err(e) => {
+                failed += 1;
+                eprintln!("{}: {:#}", file.display(), e);
+            }
+        }
+    }
+
+    println!("Migrated {} of {} cards", files.len() - failed, files.len());
+    if failed > 0 {
+        return Err(anyhow!("{} cards failed to migrate", failed));
+    }
+
+    Ok(())
+}
+
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    if path.is_dir() {
+        for entry in std::fs::read_dir(path).with_context(|| path.display().to_string())? {
+            let entry = entry?.path();
+            if entry.is_dir()
+                || entry
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| EXTENSIONS.contains(&ext))
+            {
+                collect_files(&entry, files)?;
+            }
+        }
+    } else {
+        files.push(path.to_path_buf());
+    }
+
+    Ok(())
+}
+
+fn migrate(file: &Path, output: &Path) -> anyhow::Result<PathBuf> {
+    let contents = std::fs::read_to_string(file)?;
+    let card = parse_from_str::<Card>(&contents)?;
+
+    let file_name = file
+        .file_stem()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow!("Invalid file name"))?;
+    let Some(prefix) = file_name
+        .trim_start_matches("the_")
+        .replace('+', "plus_")
+        .chars()
+        .next()
+    else {
+        return Err(anyhow!("Empty file name"));
+    };
+
+    let dir = output.join(prefix.to_string());
+    std::fs::create_dir_all(&dir)?;
+
+    let path = dir.join(file_name).with_extension("yaml");
+    std::fs::write(&path, serde_yaml::to_string(&card)?)?;
+
+    Ok(path)
+}
@@ -0,0 +1,158 @@
+//! Plays a match between two instances in the terminal. One instance hosts the game and the other
+//! joins it, and each player picks their actions by number.
+
+use std::{
+    io::{BufRead, Write},
+    net::TcpListener,
+};
+
+use anyhow::{anyhow, Context};
+use piece_lib::{
+    deck,
+    library::DeckDefinition,
+    load_cards,
+    net::{Client, Host, ServerMessage, Update},
+    Cards,
+};
+
+const USAGE: &str = "Usage: netplay <host | join> <address> <name> <deck file>";
+
+fn main() -> anyhow::Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let [mode, address, name, deck] = &args[..] else {
+        return Err(anyhow!(USAGE));
+    };
+
+    let cards = load_cards()?;
+    let deck = load_deck(&cards, deck)?;
+    match mode.as_str() {
+        "host" => host(&cards, address, name.clone(), deck),
+        "join" => join(address, name.clone(), &deck),
+        _ => Err(anyhow!(USAGE)),
+    }
+}
+
+fn host(cards: &Cards, address: &str, name: String, deck: DeckDefinition) -> anyhow::Result<()> {
+    let listener = TcpListener::bind(address).with_context(|| format!("Binding {}", address))?;
+    println!("Waiting for a player to join on {}", listener.local_addr()?);
+    let mut host = Host::accept(&listener, cards, name, deck)?;
+
+    loop {
+        if host.poll_remote()? {
+            continue;
+        }
+
+        let update = host.update();
+        print_update(&update);
+        if game_over(&update) {
+            return Ok(());
+        }
+        if update.actions.is_empty() {
+            continue;
+        }
+
+        loop {
+            match host.choose(read_choice()?) {
+                Ok(()) => break,
+                Err(e) => println!("Error: {:#}", e),
+            }
+        }
+    }
+}
+
+fn join(address: &str, name: String, deck: &DeckDefinition) -> anyhow::Result<()> {
+    let mut client = Client::connect(address, name, deck)?;
+
+    loop {
+        match client.recv()? {
+            ServerMessage::Update(update) => {
+                print_update(&update);
+                if game_over(&update) {
+                    return Ok(());
+                }
+                if !update.actions.is_empty() {
+                    client.choose(read_choice()?)?;
+                }
+            }
+            ServerMessage::Rejected { reason } => {
+                println!("Rejected: {}", reason);
+                client.choose(read_choice()?)?;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn load_deck(cards: &Cards, path: &str) -> anyhow::Result<DeckDefinition> {
+    let contents =
+        std::fs::read_to_string(path).with_context(|| format!("Reading deck: {}", path))?;
+    deck::parse_decklist(path, &contents, cards)
+}
+
+fn game_over(update: &Update) -> bool {
+    update.players.iter().any(|player| player.lost)
+}
+
+fn print_update(update: &Update) {
+    for event in update.events.iter() {
+        println!("  {}", event);
+    }
+
+    println!(
+        "\nTurn {} ({}) — {:?}",
+        update.turn + 1,
+        update.active_player,
+        update.phase
+    );
+    for player in update.players.iter() {
+        println!(
+            "{}: {} life, {} in hand, {} in library",
+            player.name, player.life_total, player.cards_in_hand, player.cards_in_library
+        );
+        for permanent in player.battlefield.iter() {
+            println!(
+                "  {}{}{}",
+                permanent.name,
+                if permanent.tapped { " (tapped)" } else { "" },
+                if permanent.counters.is_empty() {
+                    String::default()
+                } else {
+                    format!(" [{}]", permanent.counters.join(", "))
+                }
+            );
+        }
+        if !player.hand.is_empty() {
+            println!("  Hand: {}", player.hand.join(", "));
+        }
+        if !player.graveyard.is_empty() {
+            println!("  Graveyard: {}", player.graveyard.join(", "));
+        }
+    }
+    for entry in update.stack.iter().rev() {
+        println!("Stack: {}", entry);
+    }
+
+    if let Some(prompt) = update.prompt.as_ref() {
+        println!("{}", prompt);
+    }
+    for (index, action) in update.actions.iter().enumerate() {
+        println!("{:>3}: {}", index, action);
+    }
+}
+
+fn read_choice() -> anyhow::Result<usize> {
+    let stdin = std::io::stdin();
+    loop {
+        print!("> ");
+        std::io::stdout().flush()?;
+
+        let mut line = String::default();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            return Err(anyhow!("Input closed"));
+        }
+        match line.trim().parse() {
+            Ok(choice) => return Ok(choice),
+            Err(_) => println!("Enter the number of an action"),
+        }
+    }
+}
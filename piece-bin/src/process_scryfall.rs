@@ -196,18 +196,7 @@ fn parse_mana_cost(v: &str) -> Option<Vec<protobuf::EnumOrUnknown<ManaCost>>> {
                 results.push(ManaCost::GENERIC);
             }
         } else {
-            let cost = match symbol {
-                "W" => ManaCost::WHITE,
-                "U" => ManaCost::BLUE,
-                "B" => ManaCost::BLACK,
-                "R" => ManaCost::RED,
-                "G" => ManaCost::GREEN,
-                "X" => ManaCost::X,
-                "C" => ManaCost::COLORLESS,
-                _ => {
-                    return None;
-                }
-            };
+            let cost = ManaCost::from_symbol(symbol)?;
 
             if matches!(cost, ManaCost::X) {
                 if matches!(results.last(), Some(ManaCost::X)) {
@@ -0,0 +1,312 @@
+//! Loads a single card file from disk into a canned game so its author can cast or activate it and
+//! step through the results with tracing enabled. The file is read again on `reset`, so edits can
+//! be tried without restarting.
+
+use std::{
+    io::{BufRead, Write},
+    path::PathBuf,
+};
+
+use anyhow::{anyhow, Context};
+use itertools::Itertools;
+use piece_lib::{
+    battlefield::Battlefields,
+    debug,
+    effects::{Options, PendingEffects, SelectionResult},
+    in_play::{CardId, Database},
+    load_card_file, load_cards,
+    player::{AllPlayers, Owner, Player},
+    protogen::{card::Card, targets::Location},
+    stack::Stack,
+    trace::Trace,
+    turns::Phase,
+    Cards,
+};
+
+const HELP: &str = "Commands:
+  cast               cast the card from hand
+  activate <n>       activate the card's nth ability
+  choose <n>         choose an option for the pending effect
+  skip               resolve the pending effect without choosing an option
+  resolve            resolve the top of the stack
+  state              show the game
+  trace <file.dot>   write the trace so far and start a new one
+  reset              reload the card file and start over
+  help
+  quit";
+
+/// The cards each player has on the battlefield, for the card to target or interact with.
+const BATTLEFIELD: &[&str] = &["Alpine Grizzly", "Forest"];
+
+const LIBRARY_SIZE: usize = 20;
+
+struct Sandbox {
+    path: PathBuf,
+    cards: Cards,
+    db: Database,
+    players: [Owner; 2],
+    card: CardId,
+    pending: PendingEffects,
+}
+
+fn main() -> anyhow::Result<()> {
+    let Some(path) = std::env::args().nth(1) else {
+        return Err(anyhow!("Usage: sandbox <card.yaml>"));
+    };
+
+    tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_writer(std::io::stderr)
+        .with_line_number(true)
+        .with_file(true)
+        .with_target(false)
+        .init();
+
+    let path = PathBuf::from(path);
+    let card = load_card_file(&path)?;
+    let mut sandbox = Sandbox::new(path, load_cards()?, card)?;
+    sandbox.print_state();
+    println!("{}", HELP);
+
+    let stdin = std::io::stdin();
+    loop {
+        print!("> ");
+        std::io::stdout().flush()?;
+
+        let mut command = String::default();
+        if stdin.lock().read_line(&mut command)? == 0 {
+            break;
+        }
+
+        match sandbox.run(command.trim()) {
+            Ok(true) => break,
+            Ok(false) => {}
+            Err(e) => println!("Error: {:#}", e),
+        }
+    }
+
+    Ok(())
+}
+
+impl Sandbox {
+    fn new(path: PathBuf, mut cards: Cards, card: Card) -> anyhow::Result<Self> {
+        let name = card.name.clone();
+        cards.insert(name.clone(), card);
+
+        let mut all_players = AllPlayers::default();
+        let players = [
+            all_players.new_player("Author".to_string(), 20),
+            all_players.new_player("Opponent".to_string(), 20),
+        ];
+        let mut db = Database::new(all_players);
+        db.set_card_names(&cards);
+
+        for player in players {
+            for _ in 0..LIBRARY_SIZE {
+                debug::add_card(&mut db, &cards, player, "Forest", Location::IN_LIBRARY);
+            }
+            for name in BATTLEFIELD {
+                debug::add_card(&mut db, &cards, player, name, Location::ON_BATTLEFIELD);
+            }
+        }
+
+        let card = debug::add_card(&mut db, &cards, players[0], &name, Location::IN_HAND)
+            .ok_or_else(|| anyhow!("Failed to add {}", name))?;
+
+        debug::skip_to_phase(&mut db, Phase::PreCombatMainPhase);
+        Trace::start(&mut db);
+
+        Ok(Self {
+            path,
+            cards,
+            db,
+            players,
+            card,
+            pending: PendingEffects::default(),
+        })
+    }
+
+    /// Runs a single command, returning whether the sandbox should exit.
+    fn run(&mut self, command: &str) -> anyhow::Result<bool> {
+        // Mana is drained whenever the phase changes, so refill it before every command.
+        self.db.all_players[self.players[0]].infinite_mana();
+
+        let mut args = command.split_whitespace();
+        match args.next().unwrap_or_default() {
+            "cast" => {
+                self.expect_no_pending()?;
+                if !self.card.is_in_location(&self.db, Location::IN_HAND) {
+                    return Err(anyhow!("{} is not in hand", self.card.name(&self.db)));
+                }
+                self.pending = Player::play_card(&mut self.db, self.players[0], self.card);
+            }
+            "activate" => {
+                self.expect_no_pending()?;
+                let index = parse_index(args.next())?;
+                let abilities = self.db[self.card].abilities(&self.db).len();
+                if index >= abilities {
+                    return Err(anyhow!("The card has {} abilities", abilities));
+                }
+                self.pending = Battlefields::activate_ability(
+                    &mut self.db,
+                    &None,
+                    self.players[0],
+                    self.card,
+                    index,
+                );
+            }
+            "choose" => {
+                let option = parse_index(args.next())?;
+                let _ = self.pending.resolve(&mut self.db, Some(option));
+            }
+            "skip" => {
+                let _ = self.pending.resolve(&mut self.db, None);
+            }
+            "resolve" => {
+                self.expect_no_pending()?;
+                if self.db.stack.is_empty() {
+                    return Err(anyhow!("The stack is empty"));
+                }
+                self.pending = Stack::resolve_1(&mut self.db);
+            }
+            "state" => {
+                self.print_state();
+                return Ok(false);
+            }
+            "trace" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| anyhow!("Expected a file to write to"))?;
+                let trace = Trace::take(&mut self.db).unwrap_or_default();
+                std::fs::write(path, trace.to_dot())
+                    .with_context(|| format!("Writing trace: {}", path))?;
+                println!("Wrote {} events to {}", trace.nodes.len(), path);
+                Trace::start(&mut self.db);
+                return Ok(false);
+            }
+            "reset" => {
+                // Load the card before giving up the current game, in case it no longer parses.
+                let card = load_card_file(&self.path)?;
+                *self = Self::new(self.path.clone(), std::mem::take(&mut self.cards), card)?;
+                self.print_state();
+                return Ok(false);
+            }
+            "help" => {
+                println!("{}", HELP);
+                return Ok(false);
+            }
+            "quit" | "exit" => return Ok(true),
+            "" => return Ok(false),
+            command => return Err(anyhow!("Unknown command: {}", command)),
+        }
+
+        self.settle();
+        self.print_pending();
+        Ok(false)
+    }
+
+    fn expect_no_pending(&self) -> anyhow::Result<()> {
+        if self.pending.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!("Finish the pending choice first"))
+        }
+    }
+
+    /// Resolves everything which doesn't need a choice, then checks state-based actions.
+    fn settle(&mut self) {
+        while !self.pending.is_empty() && !self.pending.wants_input(&self.db) {
+            if self.pending.resolve(&mut self.db, None) == SelectionResult::Complete {
+                break;
+            }
+        }
+
+        if self.pending.is_empty() {
+            self.pending = Battlefields::check_sba(&mut self.db);
+            while !self.pending.is_empty() && !self.pending.wants_input(&self.db) {
+                if self.pending.resolve(&mut self.db, None) == SelectionResult::Complete {
+                    break;
+                }
+            }
+        }
+    }
+
+    fn print_pending(&self) {
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let (kind, options) = match self.pending.options(&self.db) {
+            Options::MandatoryList(options) => ("Choose one", options),
+            Options::OptionalList(options) => ("Choose one or skip", options),
+            Options::ListWithDefault(options) => ("Choose one or skip for the default", options),
+        };
+        println!(
+            "{} ({}):",
+            kind,
+            self.db.all_players[self.pending.priority(&self.db)].name
+        );
+        for (index, description) in options {
+            println!("  {}: {}", index, description);
+        }
+    }
+
+    fn print_state(&self) {
+        println!(
+            "Card: {} ({})",
+            self.card.name(&self.db),
+            self.path.display()
+        );
+        for player in self.players {
+            println!(
+                "{} - {} life, {} cards in hand",
+                self.db.all_players[player].name,
+                self.db.all_players[player].life_total,
+                self.db.hand[player].len()
+            );
+            println!(
+                "  Battlefield: {}",
+                self.db.battlefield[player]
+                    .iter()
+                    .map(|card| describe(&self.db, *card))
+                    .join(", ")
+            );
+            println!(
+                "  Graveyard: {}",
+                self.db.graveyard[player]
+                    .iter()
+                    .map(|card| card.name(&self.db))
+                    .join(", ")
+            );
+            println!(
+                "  Exile: {}",
+                self.db.exile[player]
+                    .iter()
+                    .map(|card| card.name(&self.db))
+                    .join(", ")
+            );
+        }
+
+        println!("Stack:");
+        for entry in self.db.stack.entries().values().rev() {
+            println!("  {}", entry.display(&self.db));
+        }
+    }
+}
+
+fn describe(db: &Database, card: CardId) -> String {
+    let mut description = card.name(db).clone();
+    if let Some(pt) = card.pt_text(db) {
+        description.push_str(&format!(" {}", pt));
+    }
+    if card.tapped(db) {
+        description.push_str(" (tapped)");
+    }
+    description
+}
+
+fn parse_index(arg: Option<&str>) -> anyhow::Result<usize> {
+    arg.ok_or_else(|| anyhow!("Expected a number"))?
+        .parse()
+        .context("Expected a number")
+}
@@ -0,0 +1,201 @@
+//! Persistent user settings, stored as yaml in the platform's config directory. Missing or
+//! unreadable settings fall back to the defaults so a bad file never prevents the game from
+//! starting.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use piece_lib::{card_metadata::CardMetadataStore, config::GameConfig};
+use serde::{Deserialize, Serialize};
+
+/// The most decks remembered in [Settings::recent_decks].
+const MAX_RECENT_DECKS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Keybindings {
+    pub pass: String,
+    pub tap_lands: String,
+    pub untap_all: String,
+    pub infinite_mana: String,
+    pub draw: String,
+    pub add_card: String,
+    pub console: String,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        Self {
+            pass: egui::Key::Num1.name().to_string(),
+            tap_lands: egui::Key::T.name().to_string(),
+            untap_all: egui::Key::Num2.name().to_string(),
+            infinite_mana: egui::Key::Num3.name().to_string(),
+            draw: egui::Key::Num4.name().to_string(),
+            add_card: egui::Key::Num5.name().to_string(),
+            console: egui::Key::Backtick.name().to_string(),
+        }
+    }
+}
+
+impl Keybindings {
+    /// Whether the key bound to an action was released this frame. Unknown key names are never
+    /// released.
+    pub fn released(ctx: &egui::Context, binding: &str) -> bool {
+        egui::Key::from_name(binding)
+            .map(|key| ctx.input(|input| input.key_released(key)))
+            .unwrap_or(false)
+    }
+}
+
+/// The sizes of the game window's resizable panels, in points. Each panel is resized by dragging
+/// its edge, and the remaining space goes to the player's battlefield.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelLayout {
+    /// The width of the column with the stack, log, and each player's mana.
+    pub stack_width: f32,
+    /// The width of the column with each player's graveyard and exile.
+    pub zones_width: f32,
+    pub mana_height: f32,
+    pub log_height: f32,
+    pub exile_height: f32,
+    pub opponent_graveyard_height: f32,
+    pub opponent_battlefield_height: f32,
+    pub actions_height: f32,
+    pub hand_height: f32,
+}
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        Self {
+            stack_width: 250.0,
+            zones_width: 250.0,
+            mana_height: 150.0,
+            log_height: 200.0,
+            exile_height: 150.0,
+            opponent_graveyard_height: 250.0,
+            opponent_battlefield_height: 350.0,
+            actions_height: 50.0,
+            hand_height: 150.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub player_name: String,
+    pub ui_scale: f32,
+    pub auto_pass: bool,
+    pub hold_priority: bool,
+    pub keybindings: Keybindings,
+    /// The rules new games are started with, unless they are played in a [Settings::format].
+    pub game: GameConfig,
+    /// The name of the format new games are played in, if any. Decks must be legal in it.
+    pub format: Option<String>,
+    /// Deck files passed with `--deck`, most recent first.
+    pub recent_decks: Vec<PathBuf>,
+    pub layout: PanelLayout,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            player_name: "Player 1".to_string(),
+            ui_scale: 1.0,
+            auto_pass: false,
+            hold_priority: false,
+            keybindings: Default::default(),
+            game: Default::default(),
+            format: None,
+            recent_decks: vec![],
+            layout: Default::default(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn path() -> Option<PathBuf> {
+        dirs_next::config_dir().map(|dir| dir.join("piece").join("settings.yaml"))
+    }
+
+    /// Loads the settings from the config directory, or the defaults if there are none.
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+
+        match Self::load_from(&path) {
+            Ok(settings) => settings,
+            Err(e) => {
+                if path.exists() {
+                    warn!("Failed to load settings from {}: {:?}", path.display(), e);
+                }
+                Self::default()
+            }
+        }
+    }
+
+    pub fn load_from(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Reading settings: {}", path.display()))?;
+        Ok(serde_yaml::from_str(&contents)?)
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = Self::path().context("No config directory")?;
+        self.save_to(&path)
+    }
+
+    pub fn save_to(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Writing settings: {}", path.display()))
+    }
+
+    /// Saves `layout` without saving any other changes which haven't been saved yet.
+    pub fn save_layout(layout: &PanelLayout) -> anyhow::Result<()> {
+        let mut saved = Self::load();
+        saved.layout = layout.clone();
+        saved.save()
+    }
+
+    /// Moves the deck to the front of the recently used decks.
+    pub fn used_deck(&mut self, deck: PathBuf) {
+        self.recent_decks.retain(|recent| *recent != deck);
+        self.recent_decks.insert(0, deck);
+        self.recent_decks.truncate(MAX_RECENT_DECKS);
+    }
+}
+
+pub fn card_metadata_path() -> Option<PathBuf> {
+    dirs_next::config_dir().map(|dir| dir.join("piece").join("card_metadata.yaml"))
+}
+
+/// Loads the user's card metadata from the config directory, or an empty store if there is none.
+pub fn load_card_metadata() -> CardMetadataStore {
+    let Some(path) = card_metadata_path() else {
+        return CardMetadataStore::default();
+    };
+
+    match CardMetadataStore::load_from(&path) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            if path.exists() {
+                warn!(
+                    "Failed to load card metadata from {}: {:?}",
+                    path.display(),
+                    e
+                );
+            }
+            CardMetadataStore::default()
+        }
+    }
+}
+
+pub fn save_card_metadata(metadata: &CardMetadataStore) -> anyhow::Result<()> {
+    let path = card_metadata_path().context("No config directory")?;
+    metadata.save_to(&path)
+}
@@ -2,15 +2,18 @@ use convert_case::{Case, Casing};
 use egui::{
     vec2, Color32, Frame, Label, Layout, PointerButton, RichText, ScrollArea, Sense, Stroke, Widget,
 };
-use indexmap::IndexMap;
 use itertools::Itertools;
 
 use piece_lib::{
+    battlefield::Battlefields,
+    combat::CombatPreview,
     effects::PendingEffects,
     in_play::{CardId, Database},
+    log::Log,
+    mana::{render_symbols, Symbol},
     player::Owner,
     protogen::{keywords::Keyword, targets::Location},
-    stack::{Selected, StackEntry, StackId, TargetType},
+    stack::{Selected, StackGroup, TargetType},
     turns::Turn,
 };
 use protobuf::Enum;
@@ -123,6 +126,9 @@ impl Widget for Card<'_> {
         let counters = source.counter_text_on();
         let has_counters = !counters.is_empty();
 
+        let keyword_counters = source.keyword_counter_text_on();
+        let has_keyword_counters = !keyword_counters.is_empty();
+
         let paragraph = std::iter::once(oracle_text)
             .chain(std::iter::once(String::default()).filter(|_| has_oracle_text))
             .chain(etb_text)
@@ -140,6 +146,17 @@ impl Widget for Card<'_> {
             .chain(std::iter::once(String::default()).filter(|_| is_modified))
             .chain(std::iter::once("Counters:".to_string()).filter(|_| has_counters))
             .chain(counters.into_iter().map(|counter| format!("  {}", counter)))
+            .chain(
+                std::iter::once(String::default()).filter(|_| has_counters && has_keyword_counters),
+            )
+            .chain(
+                std::iter::once("Keyword counters:".to_string()).filter(|_| has_keyword_counters),
+            )
+            .chain(
+                keyword_counters
+                    .into_iter()
+                    .map(|counter| format!("  {}", counter)),
+            )
             .join("\n");
 
         Frame::none()
@@ -149,6 +166,8 @@ impl Widget for Card<'_> {
                 2.0,
                 if self.highlight {
                     Color32::DARK_BLUE
+                } else if self.card.attacking(self.db).is_some() {
+                    Color32::DARK_RED
                 } else if self.card.summoning_sick(self.db) {
                     Color32::LIGHT_BLUE
                 } else {
@@ -170,9 +189,30 @@ impl Widget for Card<'_> {
                     ui.separator();
                     ui.add(Label::new(typeline));
 
+                    if let Some(target) = self.card.attacking(self.db) {
+                        ui.add(Label::new(
+                            RichText::new(format!(
+                                "Attacking {}",
+                                match self.card.attacking_planeswalker(self.db) {
+                                    Some(planeswalker) => planeswalker.name(self.db).clone(),
+                                    None => self.db.all_players[target].name.clone(),
+                                }
+                            ))
+                            .color(Color32::RED),
+                        ));
+                    }
+
                     if let Some(pt) = self.card.pt_text(self.db) {
                         ui.with_layout(Layout::right_to_left(egui::Align::Min), |ui| {
                             ui.add(Label::new(pt));
+                            if self.card.marked_damage(self.db) > 0 {
+                                if let Some(lethal) = self.card.lethal_damage_remaining(self.db) {
+                                    ui.add(Label::new(
+                                        RichText::new(format!("{} to lethal", lethal))
+                                            .color(Color32::RED),
+                                    ));
+                                }
+                            }
                         });
                     }
                 });
@@ -210,14 +250,52 @@ impl Widget for ManaDisplay {
     }
 }
 
-pub struct Stack<'stack, 'db, 'clicked> {
-    pub items: &'stack IndexMap<StackId, StackEntry>,
+pub struct CombatSummary<'db> {
     pub db: &'db Database,
+    pub preview: CombatPreview,
+}
+
+impl Widget for CombatSummary<'_> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        ui.with_layout(Layout::top_down(egui::Align::Min), |ui| {
+            for attack in self.preview.attacks.iter() {
+                ui.label(format!(
+                    "{} -> {} ({})",
+                    attack.attacker.name(self.db),
+                    self.db.all_players[attack.target].name,
+                    attack.damage
+                ));
+            }
+            for card in self.preview.dying.iter() {
+                ui.label(RichText::new(format!("{} dies", card.name(self.db))).color(Color32::RED));
+            }
+            ui.separator();
+
+            for player in self.db.all_players.all_players() {
+                let text = RichText::new(format!(
+                    "{}: {} -> {}",
+                    self.db.all_players[player].name,
+                    self.db.all_players[player].life_total,
+                    self.preview.life_totals[&player]
+                ));
+                ui.label(if self.preview.losing.contains(&player) {
+                    text.color(Color32::RED)
+                } else {
+                    text
+                });
+            }
+        })
+        .response
+    }
+}
+
+pub struct Stack<'clicked> {
+    pub items: Vec<StackGroup>,
     pub left_clicked: &'clicked mut Option<usize>,
     pub target: Option<Selected>,
 }
 
-impl Widget for Stack<'_, '_, '_> {
+impl Widget for Stack<'_> {
     fn ui(self, ui: &mut egui::Ui) -> egui::Response {
         Frame::none()
             .stroke(Stroke::new(2.0, Color32::DARK_GRAY))
@@ -233,17 +311,24 @@ impl Widget for Stack<'_, '_, '_> {
                         .auto_shrink([false, false])
                         .show(ui, |ui| {
                             ui.with_layout(Layout::top_down(egui::Align::Min), |ui| {
-                                for (idx, (stack_id, entry)) in self.items.iter().rev().enumerate()
-                                {
+                                for (idx, group) in self.items.iter().rev().enumerate() {
                                     let highlight = if let Some(TargetType::Stack(id)) =
                                         self.target.as_ref().map(|target| &target.target_type)
                                     {
-                                        id == stack_id
+                                        group.ids.contains(id)
                                     } else {
                                         false
                                     };
 
-                                    let text = RichText::new(entry.display(self.db));
+                                    let text = if group.len() > 1 {
+                                        RichText::new(format!(
+                                            "{} (x{})",
+                                            group.display,
+                                            group.len()
+                                        ))
+                                    } else {
+                                        RichText::new(&group.display)
+                                    };
                                     let text = if highlight {
                                         text.color(Color32::DARK_BLUE)
                                     } else {
@@ -266,6 +351,7 @@ pub struct Exile<'clicked> {
     pub player: Owner,
     pub cards: Vec<String>,
     pub right_clicked: &'clicked mut Option<usize>,
+    pub browse: &'clicked mut bool,
 }
 
 impl Widget for Exile<'_> {
@@ -276,7 +362,13 @@ impl Widget for Exile<'_> {
             .outer_margin(2.0)
             .show(ui, |ui| {
                 ui.with_layout(Layout::top_down(egui::Align::Min), |ui| {
-                    ui.heading("Exile");
+                    if ui
+                        .add(Label::new(RichText::new("Exile").heading()).sense(Sense::click()))
+                        .on_hover_text("Browse")
+                        .clicked()
+                    {
+                        *self.browse = true;
+                    }
                     ui.separator();
                     ui.expand_to_include_rect(ui.max_rect());
                     ScrollArea::vertical()
@@ -301,6 +393,7 @@ pub struct Graveyard<'clicked> {
     pub player: Owner,
     pub cards: Vec<String>,
     pub right_clicked: &'clicked mut Option<usize>,
+    pub browse: &'clicked mut bool,
 }
 
 impl Widget for Graveyard<'_> {
@@ -311,7 +404,13 @@ impl Widget for Graveyard<'_> {
             .outer_margin(2.0)
             .show(ui, |ui| {
                 ui.with_layout(Layout::top_down(egui::Align::Min), |ui| {
-                    ui.heading("Graveyard");
+                    if ui
+                        .add(Label::new(RichText::new("Graveyard").heading()).sense(Sense::click()))
+                        .on_hover_text("Browse")
+                        .clicked()
+                    {
+                        *self.browse = true;
+                    }
                     ui.separator();
                     ui.expand_to_include_rect(ui.max_rect());
                     ScrollArea::vertical()
@@ -335,7 +434,9 @@ impl Widget for Graveyard<'_> {
 pub struct Hand<'db, 'clicked> {
     pub db: &'db Database,
     pub owner: Owner,
-    pub cards: Vec<CardId>,
+    /// The cards the owner may play as though they were in hand, and the zone each is in. Cards
+    /// which aren't in hand are badged with their zone.
+    pub cards: Vec<(CardId, Location)>,
     pub hovered: &'clicked mut Option<usize>,
     pub left_clicked: &'clicked mut Option<usize>,
     pub right_clicked: &'clicked mut Option<usize>,
@@ -350,38 +451,25 @@ impl Widget for Hand<'_, '_> {
             .show(ui, |ui| {
                 ui.expand_to_include_rect(ui.max_rect());
                 ui.horizontal(|ui| {
-                    ScrollArea::horizontal().id_source("Hand").show(ui, |ui| {
-                        const MIN_WIDTH: f32 = 200.0;
-                        const MIN_HEIGHT: f32 = 300.0;
-                        let mut rects = vec![];
-
-                        let mut hovered = false;
-                        for index in 0..self.cards.len() {
-                            let (rect, sense) =
-                                ui.allocate_exact_size(vec2(MIN_WIDTH, MIN_HEIGHT), Sense::click());
-
-                            rects.push(rect);
-                            if sense.hovered() {
-                                hovered = true;
-                                *self.hovered = Some(index);
-                            };
-
-                            if sense.clicked_by(PointerButton::Primary) {
-                                *self.left_clicked = Some(index);
-                            } else if sense.clicked_by(PointerButton::Secondary) {
-                                *self.right_clicked = Some(index);
-                            }
-                        }
-
-                        for (index, (mut rect, card)) in
-                            rects.into_iter().zip(self.cards).enumerate()
-                        {
-                            if Some(index) == *self.hovered {
-                                rect = rect.translate(vec2(0.0, -MIN_HEIGHT));
-                                let sense = ui.allocate_rect(rect, Sense::click());
+                    ScrollArea::horizontal()
+                        .id_source(format!("hand {:?}", self.owner))
+                        .show(ui, |ui| {
+                            const MIN_WIDTH: f32 = 200.0;
+                            const MIN_HEIGHT: f32 = 300.0;
+                            let mut rects = vec![];
+
+                            let mut hovered = false;
+                            for index in 0..self.cards.len() {
+                                let (rect, sense) = ui.allocate_exact_size(
+                                    vec2(MIN_WIDTH, MIN_HEIGHT),
+                                    Sense::click(),
+                                );
+
+                                rects.push(rect);
                                 if sense.hovered() {
                                     hovered = true;
-                                }
+                                    *self.hovered = Some(index);
+                                };
 
                                 if sense.clicked_by(PointerButton::Primary) {
                                     *self.left_clicked = Some(index);
@@ -389,19 +477,55 @@ impl Widget for Hand<'_, '_> {
                                     *self.right_clicked = Some(index);
                                 }
                             }
-                            ui.put(
-                                rect,
-                                Card {
-                                    db: self.db,
-                                    card,
-                                    highlight: false,
-                                },
-                            );
-                        }
-                        if !hovered {
-                            *self.hovered = None;
-                        }
-                    });
+
+                            for (index, (mut rect, (card, location))) in
+                                rects.into_iter().zip(self.cards).enumerate()
+                            {
+                                if Some(index) == *self.hovered {
+                                    rect = rect.translate(vec2(0.0, -MIN_HEIGHT));
+                                    let sense = ui.allocate_rect(rect, Sense::click());
+                                    if sense.hovered() {
+                                        hovered = true;
+                                    }
+
+                                    if sense.clicked_by(PointerButton::Primary) {
+                                        *self.left_clicked = Some(index);
+                                    } else if sense.clicked_by(PointerButton::Secondary) {
+                                        *self.right_clicked = Some(index);
+                                    }
+                                }
+                                ui.put(
+                                    rect,
+                                    Card {
+                                        db: self.db,
+                                        card,
+                                        highlight: false,
+                                    },
+                                );
+
+                                let badge = match location {
+                                    Location::IN_GRAVEYARD => Some("Graveyard"),
+                                    Location::IN_EXILE => Some("Exile"),
+                                    _ => None,
+                                };
+                                if let Some(badge) = badge {
+                                    ui.put(
+                                        egui::Rect::from_min_size(
+                                            rect.right_bottom() - vec2(80.0, 25.0),
+                                            vec2(75.0, 20.0),
+                                        ),
+                                        Label::new(
+                                            RichText::new(badge)
+                                                .small()
+                                                .color(Color32::LIGHT_YELLOW),
+                                        ),
+                                    );
+                                }
+                            }
+                            if !hovered {
+                                *self.hovered = None;
+                            }
+                        });
                 });
             })
             .response
@@ -415,6 +539,8 @@ pub struct Battlefield<'db, 'clicked> {
     pub left_clicked: &'clicked mut Option<usize>,
     pub right_clicked: &'clicked mut Option<usize>,
     pub target: Option<Selected>,
+    /// Cards which would be affected by the option being hovered, highlighted along with the target.
+    pub affected: &'clicked [CardId],
 }
 
 impl Widget for Battlefield<'_, '_> {
@@ -450,7 +576,7 @@ impl Widget for Battlefield<'_, '_> {
                                 *id == card
                             } else {
                                 false
-                            };
+                            } || self.affected.contains(&card);
 
                             let (rect, sense) =
                                 ui.allocate_exact_size(vec2(MIN_WIDTH, MIN_HEIGHT), Sense::click());
@@ -477,6 +603,149 @@ impl Widget for Battlefield<'_, '_> {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BrowserSort {
+    #[default]
+    Zone,
+    Name,
+    ManaValue,
+    Type,
+}
+
+pub struct ZoneBrowser<'db, 'clicked> {
+    pub db: &'db Database,
+    pub viewer: Owner,
+    pub cards: Vec<(usize, CardId)>,
+    pub sort: &'clicked mut BrowserSort,
+    pub left_clicked: &'clicked mut Option<usize>,
+    pub right_clicked: &'clicked mut Option<usize>,
+}
+
+impl Widget for ZoneBrowser<'_, '_> {
+    fn ui(mut self, ui: &mut egui::Ui) -> egui::Response {
+        ui.horizontal(|ui| {
+            ui.label("Sort by");
+            ui.selectable_value(self.sort, BrowserSort::Zone, "Zone order");
+            ui.selectable_value(self.sort, BrowserSort::Name, "Name");
+            ui.selectable_value(self.sort, BrowserSort::ManaValue, "Mana value");
+            ui.selectable_value(self.sort, BrowserSort::Type, "Type");
+        });
+        ui.separator();
+
+        let hidden = self
+            .cards
+            .iter()
+            .filter(|(_, card)| !card.visible_to(self.db, self.viewer))
+            .count();
+        self.cards
+            .retain(|(_, card)| card.visible_to(self.db, self.viewer));
+
+        match self.sort {
+            BrowserSort::Zone => {}
+            BrowserSort::Name => self
+                .cards
+                .sort_by_cached_key(|(_, card)| card.name(self.db).clone()),
+            BrowserSort::ManaValue => self.cards.sort_by_cached_key(|(_, card)| {
+                (
+                    self.db[*card].modified_cost.cmc(),
+                    card.name(self.db).clone(),
+                )
+            }),
+            BrowserSort::Type => self.cards.sort_by_cached_key(|(_, card)| {
+                let mut types = self.db[*card].modified_types.iter().cloned().collect_vec();
+                types.sort();
+                (types, card.name(self.db).clone())
+            }),
+        }
+
+        if hidden > 0 {
+            ui.label(format!("{} hidden cards", hidden));
+        }
+
+        ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .max_height(640.0)
+            .show(ui, |ui| {
+                ui.with_layout(
+                    Layout::left_to_right(egui::Align::Min).with_main_wrap(true),
+                    |ui| {
+                        const MIN_WIDTH: f32 = 200.0;
+                        const MIN_HEIGHT: f32 = 300.0;
+
+                        for (idx, card) in self.cards {
+                            let (rect, sense) =
+                                ui.allocate_exact_size(vec2(MIN_WIDTH, MIN_HEIGHT), Sense::click());
+                            ui.put(
+                                rect,
+                                Card {
+                                    db: self.db,
+                                    card,
+                                    highlight: false,
+                                },
+                            );
+
+                            let sense = match Log::left_battlefield_cause(self.db, card) {
+                                Some(cause) => sense.on_hover_text(format!(
+                                    "Left the battlefield because of: {}",
+                                    cause.describe(self.db)
+                                )),
+                                None => sense,
+                            };
+
+                            if sense.clicked_by(PointerButton::Primary) {
+                                *self.left_clicked = Some(idx)
+                            } else if sense.clicked_by(PointerButton::Secondary) {
+                                *self.right_clicked = Some(idx);
+                            }
+                        }
+                    },
+                )
+                .response
+            })
+            .inner
+    }
+}
+
+fn activation_preview(
+    db: &Database,
+    pending: &Option<PendingEffects>,
+    player: Owner,
+    card: CardId,
+    ability: usize,
+) -> Option<String> {
+    let preview = Battlefields::activation_preview(db, pending, player, card, ability)?;
+
+    let mut lines = vec![];
+    match (preview.mana_text.is_empty(), preview.tap) {
+        (true, true) => lines.push(render_symbols([Symbol::Tap])),
+        (false, true) => lines.push(format!(
+            "{}, {}",
+            preview.mana_text,
+            render_symbols([Symbol::Tap])
+        )),
+        (false, false) => lines.push(preview.mana_text),
+        (true, false) => {}
+    }
+    for (mana, life) in preview.life_alternatives {
+        lines.push(format!(
+            "May pay {} life for {}",
+            life,
+            render_symbols(Symbol::from_mana_cost(&[mana.into()]))
+        ));
+    }
+    lines.extend(preview.additional_costs);
+    if let Some(targets) = preview.targets {
+        lines.push(format!(
+            "{}{} target(s), {} available",
+            if targets.optional { "Up to " } else { "" },
+            targets.count,
+            targets.available
+        ));
+    }
+
+    Some(lines.join("\n"))
+}
+
 pub struct Actions<'db, 'p, 'clicked> {
     pub db: &'db Database,
     pub player: Owner,
@@ -488,8 +757,8 @@ pub struct Actions<'db, 'p, 'clicked> {
 impl Widget for Actions<'_, '_, '_> {
     fn ui(self, ui: &mut egui::Ui) -> egui::Response {
         let abilities = if let Some(card) = self.card {
-            if card.is_in_location(self.db, Location::IN_HAND) && Turn::can_cast(self.db, card) {
-                [(0, format!("Play {}", card.name(self.db)))]
+            if card.can_play_as_though_in_hand(self.db) && Turn::can_cast(self.db, card) {
+                [(0, format!("Play {}", card.name(self.db)), None)]
                     .into_iter()
                     .chain(
                         self.db[card]
@@ -503,12 +772,33 @@ impl Widget for Actions<'_, '_, '_> {
                                     self.player,
                                     self.pending,
                                 ) {
-                                    Some((idx + 1, ability.text(self.db)))
+                                    Some((
+                                        idx + 1,
+                                        ability.text(self.db),
+                                        activation_preview(
+                                            self.db,
+                                            self.pending,
+                                            self.player,
+                                            card,
+                                            idx,
+                                        ),
+                                    ))
                                 } else {
                                     None
                                 }
                             }),
                     )
+                    .chain(
+                        (card.faceup_face(self.db).bestow.is_some()
+                            && card.is_in_location(self.db, Location::IN_HAND))
+                        .then(|| {
+                            (
+                                self.db[card].abilities(self.db).len() + 1,
+                                format!("Bestow {}", card.name(self.db)),
+                                None,
+                            )
+                        }),
+                    )
                     .collect_vec()
             } else {
                 self.db[card]
@@ -517,7 +807,11 @@ impl Widget for Actions<'_, '_, '_> {
                     .enumerate()
                     .filter_map(|(idx, (_, ability))| {
                         if ability.can_be_activated(self.db, card, self.player, self.pending) {
-                            Some((idx, ability.text(self.db)))
+                            Some((
+                                idx,
+                                ability.text(self.db),
+                                activation_preview(self.db, self.pending, self.player, card, idx),
+                            ))
                         } else {
                             None
                         }
@@ -539,8 +833,12 @@ impl Widget for Actions<'_, '_, '_> {
                     ScrollArea::horizontal()
                         .id_source("Actions")
                         .show(ui, |ui| {
-                            for (index, action) in abilities.into_iter() {
-                                if ui.button(action).clicked() {
+                            for (index, action, preview) in abilities.into_iter() {
+                                let mut button = ui.button(action);
+                                if let Some(preview) = preview {
+                                    button = button.on_hover_text(preview);
+                                }
+                                if button.clicked() {
                                     *self.left_clicked = Some(index);
                                 };
                                 ui.separator();
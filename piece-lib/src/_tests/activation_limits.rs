@@ -0,0 +1,104 @@
+use crate::{
+    battlefield::Battlefields,
+    effects::SelectionResult,
+    in_play::Database,
+    turns::{Phase, Turn},
+};
+
+const TEST_ONCE_EACH_TURN: &str = r#"
+name: Test Once Each Turn
+typeline: Artifact
+activated_abilities:
+  - cost:
+      restrictions:
+        - restriction: !OncePerTurn {}
+    effects:
+      - effect: !SelectSource {}
+    oracle_text: 'Do nothing. Activate only once each turn.'
+"#;
+
+const TEST_ONCE_EACH_GAME: &str = r#"
+name: Test Once Each Game
+typeline: Artifact
+activated_abilities:
+  - cost:
+      restrictions:
+        - restriction: !OncePerGame {}
+    effects:
+      - effect: !SelectSource {}
+    oracle_text: 'Do nothing. Activate only once each game.'
+"#;
+
+const TEST_MANA_ONCE_EACH_GAME: &str = r#"
+name: Test Mana Once Each Game
+typeline: Artifact
+mana_abilities:
+  - cost:
+      restrictions:
+        - restriction: !OncePerGame {}
+    effects:
+      - effect: !GainMana
+          gain: !Specific
+            gain: '{B}'
+          mana_source: any
+          mana_restriction: none
+    oracle_text: 'Add {B}. Activate only once each game.'
+"#;
+
+fn next_turn(db: &mut Database) {
+    db.turn.set_phase(Phase::Cleanup);
+    let _ = Turn::step(db);
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+}
+
+#[test]
+fn once_each_turn() -> anyhow::Result<()> {
+    let mut game = game! {};
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+    let card = game.upload_yaml(game.p1, TEST_ONCE_EACH_TURN)?;
+    card.move_to_battlefield(&mut game.db);
+
+    assert!(game.activate(game.p1, card, 0));
+    assert!(!game.activate(game.p1, card, 0));
+
+    next_turn(&mut game.db);
+    assert!(game.activate(game.p1, card, 0));
+
+    Ok(())
+}
+
+#[test]
+fn once_each_game() -> anyhow::Result<()> {
+    let mut game = game! {};
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+    let card = game.upload_yaml(game.p1, TEST_ONCE_EACH_GAME)?;
+    card.move_to_battlefield(&mut game.db);
+
+    assert!(game.activate(game.p1, card, 0));
+    assert!(!game.activate(game.p1, card, 0));
+
+    // The limit lasts for the rest of the game, even if the card leaves and returns.
+    next_turn(&mut game.db);
+    card.move_to_hand(&mut game.db);
+    card.move_to_battlefield(&mut game.db);
+    assert!(!game.activate(game.p1, card, 0));
+
+    Ok(())
+}
+
+#[test]
+fn mana_once_each_game() -> anyhow::Result<()> {
+    let mut game = game! {};
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+    let card = game.upload_yaml(game.p1, TEST_MANA_ONCE_EACH_GAME)?;
+    card.move_to_battlefield(&mut game.db);
+
+    let mut results = Battlefields::activate_ability(&mut game.db, &None, game.p1, card, 0);
+    assert!(!results.is_empty());
+    while results.resolve(&mut game.db, None) != SelectionResult::Complete {}
+
+    let results = Battlefields::activate_ability(&mut game.db, &None, game.p1, card, 0);
+    assert!(results.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    battlefield::{Battlefields, TargetsPreview},
+    turns::Phase,
+};
+
+#[test]
+fn previews_costs_and_targets() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Blasting Station", "Alpine Grizzly"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let player = game.p1;
+    let station = game.card(player, "Blasting Station");
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let preview = Battlefields::activation_preview(&game.db, &None, player, station, 0).unwrap();
+    assert!(preview.mana_cost.is_empty());
+    assert!(preview.tap);
+    assert_eq!(preview.additional_costs, ["Sacrifice a permanent"]);
+    // Either player or either bear.
+    assert_eq!(
+        preview.targets,
+        Some(TargetsPreview {
+            count: 1,
+            optional: false,
+            available: 4,
+        })
+    );
+    assert!(preview.can_activate);
+
+    // Previewing doesn't activate the ability.
+    assert!(!station.tapped(&game.db));
+    assert!(Battlefields::activation_preview(&game.db, &None, player, station, 1).is_none());
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{EffectBundle, PendingEffects},
+    protogen::effects::Effect,
+};
+
+const DESTROY_OR_NOTHING: &str = r#"
+- effect: !SelectMode
+    descriptions:
+      - Destroy all creatures.
+      - Do nothing.
+- effect: !Modal
+    modes:
+      - effects:
+          - effect: !SelectAll
+              restrictions:
+                - restriction: !OfType
+                    types: Creature
+                - restriction: !OnBattlefield {}
+          - effect: !DestroySelected {}
+      - effects:
+          - effect: !Nothing {}
+"#;
+
+#[test]
+fn previews_every_affected_permanent() -> anyhow::Result<()> {
+    let game = game! {
+        p1 battlefield: ["Alpine Grizzly", "Forest"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let opponents = game.card(game.p2, "Alpine Grizzly");
+
+    let effects: Vec<Effect> = serde_yaml::from_str(DESTROY_OR_NOTHING)?;
+    let pending = PendingEffects::from(EffectBundle {
+        source: Some(bear),
+        effects,
+        ..Default::default()
+    });
+
+    let affected = pending.affected_by_option(&game.db, 0);
+    assert_eq!(affected.cards, [bear, opponents]);
+    assert!(affected.players.is_empty());
+    assert!(pending.affected_by_option(&game.db, 1).is_empty());
+
+    // Previewing doesn't change the game.
+    assert_zone!(game.db, bear, battlefield);
+    assert_zone!(game.db, opponents, battlefield);
+
+    Ok(())
+}
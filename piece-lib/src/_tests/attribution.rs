@@ -0,0 +1,84 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    battlefield::Battlefields,
+    effects::{PendingEffects, SelectionResult},
+    in_play::CastFrom,
+    log::{Cause, Log, LogEntry},
+    protogen::targets::Location,
+    stack::{Selected, Stack, TargetType},
+};
+
+#[test]
+fn attributes_to_resolving_spell() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Otherworldly Journey"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p2, "Alpine Grizzly");
+    let journey = game.card(game.p1, "Otherworldly Journey");
+
+    let mut results = PendingEffects::default();
+    results.apply_results(journey.move_to_stack(
+        &mut game.db,
+        vec![Selected {
+            location: Some(Location::ON_BATTLEFIELD),
+            target_type: TargetType::Card(bear),
+            targeted: true,
+            restrictions: vec![],
+        }],
+        CastFrom::Hand,
+        vec![],
+    ));
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let mut results = Stack::resolve_1(&mut game.db);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_zone!(game.db, bear, exile);
+    assert_eq!(
+        Log::left_battlefield_cause(&game.db, bear),
+        Some(Cause::Spell(journey))
+    );
+    assert_eq!(
+        Cause::Spell(journey).describe(&game.db),
+        "Otherworldly Journey"
+    );
+
+    let (entry, _) = Log::attributed(&game.db)
+        .filter(|(entry, _)| matches!(entry, LogEntry::LeftBattlefield { .. }))
+        .last()
+        .unwrap();
+    assert_eq!(
+        entry.describe(&game.db).as_deref(),
+        Some("Alpine Grizzly is exiled")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn attributes_to_state_based_actions() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly", "Hoar Shade"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let shade = game.card(game.p1, "Hoar Shade");
+    let db = &mut game.db;
+
+    assert_eq!(Log::left_battlefield_cause(db, bear), None);
+
+    bear.mark_damage(db, Some(shade), 2);
+    let mut results = Battlefields::check_sba(db);
+    assert_eq!(results.resolve(db, None), SelectionResult::Complete);
+
+    assert_zone!(*db, bear, graveyard);
+    assert_eq!(
+        Log::left_battlefield_cause(db, bear),
+        Some(Cause::StateBasedActions)
+    );
+
+    Ok(())
+}
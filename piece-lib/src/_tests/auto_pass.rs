@@ -0,0 +1,46 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    in_play::{CardId, Database},
+    load_cards,
+    player::{Action, AllPlayers, Player},
+    turns::Phase,
+};
+
+#[test]
+fn auto_passes_without_actions() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let _ = all_players.new_player("Opponent".to_string(), 20);
+    let mut db = Database::new(all_players);
+    db.turn.set_phase(Phase::Upkeep);
+
+    let forest = CardId::upload(&mut db, &cards, player, "Forest");
+    forest.move_to_battlefield(&mut db);
+
+    let grizzly = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    grizzly.move_to_hand(&mut db);
+
+    assert_eq!(
+        Player::actions(&db, player, &None),
+        vec![Action::Activate {
+            card: forest,
+            index: 0,
+            mana: true
+        }]
+    );
+    assert!(Player::should_auto_pass(&db, player, &None));
+
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+    assert!(Player::actions(&db, player, &None).contains(&Action::Play(grizzly)));
+    assert!(!Player::should_auto_pass(&db, player, &None));
+
+    db.turn.set_phase(Phase::Upkeep);
+    let counterspell = CardId::upload(&mut db, &cards, player, "Counterspell");
+    counterspell.move_to_hand(&mut db);
+    assert!(Player::actions(&db, player, &None).contains(&Action::Play(counterspell)));
+    assert!(!Player::should_auto_pass(&db, player, &None));
+
+    Ok(())
+}
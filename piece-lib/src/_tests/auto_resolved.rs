@@ -0,0 +1,53 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    in_play::CardId,
+    protogen::card::Card,
+    turns::{AutoPolicy, Phase, Turn},
+};
+
+const TEST_SEER: &str = r#"
+name: Test Seer
+typeline: Enchantment
+cost:
+  mana_cost: '{U}'
+triggered_abilities:
+  - trigger:
+      source: upkeep
+      restrictions:
+        - restriction: !Controller
+            controller: !Self_ {}
+    effects:
+      - effect: !SelectTopOfLibrary
+          count:
+            count: !Fixed
+              count: 1
+      - effect: !Scry {}
+    oracle_text: At the beginning of your upkeep, scry 1.
+"#;
+
+#[test]
+fn records_triggers_resolved_by_policy() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 library: ["Forest" * 10],
+        p2 library: ["Forest" * 10],
+    };
+    let seer: Card = serde_yaml::from_str(TEST_SEER)?;
+    let seer = CardId::upload_card_or_token(&mut game.db, game.p1, seer, false);
+    seer.move_to_battlefield(&mut game.db);
+
+    Turn::advance_until(&mut game.db, Phase::Draw, game.p1, AutoPolicy::Decline)?;
+
+    let auto_resolved = game.db.turn.auto_resolved();
+    assert_eq!(auto_resolved.len(), 1);
+    assert_eq!(auto_resolved[0].source, seer);
+    assert_eq!(
+        auto_resolved[0].warning(&game.db),
+        "Test Seer trigger resolved automatically"
+    );
+
+    game.db.turn.clear_auto_resolved();
+    assert!(game.db.turn.auto_resolved().is_empty());
+
+    Ok(())
+}
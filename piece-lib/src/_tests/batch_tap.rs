@@ -0,0 +1,58 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    battlefield::Battlefields,
+    effects::SelectionResult,
+    player::{mana_pool::FloatingMana, Player},
+    protogen::mana::{Mana, ManaRestriction, ManaSource},
+    turns::Phase,
+};
+
+fn green(count: usize) -> FloatingMana {
+    FloatingMana {
+        mana: Mana::GREEN,
+        source: ManaSource::ANY,
+        restriction: ManaRestriction::NONE,
+        count,
+    }
+}
+
+#[test]
+fn taps_lands_at_once() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Forest" * 3],
+        p1 hand: ["Alpine Grizzly"],
+    };
+    let player = game.p1;
+    let forests = game.cards_named(player, "Forest").to_vec();
+    let grizzly = game.card(player, "Alpine Grizzly");
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let abilities = forests
+        .iter()
+        .map(|forest| (*forest, 0))
+        .collect::<Vec<_>>();
+    let activation = Battlefields::activate_mana_abilities(&mut game.db, &None, player, &abilities);
+    assert!(activation.pending.is_empty());
+    assert_eq!(activation.floated, [green(3)]);
+    for forest in forests.iter() {
+        assert!(forest.tapped(&game.db));
+    }
+
+    // Tapped lands can't be activated again.
+    let activation = Battlefields::activate_mana_abilities(&mut game.db, &None, player, &abilities);
+    assert!(activation.pending.is_empty());
+    assert_eq!(activation.floated, []);
+
+    let mut results = Player::play_card(&mut game.db, player, grizzly);
+    assert_eq!(results.floating_mana(&game.db, player), [green(3)]);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    // Pay the green, which isn't spent until the rest of the cost is paid.
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::PendingChoice);
+    assert_eq!(game.db.all_players[player].mana_pool.floating(), [green(3)]);
+    assert_eq!(results.floating_mana(&game.db, player), [green(2)]);
+
+    Ok(())
+}
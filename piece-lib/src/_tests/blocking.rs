@@ -0,0 +1,208 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    battlefield::Battlefields,
+    combat,
+    effects::SelectionResult,
+    in_play::{CardId, Database},
+    protogen::counters::Counter,
+    turns::{Phase, Turn},
+};
+
+fn give(db: &mut Database, card: CardId, counter: Counter) {
+    db[card].counters.insert(counter, 1);
+    card.apply_modifiers_layered(db);
+}
+
+/// Declares blockers, blocking the first attacker with the first `blockers` creatures which can
+/// block it.
+fn declare_blockers(db: &mut Database, blockers: usize) {
+    db.turn.set_phase(Phase::DeclareAttackers);
+    let mut results = Turn::step(db);
+    for _ in 0..blockers {
+        assert_eq!(results.resolve(db, Some(0)), SelectionResult::PendingChoice);
+        assert_eq!(results.resolve(db, Some(0)), SelectionResult::PendingChoice);
+    }
+    while results.resolve(db, None) != SelectionResult::Complete {}
+}
+
+/// Steps through the first strike and regular combat damage steps.
+fn deal_damage(db: &mut Database) {
+    for _ in 0..2 {
+        let mut results = Turn::step(db);
+        while results.resolve(db, None) != SelectionResult::Complete {}
+        let mut results = Battlefields::check_sba(db);
+        while results.resolve(db, None) != SelectionResult::Complete {}
+    }
+    assert_eq!(db.turn.phase, Phase::Damage);
+}
+
+#[test]
+fn blockers_and_attackers_damage_each_other() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let attacker = game.card(game.p1, "Alpine Grizzly");
+    let blocker = game.card(game.p2, "Alpine Grizzly");
+    let db = &mut game.db;
+    db[attacker].attacking = Some(game.p2);
+
+    db.turn.set_phase(Phase::DeclareAttackers);
+    let mut results = Turn::step(db);
+    assert_eq!(results.priority(db), game.p2);
+    assert_eq!(results.resolve(db, Some(0)), SelectionResult::PendingChoice);
+    assert_eq!(results.resolve(db, Some(0)), SelectionResult::PendingChoice);
+    assert_eq!(results.resolve(db, None), SelectionResult::Complete);
+
+    assert_eq!(blocker.blocking(db), Some(attacker));
+    assert!(attacker.blocked(db));
+    assert_eq!(combat::blockers(db, attacker), [blocker]);
+    assert_eq!(combat::combat_preview(db).life_totals[&game.p2], 20);
+
+    deal_damage(db);
+    assert_eq!(db.all_players[game.p2].life_total, 20);
+    assert_zone!(*db, attacker, graveyard);
+    assert_zone!(*db, blocker, graveyard);
+
+    Ok(())
+}
+
+#[test]
+fn flying_needs_flying_or_reach_to_block() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Breezekeeper"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let djinn = game.card(game.p1, "Breezekeeper");
+    let bear = game.card(game.p2, "Alpine Grizzly");
+    let db = &mut game.db;
+    db[djinn].attacking = Some(game.p2);
+
+    assert!(!bear.can_block(db, djinn));
+    declare_blockers(db, 0);
+    assert!(!djinn.blocked(db));
+
+    deal_damage(db);
+    assert_eq!(db.all_players[game.p2].life_total, 16);
+
+    give(db, bear, Counter::REACH);
+    assert!(bear.can_block(db, djinn));
+
+    Ok(())
+}
+
+#[test]
+fn menace_needs_two_blockers() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 battlefield: ["Alpine Grizzly" * 2],
+    };
+    let attacker = game.card(game.p1, "Alpine Grizzly");
+    let blockers = game.cards_named(game.p2, "Alpine Grizzly").to_vec();
+    let db = &mut game.db;
+    db[attacker].attacking = Some(game.p2);
+    give(db, attacker, Counter::MENACE);
+
+    db.turn.set_phase(Phase::DeclareAttackers);
+    let mut results = Turn::step(db);
+    assert_eq!(results.resolve(db, Some(0)), SelectionResult::PendingChoice);
+    assert_eq!(results.resolve(db, Some(0)), SelectionResult::PendingChoice);
+    // A lone blocker is rejected, and both creatures can be declared again.
+    assert_eq!(results.resolve(db, None), SelectionResult::TryAgain);
+    assert_eq!(results.options(db).len(), 2);
+    assert_eq!(results.resolve(db, None), SelectionResult::Complete);
+    assert!(!attacker.blocked(db));
+    assert_eq!(blockers[0].blocking(db), None);
+
+    declare_blockers(db, 2);
+    assert!(attacker.blocked(db));
+    assert_eq!(combat::blockers(db, attacker), blockers);
+
+    // Lethal damage is assigned to the first blocker before the rest goes to the second.
+    deal_damage(db);
+    assert_eq!(db.all_players[game.p2].life_total, 20);
+    assert_zone!(*db, attacker, graveyard);
+    assert_zone!(*db, blockers[0], graveyard);
+    assert_zone!(*db, blockers[1], graveyard);
+
+    Ok(())
+}
+
+#[test]
+fn attacking_player_orders_blockers() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 battlefield: ["Alpine Grizzly", "Hexplate Golem"],
+    };
+    let attacker = game.card(game.p1, "Alpine Grizzly");
+    let bear = game.card(game.p2, "Alpine Grizzly");
+    let golem = game.card(game.p2, "Hexplate Golem");
+    let db = &mut game.db;
+    db[attacker].attacking = Some(game.p2);
+    give(db, attacker, Counter::MENACE);
+
+    // Declaring the blocks leaves the attacking player to choose the damage assignment order.
+    db.turn.set_phase(Phase::DeclareAttackers);
+    let mut results = Turn::step(db);
+    for _ in 0..2 {
+        assert_eq!(results.resolve(db, Some(0)), SelectionResult::PendingChoice);
+        assert_eq!(results.resolve(db, Some(0)), SelectionResult::PendingChoice);
+    }
+    assert_eq!(results.resolve(db, None), SelectionResult::TryAgain);
+    assert_eq!(combat::blockers(db, attacker), [bear, golem]);
+    assert_eq!(results.priority(db), game.p1);
+    assert_eq!(results.resolve(db, Some(1)), SelectionResult::Complete);
+    assert_eq!(combat::blockers(db, attacker), [golem, bear]);
+
+    // All of the damage goes to the golem, since it isn't lethal.
+    deal_damage(db);
+    assert_zone!(*db, attacker, graveyard);
+    assert_zone!(*db, bear, battlefield);
+    assert_eq!(golem.marked_damage(db), 4);
+
+    Ok(())
+}
+
+#[test]
+fn first_strike_damage_is_dealt_first() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let attacker = game.card(game.p1, "Alpine Grizzly");
+    let blocker = game.card(game.p2, "Alpine Grizzly");
+    let db = &mut game.db;
+    db[attacker].attacking = Some(game.p2);
+    give(db, attacker, Counter::FIRST_STRIKE);
+
+    declare_blockers(db, 1);
+    deal_damage(db);
+    assert_zone!(*db, attacker, battlefield);
+    assert_zone!(*db, blocker, graveyard);
+    assert_eq!(db.all_players[game.p2].life_total, 20);
+
+    Ok(())
+}
+
+#[test]
+fn trample_assigns_excess_damage_to_the_player() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let attacker = game.card(game.p1, "Alpine Grizzly");
+    let blocker = game.card(game.p2, "Alpine Grizzly");
+    let db = &mut game.db;
+    db[attacker].attacking = Some(game.p2);
+    give(db, attacker, Counter::TRAMPLE);
+    give(db, attacker, Counter::DEATHTOUCH);
+
+    declare_blockers(db, 1);
+    deal_damage(db);
+    // With deathtouch, 1 damage is lethal to the blocker.
+    assert_eq!(db.all_players[game.p2].life_total, 17);
+    assert_zone!(*db, blocker, graveyard);
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{EffectBehaviors, PendingEffects, SelectedStack, SelectionResult},
+    protogen::{effects::MoveToBattlefield, targets::Location},
+    stack::{Selected, TargetType},
+    turns::{AutoPolicy, Phase, Turn},
+};
+
+#[test]
+fn phases_out_and_in_with_aura() -> anyhow::Result<()> {
+    let game = game! {
+        p1 library: ["Forest" * 10],
+        p1 battlefield: ["Breezekeeper"],
+        p1 hand: ["Abzan Runemark"],
+        p2 library: ["Forest" * 10],
+    };
+    let (player, opponent) = (game.p1, game.p2);
+    let keeper = game.card(player, "Breezekeeper");
+    let aura = game.card(player, "Abzan Runemark");
+    let mut db = game.db;
+    let mut results = PendingEffects::new(SelectedStack::new(vec![Selected {
+        location: Some(Location::ON_BATTLEFIELD),
+        target_type: TargetType::Card(keeper),
+        targeted: true,
+        restrictions: vec![],
+    }]));
+    results.selected.save();
+    results.selected.clear();
+    results.selected.push(Selected {
+        location: Some(Location::IN_STACK),
+        target_type: TargetType::Card(aura),
+        targeted: false,
+        restrictions: vec![],
+    });
+    let to_apply = MoveToBattlefield::default().apply(&mut db, None, &mut results.selected, false);
+    results.apply_results(to_apply);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+    assert_pt!(db, keeper, 6 / 6);
+
+    Turn::advance_until(&mut db, Phase::Upkeep, opponent, AutoPolicy::Decline)?;
+    Turn::advance_until(&mut db, Phase::Upkeep, player, AutoPolicy::Decline)?;
+
+    assert!(keeper.phased_out(&db));
+    assert!(aura.phased_out(&db));
+    assert!(!keeper.is_in_location(&db, Location::ON_BATTLEFIELD));
+    assert!(!aura.is_in_location(&db, Location::ON_BATTLEFIELD));
+    assert!(db.battlefield[player].is_empty());
+    assert_eq!(db.battlefield.phased_out.len(), 2);
+
+    let object_id = db[keeper].object_id;
+    Turn::advance_until(&mut db, Phase::Upkeep, player, AutoPolicy::Decline)?;
+
+    assert!(!keeper.phased_out(&db));
+    assert!(!aura.phased_out(&db));
+    assert!(keeper.is_in_location(&db, Location::ON_BATTLEFIELD));
+    assert_eq!(db[aura].enchanting, Some(keeper));
+    assert_eq!(db[keeper].object_id, object_id);
+    assert_pt!(db, keeper, 6 / 6);
+    assert!(db.battlefield.phased_out.is_empty());
+
+    Ok(())
+}
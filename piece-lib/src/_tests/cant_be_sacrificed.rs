@@ -0,0 +1,59 @@
+use crate::{
+    battlefield::Battlefields,
+    effects::{EffectBehaviors, SelectedStack},
+    in_play::CardId,
+    protogen::{card::Card, effects::Sacrifice, targets::Location},
+    stack::{Selected, TargetType},
+    turns::Phase,
+};
+
+const TEST_PROTECTOR: &str = r#"
+name: Test Protector
+typeline: Enchantment
+static_abilities:
+  - ability: !CantBeSacrificed
+      restrictions:
+        - restriction: !Controller
+            controller: !Self_ {}
+        - restriction: !OfType
+            types: Creature
+"#;
+
+#[test]
+fn protected_permanents_cant_be_sacrificed() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Blasting Station", "Alpine Grizzly"],
+    };
+    let player = game.p1;
+    let station = game.card(player, "Blasting Station");
+    let bear = game.card(player, "Alpine Grizzly");
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let ability = game.db[station].abilities(&game.db)[0].1.clone();
+    assert!(ability.can_be_activated(&game.db, station, player, &None));
+
+    let protector: Card = serde_yaml::from_str(TEST_PROTECTOR)?;
+    let protector = CardId::upload_card_or_token(&mut game.db, player, protector, false);
+    protector.move_to_battlefield(&mut game.db);
+
+    // The only creature to sacrifice is protected, so the cost can't be paid.
+    assert!(!ability.can_be_activated(&game.db, station, player, &None));
+    let preview = Battlefields::activation_preview(&game.db, &None, player, station, 0).unwrap();
+    assert!(!preview.can_activate);
+
+    // Effects can't sacrifice it either.
+    let _ = Sacrifice::default().apply(
+        &mut game.db,
+        Some(station),
+        &mut SelectedStack::new(vec![Selected {
+            location: Some(Location::ON_BATTLEFIELD),
+            target_type: TargetType::Card(bear),
+            targeted: false,
+            restrictions: vec![],
+        }]),
+        false,
+    );
+    assert_zone!(game.db, bear, battlefield);
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    filter::CardFilter,
+    protogen::targets::{restriction, Location, Restriction},
+};
+
+#[test]
+fn filters_by_location_controller_and_restrictions() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly" * 2, "Forest"],
+        p1 hand: ["Alpine Grizzly"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let bears = game.cards_named(game.p1, "Alpine Grizzly").to_vec();
+    let forest = game.card(game.p1, "Forest");
+    let opposing_bear = game.card(game.p2, "Alpine Grizzly");
+
+    assert_eq!(CardFilter::on_battlefield().count(&game.db), 4);
+    assert_eq!(
+        CardFilter::on_battlefield()
+            .controlled_by(game.p1)
+            .cards(&game.db),
+        [bears[0], bears[1], forest]
+    );
+    assert_eq!(
+        CardFilter::any()
+            .in_locations([Location::IN_HAND])
+            .cards(&game.db),
+        [bears[2]]
+    );
+
+    let not_self = [Restriction {
+        restriction: Some(restriction::NotSelf::default().into()),
+        ..Default::default()
+    }];
+    let filter = CardFilter::on_battlefield()
+        .passing(bears[0], &not_self)
+        .matching(|db, card| card.toughness(db).is_some());
+    assert_eq!(filter.cards(&game.db), [bears[1], opposing_bear]);
+    assert!(!filter.matches(&game.db, bears[0]));
+
+    bears[1].phase_out(&mut game.db);
+    assert_eq!(filter.cards(&game.db), [opposing_bear]);
+
+    Ok(())
+}
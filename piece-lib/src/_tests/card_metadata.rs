@@ -0,0 +1,41 @@
+use pretty_assertions::assert_eq;
+
+use crate::card_metadata::CardMetadataStore;
+
+#[test]
+fn tracks_favorites_tags_and_notes() -> anyhow::Result<()> {
+    let mut store = CardMetadataStore::default();
+    store.set_favorite("Alpine Grizzly", true);
+    store.add_tag("Alpine Grizzly", "beaters");
+    store.add_tag("Forest", "lands");
+    store.set_notes("Forest", "Always good");
+
+    assert!(store.is_favorite("Alpine Grizzly"));
+    assert!(!store.is_favorite("Forest"));
+    assert_eq!(store.favorites().collect::<Vec<_>>(), ["Alpine Grizzly"]);
+    assert_eq!(store.tagged("lands").collect::<Vec<_>>(), ["Forest"]);
+    assert_eq!(
+        store.tags().into_iter().collect::<Vec<_>>(),
+        ["beaters", "lands"]
+    );
+    assert_eq!(store.get("Forest").unwrap().notes, "Always good");
+
+    let reloaded: CardMetadataStore = serde_yaml::from_str(&serde_yaml::to_string(&store)?)?;
+    assert_eq!(reloaded, store);
+
+    Ok(())
+}
+
+#[test]
+fn forgets_cards_without_metadata() {
+    let mut store = CardMetadataStore::default();
+    store.toggle_favorite("Alpine Grizzly");
+    store.add_tag("Alpine Grizzly", "beaters");
+
+    store.toggle_favorite("Alpine Grizzly");
+    assert!(store.get("Alpine Grizzly").is_some());
+
+    store.remove_tag("Alpine Grizzly", "beaters");
+    assert_eq!(store.get("Alpine Grizzly"), None);
+    assert_eq!(store, CardMetadataStore::default());
+}
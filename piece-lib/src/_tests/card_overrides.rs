@@ -0,0 +1,46 @@
+use pretty_assertions::assert_eq;
+
+use crate::{load_cards_with_overrides, protogen::cost::ManaCost};
+
+#[test]
+fn patches_card() -> anyhow::Result<()> {
+    let dir = std::env::temp_dir().join(format!("piece-overrides-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(
+        dir.join("alpine_grizzly.yaml"),
+        "name: Alpine Grizzly\npower: 5\ncost:\n  mana_cost: '{1}{G}'\n",
+    )?;
+    std::fs::write(dir.join("notes.txt"), "not a patch")?;
+
+    let cards = load_cards_with_overrides(&dir);
+    std::fs::remove_dir_all(&dir)?;
+    let cards = cards?;
+
+    let grizzly = &cards["Alpine Grizzly"];
+    assert_eq!(grizzly.power, Some(5));
+    assert_eq!(grizzly.toughness, Some(2));
+    assert_eq!(
+        grizzly
+            .cost
+            .mana_cost
+            .iter()
+            .map(|cost| cost.enum_value().unwrap())
+            .collect::<Vec<_>>(),
+        [ManaCost::GENERIC, ManaCost::GREEN]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn rejects_unknown_card() -> anyhow::Result<()> {
+    let dir = std::env::temp_dir().join(format!("piece-overrides-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join("missing.yaml"), "name: Not a card\npower: 5\n")?;
+
+    let cards = load_cards_with_overrides(&dir);
+    std::fs::remove_dir_all(&dir)?;
+    assert!(cards.is_err());
+
+    Ok(())
+}
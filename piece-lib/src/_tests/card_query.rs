@@ -0,0 +1,92 @@
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+
+use crate::{
+    card_query::{CardQuery, SearchCards},
+    load_cards,
+    protogen::{color::Color, types::Type},
+    Cards,
+};
+
+fn cards() -> anyhow::Result<Cards> {
+    let cards = load_cards()?;
+    Ok([
+        "Alpine Grizzly",
+        "Hoar Shade",
+        "Market Gnome",
+        "Kozilek's Sentinel",
+        "Forest",
+    ]
+    .into_iter()
+    .map(|name| (name.to_string(), cards[name].clone()))
+    .collect())
+}
+
+fn names(cards: &Cards, query: &CardQuery) -> Vec<String> {
+    cards
+        .search(query)
+        .map(|(name, _)| name.clone())
+        .collect_vec()
+}
+
+#[test]
+fn filters() -> anyhow::Result<()> {
+    let cards = cards()?;
+
+    assert_eq!(names(&cards, &CardQuery::default()).len(), 5);
+    assert_eq!(
+        names(&cards, &CardQuery::default().named("SHADE")),
+        ["Hoar Shade"]
+    );
+    assert_eq!(
+        names(&cards, &CardQuery::default().of_types([Type::ARTIFACT])),
+        ["Market Gnome"]
+    );
+    assert_eq!(
+        names(
+            &cards,
+            &CardQuery::default()
+                .of_types([Type::CREATURE])
+                .of_colors([Color::COLORLESS])
+        ),
+        ["Kozilek's Sentinel"]
+    );
+    assert_eq!(
+        names(&cards, &CardQuery::default().cmc(3..=4)),
+        ["Alpine Grizzly", "Hoar Shade"]
+    );
+    assert_eq!(
+        names(&cards, &CardQuery::default().text(r"draw\s+a\s+card")?),
+        ["Market Gnome"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parses_queries() -> anyhow::Result<()> {
+    let cards = cards()?;
+
+    assert_eq!(
+        names(&cards, &CardQuery::parse("t:creature c:g")?),
+        ["Alpine Grizzly"]
+    );
+    assert_eq!(
+        names(&cards, &CardQuery::parse("t:shade cmc:4")?),
+        ["Hoar Shade"]
+    );
+    assert_eq!(
+        names(&cards, &CardQuery::parse("cmc:0-1 gnome")?),
+        ["Market Gnome"]
+    );
+    assert_eq!(
+        names(&cards, &CardQuery::parse("o:devoid")?),
+        ["Kozilek's Sentinel"]
+    );
+
+    assert!(CardQuery::parse("t:nonsense").is_err());
+    assert!(CardQuery::parse("c:x").is_err());
+    assert!(CardQuery::parse("cmc:two").is_err());
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use std::collections::HashSet;
+
+use pretty_assertions::assert_eq;
+
+use crate::{
+    protogen::{color::Color, counters::Counter, targets::Location, types::Type},
+    types::TypeSet,
+};
+
+#[test]
+fn characteristics_in_every_zone() -> anyhow::Result<()> {
+    let game = game! {
+        p1 hand: ["Alpine Grizzly"],
+        p1 graveyard: ["Alpine Grizzly"],
+    };
+    let in_hand = game.db.hand[game.p1][0];
+    let in_graveyard = game.db.graveyard[game.p1][0];
+
+    let characteristics = in_hand
+        .characteristics(&game.db, Location::IN_HAND)
+        .unwrap();
+    assert_eq!(characteristics.name, "Alpine Grizzly");
+    assert_eq!(characteristics.mana_value, 3);
+    assert_eq!(characteristics.power, Some(4));
+    assert_eq!(characteristics.toughness, Some(2));
+    assert_eq!(characteristics.types, TypeSet::from([Type::CREATURE]));
+    assert_eq!(characteristics.colors, HashSet::from([Color::GREEN]));
+
+    assert_eq!(
+        in_graveyard.characteristics(&game.db, Location::IN_GRAVEYARD),
+        Some(characteristics)
+    );
+    assert_eq!(
+        in_hand.characteristics(&game.db, Location::IN_GRAVEYARD),
+        None
+    );
+
+    Ok(())
+}
+
+#[test]
+fn counters_do_not_follow_cards_out_of_play() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+
+    *game.db[bear].counters.entry(Counter::P1P1).or_default() += 2;
+    bear.apply_modifiers_layered(&mut game.db);
+    let on_battlefield = bear
+        .characteristics(&game.db, Location::ON_BATTLEFIELD)
+        .unwrap();
+    assert_eq!(on_battlefield.power, Some(6));
+    assert_eq!(on_battlefield.toughness, Some(4));
+
+    bear.move_to_graveyard(&mut game.db);
+    let in_graveyard = bear
+        .characteristics(&game.db, Location::IN_GRAVEYARD)
+        .unwrap();
+    assert_eq!(in_graveyard.power, Some(4));
+    assert_eq!(in_graveyard.toughness, Some(2));
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    battlefield::Battlefields, checkpoint::Checkpoints, effects::SelectionResult, player::Player,
+    turns::Phase,
+};
+
+#[test]
+fn backs_out_of_nested_actions() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Mountain" * 5],
+        p1 hand: ["Thermal Blast"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let player = game.p1;
+    let blast = game.card(player, "Thermal Blast");
+    let mountains = game.cards_named(player, "Mountain").to_vec();
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let mut checkpoints = Checkpoints::default();
+    checkpoints.push(&game.db, &None);
+
+    let mut results = Player::play_card(&mut game.db, player, blast);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    // Choose the bear as the target, leaving the costs unpaid.
+    let result = results.resolve(&mut game.db, Some(0));
+    assert_eq!(result, SelectionResult::TryAgain);
+    while !results.wants_input(&game.db) {
+        let _ = results.resolve(&mut game.db, None);
+    }
+
+    // Tap the lands for mana while paying.
+    let pending = Some(results);
+    checkpoints.push(&game.db, &pending);
+    let abilities = mountains
+        .iter()
+        .map(|mountain| (*mountain, 0))
+        .collect::<Vec<_>>();
+    let activation =
+        Battlefields::activate_mana_abilities(&mut game.db, &pending, player, &abilities);
+    assert!(activation.pending.is_empty());
+    assert_eq!(activation.floated.len(), 1);
+    assert!(mountains.iter().all(|mountain| mountain.tapped(&game.db)));
+
+    // Backing out of tapping the lands leaves the spell waiting for its costs.
+    let restored = checkpoints.cancel(&mut game.db).unwrap();
+    assert!(restored.is_some_and(|pending| !pending.is_empty()));
+    assert!(mountains.iter().all(|mountain| !mountain.tapped(&game.db)));
+    assert!(game.db.all_players[player].mana_pool.floating().is_empty());
+
+    // Backing out of casting the spell returns it to hand.
+    let restored = checkpoints.cancel(&mut game.db).unwrap();
+    assert!(restored.is_none());
+    assert_zone!(game.db, blast, hand);
+    assert!(checkpoints.is_empty());
+    assert!(checkpoints.cancel(&mut game.db).is_none());
+
+    Ok(())
+}
+
+#[test]
+fn cant_back_out_after_drawing() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 library: ["Forest" * 2],
+    };
+    let player = game.p1;
+
+    let mut checkpoints = Checkpoints::default();
+    checkpoints.push(&game.db, &None);
+    assert!(checkpoints.can_cancel(&game.db));
+
+    let mut results = Player::draw(player, 1);
+    while results.resolve(&mut game.db, None) != SelectionResult::Complete {}
+    assert_eq!(game.db.hand[player].len(), 1);
+
+    assert!(!checkpoints.can_cancel(&game.db));
+    assert!(checkpoints.cancel(&mut game.db).is_none());
+    assert_eq!(game.db.hand[player].len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn restores_snapshots() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Mountain" * 5],
+        p1 hand: ["Thermal Blast"],
+        p1 library: ["Forest" * 2],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let player = game.p1;
+    let blast = game.card(player, "Thermal Blast");
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let before = game.db.snapshot(&None);
+
+    let mut results = Player::play_card(&mut game.db, player, blast);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut game.db, Some(0));
+    assert_eq!(result, SelectionResult::TryAgain);
+    while !results.wants_input(&game.db) {
+        let _ = results.resolve(&mut game.db, None);
+    }
+
+    // Snapshots capture the effects which are partway through resolving.
+    let paying = game.db.snapshot(&Some(results));
+
+    // Unlike checkpoints, snapshots can be restored after drawing.
+    let mut results = Player::draw(player, 1);
+    while results.resolve(&mut game.db, None) != SelectionResult::Complete {}
+    assert_eq!(game.db.hand[player].len(), 2);
+
+    let restored = game.db.restore(paying).unwrap();
+    assert!(restored.is_some_and(|pending| !pending.is_empty()));
+    assert_eq!(game.db.hand[player].len(), 1);
+    assert_eq!(game.db.all_players[player].library.len(), 2);
+
+    // Restoring a snapshot keeps the others, and snapshots can be restored more than once.
+    let restored = game.db.restore(before).unwrap();
+    assert!(restored.is_none());
+    assert_zone!(game.db, blast, hand);
+    assert!(game.db.restore(paying).is_some());
+    assert!(game.db.restore(before).is_some());
+
+    game.db.release(before);
+    assert!(game.db.restore(before).is_none());
+    assert_zone!(game.db, blast, hand);
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    abilities::Ability,
+    effects::SelectionResult,
+    in_play::{CardId, Database},
+    stack::Stack,
+    turns::{Phase, Turn},
+};
+
+/// Casts the card, resolves it, and chooses the only name matching the search for its etb.
+fn cast_naming(db: &mut Database, card: CardId, search: &str) {
+    // Pay the cost with the default mana.
+    let mut results = Stack::move_card_to_stack_from_hand(db, card);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+
+    let mut results = Stack::resolve_1(db);
+    let result = results.resolve(db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let mut results = Stack::resolve_1(db);
+    let result = results.resolve(db, None);
+    assert_eq!(result, SelectionResult::PendingChoice);
+    assert!(results.options(db).len() > 1);
+
+    results.search(search);
+    assert_eq!(results.options(db).len(), 1);
+    let result = results.resolve(db, Some(0));
+    assert_eq!(result, SelectionResult::Complete);
+}
+
+#[test]
+fn prevents_activating_chosen_name() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Abzan Banner", "Blasting Station", "Alpine Grizzly"],
+        p1 hand: ["Pithing Needle"],
+    };
+    let banner = game.card(game.p1, "Abzan Banner");
+    let station = game.card(game.p1, "Blasting Station");
+    let needle = game.card(game.p1, "Pithing Needle");
+    let db = &mut game.db;
+    db.all_players[game.p1].infinite_mana();
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    cast_naming(db, needle, "abzan ban");
+
+    for (source, ability) in db[banner].abilities(db) {
+        let allowed = matches!(ability, Ability::Mana(_));
+        assert_eq!(
+            ability.can_be_activated(db, source, game.p1, &None),
+            allowed
+        );
+    }
+    for (source, ability) in db[station].abilities(db) {
+        assert!(ability.can_be_activated(db, source, game.p1, &None));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn prevents_casting_chosen_name() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Meddling Mage"],
+        p2 hand: ["Counterspell", "Annul"],
+    };
+    let mage = game.card(game.p1, "Meddling Mage");
+    let counterspell = game.card(game.p2, "Counterspell");
+    let annul = game.card(game.p2, "Annul");
+    let db = &mut game.db;
+    db.all_players[game.p1].infinite_mana();
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    assert!(Turn::can_cast(db, counterspell));
+    cast_naming(db, mage, "counterspell");
+
+    assert!(!Turn::can_cast(db, counterspell));
+    assert!(Turn::can_cast(db, annul));
+
+    Ok(())
+}
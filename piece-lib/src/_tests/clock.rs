@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use pretty_assertions::assert_eq;
+
+use crate::{
+    clock::{Clock, Timeout, TimeoutPolicy},
+    in_play::Database,
+    player::AllPlayers,
+    turns::{Phase, Turn},
+};
+
+#[test]
+fn charges_priority_player() {
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let opponent = all_players.new_player("Opponent".to_string(), 20);
+    let mut db = Database::new(all_players);
+
+    let minute = Duration::from_secs(60);
+    Clock::start(&mut db, player, minute, minute, TimeoutPolicy::Lose);
+    Clock::start(
+        &mut db,
+        opponent,
+        minute,
+        minute,
+        TimeoutPolicy::AutoDefault,
+    );
+
+    assert_eq!(Clock::elapse(&mut db, None, minute / 2), None);
+    assert_eq!(Clock::get(&db, player).unwrap().remaining, minute / 2);
+    assert_eq!(Clock::get(&db, opponent).unwrap().remaining, minute);
+
+    db.turn.step_priority();
+    assert_eq!(
+        Clock::elapse(&mut db, None, minute * 2),
+        Some(Timeout {
+            player: opponent,
+            policy: TimeoutPolicy::AutoDefault,
+        })
+    );
+    assert!(!db.all_players[opponent].lost);
+    assert_eq!(
+        Clock::elapse(&mut db, None, minute),
+        Some(Timeout {
+            player: opponent,
+            policy: TimeoutPolicy::AutoDefault,
+        })
+    );
+
+    db.turn.set_phase(Phase::Cleanup);
+    let _ = Turn::step(&mut db);
+    assert_eq!(db.turn.active_player(), opponent);
+    assert_eq!(Clock::get(&db, opponent).unwrap().remaining, minute);
+
+    db.turn.step_priority();
+    assert_eq!(
+        Clock::elapse(&mut db, None, minute),
+        Some(Timeout {
+            player,
+            policy: TimeoutPolicy::Lose,
+        })
+    );
+    assert!(db.all_players[player].lost);
+}
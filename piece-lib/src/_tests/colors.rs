@@ -0,0 +1,64 @@
+use std::collections::HashSet;
+
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{PendingEffects, SelectionResult},
+    in_play::{CardId, CastFrom, Database},
+    protogen::color::Color,
+    stack::Stack,
+};
+
+fn cast(db: &mut Database, card: CardId) {
+    let mut results = PendingEffects::default();
+    results.apply_results(card.move_to_stack(db, vec![], CastFrom::Hand, vec![]));
+    while results.resolve(db, None) != SelectionResult::Complete {}
+
+    while !db.stack.is_empty() {
+        let mut results = Stack::resolve_1(db);
+        while results.resolve(db, None) != SelectionResult::Complete {}
+    }
+}
+
+#[test]
+fn color_indicator() -> anyhow::Result<()> {
+    let game = game! {
+        p1 battlefield: ["Dryad Arbor"],
+    };
+    let arbor = game.card(game.p1, "Dryad Arbor");
+
+    assert_eq!(
+        game.db[arbor].modified_colors,
+        HashSet::from([Color::GREEN])
+    );
+    // It has no mana cost, but is still a Forest.
+    assert_eq!(game.db[arbor].modified_mana_abilities.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn devoid_is_colorless() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Alpine Grizzly"],
+        p1 battlefield: ["Kozilek's Sentinel"],
+    };
+    let sentinel = game.card(game.p1, "Kozilek's Sentinel");
+    let bear = game.card(game.p1, "Alpine Grizzly");
+
+    assert_eq!(game.db[sentinel].modified_colors, HashSet::default());
+    assert_eq!(
+        sentinel.faceup_face(&game.db).color_identity(),
+        HashSet::from([Color::RED])
+    );
+
+    cast(&mut game.db, bear);
+    assert_pt!(game.db, sentinel, 1 / 4);
+
+    let spell = game.upload(game.p1, "Kozilek's Sentinel");
+    spell.move_to_hand(&mut game.db);
+    cast(&mut game.db, spell);
+    assert_pt!(game.db, sentinel, 2 / 4);
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+
+use crate::{
+    combat::{
+        combat_preview, enumerate_attack_options, enumerate_block_options, safe_attacks,
+        AttackPreview,
+    },
+    in_play::{CardId, Database},
+    load_cards,
+    player::{AllPlayers, Owner},
+    protogen::counters::Counter,
+    turns::Phase,
+};
+
+/// Has `attacker` attack `defender` and be blocked by `blocker`.
+fn block(db: &mut Database, attacker: CardId, blocker: CardId, defender: Owner) {
+    db[attacker].attacking = Some(defender);
+    db[attacker].blocked = true;
+    db[attacker].blocked_by.push(blocker);
+    db[blocker].blocking = Some(attacker);
+}
+
+#[test]
+fn previews_damage() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let opponent = all_players.new_player("Opponent".to_string(), 4);
+    let mut db = Database::new(all_players);
+
+    let creature = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    creature.move_to_battlefield(&mut db);
+    assert!(combat_preview(&db).is_empty());
+
+    db[creature].attacking = Some(opponent);
+    db.turn.set_phase(Phase::DeclareBlockers);
+
+    let preview = combat_preview(&db);
+    assert_eq!(
+        preview.attacks,
+        [AttackPreview {
+            attacker: creature,
+            target: opponent,
+            planeswalker: None,
+            damage: 4,
+        }]
+    );
+    assert_eq!(preview.life_totals[&opponent], 0);
+    assert_eq!(preview.life_totals[&player], 20);
+    assert_eq!(preview.losing, [opponent]);
+    assert_eq!(db.all_players[opponent].life_total, 4);
+
+    db.turn.set_phase(Phase::Damage);
+    assert_eq!(combat_preview(&db).attacks[0].damage, 0);
+
+    Ok(())
+}
+
+#[test]
+fn previews_damage_to_and_from_blockers() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 battlefield: ["Dryad Arbor"],
+    };
+    let attacker = game.card(game.p1, "Alpine Grizzly");
+    let blocker = game.card(game.p2, "Dryad Arbor");
+    let db = &mut game.db;
+    for counter in [Counter::TRAMPLE, Counter::LIFELINK] {
+        db[attacker].counters.insert(counter, 1);
+    }
+    attacker.apply_modifiers_layered(db);
+    block(db, attacker, blocker, game.p2);
+    db.turn.set_phase(Phase::DeclareBlockers);
+
+    // Lethal damage goes to the blocker and the rest tramples over to the player.
+    let preview = combat_preview(db);
+    assert_eq!(preview.attacks[0].damage, 3);
+    assert_eq!(
+        preview.creature_damage.into_iter().collect_vec(),
+        [(blocker, 1), (attacker, 1)]
+    );
+    assert_eq!(preview.dying, [blocker]);
+    assert_eq!(preview.life_totals[&game.p1], 24);
+    assert_eq!(preview.life_totals[&game.p2], 17);
+
+    Ok(())
+}
+
+#[test]
+fn previews_first_strike_deaths() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let attacker = game.card(game.p1, "Alpine Grizzly");
+    let blocker = game.card(game.p2, "Alpine Grizzly");
+    let db = &mut game.db;
+    db[attacker].counters.insert(Counter::FIRST_STRIKE, 1);
+    attacker.apply_modifiers_layered(db);
+    block(db, attacker, blocker, game.p2);
+    db.turn.set_phase(Phase::DeclareBlockers);
+
+    // The blocker is destroyed before it can deal its damage.
+    let preview = combat_preview(db);
+    assert_eq!(preview.attacks[0].damage, 0);
+    assert_eq!(
+        preview.creature_damage.into_iter().collect_vec(),
+        [(blocker, 4)]
+    );
+    assert_eq!(preview.dying, [blocker]);
+    assert_eq!(preview.life_totals[&game.p2], 20);
+
+    Ok(())
+}
+
+#[test]
+fn enumerates_attacks() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let opponent = all_players.new_player("Opponent".to_string(), 20);
+    let mut db = Database::new(all_players);
+    assert_eq!(enumerate_attack_options(&db, player).len(), 1);
+
+    let bears = (0..2)
+        .map(|_| {
+            let bear = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+            bear.move_to_battlefield(&mut db);
+            bear
+        })
+        .collect::<Vec<_>>();
+    let djinn = CardId::upload(&mut db, &cards, player, "Breezekeeper");
+    djinn.move_to_battlefield(&mut db);
+    assert_eq!(enumerate_attack_options(&db, player).len(), 1);
+
+    db.turn.turn_count += db.turn.turns_per_round();
+    let options = enumerate_attack_options(&db, player);
+    // Attacking with neither, one, or both bears, with or without the djinn.
+    assert_eq!(options.len(), 6);
+    assert!(options[0].attacks.is_empty());
+    assert!(options
+        .iter()
+        .any(|option| option.attacks == [(bears[0], opponent), (djinn, opponent)]));
+    assert!(!options
+        .iter()
+        .any(|option| option.attacks.contains(&(bears[1], opponent))
+            && !option.attacks.contains(&(bears[0], opponent))));
+
+    Ok(())
+}
+
+#[test]
+fn enumerates_blocks() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let opponent = all_players.new_player("Opponent".to_string(), 20);
+    let mut db = Database::new(all_players);
+
+    let bear = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    bear.move_to_battlefield(&mut db);
+    let djinn = CardId::upload(&mut db, &cards, player, "Breezekeeper");
+    djinn.move_to_battlefield(&mut db);
+    db[bear].attacking = Some(opponent);
+    db[djinn].attacking = Some(opponent);
+
+    let blocker = CardId::upload(&mut db, &cards, opponent, "Alpine Grizzly");
+    blocker.move_to_battlefield(&mut db);
+
+    let options = enumerate_block_options(&db, opponent);
+    assert_eq!(options.len(), 2);
+    assert!(options[0].blocks.is_empty());
+    assert_eq!(options[1].blocks, [(blocker, bear)]);
+
+    blocker.tap(&mut db);
+    assert_eq!(enumerate_block_options(&db, opponent).len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn finds_safe_attacks() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let opponent = all_players.new_player("Opponent".to_string(), 20);
+    let mut db = Database::new(all_players);
+
+    let bear = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    bear.move_to_battlefield(&mut db);
+    let djinn = CardId::upload(&mut db, &cards, player, "Breezekeeper");
+    djinn.move_to_battlefield(&mut db);
+    db.turn.turn_count += db.turn.turns_per_round();
+
+    // Nothing can block yet, so every attack is safe.
+    assert_eq!(
+        safe_attacks(&db, player).attacks,
+        [(bear, opponent), (djinn, opponent)]
+    );
+
+    // The blocker can kill the bear, but can't block the flying djinn.
+    let blocker = CardId::upload(&mut db, &cards, opponent, "Alpine Grizzly");
+    blocker.move_to_battlefield(&mut db);
+    assert_eq!(safe_attacks(&db, player).attacks, [(djinn, opponent)]);
+
+    blocker.tap(&mut db);
+    assert_eq!(safe_attacks(&db, player).attacks.len(), 2);
+
+    Ok(())
+}
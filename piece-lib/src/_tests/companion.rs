@@ -0,0 +1,100 @@
+use indexmap::IndexSet;
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::SelectionResult,
+    in_play::{CardId, Database},
+    library::DeckDefinition,
+    load_cards,
+    player::{AllPlayers, Player},
+    protogen::card::Card,
+    turns::Phase,
+};
+
+const COMPANION: &str = r#"
+name: Test Companion
+typeline: Legendary Creature - Elemental Hippo
+cost:
+  mana_cost: '{5}{G}{U}'
+companion:
+  applies_to:
+    - restriction: !NotOfType
+        types: Land
+  restrictions:
+    - restriction: !Cmc
+        cmc: !Comparison
+          value: !GreaterThanOrEqual
+            value: 3
+power: 7
+toughness: 7
+"#;
+
+#[test]
+fn validates_deck() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let companion: Card = serde_yaml::from_str(COMPANION)?;
+
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let mut db = Database::new(all_players);
+
+    let mut def = DeckDefinition::default();
+    def.add_card("Alpine Grizzly".to_string(), 4);
+    def.add_card("Forest".to_string(), 4);
+    db.all_players[player].library = def.build_deck(&mut db, &cards, player);
+
+    let card = CardId::upload_card_or_token(&mut db, player, companion.clone(), false);
+    Player::set_companion(&mut db, player, card)?;
+    assert_eq!(Player::companion(&db, player), Some(card));
+
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let mut db = Database::new(all_players);
+
+    def.add_card("Student of Warfare".to_string(), 1);
+    db.all_players[player].library = def.build_deck(&mut db, &cards, player);
+
+    let card = CardId::upload_card_or_token(&mut db, player, companion, false);
+    let err = Player::set_companion(&mut db, player, card).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Test Companion cannot be a companion for this deck: Student of Warfare"
+    );
+    assert_eq!(Player::companion(&db, player), None);
+
+    let not_companion = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    assert!(Player::set_companion(&mut db, player, not_companion).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn puts_companion_in_hand() -> anyhow::Result<()> {
+    let companion: Card = serde_yaml::from_str(COMPANION)?;
+
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let mut db = Database::new(all_players);
+
+    let card = CardId::upload_card_or_token(&mut db, player, companion, false);
+    Player::set_companion(&mut db, player, card)?;
+    assert!(!Player::can_put_companion_in_hand(&db, player));
+
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+    assert!(!Player::can_put_companion_in_hand(&db, player));
+
+    db.all_players[player].infinite_mana();
+    assert!(Player::can_put_companion_in_hand(&db, player));
+
+    let mut results = Player::put_companion_in_hand(&mut db, player);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(db.hand[player], IndexSet::from([card]));
+    assert_eq!(Player::companion(&db, player), None);
+    assert!(!Player::can_put_companion_in_hand(&db, player));
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{PendingEffects, SelectionResult},
+    in_play::{CardId, Database},
+    stack::Stack,
+    turns::Phase,
+};
+
+/// The option voting for the card.
+fn vote_for(db: &Database, results: &PendingEffects, card: CardId) -> usize {
+    results
+        .option_details(db)
+        .into_iter()
+        .find(|option| option.card == Some(card))
+        .map(|option| option.index)
+        .unwrap()
+}
+
+#[test]
+fn exiles_tied_permanents() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Council's Judgment"],
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 battlefield: ["Alpine Grizzly" * 2, "Forest"],
+    };
+    let judgment = game.card(game.p1, "Council's Judgment");
+    let ours = game.card(game.p1, "Alpine Grizzly");
+    let theirs = game.cards_named(game.p2, "Alpine Grizzly").to_vec();
+    let forest = game.card(game.p2, "Forest");
+    let db = &mut game.db;
+    db.all_players[game.p1].infinite_mana();
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let mut results = Stack::move_card_to_stack_from_hand(db, judgment);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+
+    let mut results = Stack::resolve_1(db);
+    assert_eq!(results.resolve(db, None), SelectionResult::PendingChoice);
+    assert_eq!(results.priority(db), game.p1);
+    // Only nonland permanents the caster doesn't control can be voted for.
+    assert_eq!(results.options(db).len(), 2);
+
+    let option = vote_for(db, &results, theirs[0]);
+    assert_eq!(
+        results.resolve(db, Some(option)),
+        SelectionResult::PendingChoice
+    );
+    assert_eq!(results.priority(db), game.p2);
+
+    let option = vote_for(db, &results, theirs[1]);
+    assert_eq!(results.resolve(db, Some(option)), SelectionResult::TryAgain);
+    assert_eq!(results.resolve(db, None), SelectionResult::Complete);
+
+    assert_zone!(*db, theirs[0], exile);
+    assert_zone!(*db, theirs[1], exile);
+    assert_zone!(*db, ours, battlefield);
+    assert_zone!(*db, forest, battlefield);
+
+    Ok(())
+}
+
+#[test]
+fn exiles_permanent_with_most_votes() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Council's Judgment"],
+        p2 battlefield: ["Alpine Grizzly" * 2],
+    };
+    let judgment = game.card(game.p1, "Council's Judgment");
+    let theirs = game.cards_named(game.p2, "Alpine Grizzly").to_vec();
+    let db = &mut game.db;
+    db.all_players[game.p1].infinite_mana();
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let mut results = Stack::move_card_to_stack_from_hand(db, judgment);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+
+    let mut results = Stack::resolve_1(db);
+    assert_eq!(results.resolve(db, None), SelectionResult::PendingChoice);
+    for _ in 0..2 {
+        let option = vote_for(db, &results, theirs[1]);
+        let _ = results.resolve(db, Some(option));
+    }
+    assert_eq!(results.resolve(db, None), SelectionResult::Complete);
+
+    assert_zone!(*db, theirs[0], battlefield);
+    assert_zone!(*db, theirs[1], exile);
+
+    Ok(())
+}
@@ -6,8 +6,9 @@ use crate::{
     in_play::Database,
     in_play::{CardId, CastFrom},
     load_cards,
-    player::AllPlayers,
+    player::{AllPlayers, Player},
     stack::Stack,
+    turns::Phase,
 };
 
 #[test]
@@ -63,3 +64,32 @@ fn resolves_counterspells() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn casting_without_targets_leaves_the_spell_in_hand() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Island" * 2],
+        p1 hand: ["Counterspell"],
+    };
+    let counterspell = game.card(game.p1, "Counterspell");
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    // Choose the first option wherever there is one, the way the AI's rollouts do. There's no
+    // spell to target, so Counterspell can't be cast.
+    let mut results = Player::play_card(&mut game.db, game.p1, counterspell);
+    for _ in 0..10 {
+        let option = if results.options(&game.db).is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+        if results.resolve(&mut game.db, option) == SelectionResult::Complete {
+            break;
+        }
+    }
+
+    assert!(game.db.stack.is_empty());
+    assert_zone!(game.db, counterspell, hand);
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    debug::{add_card, import_board, set_life_total, skip_to_phase},
+    in_play::Database,
+    load_cards,
+    player::AllPlayers,
+    protogen::targets::Location,
+    turns::Phase,
+};
+
+#[test]
+fn modifies_state() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let mut db = Database::new(all_players);
+
+    let card = add_card(
+        &mut db,
+        &cards,
+        player,
+        "Alpine Grizzly",
+        Location::ON_BATTLEFIELD,
+    )
+    .unwrap();
+    assert!(card.is_in_location(&db, Location::ON_BATTLEFIELD));
+
+    let card = add_card(&mut db, &cards, player, "Forest", Location::IN_GRAVEYARD).unwrap();
+    assert!(card.is_in_location(&db, Location::IN_GRAVEYARD));
+    assert!(add_card(&mut db, &cards, player, "Not a card", Location::IN_HAND).is_none());
+
+    set_life_total(&mut db, player, 3);
+    assert_eq!(db.all_players[player].life_total, 3);
+
+    skip_to_phase(&mut db, Phase::EndStep);
+    assert_eq!(db.turn.phase, Phase::EndStep);
+    assert_eq!(db.turn.priority_player(), player);
+
+    Ok(())
+}
+
+#[test]
+fn imports_board() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player1 = all_players.new_player("Player".to_string(), 20);
+    let player2 = all_players.new_player("Player".to_string(), 20);
+    let mut db = Database::new(all_players);
+
+    let warnings = import_board(
+        &mut db,
+        &cards,
+        &[player1, player2],
+        "P1: 3 Forest (2 tapped), alpine grizzly, Hoar Shade in graveyard;\
+         P2: 17 life, 2 cards in hand, Not a card",
+    );
+    assert_eq!(warnings, vec!["Unknown card \"not a card\"".to_string()]);
+
+    let battlefield = db.battlefield[player1].iter().copied().collect::<Vec<_>>();
+    assert_eq!(battlefield.len(), 4);
+    assert_eq!(
+        battlefield.iter().filter(|card| card.tapped(&db)).count(),
+        2
+    );
+    assert_eq!(battlefield[3].name(&db), "Alpine Grizzly");
+    assert_eq!(db.graveyard[player1].len(), 1);
+
+    assert_eq!(db.all_players[player2].life_total, 17);
+    assert_eq!(db.hand[player2].len(), 2);
+
+    Ok(())
+}
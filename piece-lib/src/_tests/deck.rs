@@ -0,0 +1,242 @@
+use std::collections::{BTreeMap, HashSet};
+
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    deck::{
+        analyze_hand, at_least, choose_bottom, import_library, is_creature, parse_decklist,
+        random_deck, sealed_pool, split_lands, stats, DeckConstraints,
+    },
+    in_play::Database,
+    library::DeckDefinition,
+    load_cards,
+    player::AllPlayers,
+    protogen::{color::Color, mana::Mana, types::Type},
+    Cards,
+};
+
+#[test]
+fn computes_stats() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+
+    let mut deck = DeckDefinition::default();
+    deck.add_card("Forest".to_string(), 17);
+    deck.add_card("Alpine Grizzly".to_string(), 23);
+    deck.add_card("Not a card".to_string(), 1);
+
+    let stats = stats(&deck, &cards);
+    assert_eq!(stats.size, 40);
+    assert_eq!(stats.lands, 17);
+    assert_eq!(stats.mana_curve.into_iter().collect::<Vec<_>>(), [(3, 23)]);
+    assert_eq!(stats.color_sources.get(&Mana::GREEN), Some(&17));
+    assert_eq!(stats.color_requirements.get(&Mana::GREEN), Some(&23));
+    assert_eq!(stats.type_distribution.get(&Type::LAND), Some(&17));
+    assert_eq!(stats.type_distribution.get(&Type::CREATURE), Some(&23));
+    assert_eq!(stats.unknown_cards, ["Not a card"]);
+
+    let grizzly = &stats.cast_probabilities[0];
+    assert_eq!(grizzly.name, "Alpine Grizzly");
+    assert_eq!(grizzly.by_turn[1], 0.0);
+    assert!(grizzly.by_turn[2] > 0.5);
+    assert!(grizzly.by_turn[3] > grizzly.by_turn[2]);
+
+    Ok(())
+}
+
+#[test]
+fn analyzes_hands() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+
+    let mut deck = DeckDefinition::default();
+    deck.add_card("Forest".to_string(), 17);
+    deck.add_card("Alpine Grizzly".to_string(), 23);
+    let stats = stats(&deck, &cards);
+
+    let forest = &cards["Forest"];
+    let grizzly = &cards["Alpine Grizzly"];
+    let plains = &cards["Plains"];
+
+    let hand = [forest, forest, forest, grizzly, grizzly, grizzly, grizzly];
+    let analysis = analyze_hand(&hand, &stats);
+    assert_eq!(analysis.lands, 3);
+    assert_eq!(analysis.early_plays, 4);
+    assert!(analysis.missing_colors.is_empty());
+    assert!(analysis.keep);
+
+    let hand = [forest, grizzly, grizzly, grizzly, grizzly, grizzly, grizzly];
+    assert!(!analyze_hand(&hand, &stats).keep);
+
+    let hand = [plains, plains, plains, grizzly, grizzly, grizzly, grizzly];
+    let analysis = analyze_hand(&hand, &stats);
+    assert_eq!(analysis.missing_colors, [Mana::GREEN]);
+
+    let hand = [forest, forest, forest, forest, forest, forest, grizzly];
+    assert!(!analyze_hand(&hand, &stats).keep);
+    let bottom = choose_bottom(&hand, &stats, 2);
+    assert_eq!(bottom.len(), 2);
+    assert!(bottom.iter().all(|idx| hand[*idx].name == "Forest"));
+
+    Ok(())
+}
+
+#[test]
+fn hypergeometric() {
+    assert_eq!(at_least(10, 10, 3, 3), 1.0);
+    assert_eq!(at_least(10, 2, 3, 3), 0.0);
+    assert!((at_least(4, 2, 2, 1) - 5.0 / 6.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn generates_random_decks() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let mut constraints = DeckConstraints {
+        creatures: 5,
+        colors: HashSet::from([Color::GREEN, Color::WHITE]),
+        curve: BTreeMap::from([(1, 4)]),
+        max_copies: 2,
+        ..Default::default()
+    };
+
+    let deck = random_deck(&cards, &constraints, &mut rng)?;
+    let stats = stats(&deck, &cards);
+    assert_eq!(stats.size, 40);
+    assert_eq!(stats.lands, 17);
+    assert!(stats.mana_curve.get(&1).copied().unwrap_or_default() <= 4);
+    assert!(stats.unknown_cards.is_empty());
+
+    let mut creatures = 0;
+    for (name, count) in deck.cards() {
+        let card = &cards[name];
+        assert!(card
+            .color_identity()
+            .into_iter()
+            .all(|color| constraints.colors.contains(&color)));
+        if is_creature(card) {
+            creatures += count;
+        }
+        if !matches!(name, "Plains" | "Forest") {
+            assert!(count <= 2, "{} x{}", name, count);
+        }
+    }
+    assert!(creatures >= 5);
+
+    constraints.lands = 41;
+    assert!(random_deck(&cards, &constraints, &mut rng).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn generates_sealed_pools() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let pool = sealed_pool(&cards, 90, &mut rng);
+    assert_eq!(pool.cards().map(|(_, count)| count).sum::<usize>(), 90);
+    assert!(pool
+        .cards()
+        .all(|(name, _)| !matches!(name, "Plains" | "Island" | "Swamp" | "Mountain" | "Forest")));
+
+    Ok(())
+}
+
+#[test]
+fn weights_sealed_pools_by_rarity() -> anyhow::Result<()> {
+    let cards = load_cards()?
+        .into_iter()
+        .filter(|(name, _)| {
+            matches!(
+                name.as_str(),
+                "Alpine Grizzly" | "Bat Colony" | "Clone" | "Elesh Norn, Grand Cenobite"
+            )
+        })
+        .collect::<Cards>();
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let pool = sealed_pool(&cards, 14 * 8, &mut rng);
+    let count = |name: &str| {
+        pool.cards()
+            .find(|(card, _)| *card == name)
+            .map(|(_, count)| count)
+            .unwrap_or_default()
+    };
+    assert_eq!(count("Alpine Grizzly"), 80);
+    assert_eq!(count("Bat Colony"), 24);
+    assert_eq!(count("Clone") + count("Elesh Norn, Grand Cenobite"), 8);
+
+    Ok(())
+}
+
+#[test]
+fn parses_decklists() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+
+    let def = parse_decklist(
+        "test",
+        "Deck
+        4x Alpine Grizzly
+        // Lands
+        16 Forest (M21) 274
+        1 Forest
+
+        Sideboard
+        2 Hoar Shade",
+        &cards,
+    )?;
+    assert_eq!(
+        def.cards().sorted().collect_vec(),
+        [("Alpine Grizzly", 4), ("Forest", 17)]
+    );
+
+    let error = parse_decklist("test", "4 Alpine Grizly\nForest\n2 Forest", &cards)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("Unknown card: Alpine Grizly"));
+    assert!(error.contains("Expected '<count> <name>', got: Forest"));
+
+    Ok(())
+}
+
+#[test]
+fn imports_libraries() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let mut db = Database::new(all_players);
+
+    let library = import_library(
+        &mut db,
+        &cards,
+        player,
+        "test",
+        "4 Alpine Grizzly\n16 Forest",
+    )?;
+    assert_eq!(library.len(), 20);
+    assert_eq!(
+        library
+            .cards()
+            .filter(|card| card.name(&db) == "Forest")
+            .count(),
+        16
+    );
+    assert!(import_library(&mut db, &cards, player, "test", "1 Not a card").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn splits_lands() {
+    assert_eq!(
+        split_lands(17, &BTreeMap::from([(Mana::GREEN, 2), (Mana::WHITE, 1)])),
+        BTreeMap::from([(Mana::GREEN, 11), (Mana::WHITE, 6)])
+    );
+    assert!(split_lands(17, &BTreeMap::default()).is_empty());
+    assert_eq!(
+        split_lands(1, &BTreeMap::from([(Mana::GREEN, 2), (Mana::WHITE, 1)])),
+        BTreeMap::from([(Mana::GREEN, 1)])
+    );
+}
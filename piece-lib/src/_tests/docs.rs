@@ -0,0 +1,24 @@
+use pretty_assertions::assert_eq;
+
+use crate::docs::effects;
+
+#[test]
+fn documents_effects() {
+    let effects = effects();
+
+    let add_counters = effects
+        .iter()
+        .find(|effect| effect.tag == "AddCounters")
+        .unwrap();
+    assert_eq!(add_counters.message, "AddCounters");
+    assert_eq!(
+        add_counters.description.as_deref(),
+        Some("Add some number of counters to the selected target(s).")
+    );
+    assert!(add_counters
+        .fields
+        .iter()
+        .any(|field| field.name == "counter" && field.ty == "Counter"));
+
+    assert!(effects.windows(2).all(|pair| pair[0].tag < pair[1].tag));
+}
@@ -48,7 +48,7 @@ fn adds_land_types() -> anyhow::Result<()> {
     let result = results.resolve(&mut db, None);
     assert_eq!(result, SelectionResult::Complete);
 
-    assert_eq!(Player::lands_per_turn(&mut db, player), 2);
+    assert_eq!(Player::lands_per_turn(&db, player), 2);
 
     assert_eq!(
         db[land].modified_subtypes,
@@ -60,6 +60,8 @@ fn adds_land_types() -> anyhow::Result<()> {
             Subtype::FOREST,
         ])
     );
+    // Each basic land type grants its mana ability, without duplicating the forest's.
+    assert_eq!(db[land].modified_mana_abilities.len(), 5);
 
     Ok(())
 }
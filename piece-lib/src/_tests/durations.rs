@@ -0,0 +1,112 @@
+use crate::{
+    battlefield::Battlefields,
+    effects::{EffectBundle, PendingEffects, SelectionResult},
+    in_play::{CardId, Database},
+    protogen::{effects::Effect, targets::Location},
+    stack::{Selected, TargetType},
+    turns::{Phase, Turn},
+};
+
+fn pump(db: &mut Database, source: CardId, target: CardId, duration: &str) -> anyhow::Result<()> {
+    let effect: Effect = serde_yaml::from_str(&format!(
+        r#"
+effect: !ApplyModifier
+  modifier:
+    modifier:
+      add_power: 2
+    duration: {}
+"#,
+        duration
+    ))?;
+
+    let mut results = PendingEffects::from(EffectBundle {
+        push_on_enter: Some(vec![Selected {
+            location: Some(Location::ON_BATTLEFIELD),
+            target_type: TargetType::Card(target),
+            targeted: false,
+            restrictions: vec![],
+        }]),
+        source: Some(source),
+        effects: vec![effect],
+        ..Default::default()
+    });
+    while results.resolve(db, None) != SelectionResult::Complete {}
+
+    Ok(())
+}
+
+fn step(db: &mut Database, phase: Phase) {
+    db.turn.set_phase(phase);
+    let mut results = Turn::step(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+}
+
+#[test]
+fn until_end_of_combat() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly", "Forest"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let forest = game.card(game.p1, "Forest");
+    let db = &mut game.db;
+
+    pump(db, forest, bear, "until end of combat")?;
+    assert_pt!(*db, bear, 6 / 2);
+
+    step(db, Phase::DeclareBlockers);
+    assert_pt!(*db, bear, 6 / 2);
+
+    step(db, Phase::Damage);
+    assert_eq!(db.turn.phase, Phase::EndCombat);
+    assert_pt!(*db, bear, 6 / 2);
+
+    step(db, Phase::EndCombat);
+    assert_pt!(*db, bear, 4 / 2);
+
+    Ok(())
+}
+
+#[test]
+fn until_your_next_turn() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Forest"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p2, "Alpine Grizzly");
+    let forest = game.card(game.p1, "Forest");
+    let db = &mut game.db;
+
+    pump(db, forest, bear, "until your next turn")?;
+    assert_pt!(*db, bear, 6 / 2);
+
+    step(db, Phase::Cleanup);
+    assert_eq!(db.turn.active_player(), game.p2);
+    assert_pt!(*db, bear, 6 / 2);
+
+    step(db, Phase::Cleanup);
+    assert_eq!(db.turn.active_player(), game.p1);
+    assert_pt!(*db, bear, 4 / 2);
+
+    Ok(())
+}
+
+#[test]
+fn while_you_control_source() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly", "Forest"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let forest = game.card(game.p1, "Forest");
+    let db = &mut game.db;
+
+    pump(db, forest, bear, "while you control source")?;
+    step(db, Phase::Cleanup);
+    assert_pt!(*db, bear, 6 / 2);
+
+    forest.move_to_graveyard(db);
+    let mut results = Battlefields::check_sba(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+    assert_pt!(*db, bear, 4 / 2);
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{EffectBundle, PendingEffects, SelectionResult},
+    protogen::{effects::RemoveFromCombat, targets::Location},
+    stack::{Selected, TargetType},
+    turns::{Phase, Turn},
+};
+
+#[test]
+fn clears_attackers() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let db = &mut game.db;
+    db[bear].attacking = Some(game.p2);
+
+    db.turn.set_phase(Phase::Damage);
+    let mut results = Turn::step(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+    assert_eq!(db.turn.phase, Phase::EndCombat);
+    assert_eq!(bear.attacking(db), Some(game.p2));
+
+    let mut results = Turn::step(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+    assert_eq!(db.turn.phase, Phase::PostCombatMainPhase);
+    assert_eq!(bear.attacking(db), None);
+
+    Ok(())
+}
+
+#[test]
+fn removes_from_combat() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let db = &mut game.db;
+    db[bear].attacking = Some(game.p2);
+
+    db.turn.set_phase(Phase::DeclareBlockers);
+    let mut results = PendingEffects::from(EffectBundle {
+        push_on_enter: Some(vec![Selected {
+            location: Some(Location::ON_BATTLEFIELD),
+            target_type: TargetType::Card(bear),
+            targeted: false,
+            restrictions: vec![],
+        }]),
+        source: Some(bear),
+        effects: vec![RemoveFromCombat::default().into()],
+        ..Default::default()
+    });
+    while results.resolve(db, None) != SelectionResult::Complete {}
+    assert_eq!(bear.attacking(db), None);
+
+    let mut results = Turn::step(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+    let mut results = Turn::step(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+    assert_eq!(db.all_players[game.p2].life_total, 20);
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use pretty_assertions::assert_eq;
+
+use crate::protogen::effects::Duration;
+
+#[test]
+fn orders_across_players() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly", "Forest"],
+        p2 battlefield: ["Hoar Shade"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let forest = game.card(game.p1, "Forest");
+    let shade = game.card(game.p2, "Hoar Shade");
+    let db = &mut game.db;
+
+    assert_eq!(db.battlefield_entry_order(), vec![bear, forest, shade]);
+
+    shade.move_to_graveyard(db);
+    bear.move_to_graveyard(db);
+    assert_eq!(db.graveyard_order(), vec![shade, bear]);
+    assert!(shade.timestamp(db) < bear.timestamp(db));
+
+    // Leaving and re-entering a zone gives a card a new timestamp.
+    shade.move_to_battlefield(db);
+    assert_eq!(db.battlefield_entry_order(), vec![forest, shade]);
+
+    bear.move_to_exile(db, forest, None, Duration::PERMANENTLY);
+    forest.move_to_exile(db, forest, None, Duration::PERMANENTLY);
+    assert_eq!(db.exile_order(), vec![bear, forest]);
+    assert!(db.graveyard_order().is_empty());
+
+    Ok(())
+}
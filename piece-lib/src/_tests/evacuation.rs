@@ -0,0 +1,49 @@
+use crate::{
+    effects::SelectionResult, in_play::CardId, protogen::card::Card, stack::Stack, turns::Phase,
+};
+
+const TEST_TOKEN: &str = r#"
+name: Test Soldier
+typeline: Creature - Soldier
+power: 1
+toughness: 1
+"#;
+
+#[test]
+fn returns_creatures_to_owners_hands() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Evacuation"],
+        p1 battlefield: ["Alpine Grizzly", "Forest"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let evacuation = game.card(game.p1, "Evacuation");
+    let ours = game.card(game.p1, "Alpine Grizzly");
+    let forest = game.card(game.p1, "Forest");
+    let theirs = game.card(game.p2, "Alpine Grizzly");
+    let token: Card = serde_yaml::from_str(TEST_TOKEN)?;
+    let token = CardId::upload_card_or_token(&mut game.db, game.p2, token, true);
+    token.move_to_battlefield(&mut game.db);
+
+    let (p1, p2) = (game.p1, game.p2);
+    let db = &mut game.db;
+    db.all_players[p1].infinite_mana();
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let mut results = Stack::move_card_to_stack_from_hand(db, evacuation);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+
+    let mut results = Stack::resolve_1(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+
+    assert_zone!(*db, ours, hand);
+    assert_zone!(*db, theirs, hand);
+    assert_zone!(*db, forest, battlefield);
+    assert!(db.hand[p1].contains(&ours));
+    assert!(db.hand[p2].contains(&theirs));
+
+    // Tokens cease to exist rather than going to their owner's hand.
+    assert!(!db.hand[p2].contains(&token));
+    assert!(!db.battlefield[p2].contains(&token));
+
+    Ok(())
+}
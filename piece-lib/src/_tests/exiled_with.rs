@@ -0,0 +1,76 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    in_play::{CardId, Database},
+    load_cards,
+    log::LogId,
+    player::AllPlayers,
+    protogen::{
+        card::Card,
+        effects::{Count, Duration},
+        targets::{restriction, Restriction},
+    },
+};
+
+const PROCESSOR: &str = r#"
+name: Test Processor
+typeline: Creature - Eldrazi Processor
+cost:
+  mana_cost: '{3}'
+dynamic_power_toughness:
+  count: !NumberOfCardsExiledWith {}
+"#;
+
+#[test]
+fn counts_cards_exiled_with() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let processor: Card = serde_yaml::from_str(PROCESSOR)?;
+
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let mut db = Database::new(all_players);
+
+    let processor = CardId::upload_card_or_token(&mut db, player, processor, false);
+    processor.move_to_battlefield(&mut db);
+    let other = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    other.move_to_battlefield(&mut db);
+
+    let grizzly = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    grizzly.move_to_graveyard(&mut db);
+    let forest = CardId::upload(&mut db, &cards, player, "Forest");
+    forest.move_to_graveyard(&mut db);
+
+    assert_eq!(processor.power(&db), Some(0));
+
+    grizzly.move_to_exile(&mut db, processor, None, Duration::PERMANENTLY);
+    forest.move_to_exile(&mut db, processor, None, Duration::PERMANENTLY);
+    assert_eq!(processor.power(&db), Some(2));
+    assert_eq!(processor.toughness(&db), Some(2));
+
+    let creatures: Count = serde_yaml::from_str(
+        r#"
+count: !NumberOfCardsExiledWith
+  restrictions:
+    - restriction: !OfType
+        types: Creature
+"#,
+    )?;
+    assert_eq!(creatures.count(&db, Some(processor), &[]), 1);
+
+    let exiled_with = [Restriction {
+        restriction: Some(restriction::Restriction::ExiledWith(Default::default())),
+        ..Default::default()
+    }];
+    assert!(grizzly.passes_restrictions(&db, LogId::current(&db), processor, &exiled_with));
+    assert!(!grizzly.passes_restrictions(&db, LogId::current(&db), other, &exiled_with));
+
+    grizzly.move_to_hand(&mut db);
+    assert_eq!(processor.power(&db), Some(1));
+    assert!(!grizzly.passes_restrictions(&db, LogId::current(&db), processor, &exiled_with));
+
+    // Exiling the card again with a different source no longer counts it for the first.
+    forest.move_to_exile(&mut db, other, None, Duration::PERMANENTLY);
+    assert_eq!(processor.power(&db), Some(0));
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    battlefield::Battlefields,
+    effects::{EffectBundle, PendingEffects, SelectionResult},
+    in_play::{CardId, Database},
+    protogen::{card::Card, effects::Fight, targets::Location},
+    stack::{Selected, TargetType},
+};
+
+const TEST_VIPER: &str = r#"
+name: Test Viper
+typeline: Creature - Snake
+cost:
+  mana_cost: '{B}'
+power: 1
+toughness: 1
+keywords: Deathtouch
+"#;
+
+const TEST_CLERIC: &str = r#"
+name: Test Cleric
+typeline: Creature - Human Cleric
+cost:
+  mana_cost: '{W}'
+power: 2
+toughness: 5
+keywords: Lifelink
+"#;
+
+fn fight(db: &mut Database, source: CardId, fighters: &[CardId]) {
+    let mut results = PendingEffects::from(EffectBundle {
+        push_on_enter: Some(
+            fighters
+                .iter()
+                .map(|card| Selected {
+                    location: Some(Location::ON_BATTLEFIELD),
+                    target_type: TargetType::Card(*card),
+                    targeted: false,
+                    restrictions: vec![],
+                })
+                .collect(),
+        ),
+        source: Some(source),
+        effects: vec![Fight::default().into()],
+        ..Default::default()
+    });
+    while results.resolve(db, None) != SelectionResult::Complete {}
+
+    let mut results = Battlefields::check_sba(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+}
+
+#[test]
+fn deathtouch_applies() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let viper: Card = serde_yaml::from_str(TEST_VIPER)?;
+    let viper = CardId::upload_card_or_token(&mut game.db, game.p2, viper, false);
+    viper.move_to_battlefield(&mut game.db);
+    let bear = game.card(game.p1, "Alpine Grizzly");
+
+    fight(&mut game.db, bear, &[bear, viper]);
+
+    assert_zone!(game.db, bear, graveyard);
+    assert_zone!(game.db, viper, graveyard);
+
+    Ok(())
+}
+
+#[test]
+fn lifelink_applies() -> anyhow::Result<()> {
+    let mut game = game! {
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let cleric: Card = serde_yaml::from_str(TEST_CLERIC)?;
+    let cleric = CardId::upload_card_or_token(&mut game.db, game.p1, cleric, false);
+    cleric.move_to_battlefield(&mut game.db);
+    let bear = game.card(game.p2, "Alpine Grizzly");
+
+    fight(&mut game.db, cleric, &[cleric, bear]);
+
+    assert_zone!(game.db, cleric, battlefield);
+    assert_eq!(cleric.marked_damage(&game.db), 4);
+    assert_zone!(game.db, bear, graveyard);
+    assert_eq!(game.db.all_players[game.p1].life_total, 22);
+
+    Ok(())
+}
+
+#[test]
+fn fights_source() -> anyhow::Result<()> {
+    let mut game = game! {
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let cleric: Card = serde_yaml::from_str(TEST_CLERIC)?;
+    let cleric = CardId::upload_card_or_token(&mut game.db, game.p1, cleric, false);
+    cleric.move_to_battlefield(&mut game.db);
+    let bear = game.card(game.p2, "Alpine Grizzly");
+
+    fight(&mut game.db, cleric, &[bear]);
+
+    assert_eq!(cleric.marked_damage(&game.db), 4);
+    assert_zone!(game.db, bear, graveyard);
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use pretty_assertions::assert_eq;
+
+use crate::{effects::SelectionResult, stack::Stack, turns::Phase};
+
+#[test]
+fn each_player_sacrifices_a_creature() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Fleshbag Marauder"],
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 battlefield: ["Alpine Grizzly", "Forest"],
+    };
+    let marauder = game.card(game.p1, "Fleshbag Marauder");
+    let ours = game.card(game.p1, "Alpine Grizzly");
+    let theirs = game.card(game.p2, "Alpine Grizzly");
+    let forest = game.card(game.p2, "Forest");
+    let db = &mut game.db;
+    db.all_players[game.p1].infinite_mana();
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let mut results = Stack::move_card_to_stack_from_hand(db, marauder);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+
+    let mut results = Stack::resolve_1(db);
+    assert_eq!(results.resolve(db, None), SelectionResult::Complete);
+
+    // The controller chooses first, and may sacrifice the marauder itself.
+    let mut results = Stack::resolve_1(db);
+    assert_eq!(results.resolve(db, None), SelectionResult::PendingChoice);
+    assert_eq!(results.priority(db), game.p1);
+    assert_eq!(results.options(db).len(), 2);
+    let option = results
+        .option_details(db)
+        .into_iter()
+        .find(|option| option.card == Some(ours))
+        .unwrap()
+        .index;
+    assert_eq!(
+        results.resolve(db, Some(option)),
+        SelectionResult::PendingChoice
+    );
+
+    // The opponent only has one creature to choose.
+    assert_eq!(results.priority(db), game.p2);
+    assert_eq!(results.options(db).len(), 1);
+    assert_eq!(results.resolve(db, Some(0)), SelectionResult::TryAgain);
+    assert_eq!(results.resolve(db, None), SelectionResult::Complete);
+
+    assert_zone!(*db, marauder, battlefield);
+    assert_zone!(*db, ours, graveyard);
+    assert_zone!(*db, theirs, graveyard);
+    assert_zone!(*db, forest, battlefield);
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    format::{Format, Violation},
+    library::DeckDefinition,
+    load_cards,
+};
+
+const FORMAT: &str = r#"
+name: Test Format
+banned:
+  - Hoar Shade
+restricted:
+  - Student of Warfare
+max_copies: 3
+min_deck_size: 10
+game:
+  starting_life: 30
+"#;
+
+#[test]
+fn legal_deck() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let format: Format = serde_yaml::from_str(FORMAT)?;
+    assert_eq!(format.game.starting_life, 30);
+
+    let mut def = DeckDefinition::default();
+    def.add_card("Alpine Grizzly".to_string(), 3);
+    def.add_card("Student of Warfare".to_string(), 1);
+    def.add_card("Forest".to_string(), 8);
+
+    assert_eq!(format.check(&def, &cards), vec![]);
+
+    Ok(())
+}
+
+#[test]
+fn illegal_deck() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let format: Format = serde_yaml::from_str(FORMAT)?;
+
+    let mut def = DeckDefinition::default();
+    def.add_card("Alpine Grizzly".to_string(), 4);
+    def.add_card("Hoar Shade".to_string(), 1);
+    def.add_card("Student of Warfare".to_string(), 2);
+
+    assert_eq!(
+        format.check(&def, &cards),
+        vec![
+            Violation::TooManyCopies {
+                name: "Alpine Grizzly".to_string(),
+                count: 4,
+                max: 3
+            },
+            Violation::Banned("Hoar Shade".to_string()),
+            Violation::TooManyCopies {
+                name: "Student of Warfare".to_string(),
+                count: 2,
+                max: 1
+            },
+            Violation::TooFewCards { size: 7, min: 10 },
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn allowed_sets() -> anyhow::Result<()> {
+    let mut cards = load_cards()?;
+    cards.get_mut("Alpine Grizzly").unwrap().sets = vec!["KTK".to_string()];
+
+    let format: Format = serde_yaml::from_str(
+        r#"
+name: Khans Block
+sets: [KTK, FRF]
+min_deck_size: 0
+"#,
+    )?;
+
+    let mut def = DeckDefinition::default();
+    def.add_card("Alpine Grizzly".to_string(), 4);
+    assert_eq!(format.check(&def, &cards), vec![]);
+
+    def.add_card("Hoar Shade".to_string(), 1);
+    assert_eq!(
+        format.check(&def, &cards),
+        vec![Violation::NotInSets("Hoar Shade".to_string())]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    game::{Game, GameAction},
+    library::DeckDefinition,
+    load_cards,
+    player::Action,
+    protogen::targets::Location,
+    turns::Phase,
+};
+
+fn forests(count: usize) -> DeckDefinition {
+    let mut def = DeckDefinition::default();
+    def.add_card("Forest".to_string(), count);
+    def
+}
+
+#[test]
+fn plays_a_match() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut game = Game::new(
+        &cards,
+        [
+            ("Player".to_string(), forests(20)),
+            ("Opponent".to_string(), forests(20)),
+        ],
+    )?;
+    let [player, opponent] = game.players() else {
+        panic!("Expected 2 players");
+    };
+    let (player, opponent) = (*player, *opponent);
+
+    assert_eq!(game.db().hand[player].len(), 7);
+    assert_eq!(game.db().hand[opponent].len(), 7);
+    assert_eq!(game.player_to_act(), player);
+    assert!(game.pending_choice().is_none());
+    assert!(game.legal_actions(opponent).is_empty());
+    assert!(game.apply(GameAction::Choose(None)).is_err());
+
+    for _ in 0..20 {
+        if game.db().turn.phase == Phase::PreCombatMainPhase {
+            break;
+        }
+        game.apply(GameAction::PassPriority)?;
+    }
+    assert_eq!(game.db().turn.phase, Phase::PreCombatMainPhase);
+    assert_eq!(game.player_to_act(), player);
+
+    let play = game
+        .legal_actions(player)
+        .into_iter()
+        .find(|action| matches!(action, GameAction::Act(Action::Play(_))))
+        .unwrap();
+    let GameAction::Act(Action::Play(forest)) = play else {
+        unreachable!()
+    };
+    game.apply(play)?;
+    assert!(forest.is_in_location(game.db(), Location::ON_BATTLEFIELD));
+    assert_eq!(game.player_to_act(), player);
+
+    // Only one land can be played each turn.
+    assert!(!game
+        .legal_actions(player)
+        .iter()
+        .any(|action| matches!(action, GameAction::Act(Action::Play(_)))));
+    assert!(game.apply(play).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn rejects_invalid_decks() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    assert!(Game::new(&cards, [("Player".to_string(), forests(20))]).is_err());
+    assert!(Game::new(
+        &cards,
+        [
+            ("Player".to_string(), forests(20)),
+            ("Opponent".to_string(), forests(3)),
+        ],
+    )
+    .is_err());
+
+    let mut def = forests(20);
+    def.add_card("Not a card".to_string(), 1);
+    assert!(Game::new(
+        &cards,
+        [
+            ("Player".to_string(), def),
+            ("Opponent".to_string(), forests(20)),
+        ],
+    )
+    .is_err());
+
+    Ok(())
+}
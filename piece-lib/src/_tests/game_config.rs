@@ -0,0 +1,62 @@
+use pretty_assertions::assert_eq;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    config::{GameConfig, Vanguard},
+    effects::SelectionResult,
+    in_play::{CardId, Database},
+    library::Library,
+    load_cards,
+    player::{AllPlayers, Player},
+    turns::{Phase, Turn},
+};
+
+#[test]
+fn sets_up_players_from_config() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::new(GameConfig::commander());
+    let player = all_players.join("Player".to_string(), Vanguard::new(-5, 1));
+    let opponent = all_players.join("Opponent".to_string(), Vanguard::default());
+    let mut db = Database::new(all_players);
+    for _ in 0..20 {
+        let card = CardId::upload(&mut db, &cards, player, "Forest");
+        Library::place_on_top(&mut db, player, card);
+    }
+
+    assert_eq!(db.all_players[player].life_total, 35);
+    assert_eq!(db.all_players[opponent].life_total, 40);
+
+    Player::draw_initial_hand(&mut db, player);
+    assert_eq!(db.hand[player].len(), 8);
+
+    // The first mulligan is free.
+    let mut rng = StdRng::seed_from_u64(0);
+    Player::mulligan(&mut db, player, &mut rng);
+    assert_eq!(db.hand[player].len(), 8);
+    assert_eq!(Player::cards_to_bottom(&db, player), 0);
+    Player::mulligan(&mut db, player, &mut rng);
+    assert_eq!(Player::cards_to_bottom(&db, player), 1);
+
+    Ok(())
+}
+
+#[test]
+fn starting_player_draws() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::new(GameConfig {
+        starting_player_draws: true,
+        ..Default::default()
+    });
+    let player = all_players.join("Player".to_string(), Vanguard::default());
+    let _ = all_players.join("Opponent".to_string(), Vanguard::default());
+    let mut db = Database::new(all_players);
+    let card = CardId::upload(&mut db, &cards, player, "Forest");
+    Library::place_on_top(&mut db, player, card);
+
+    db.turn.set_phase(Phase::Upkeep);
+    let mut results = Turn::step(&mut db);
+    while results.resolve(&mut db, None) != SelectionResult::Complete {}
+    assert_eq!(db.hand[player].len(), 1);
+
+    Ok(())
+}
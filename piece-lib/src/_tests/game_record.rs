@@ -0,0 +1,128 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    config::Cosmetics,
+    effects::SelectionResult,
+    player::Player,
+    record::GameRecord,
+    stack::Stack,
+    turns::{AutoPolicy, Phase, Turn},
+};
+
+#[test]
+fn exports_once_every_player_finishes() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 hand: ["Forest"],
+        p2 library: ["Alpine Grizzly" * 2],
+    };
+    let forest = game.card(game.p2, "Forest");
+    let db = &mut game.db;
+
+    db.turn.turn_count += db.turn.turns_per_round();
+    db.turn.set_phase(Phase::BeginCombat);
+
+    let mut results = Turn::step(db);
+    let result = results.resolve(db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    Player::put_on_bottom(db, game.p2, forest);
+
+    assert!(GameRecord::export(db).is_none());
+    GameRecord::finish(db, game.p1);
+    assert!(GameRecord::export(db).is_none());
+    GameRecord::finish(db, game.p2);
+
+    let record = GameRecord::export(db).unwrap();
+    assert_eq!(record.players.len(), 2);
+    assert_eq!(record.players[0].battlefield, ["Alpine Grizzly"]);
+    assert_eq!(
+        record.players[1].library,
+        ["Alpine Grizzly", "Alpine Grizzly", "Forest"]
+    );
+    assert!(record.players[1].hand.is_empty());
+
+    let attack = &record.decisions[0];
+    assert_eq!(attack.player, "Player");
+    assert_eq!(attack.effect, "ChooseAttackers");
+    assert_eq!(attack.chosen, Some(0));
+    assert!(!attack.options.is_empty());
+
+    let bottom = record.decisions.last().unwrap();
+    assert_eq!(bottom.player, "Opponent");
+    assert_eq!(bottom.effect, "PutOnBottom");
+
+    Ok(())
+}
+
+#[test]
+fn exports_cosmetics() -> anyhow::Result<()> {
+    let mut game = game! {
+        p2 hand: ["Forest"],
+    };
+    let forest = game.card(game.p2, "Forest");
+    let cosmetics = Cosmetics::new(Some("sleeve".to_string()), None, Some("avatar".to_string()));
+    game.db.all_players[game.p2].cosmetics = cosmetics.clone();
+
+    assert_eq!(forest.cosmetics(&game.db), &cosmetics);
+
+    GameRecord::finish(&mut game.db, game.p1);
+    GameRecord::finish(&mut game.db, game.p2);
+    let record = GameRecord::export(&game.db).unwrap();
+    assert_eq!(record.players[0].cosmetics, Cosmetics::default());
+    assert_eq!(record.players[1].cosmetics, cosmetics);
+
+    Ok(())
+}
+
+#[test]
+fn transcript() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Forest", "Alpine Grizzly"],
+        p1 library: ["Forest" * 5],
+        p2 library: ["Forest" * 5],
+    };
+    let forest = game.card(game.p1, "Forest");
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let db = &mut game.db;
+
+    // Mana empties from pools between steps, so it's added once the main phase is reached.
+    Turn::advance_until(db, Phase::PreCombatMainPhase, game.p1, AutoPolicy::Decline)?;
+    db.all_players[game.p1].infinite_mana();
+
+    let mut results = Player::play_card(db, game.p1, forest);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+    let mut results = Player::play_card(db, game.p1, bear);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+    let mut results = Stack::resolve_1(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+
+    Turn::advance_until(db, Phase::PreCombatMainPhase, game.p2, AutoPolicy::Decline)?;
+
+    assert!(GameRecord::transcript(db, false).is_none());
+    GameRecord::finish(db, game.p1);
+    GameRecord::finish(db, game.p2);
+
+    assert_eq!(
+        GameRecord::transcript(db, false).unwrap(),
+        "Turn 1 — Player\n  \
+           Player plays Forest\n  \
+           Player casts Alpine Grizzly\n  \
+           Alpine Grizzly resolves\n\
+         \n\
+         Turn 2 — Opponent\n"
+    );
+
+    let transcript = GameRecord::transcript(db, true).unwrap();
+    assert!(transcript.contains("Turn 2 — Opponent\n  [Player: 20 life, "));
+    assert!(transcript.contains("battlefield: Forest, Alpine Grizzly]\n"));
+    assert!(transcript.contains("\nEnd of game\n"));
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::SelectionResult,
+    protogen::mana::{Mana, ManaRestriction, ManaSource},
+    turns::{Phase, Turn},
+};
+
+#[test]
+fn declining_tax_removes_attackers() -> anyhow::Result<()> {
+    let game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 battlefield: ["Ghostly Prison"],
+    };
+    let player = game.p1;
+    let bear = game.card(player, "Alpine Grizzly");
+    let mut db = game.db;
+
+    db.turn.turn_count += db.turn.turns_per_round();
+    db.turn.set_phase(Phase::BeginCombat);
+
+    let mut results = Turn::step(&mut db);
+    let result = results.resolve(&mut db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(&mut db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(bear.attacking(&db), None);
+    assert!(!bear.tapped(&db));
+
+    Ok(())
+}
+
+#[test]
+fn paying_tax_attacks() -> anyhow::Result<()> {
+    let game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 battlefield: ["Ghostly Prison"],
+    };
+    let (player, opponent) = (game.p1, game.p2);
+    let bear = game.card(player, "Alpine Grizzly");
+    let mut db = game.db;
+
+    db.turn.turn_count += db.turn.turns_per_round();
+    db.turn.set_phase(Phase::BeginCombat);
+
+    let mut results = Turn::step(&mut db);
+    for _ in 0..2 {
+        db.all_players[player]
+            .mana_pool
+            .apply(Mana::WHITE, ManaSource::ANY, ManaRestriction::NONE);
+    }
+
+    let result = results.resolve(&mut db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(&mut db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(&mut db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(&mut db, Some(0));
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(bear.attacking(&db), Some(opponent));
+    assert!(bear.tapped(&db));
+    assert_eq!(db.all_players[player].mana_pool.available_mana().count(), 0);
+
+    Ok(())
+}
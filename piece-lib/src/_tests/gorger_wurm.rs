@@ -0,0 +1,64 @@
+use indexmap::IndexSet;
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::SelectionResult,
+    in_play::{CardId, Database},
+    load_cards,
+    player::AllPlayers,
+    protogen::counters::Counter,
+    stack::Stack,
+    turns::Phase,
+};
+
+#[test]
+fn devours_creatures() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    all_players[player].infinite_mana();
+    let mut db = Database::new(all_players);
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let bear1 = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    bear1.move_to_battlefield(&mut db);
+    let bear2 = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    bear2.move_to_battlefield(&mut db);
+    let bear3 = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    bear3.move_to_battlefield(&mut db);
+
+    let wurm = CardId::upload(&mut db, &cards, player, "Gorger Wurm");
+    let mut results = Stack::move_card_to_stack_from_hand(&mut db, wurm);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    // Pay the red
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::PendingChoice);
+    // Pay the green
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::PendingChoice);
+    // Pay the generic
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let mut results = Stack::resolve_1(&mut db);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    // Devour the first two bears, then stop.
+    let mut results = Stack::resolve_1(&mut db);
+    let result = results.resolve(&mut db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(&mut db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(db.graveyard[player], IndexSet::from([bear1, bear2]));
+    assert_eq!(db[wurm].counters.get(&Counter::P1P1).copied(), Some(2));
+    assert_eq!(wurm.power(&db), Some(7));
+    assert_eq!(wurm.toughness(&db), Some(7));
+    Ok(())
+}
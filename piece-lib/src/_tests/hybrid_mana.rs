@@ -0,0 +1,205 @@
+use std::collections::HashSet;
+
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{Options, SelectionResult},
+    mana::Symbol,
+    player::Player,
+    protogen::{
+        card::Card,
+        color::Color,
+        cost::ManaCost,
+        mana::{Mana, ManaRestriction, ManaSource},
+    },
+    turns::Phase,
+};
+
+const TEST_FINKS: &str = r#"
+name: Test Finks
+typeline: Creature - Ouphe
+cost:
+  mana_cost: '{1}{G/W}{G/W}'
+power: 3
+toughness: 2
+"#;
+
+const TEST_SPECTRAL_PROCESSION: &str = r#"
+name: Test Spectral Procession
+typeline: Sorcery
+cost:
+  mana_cost: '{2/W}'
+"#;
+
+const TEST_MUTAGENIC_GROWTH: &str = r#"
+name: Test Mutagenic Growth
+typeline: Instant
+cost:
+  mana_cost: '{G/P}'
+"#;
+
+fn add_mana(game: &mut crate::_tests::support::Game, mana: Mana) {
+    game.db.all_players[game.p1]
+        .mana_pool
+        .apply(mana, ManaSource::ANY, ManaRestriction::NONE);
+}
+
+#[test]
+fn parses_hybrid_symbols() -> anyhow::Result<()> {
+    let card: Card = serde_yaml::from_str(
+        r#"
+name: Test Hybrid
+typeline: Sorcery
+cost:
+  mana_cost: '{1}{G/W}{2/U}{B/P}'
+"#,
+    )?;
+
+    assert_eq!(
+        card.cost
+            .mana_cost
+            .iter()
+            .map(|cost| cost.enum_value().unwrap())
+            .collect::<Vec<_>>(),
+        [
+            ManaCost::GENERIC,
+            ManaCost::GREEN_WHITE,
+            ManaCost::TWO_BLUE,
+            ManaCost::PHYREXIAN_BLACK
+        ]
+    );
+    assert_eq!(card.cost.cmc(), 5);
+    assert_eq!(
+        card.color_identity(),
+        HashSet::from([Color::GREEN, Color::WHITE, Color::BLUE, Color::BLACK])
+    );
+    assert_eq!(
+        Symbol::from_mana_cost(&card.cost.mana_cost),
+        [
+            Symbol::Generic(1),
+            Symbol::Hybrid(Mana::GREEN, Mana::WHITE),
+            Symbol::TwoHybrid(Mana::BLUE),
+            Symbol::Phyrexian(Mana::BLACK),
+        ]
+    );
+
+    let reloaded: Card = serde_yaml::from_str(&serde_yaml::to_string(&card)?)?;
+    assert_eq!(reloaded.cost.mana_cost, card.cost.mana_cost);
+
+    assert!(serde_yaml::from_str::<Card>(
+        r#"
+name: Test Hybrid
+typeline: Sorcery
+cost:
+  mana_cost: '{W/W}'
+"#,
+    )
+    .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn pays_hybrid_with_either_color() -> anyhow::Result<()> {
+    let mut game = game! {};
+    let player = game.p1;
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+    let finks = game.upload_yaml(game.p1, TEST_FINKS)?;
+    finks.move_to_hand(&mut game.db);
+
+    add_mana(&mut game, Mana::GREEN);
+    add_mana(&mut game, Mana::WHITE);
+    assert!(!Player::can_afford(&game.db, player, finks));
+    add_mana(&mut game, Mana::RED);
+    assert!(Player::can_afford(&game.db, player, finks));
+
+    let mut results = Player::play_card(&mut game.db, player, finks);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    // Pay the green
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::PendingChoice);
+    // Pay the white
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::PendingChoice);
+    // Pay the generic
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(
+        game.db.all_players[player]
+            .mana_pool
+            .available_mana()
+            .count(),
+        0
+    );
+    assert_zone!(game.db, finks, stack);
+
+    Ok(())
+}
+
+#[test]
+fn pays_two_generic_for_two_hybrid() -> anyhow::Result<()> {
+    let mut game = game! {};
+    let player = game.p1;
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+    let procession = game.upload_yaml(game.p1, TEST_SPECTRAL_PROCESSION)?;
+    procession.move_to_hand(&mut game.db);
+    assert_eq!(game.db[procession].modified_cost.cmc(), 2);
+
+    add_mana(&mut game, Mana::RED);
+    assert!(!Player::can_afford(&game.db, player, procession));
+    add_mana(&mut game, Mana::RED);
+    assert!(Player::can_afford(&game.db, player, procession));
+
+    let mut results = Player::play_card(&mut game.db, player, procession);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(
+        game.db.all_players[player]
+            .mana_pool
+            .available_mana()
+            .count(),
+        0
+    );
+    assert_zone!(game.db, procession, stack);
+
+    Ok(())
+}
+
+#[test]
+fn pays_life_for_phyrexian() -> anyhow::Result<()> {
+    let mut game = game! {};
+    let player = game.p1;
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+    let growth = game.upload_yaml(game.p1, TEST_MUTAGENIC_GROWTH)?;
+    growth.move_to_hand(&mut game.db);
+    assert!(Player::can_afford(&game.db, player, growth));
+
+    let mut results = Player::play_card(&mut game.db, player, growth);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::PendingChoice);
+    assert_eq!(
+        results.options(&game.db),
+        Options::ListWithDefault(vec![(0, "Pay 2 life".to_string())])
+    );
+
+    let result = results.resolve(&mut game.db, Some(0));
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(game.db.all_players[player].life_total, 18);
+    assert_zone!(game.db, growth, stack);
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use crate::{
+    abilities::Ability,
+    effects::{Options, PendingEffects},
+    in_play::CardId,
+    protogen::card::Card,
+    stack::Stack,
+};
+
+const TEST_LIFE_WATCHER: &str = r#"
+name: Test Life Watcher
+typeline: Artifact
+triggered_abilities:
+  - trigger:
+      source: enters the battlefield
+      from: anywhere
+    effects:
+      - effect: !SelectEffectController {}
+      - effect: !GainLife
+          count:
+            count: !Fixed
+              count: 1
+    oracle_text: Whenever a permanent enters the battlefield, you gain 1 life.
+"#;
+
+/// Puts the watcher's trigger on the stack `count` times above a spell.
+fn setup(count: usize) -> anyhow::Result<crate::_tests::support::Game> {
+    let mut game = game! {
+        p1 hand: ["Alpine Grizzly"],
+    };
+    let card: Card = serde_yaml::from_str(TEST_LIFE_WATCHER)?;
+    let watcher = CardId::upload_card_or_token(&mut game.db, game.p1, card, false);
+    watcher.move_to_battlefield(&mut game.db);
+
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let _ = Stack::push_card(&mut game.db, bear, vec![], vec![]);
+
+    let trigger = game.db[watcher]
+        .modified_triggers
+        .values()
+        .flatten()
+        .next()
+        .unwrap()
+        .clone();
+    for _ in 0..count {
+        let _ = Stack::push_ability(
+            &mut game.db,
+            watcher,
+            Ability::TriggeredAbility(trigger.clone()),
+            vec![],
+        );
+    }
+
+    Ok(game)
+}
+
+#[test]
+fn groups_identical_triggers() -> anyhow::Result<()> {
+    let game = setup(3)?;
+
+    let groups = game.db.stack.grouped(&game.db);
+    assert_eq!(
+        groups.iter().map(|group| group.len()).collect::<Vec<_>>(),
+        [1, 3]
+    );
+    assert_eq!(game.db.stack.identical_at_top(&game.db), 3);
+
+    // Identical triggers are only offered once when ordering the stack.
+    let pending = PendingEffects::organize_stack(&game.db);
+    let Options::ListWithDefault(options) = pending.options(&game.db) else {
+        unreachable!()
+    };
+    assert_eq!(options.len(), 2);
+    assert!(options[1].1.ends_with("(x3)"));
+
+    Ok(())
+}
+
+#[test]
+fn resolves_identical_triggers() -> anyhow::Result<()> {
+    let mut game = setup(3)?;
+
+    let pending = Stack::resolve_identical(&mut game.db);
+    assert!(pending.is_empty());
+    assert_eq!(game.db.all_players[game.p1].life_total, 23);
+
+    // The spell beneath the triggers is left on the stack.
+    assert_eq!(game.db.stack.entries().len(), 1);
+    assert_eq!(game.db.stack.identical_at_top(&game.db), 1);
+
+    Ok(())
+}
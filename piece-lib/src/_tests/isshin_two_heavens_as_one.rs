@@ -0,0 +1,82 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::SelectionResult,
+    in_play::{CardId, Database},
+    protogen::card::Card,
+    turns::{Phase, Turn},
+};
+
+const WAR_DRUMMER: &str = r#"
+name: Test War Drummer
+typeline: Creature - Human
+cost:
+  mana_cost: '{2}'
+static_abilities:
+  - ability: !TriggerAdditionalTime
+      sources: attacks
+      restrictions:
+        - restriction: !Controller
+            controller: !Self_ {}
+power: 1
+toughness: 1
+"#;
+
+fn attack_with_first(db: &mut Database) {
+    db.turn.turn_count += db.turn.turns_per_round();
+    db.turn.set_phase(Phase::BeginCombat);
+
+    let mut results = Turn::step(db);
+    let result = results.resolve(db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    for _ in 0..10 {
+        if results.resolve(db, None) == SelectionResult::Complete {
+            break;
+        }
+    }
+
+    assert!(results.is_empty());
+}
+
+#[test]
+fn attack_triggers_trigger_twice() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Sanguine Evangelist", "Isshin, Two Heavens as One"],
+    };
+    let evangelist = game.card(game.p1, "Sanguine Evangelist");
+
+    attack_with_first(&mut game.db);
+    assert_eq!(evangelist.attacking(&game.db), Some(game.p2));
+    assert_eq!(game.db.stack.entries.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn additional_triggers_add_together() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Sanguine Evangelist", "Isshin, Two Heavens as One"],
+    };
+    let drummer: Card = serde_yaml::from_str(WAR_DRUMMER)?;
+    let drummer = CardId::upload_card_or_token(&mut game.db, game.p1, drummer, false);
+    drummer.move_to_battlefield(&mut game.db);
+
+    attack_with_first(&mut game.db);
+    assert_eq!(game.db.stack.entries.len(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn triggers_once_without_isshin() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Sanguine Evangelist"],
+    };
+
+    attack_with_first(&mut game.db);
+    assert_eq!(game.db.stack.entries.len(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{EffectBehaviors, SelectedStack},
+    protogen::{
+        counters::Counter,
+        effects::{count::Fixed, AddCounters, Count, RemoveCounters},
+        targets::Location,
+    },
+    stack::{Selected, TargetType},
+};
+
+fn one() -> protobuf::MessageField<Count> {
+    protobuf::MessageField::some(Count {
+        count: Some(
+            Fixed {
+                count: 1,
+                ..Default::default()
+            }
+            .into(),
+        ),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn keyword_counters_grant_keywords() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let mut selected = SelectedStack::new(vec![Selected {
+        location: Some(Location::ON_BATTLEFIELD),
+        target_type: TargetType::Card(bear),
+        targeted: false,
+        restrictions: vec![],
+    }]);
+
+    for counter in [Counter::FLYING, Counter::P1P1] {
+        let _ = AddCounters {
+            counter: counter.into(),
+            count: one(),
+            ..Default::default()
+        }
+        .apply(&mut game.db, Some(bear), &mut selected, false);
+    }
+
+    assert!(bear.flying(&game.db));
+    assert_eq!(game.db[bear].counter_text_on(), ["+1/+1 x1"]);
+    assert_eq!(game.db[bear].keyword_counter_text_on(), ["Flying"]);
+
+    let _ = RemoveCounters {
+        counter: Counter::FLYING.into(),
+        count: one(),
+        ..Default::default()
+    }
+    .apply(&mut game.db, Some(bear), &mut selected, false);
+
+    assert!(!bear.flying(&game.db));
+    assert!(game.db[bear].keyword_counter_text_on().is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    battlefield::Battlefields, effects::SelectionResult, in_play::CardId, protogen::card::Card,
+};
+
+const TEST_VIPER: &str = r#"
+name: Test Viper
+typeline: Creature - Snake
+cost:
+  mana_cost: '{B}'
+power: 1
+toughness: 1
+keywords: Deathtouch
+"#;
+
+#[test]
+fn counts_down_marked_damage() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly", "Hoar Shade"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let shade = game.card(game.p1, "Hoar Shade");
+    let db = &mut game.db;
+
+    assert_eq!(bear.lethal_damage_remaining(db), Some(2));
+    bear.mark_damage(db, Some(shade), 1);
+    assert_eq!(bear.lethal_damage_remaining(db), Some(1));
+
+    let mut results = Battlefields::check_sba(db);
+    assert_eq!(results.resolve(db, None), SelectionResult::Complete);
+    assert_zone!(*db, bear, battlefield);
+
+    bear.mark_damage(db, Some(shade), 3);
+    assert_eq!(bear.lethal_damage_remaining(db), Some(0));
+
+    let mut results = Battlefields::check_sba(db);
+    assert_eq!(results.resolve(db, None), SelectionResult::Complete);
+    assert_zone!(*db, bear, graveyard);
+
+    Ok(())
+}
+
+#[test]
+fn deathtouch_is_lethal() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let viper: Card = serde_yaml::from_str(TEST_VIPER)?;
+    let viper = CardId::upload_card_or_token(&mut game.db, game.p2, viper, false);
+    viper.move_to_battlefield(&mut game.db);
+
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let db = &mut game.db;
+
+    bear.mark_damage(db, Some(viper), 0);
+    assert_eq!(bear.lethal_damage_remaining(db), Some(2));
+
+    bear.mark_damage(db, Some(viper), 1);
+    assert_eq!(bear.lethal_damage_remaining(db), Some(0));
+
+    let mut results = Battlefields::check_sba(db);
+    assert_eq!(results.resolve(db, None), SelectionResult::Complete);
+    assert_zone!(*db, bear, graveyard);
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use pretty_assertions::assert_eq;
+
+use crate::{in_play::Database, library::DeckDefinition, load_cards, player::AllPlayers};
+
+#[test]
+fn builds_decks_in_name_order() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let mut db = Database::new(all_players);
+
+    let mut deck = DeckDefinition::default();
+    deck.add_card("Plains".to_string(), 1);
+    deck.add_card("Forest".to_string(), 2);
+    deck.add_card("Alpine Grizzly".to_string(), 1);
+
+    // The unshuffled order doesn't depend on how the definition is stored, so shuffling with a
+    // seeded rng gives the same library every time.
+    let library = deck.build_deck(&mut db, &cards, player);
+    let names = library
+        .cards
+        .iter()
+        .map(|card| card.name(&db).as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["Alpine Grizzly", "Forest", "Forest", "Plains"]);
+
+    Ok(())
+}
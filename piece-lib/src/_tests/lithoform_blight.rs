@@ -51,6 +51,7 @@ fn works() -> anyhow::Result<()> {
     assert_eq!(result, SelectionResult::Complete);
 
     assert_eq!(db[land].modified_subtypes, SubtypeSet::from([]));
+    assert_eq!(db[land].modified_mana_abilities.len(), 2);
 
     Ok(())
 }
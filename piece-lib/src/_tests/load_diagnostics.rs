@@ -0,0 +1,48 @@
+use std::borrow::Cow;
+
+use pretty_assertions::assert_eq;
+
+use crate::parse_protos;
+
+#[test]
+fn collects_all_errors() {
+    let files = [
+        (
+            Cow::Borrowed("good.yaml"),
+            Cow::Borrowed("name: Good\ntypeline: Creature - Bear\n".as_bytes()),
+        ),
+        (
+            Cow::Borrowed("bad.yaml"),
+            Cow::Borrowed("name: Bad\nnot_a_field: 1\n".as_bytes()),
+        ),
+        (
+            Cow::Borrowed("worse.yaml"),
+            Cow::Borrowed("name: [Worse\n".as_bytes()),
+        ),
+    ];
+
+    let (cards, errors) = parse_protos(files.into_iter());
+    assert_eq!(
+        cards
+            .iter()
+            .map(|(card, file)| (card.name.as_str(), &**file))
+            .collect::<Vec<_>>(),
+        [("Good", "good.yaml")]
+    );
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].to_string().contains("bad.yaml"));
+    assert!(errors[1].to_string().contains("worse.yaml"));
+}
+
+#[test]
+fn reports_invalid_utf8() {
+    let files = [(
+        Cow::Borrowed("binary.yaml"),
+        Cow::Borrowed([b'n', b'a', b'm', b'e', b':', b' ', 0xff, 0xfe].as_slice()),
+    )];
+
+    let (cards, errors) = parse_protos(files.into_iter());
+    assert!(cards.is_empty());
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].to_string().contains("binary.yaml"));
+}
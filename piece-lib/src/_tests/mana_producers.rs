@@ -0,0 +1,63 @@
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+
+use crate::{
+    battlefield::Battlefields,
+    effects::SelectionResult,
+    player::Player,
+    protogen::{
+        mana::{spend_reason::Other, Mana},
+        types::Type,
+    },
+    turns::Phase,
+};
+
+#[test]
+fn tracks_mana_producers() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Forest", "Oaken Siren"],
+    };
+    let forest = game.card(game.p1, "Forest");
+    let siren = game.card(game.p1, "Oaken Siren");
+
+    game.db.turn.turn_count += game.db.turn.turns_per_round();
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    for card in [forest, siren] {
+        let mut results = Battlefields::activate_ability(&mut game.db, &None, game.p1, card, 0);
+        let result = results.resolve(&mut game.db, None);
+        assert_eq!(result, SelectionResult::Complete);
+    }
+
+    let pool = &game.db.all_players[game.p1].mana_pool;
+    assert_eq!(
+        pool.producers(Mana::GREEN)
+            .map(|producer| producer.card)
+            .collect_vec(),
+        [forest]
+    );
+    assert_eq!(
+        pool.producers(Mana::BLUE)
+            .map(|producer| producer.card)
+            .collect_vec(),
+        [siren]
+    );
+    assert_eq!(
+        pool.available_from(|producer| producer.types.contains(&Type::CREATURE)),
+        1
+    );
+    assert_eq!(pool.available_from(|producer| producer.snow()), 0);
+
+    assert!(Player::spend_mana(
+        &mut game.db,
+        game.p1,
+        &[Mana::GREEN],
+        &[],
+        &Other::default().into(),
+    ));
+    let pool = &game.db.all_players[game.p1].mana_pool;
+    assert_eq!(pool.producers(Mana::GREEN).count(), 0);
+    assert_eq!(pool.producers(Mana::BLUE).count(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    mana::{render_symbols, Symbol},
+    protogen::{
+        cost::{AbilityCost, ManaCost},
+        mana::Mana,
+    },
+};
+
+#[test]
+fn combines_generic_mana() {
+    let cost = AbilityCost {
+        mana_cost: vec![
+            ManaCost::GENERIC.into(),
+            ManaCost::GENERIC.into(),
+            ManaCost::WHITE.into(),
+            ManaCost::GENERIC.into(),
+        ],
+        tap: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        cost.symbols(),
+        [Symbol::Generic(3), Symbol::Mana(Mana::WHITE), Symbol::Tap]
+    );
+    assert_eq!(render_symbols(cost.symbols()), "\u{e608}\u{e600}\u{e61a}");
+    assert_eq!(cost.text(), "\u{e608}\u{e600}");
+}
+
+#[test]
+fn renders_every_symbol() {
+    assert_eq!(
+        render_symbols([
+            Symbol::Hybrid(Mana::WHITE, Mana::BLUE),
+            Symbol::TwoHybrid(Mana::GREEN),
+            Symbol::Phyrexian(Mana::BLACK),
+            Symbol::Snow,
+            Symbol::X,
+            Symbol::Untap,
+        ]),
+        "\u{e600}/\u{e601}\u{e607}/\u{e604}\u{e602}/\u{e618}\u{e619}\u{e615}\u{e61b}"
+    );
+    assert_eq!(
+        render_symbols([Symbol::Generic(16), Symbol::Generic(21)]),
+        "\u{e62a}21"
+    );
+}
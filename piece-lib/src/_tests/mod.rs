@@ -1,39 +1,127 @@
+#[macro_use]
+mod support;
+
 mod abuelos_awakening;
 mod abzan_banner;
 mod abzan_runemark;
+mod activation_limits;
+mod activation_preview;
+mod affected_by_option;
 mod allosuarus_shepherd;
+mod attribution;
+mod auto_pass;
+mod auto_resolved;
 mod basic_lands;
 mod bat_colony;
+mod batch_tap;
 mod blasting_station;
+mod blocking;
 mod blood_scrivener;
+mod breezekeeper;
+mod cant_be_sacrificed;
+mod card_filter;
+mod card_metadata;
+mod card_overrides;
+mod card_query;
+mod characteristics;
+mod checkpoints;
+mod choose_card_name;
+mod clock;
 mod clone;
+mod colors;
+mod combat;
+mod companion;
+mod councils_judgment;
 mod counterspell;
 mod darigaazs_attendant;
 mod dauntless_dismantler;
 mod deadapult;
+mod debug;
+mod deck;
 mod deconstruction_hammer;
+mod docs;
 mod dryad_of_the_ilysian_grove;
+mod durations;
 mod dusk_rose_reliquary;
 mod eaten_by_piranhas;
 mod elesh_norn_grand_cenobite;
+mod end_of_combat;
+mod entry_order;
+mod evacuation;
+mod exiled_with;
 mod fabrication_foundry;
 mod family_reunion;
+mod fight;
+mod fleshbag_marauder;
 mod forbidden_friendship;
+mod format;
+mod game;
+mod game_config;
+mod game_record;
+mod ghostly_prison;
 mod glowspore_shaman;
+mod gorger_wurm;
 mod haunting_imitation;
 mod hoar_shade;
+mod hybrid_mana;
+mod identical_triggers;
+mod isshin_two_heavens_as_one;
+mod keyword_counters;
 mod king_crab;
 mod krosan_verge;
+mod lethal_damage;
+mod library_order;
 mod lithoform_blight;
+mod load_diagnostics;
 mod mace_of_the_valiant;
 mod majestic_metamorphosis;
+mod mana_producers;
+mod mana_symbols;
+mod mulligan;
+mod necrotic_ooze;
+mod net;
+mod nimbus_naiad;
+mod notifications;
+mod option_details;
+mod otherworldly_journey;
+mod palisade_giant;
 mod paradise_mantle;
+mod pay_life_for_mana;
+mod planeswalkers;
+mod play_as_though_in_hand;
 mod plus_two_mace;
+mod priority;
+mod proliferate;
+mod protection;
 mod quicksand_whirlpool;
+mod reality_ripple;
 mod reality_shift;
 mod recruiter_of_the_guard;
+mod regeneration;
+mod remove_all_counters;
+mod resolve_until_choice;
+mod rest_in_peace;
+mod restriction_cache;
+mod revealed;
+mod roles;
+mod sagas;
+mod saved_games;
 mod sinister_strength;
+mod state_based_actions;
+mod stony_silence;
+mod student_of_warfare;
+mod stun_counters;
+mod suspended_triggers;
 mod the_everflowing_well;
 mod thermal_blast;
+mod timetwister;
 mod titania_protector_of_argoth;
+mod token_actions;
+mod trace;
+mod trigger_limits;
+mod turns;
+mod vanilla;
+mod vulturous_aven;
+mod x_bounds;
 mod zhulodok_void_gorger;
+mod zone_change_replacement;
@@ -0,0 +1,35 @@
+use pretty_assertions::assert_eq;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    in_play::Database,
+    library::DeckDefinition,
+    load_cards,
+    player::{AllPlayers, Player},
+};
+
+#[test]
+fn mulligans() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let mut db = Database::new(all_players);
+
+    let mut def = DeckDefinition::default();
+    def.add_card("Forest".to_string(), 10);
+    def.add_card("Alpine Grizzly".to_string(), 10);
+    db.all_players[player].library = def.build_deck(&mut db, &cards, player);
+    Player::draw_initial_hand(&mut db, player);
+
+    Player::mulligan(&mut db, player, &mut StdRng::seed_from_u64(0));
+    assert_eq!(Player::mulligans(&db, player), 1);
+    assert_eq!(db.hand[player].len(), 7);
+    assert_eq!(db.all_players[player].library.len(), 13);
+
+    let bottom = *db.hand[player].first().unwrap();
+    Player::put_on_bottom(&mut db, player, bottom);
+    assert_eq!(db.hand[player].len(), 6);
+    assert_eq!(db.all_players[player].library.cards().last(), Some(bottom));
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    battlefield::Battlefields, effects::SelectionResult, in_play::CardId, in_play::Database,
+    load_cards, player::AllPlayers, protogen::effects::Duration, stack::Stack, turns::Phase,
+};
+
+#[test]
+fn gains_abilities_of_graveyard_creatures() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    all_players[player].infinite_mana();
+
+    let mut db = Database::new(all_players);
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let ooze = CardId::upload(&mut db, &cards, player, "Necrotic Ooze");
+    ooze.move_to_battlefield(&mut db);
+    assert!(db[ooze].abilities(&db).is_empty());
+
+    let shade = CardId::upload(&mut db, &cards, player, "Hoar Shade");
+    shade.move_to_graveyard(&mut db);
+    let mut results = Battlefields::check_sba(&mut db);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+    assert_eq!(db[ooze].abilities(&db).len(), 1);
+
+    let mut results = Battlefields::activate_ability(&mut db, &None, player, ooze, 0);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let mut results = Stack::resolve_1(&mut db);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    // The ability refers to the ooze, not the shade it came from.
+    assert_eq!(ooze.power(&db), Some(5));
+    assert_eq!(ooze.toughness(&db), Some(4));
+
+    shade.move_to_exile(&mut db, shade, None, Duration::PERMANENTLY);
+    let mut results = Battlefields::check_sba(&mut db);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+    assert!(db[ooze].abilities(&db).is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn updates_shared_abilities_when_cards_change_zones() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+
+    let mut db = Database::new(all_players);
+
+    let ooze = CardId::upload(&mut db, &cards, player, "Necrotic Ooze");
+    ooze.move_to_battlefield(&mut db);
+
+    let shade = CardId::upload(&mut db, &cards, player, "Hoar Shade");
+    shade.move_to_graveyard(&mut db);
+    assert_eq!(db[ooze].abilities(&db).len(), 1);
+
+    shade.move_to_hand(&mut db);
+    assert!(db[ooze].abilities(&db).is_empty());
+
+    Ok(())
+}
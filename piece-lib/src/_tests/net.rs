@@ -0,0 +1,83 @@
+use std::net::TcpListener;
+
+use pretty_assertions::assert_eq;
+
+use crate::{
+    library::DeckDefinition,
+    load_cards,
+    net::{Client, Host, ServerMessage},
+};
+
+fn forests(count: usize) -> DeckDefinition {
+    let mut def = DeckDefinition::default();
+    def.add_card("Forest".to_string(), count);
+    def
+}
+
+#[test]
+fn plays_over_tcp() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let address = listener.local_addr()?;
+    let client =
+        std::thread::spawn(move || Client::connect(address, "Opponent".to_string(), &forests(20)));
+    let mut host = Host::accept(&listener, &cards, "Player".to_string(), forests(20))?;
+    let mut client = client.join().unwrap()?;
+
+    // The client only sees its own hand, and has to wait while the host has priority.
+    let ServerMessage::Update(update) = client.recv()? else {
+        panic!("Expected an update");
+    };
+    assert!(update.players[0].hand.is_empty());
+    assert_eq!(update.players[0].cards_in_hand, 7);
+    assert_eq!(update.players[1].hand.len(), 7);
+    assert!(update.actions.is_empty());
+    assert!(!host.poll_remote()?);
+
+    let update = host.update();
+    assert_eq!(update.players[0].hand.len(), 7);
+    let pass = update
+        .actions
+        .iter()
+        .position(|action| action == "Pass priority")
+        .unwrap();
+    host.choose(pass)?;
+    assert_eq!(host.game().player_to_act(), host.remote_player());
+
+    let ServerMessage::Update(update) = client.recv()? else {
+        panic!("Expected an update");
+    };
+    let pass = update
+        .actions
+        .iter()
+        .position(|action| action == "Pass priority")
+        .unwrap();
+
+    // Choices which weren't offered are rejected without changing the game.
+    client.choose(update.actions.len())?;
+    assert!(!host.poll_remote()?);
+    assert!(matches!(client.recv()?, ServerMessage::Rejected { .. }));
+    assert_eq!(host.game().player_to_act(), host.remote_player());
+
+    client.choose(pass)?;
+    assert!(host.poll_remote()?);
+    assert!(matches!(client.recv()?, ServerMessage::Update(_)));
+    assert_eq!(host.game().player_to_act(), host.local_player());
+
+    Ok(())
+}
+
+#[test]
+fn rejects_invalid_decks() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let address = listener.local_addr()?;
+    let client =
+        std::thread::spawn(move || Client::connect(address, "Opponent".to_string(), &forests(3)));
+    assert!(Host::accept(&listener, &cards, "Player".to_string(), forests(20)).is_err());
+
+    let mut client = client.join().unwrap()?;
+    assert!(matches!(client.recv()?, ServerMessage::Rejected { .. }));
+
+    Ok(())
+}
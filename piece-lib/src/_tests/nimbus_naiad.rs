@@ -0,0 +1,115 @@
+use pretty_assertions::assert_eq;
+use protobuf::Enum;
+
+use crate::{
+    battlefield::Battlefields,
+    effects::SelectionResult,
+    in_play::{CardId, Database},
+    load_cards,
+    player::{AllPlayers, Player},
+    protogen::{keywords::Keyword, targets::Location, types::Type},
+    stack::Stack,
+    turns::Phase,
+    types::TypeSet,
+};
+
+#[test]
+fn bestows() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    all_players[player].infinite_mana();
+    let mut db = Database::new(all_players);
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let bear = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    bear.move_to_battlefield(&mut db);
+
+    let naiad = CardId::upload(&mut db, &cards, player, "Nimbus Naiad");
+    naiad.move_to_hand(&mut db);
+    let mut results = Player::bestow(&mut db, player, naiad);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    // Choose the target
+    let result = results.resolve(&mut db, Some(0));
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    // Pay the blue
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::PendingChoice);
+    // Pay the generic
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert!(!naiad.types_intersect(&db, &TypeSet::from([Type::CREATURE])));
+
+    let mut results = Stack::resolve_1(&mut db);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert!(naiad.is_in_location(&db, Location::ON_BATTLEFIELD));
+    assert!(!naiad.types_intersect(&db, &TypeSet::from([Type::CREATURE])));
+    assert_eq!(naiad.power(&db), None);
+    assert_eq!(bear.power(&db), Some(6));
+    assert_eq!(bear.toughness(&db), Some(4));
+    assert!(db[bear]
+        .modified_keywords
+        .contains_key(&Keyword::FLYING.value()));
+
+    bear.move_to_graveyard(&mut db);
+    let mut results = Battlefields::check_sba(&mut db);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert!(naiad.is_in_location(&db, Location::ON_BATTLEFIELD));
+    assert!(naiad.types_intersect(&db, &TypeSet::from([Type::CREATURE])));
+    assert_eq!(naiad.power(&db), Some(2));
+    assert_eq!(naiad.toughness(&db), Some(2));
+
+    Ok(())
+}
+
+#[test]
+fn bestow_target_gone() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    all_players[player].infinite_mana();
+    let mut db = Database::new(all_players);
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let bear = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    bear.move_to_battlefield(&mut db);
+
+    let naiad = CardId::upload(&mut db, &cards, player, "Nimbus Naiad");
+    naiad.move_to_hand(&mut db);
+    let mut results = Player::bestow(&mut db, player, naiad);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, Some(0));
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    bear.move_to_graveyard(&mut db);
+
+    let mut results = Stack::resolve_1(&mut db);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert!(naiad.is_in_location(&db, Location::ON_BATTLEFIELD));
+    assert!(naiad.types_intersect(&db, &TypeSet::from([Type::CREATURE])));
+    assert_eq!(db[naiad].enchanting, None);
+    assert_eq!(naiad.power(&db), Some(2));
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    battlefield::Battlefields,
+    in_play::Database,
+    notifications::{Notification, Notifications},
+    player::AllPlayers,
+    turns::{Phase, Turn},
+};
+
+#[test]
+fn notifies() {
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let opponent = all_players.new_player("Opponent".to_string(), 20);
+    let mut db = Database::new(all_players);
+
+    Notifications::decision(&mut db, None);
+    Notifications::decision(&mut db, None);
+    db.turn.step_priority();
+    Notifications::decision(&mut db, None);
+    assert_eq!(
+        Notifications::drain(&mut db),
+        vec![
+            Notification::ActionRequired(player),
+            Notification::ActionRequired(opponent),
+        ]
+    );
+
+    db.turn.set_phase(Phase::Cleanup);
+    let _ = Turn::step(&mut db);
+    assert_eq!(
+        Notifications::drain(&mut db),
+        vec![Notification::YourTurn(opponent)]
+    );
+
+    db.all_players[player].life_total = 3;
+    let _ = Battlefields::check_sba(&mut db);
+    let _ = Battlefields::check_sba(&mut db);
+    assert_eq!(
+        Notifications::drain(&mut db),
+        vec![Notification::LifeLow { player, life: 3 }]
+    );
+
+    db.all_players[player].lost = true;
+    let _ = Battlefields::check_sba(&mut db);
+    Notifications::decision(&mut db, None);
+    assert_eq!(
+        Notifications::drain(&mut db),
+        vec![Notification::GameOver {
+            winner: Some(opponent)
+        }]
+    );
+}
@@ -0,0 +1,48 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{OptionCategory, SelectionResult},
+    player::Player,
+    protogen::targets::Location,
+};
+
+#[test]
+fn describes_targets() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Thermal Blast"],
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    game.db.all_players[game.p1].infinite_mana();
+
+    let blast = game.card(game.p1, "Thermal Blast");
+    let mine = game.card(game.p1, "Alpine Grizzly");
+    let theirs = game.card(game.p2, "Alpine Grizzly");
+
+    let db = &mut game.db;
+    let mut pending = Player::play_card(db, game.p1, blast);
+    while !pending.wants_input(db) {
+        assert_ne!(pending.resolve(db, None), SelectionResult::Complete);
+    }
+
+    let details = pending.option_details(db);
+    assert_eq!(details.len(), 2);
+    for detail in details.iter() {
+        assert_eq!(detail.description, "Alpine Grizzly");
+        assert_eq!(
+            detail.category,
+            OptionCategory::Card(Location::ON_BATTLEFIELD)
+        );
+        assert_eq!(detail.player, None);
+        assert_eq!(detail.is_default, detail.index == 0);
+    }
+
+    let mut cards = details
+        .iter()
+        .filter_map(|detail| detail.card)
+        .collect::<Vec<_>>();
+    cards.sort_by_key(|card| *card != mine);
+    assert_eq!(cards, vec![mine, theirs]);
+
+    Ok(())
+}
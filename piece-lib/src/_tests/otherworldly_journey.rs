@@ -0,0 +1,99 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{PendingEffects, SelectionResult},
+    in_play::CastFrom,
+    player::Owner,
+    protogen::targets::Location,
+    stack::{Selected, Stack, TargetType},
+    turns::{AutoPolicy, Phase, Turn},
+};
+
+#[test]
+fn returns_at_next_end_step() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 library: ["Forest" * 10],
+        p1 hand: ["Otherworldly Journey"],
+        p2 library: ["Forest" * 10],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p2, "Alpine Grizzly");
+    let journey = game.card(game.p1, "Otherworldly Journey");
+
+    Turn::advance_until(
+        &mut game.db,
+        Phase::PreCombatMainPhase,
+        game.p1,
+        AutoPolicy::Decline,
+    )?;
+
+    let mut results = PendingEffects::default();
+    results.apply_results(journey.move_to_stack(
+        &mut game.db,
+        vec![Selected {
+            location: Some(Location::ON_BATTLEFIELD),
+            target_type: TargetType::Card(bear),
+            targeted: true,
+            restrictions: vec![],
+        }],
+        CastFrom::Hand,
+        vec![],
+    ));
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let mut results = Stack::resolve_1(&mut game.db);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_zone!(game.db, bear, exile);
+
+    Turn::advance_until(&mut game.db, Phase::EndStep, game.p1, AutoPolicy::Decline)?;
+
+    assert_zone!(game.db, bear, battlefield);
+    assert_eq!(Owner::from(game.db[bear].controller), game.p2);
+    assert_pt!(game.db, bear, 5 / 3);
+
+    Ok(())
+}
+
+#[test]
+fn cast_during_end_step_waits_for_next_turn() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 library: ["Forest" * 10],
+        p1 hand: ["Otherworldly Journey"],
+        p2 library: ["Forest" * 10],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p2, "Alpine Grizzly");
+    let journey = game.card(game.p1, "Otherworldly Journey");
+
+    Turn::advance_until(&mut game.db, Phase::EndStep, game.p1, AutoPolicy::Decline)?;
+
+    let mut results = PendingEffects::default();
+    results.apply_results(journey.move_to_stack(
+        &mut game.db,
+        vec![Selected {
+            location: Some(Location::ON_BATTLEFIELD),
+            target_type: TargetType::Card(bear),
+            targeted: true,
+            restrictions: vec![],
+        }],
+        CastFrom::Hand,
+        vec![],
+    ));
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let mut results = Stack::resolve_1(&mut game.db);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    Turn::advance_until(&mut game.db, Phase::Upkeep, game.p2, AutoPolicy::Decline)?;
+    assert_zone!(game.db, bear, exile);
+
+    Turn::advance_until(&mut game.db, Phase::EndStep, game.p2, AutoPolicy::Decline)?;
+    assert_zone!(game.db, bear, battlefield);
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{EffectBundle, PendingEffects, SelectionResult},
+    in_play::{CardId, CastFrom, Database},
+    load_cards,
+    player::{AllPlayers, Owner, Player},
+    protogen::{
+        card::Card,
+        effects::{count::Fixed, Count, DamageSelected},
+        targets::Location,
+    },
+    stack::{Selected, Stack, TargetType},
+};
+
+const BODYGUARD: &str = r#"
+name: Test Bodyguard
+typeline: Creature - Human Soldier
+cost:
+  mana_cost: '{1}{W}'
+replacement_abilities:
+  - restrictions:
+      - restriction: !IsPlayer {}
+      - restriction: !Controller
+          controller: !Self_ {}
+    effects:
+      - effect: !RedirectDamage
+          optional: true
+          restrictions:
+            - restriction: !Controller
+                controller: !Self_ {}
+            - restriction: !OfType
+                types: Creature
+    replacing: damage
+power: 1
+toughness: 1
+"#;
+
+fn damage(count: i32) -> DamageSelected {
+    DamageSelected {
+        count: protobuf::MessageField::some(Count {
+            count: Some(
+                Fixed {
+                    count,
+                    ..Default::default()
+                }
+                .into(),
+            ),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+fn play(db: &mut Database, player: Owner, card: CardId) {
+    card.move_to_hand(db);
+    let mut results = Player::play_card(db, player, card);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+
+    let mut results = Stack::resolve_1(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+}
+
+#[test]
+fn redirects_damage() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    all_players[player].infinite_mana();
+    let opponent = all_players.new_player("Opponent".to_string(), 20);
+    all_players[opponent].infinite_mana();
+
+    let mut db = Database::new(all_players);
+
+    let giant = CardId::upload(&mut db, &cards, player, "Palisade Giant");
+    play(&mut db, player, giant);
+    assert_eq!(giant.location(&db), Some(Location::ON_BATTLEFIELD));
+
+    let bear = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    bear.move_to_battlefield(&mut db);
+
+    let blast = CardId::upload(&mut db, &cards, opponent, "Thermal Blast");
+    let mut results = PendingEffects::default();
+    results.apply_results(blast.move_to_stack(
+        &mut db,
+        vec![Selected {
+            location: Some(Location::ON_BATTLEFIELD),
+            target_type: TargetType::Card(bear),
+            targeted: true,
+            restrictions: vec![],
+        }],
+        CastFrom::Hand,
+        vec![],
+    ));
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let mut results = Stack::resolve_1(&mut db);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(bear.marked_damage(&db), 0);
+    assert_eq!(giant.marked_damage(&db), 3);
+
+    let source = CardId::upload(&mut db, &cards, opponent, "Alpine Grizzly");
+    source.move_to_battlefield(&mut db);
+
+    let mut results = PendingEffects::from(EffectBundle {
+        push_on_enter: Some(vec![Selected {
+            location: None,
+            target_type: TargetType::Player(player),
+            targeted: false,
+            restrictions: vec![],
+        }]),
+        source: Some(source),
+        effects: vec![damage(2).into()],
+        ..Default::default()
+    });
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(db.all_players[player].life_total, 20);
+    assert_eq!(giant.marked_damage(&db), 5);
+
+    Ok(())
+}
+
+#[test]
+fn optional_redirect_prompts_controller() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    all_players[player].infinite_mana();
+    let opponent = all_players.new_player("Opponent".to_string(), 20);
+
+    let mut db = Database::new(all_players);
+
+    let bodyguard: Card = serde_yaml::from_str(BODYGUARD)?;
+    let bodyguard = CardId::upload_card_or_token(&mut db, player, bodyguard, false);
+    play(&mut db, player, bodyguard);
+
+    let bear = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    bear.move_to_battlefield(&mut db);
+
+    let source = CardId::upload(&mut db, &cards, opponent, "Alpine Grizzly");
+    source.move_to_battlefield(&mut db);
+
+    let bundle = || EffectBundle {
+        push_on_enter: Some(vec![Selected {
+            location: None,
+            target_type: TargetType::Player(player),
+            targeted: false,
+            restrictions: vec![],
+        }]),
+        source: Some(source),
+        effects: vec![damage(2).into()],
+        ..Default::default()
+    };
+
+    let mut results = PendingEffects::from(bundle());
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    assert!(results.wants_input(&db));
+    assert_eq!(results.priority(&db), player);
+    assert_eq!(results.options(&db).len(), 2);
+
+    let result = results.resolve(&mut db, Some(1));
+    assert_eq!(result, SelectionResult::Complete);
+    assert_eq!(db.all_players[player].life_total, 20);
+    assert_eq!(bodyguard.marked_damage(&db) + bear.marked_damage(&db), 2);
+
+    let mut results = PendingEffects::from(bundle());
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+    assert_eq!(db.all_players[player].life_total, 18);
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{Options, SelectionResult},
+    in_play::CardId,
+    player::Player,
+    protogen::{
+        card::Card,
+        mana::{Mana, ManaRestriction, ManaSource},
+        targets::Location,
+    },
+    turns::Phase,
+};
+
+const LIFE_FONT: &str = r#"
+name: Test Life Font
+typeline: Enchantment
+cost:
+  mana_cost: '{B}'
+static_abilities:
+  - ability: !PayLifeForMana
+      mana_cost: '{B}'
+      life: 2
+"#;
+
+#[test]
+fn pays_life_for_black_mana() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Blood Scrivener"],
+    };
+    let player = game.p1;
+    let scrivener = game.card(player, "Blood Scrivener");
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    game.db.all_players[player].mana_pool.apply(
+        Mana::GREEN,
+        ManaSource::ANY,
+        ManaRestriction::NONE,
+    );
+    assert!(!Player::can_afford(&game.db, player, scrivener));
+
+    let font: Card = serde_yaml::from_str(LIFE_FONT)?;
+    let font = CardId::upload_card_or_token(&mut game.db, player, font, false);
+    font.move_to_battlefield(&mut game.db);
+    assert!(Player::can_afford(&game.db, player, scrivener));
+
+    let mut results = Player::play_card(&mut game.db, player, scrivener);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::PendingChoice);
+    assert_eq!(
+        results.options(&game.db),
+        Options::ListWithDefault(vec![(1, "Pay 2 life".to_string())])
+    );
+
+    let result = results.resolve(&mut game.db, Some(1));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(game.db.all_players[player].life_total, 18);
+    assert_eq!(
+        game.db.all_players[player]
+            .mana_pool
+            .available_mana()
+            .count(),
+        0
+    );
+    assert_eq!(scrivener.location(&game.db), Some(Location::IN_STACK));
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    battlefield::Battlefields,
+    combat,
+    effects::SelectionResult,
+    in_play::Database,
+    turns::{Phase, Turn},
+};
+
+const TEST_PLANESWALKER: &str = r#"
+name: Test Planeswalker
+typeline: Legendary Planeswalker
+loyalty: 3
+activated_abilities:
+  - cost:
+      loyalty: 1
+    effects:
+      - effect: !SelectSource {}
+    oracle_text: 'Do nothing.'
+  - cost:
+      loyalty: -3
+    effects:
+      - effect: !SelectSource {}
+    oracle_text: 'Do nothing.'
+"#;
+
+/// Moves on to the same player's next turn.
+fn next_turn(db: &mut Database) {
+    for _ in 0..db.turn.turns_per_round() {
+        db.turn.set_phase(Phase::Cleanup);
+        let _ = Turn::step(db);
+    }
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+}
+
+#[test]
+fn enters_with_loyalty() -> anyhow::Result<()> {
+    let mut game = game! {};
+    let planeswalker = game.upload_yaml(game.p1, TEST_PLANESWALKER)?;
+    planeswalker.move_to_battlefield(&mut game.db);
+
+    assert!(planeswalker.is_planeswalker(&game.db));
+    assert_eq!(planeswalker.loyalty(&game.db), 3);
+
+    Ok(())
+}
+
+#[test]
+fn one_loyalty_ability_each_turn() -> anyhow::Result<()> {
+    let mut game = game! {};
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+    let planeswalker = game.upload_yaml(game.p1, TEST_PLANESWALKER)?;
+    planeswalker.move_to_battlefield(&mut game.db);
+
+    assert!(game.activate(game.p1, planeswalker, 0));
+    assert_eq!(planeswalker.loyalty(&game.db), 4);
+    assert!(!game.activate(game.p1, planeswalker, 0));
+    assert!(!game.activate(game.p1, planeswalker, 1));
+    assert_eq!(planeswalker.loyalty(&game.db), 4);
+
+    next_turn(&mut game.db);
+    assert!(game.activate(game.p1, planeswalker, 0));
+    assert_eq!(planeswalker.loyalty(&game.db), 5);
+
+    Ok(())
+}
+
+#[test]
+fn loyalty_abilities_are_sorcery_speed() -> anyhow::Result<()> {
+    let mut game = game! {};
+    let planeswalker = game.upload_yaml(game.p1, TEST_PLANESWALKER)?;
+    planeswalker.move_to_battlefield(&mut game.db);
+
+    game.db.turn.set_phase(Phase::BeginCombat);
+    assert!(!game.activate(game.p1, planeswalker, 0));
+
+    game.db.turn.set_phase(Phase::PostCombatMainPhase);
+    assert!(game.activate(game.p1, planeswalker, 0));
+
+    Ok(())
+}
+
+#[test]
+fn dies_without_loyalty() -> anyhow::Result<()> {
+    let mut game = game! {};
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+    let planeswalker = game.upload_yaml(game.p1, TEST_PLANESWALKER)?;
+    planeswalker.move_to_battlefield(&mut game.db);
+
+    assert!(game.activate(game.p1, planeswalker, 1));
+    assert_eq!(planeswalker.loyalty(&game.db), 0);
+    assert_zone!(game.db, planeswalker, battlefield);
+
+    let mut results = Battlefields::check_sba(&mut game.db);
+    while results.resolve(&mut game.db, None) != SelectionResult::Complete {}
+    assert_zone!(game.db, planeswalker, graveyard);
+
+    Ok(())
+}
+
+#[test]
+fn minus_abilities_need_enough_loyalty() -> anyhow::Result<()> {
+    let mut game = game! {};
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+    let planeswalker = game.upload_yaml(game.p1, TEST_PLANESWALKER)?;
+    planeswalker.move_to_battlefield(&mut game.db);
+    game.db[planeswalker].counters.clear();
+
+    assert!(!game.activate(game.p1, planeswalker, 1));
+    assert!(game.activate(game.p1, planeswalker, 0));
+    assert_eq!(planeswalker.loyalty(&game.db), 1);
+
+    Ok(())
+}
+
+#[test]
+fn attacking_a_planeswalker_removes_loyalty() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let planeswalker = game.upload_yaml(game.p2, TEST_PLANESWALKER)?;
+    planeswalker.move_to_battlefield(&mut game.db);
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let db = &mut game.db;
+    db[bear].attacking = Some(game.p2);
+    db[bear].attacking_planeswalker = Some(planeswalker);
+    db.turn.set_phase(Phase::DeclareAttackers);
+
+    let preview = combat::combat_preview(db);
+    assert_eq!(preview.attacks[0].planeswalker, Some(planeswalker));
+    assert_eq!(preview.attacks[0].damage, 4);
+    assert_eq!(preview.life_totals[&game.p2], 20);
+
+    let mut results = Turn::step(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+    for _ in 0..2 {
+        let mut results = Turn::step(db);
+        while results.resolve(db, None) != SelectionResult::Complete {}
+        let mut results = Battlefields::check_sba(db);
+        while results.resolve(db, None) != SelectionResult::Complete {}
+    }
+
+    assert_eq!(db.turn.phase, Phase::Damage);
+    assert_eq!(db.all_players[game.p2].life_total, 20);
+    assert_zone!(*db, planeswalker, graveyard);
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::SelectionResult,
+    in_play::CardId,
+    player::{Action, Owner, Player},
+    protogen::{card::Card, targets::Location},
+    turns::Phase,
+};
+
+const TEST_CRUCIBLE: &str = r#"
+name: Test Crucible
+typeline: Artifact
+static_abilities:
+  - ability: !PlayAsThoughInHand
+      restrictions:
+        - restriction: !Location
+            locations: In Graveyard
+        - restriction: !OfType
+            types: Land
+"#;
+
+fn crucible(game: &mut crate::_tests::support::Game, player: Owner) -> anyhow::Result<CardId> {
+    let card: Card = serde_yaml::from_str(TEST_CRUCIBLE)?;
+    let card = CardId::upload_card_or_token(&mut game.db, player, card, false);
+    card.move_to_battlefield(&mut game.db);
+    Ok(card)
+}
+
+#[test]
+fn plays_lands_from_graveyard() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Alpine Grizzly"],
+        p1 graveyard: ["Forest", "Alpine Grizzly"],
+        p1 exile: ["Forest"],
+    };
+    let player = game.p1;
+    let opponent = game.p2;
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+    let in_hand = game.card(player, "Alpine Grizzly");
+    let forest = game.cards_named(player, "Forest")[0];
+
+    assert_eq!(
+        Player::playable_cards(&game.db, player),
+        [(in_hand, Location::IN_HAND)]
+    );
+
+    // An opponent's permission doesn't let the player play their own cards.
+    crucible(&mut game, opponent)?;
+    assert_eq!(Player::playable_cards(&game.db, player).len(), 1);
+
+    crucible(&mut game, player)?;
+    assert_eq!(
+        Player::playable_cards(&game.db, player),
+        [
+            (in_hand, Location::IN_HAND),
+            (forest, Location::IN_GRAVEYARD)
+        ]
+    );
+    assert!(Player::actions(&game.db, player, &None).contains(&Action::Play(forest)));
+
+    let mut pending = Player::play_card(&mut game.db, player, forest);
+    assert_eq!(
+        pending.resolve(&mut game.db, None),
+        SelectionResult::Complete
+    );
+    assert_zone!(game.db, forest, battlefield);
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{PendingEffects, SelectionResult},
+    in_play::CastFrom,
+    protogen::targets::Location,
+    stack::{Selected, TargetType},
+    turns::{Phase, Turn},
+};
+
+#[test]
+fn responding_player_gets_priority_back() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 hand: ["Otherworldly Journey"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let journey = game.card(game.p2, "Otherworldly Journey");
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    game.db.turn.pass_priority();
+    assert_eq!(game.db.turn.priority_player(), game.p2);
+
+    let mut results = PendingEffects::default();
+    results.apply_results(journey.move_to_stack(
+        &mut game.db,
+        vec![Selected {
+            location: Some(Location::ON_BATTLEFIELD),
+            target_type: TargetType::Card(bear),
+            targeted: true,
+            restrictions: vec![],
+        }],
+        CastFrom::Hand,
+        vec![],
+    ));
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+    assert!(!game.db.turn.passed_full_priority_round());
+
+    // The first player passing doesn't count towards the round once a spell has been cast.
+    game.db.turn.pass_priority();
+    assert!(!game.db.turn.passed_full_priority_round());
+    assert_eq!(game.db.turn.priority_player(), game.p1);
+
+    let results = Turn::step(&mut game.db);
+    assert!(results.is_empty());
+    assert_eq!(game.db.stack.entries().len(), 1);
+    assert_zone!(game.db, bear, battlefield);
+
+    game.db.turn.pass_priority();
+    assert!(game.db.turn.passed_full_priority_round());
+
+    let mut results = Turn::step(&mut game.db);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_zone!(game.db, bear, exile);
+    assert!(game.db.stack.is_empty());
+    assert_eq!(game.db.turn.phase, Phase::PreCombatMainPhase);
+
+    Ok(())
+}
+
+#[test]
+fn spells_wait_for_every_player_to_pass() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+        p1 hand: ["Otherworldly Journey"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let journey = game.card(game.p1, "Otherworldly Journey");
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let mut results = PendingEffects::default();
+    results.apply_results(journey.move_to_stack(
+        &mut game.db,
+        vec![Selected {
+            location: Some(Location::ON_BATTLEFIELD),
+            target_type: TargetType::Card(bear),
+            targeted: true,
+            restrictions: vec![],
+        }],
+        CastFrom::Hand,
+        vec![],
+    ));
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    // Nobody has passed since the spell was cast.
+    let results = Turn::step(&mut game.db);
+    assert!(results.is_empty());
+    assert_eq!(game.db.stack.entries().len(), 1);
+    assert_zone!(game.db, bear, battlefield);
+
+    for _ in 0..game.db.turn.turns_per_round() {
+        game.db.turn.pass_priority();
+    }
+
+    let mut results = Turn::step(&mut game.db);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_zone!(game.db, bear, exile);
+    assert!(game.db.stack.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{PendingEffects, SelectionResult},
+    in_play::CastFrom,
+    protogen::counters::Counter,
+    stack::Stack,
+};
+
+#[test]
+fn adds_one_of_each_kind() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 library: ["Forest"],
+        p1 hand: ["Steady Progress"],
+        p1 battlefield: ["Student of Warfare"],
+        p2 battlefield: ["Alpine Grizzly", "Hoar Shade"],
+    };
+    let progress = game.card(game.p1, "Steady Progress");
+    let student = game.card(game.p1, "Student of Warfare");
+    let bear = game.card(game.p2, "Alpine Grizzly");
+    let shade = game.card(game.p2, "Hoar Shade");
+
+    *game.db[student].counters.entry(Counter::LEVEL).or_default() = 2;
+    student.apply_modifiers_layered(&mut game.db);
+    *game.db[bear].counters.entry(Counter::P1P1).or_default() = 1;
+    *game.db[bear].counters.entry(Counter::STUN).or_default() = 1;
+    bear.apply_modifiers_layered(&mut game.db);
+    assert_pt!(game.db, student, 3 / 3);
+
+    let mut results = PendingEffects::default();
+    results.apply_results(progress.move_to_stack(&mut game.db, vec![], CastFrom::Hand, vec![]));
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    // The shade has no counters, so only the student and the bear can be chosen.
+    let mut results = Stack::resolve_1(&mut game.db);
+    assert_eq!(results.options(&game.db).len(), 2);
+    let result = results.resolve(&mut game.db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(&mut game.db, Some(0));
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(game.db[student].counters.get(&Counter::LEVEL), Some(&3));
+    assert_eq!(game.db[bear].counters.get(&Counter::P1P1), Some(&2));
+    assert_eq!(game.db[bear].counters.get(&Counter::STUN), Some(&2));
+    assert!(game.db[shade].counters.values().all(|count| *count == 0));
+    assert_eq!(game.db.hand[game.p1].len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn chooses_no_permanents() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 library: ["Forest"],
+        p1 hand: ["Steady Progress"],
+        p1 battlefield: ["Student of Warfare"],
+    };
+    let progress = game.card(game.p1, "Steady Progress");
+    let student = game.card(game.p1, "Student of Warfare");
+
+    *game.db[student].counters.entry(Counter::LEVEL).or_default() = 2;
+    student.apply_modifiers_layered(&mut game.db);
+
+    let mut results = PendingEffects::default();
+    results.apply_results(progress.move_to_stack(&mut game.db, vec![], CastFrom::Hand, vec![]));
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let mut results = Stack::resolve_1(&mut game.db);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(game.db[student].counters.get(&Counter::LEVEL), Some(&2));
+
+    Ok(())
+}
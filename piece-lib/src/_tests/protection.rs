@@ -0,0 +1,144 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    battlefield::Battlefields,
+    effects::SelectionResult,
+    in_play::{CardId, ModifierId},
+    protogen::{
+        card::Card,
+        color::Color,
+        effects::{BattlefieldModifier, Duration, ModifyBattlefield, Protection, SelectTargets},
+    },
+};
+
+const TEST_KNIGHT: &str = r#"
+name: Test Knight
+typeline: Creature - Human Knight
+cost:
+  mana_cost: '{W}{W}'
+power: 2
+toughness: 2
+protection:
+  colors: Red
+  types: Enchantment
+"#;
+
+const TEST_GOBLIN: &str = r#"
+name: Test Goblin
+typeline: Creature - Goblin
+cost:
+  mana_cost: '{R}'
+power: 2
+toughness: 2
+"#;
+
+fn setup(game: &mut crate::_tests::support::Game) -> anyhow::Result<(CardId, CardId)> {
+    let knight: Card = serde_yaml::from_str(TEST_KNIGHT)?;
+    let knight = CardId::upload_card_or_token(&mut game.db, game.p1, knight, false);
+    knight.move_to_battlefield(&mut game.db);
+
+    let goblin: Card = serde_yaml::from_str(TEST_GOBLIN)?;
+    let goblin = CardId::upload_card_or_token(&mut game.db, game.p2, goblin, false);
+    goblin.move_to_battlefield(&mut game.db);
+
+    Ok((knight, goblin))
+}
+
+#[test]
+fn prevents_damage() -> anyhow::Result<()> {
+    let mut game = game! {
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let (knight, goblin) = setup(&mut game)?;
+    let bear = game.card(game.p2, "Alpine Grizzly");
+
+    knight.mark_damage(&mut game.db, Some(goblin), 2);
+    assert_eq!(knight.marked_damage(&game.db), 0);
+
+    knight.mark_damage(&mut game.db, Some(bear), 1);
+    assert_eq!(knight.marked_damage(&game.db), 1);
+
+    Ok(())
+}
+
+#[test]
+fn cant_be_blocked_or_targeted() -> anyhow::Result<()> {
+    let mut game = game! {
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let (knight, goblin) = setup(&mut game)?;
+    let bear = game.card(game.p2, "Alpine Grizzly");
+
+    assert!(!goblin.can_block(&game.db, knight));
+    assert!(bear.can_block(&game.db, knight));
+
+    assert!(!knight.can_be_targeted(&game.db, goblin));
+    assert!(knight.can_be_targeted(&game.db, bear));
+
+    let select = SelectTargets::default();
+    assert!(!select
+        .valid_targets(&game.db, Some(goblin), &[])
+        .any(|target| target.id(&game.db) == Some(knight)));
+    assert!(select
+        .valid_targets(&game.db, Some(bear), &[])
+        .any(|target| target.id(&game.db) == Some(knight)));
+
+    Ok(())
+}
+
+#[test]
+fn auras_fall_off() -> anyhow::Result<()> {
+    let mut game = game! {
+        p2 battlefield: ["Petrify"],
+    };
+    let (knight, _) = setup(&mut game)?;
+    let petrify = game.card(game.p2, "Petrify");
+    game.db[petrify].enchanting = Some(knight);
+
+    let mut results = Battlefields::check_sba(&mut game.db);
+    while results.resolve(&mut game.db, None) != SelectionResult::Complete {}
+    assert_zone!(game.db, petrify, graveyard);
+
+    Ok(())
+}
+
+#[test]
+fn modifiers_grant_protection() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let (knight, goblin) = setup(&mut game)?;
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let opposing_bear = game.card(game.p2, "Alpine Grizzly");
+
+    let protection_from = |color: Color| BattlefieldModifier {
+        modifier: protobuf::MessageField::some(ModifyBattlefield {
+            add_protection: protobuf::MessageField::some(Protection {
+                colors: vec![protobuf::EnumOrUnknown::new(color)],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        duration: protobuf::EnumOrUnknown::new(Duration::UNTIL_END_OF_TURN),
+        ..Default::default()
+    };
+
+    let modifier =
+        ModifierId::upload_temporary_modifier(&mut game.db, bear, protection_from(Color::RED));
+    bear.apply_modifier(&mut game.db, modifier);
+    assert!(bear.protected_from(&game.db, goblin));
+    assert!(!goblin.can_block(&game.db, bear));
+    bear.mark_damage(&mut game.db, Some(goblin), 2);
+    assert_eq!(bear.marked_damage(&game.db), 0);
+
+    // Granted protection adds to the protection the knight already has.
+    let modifier =
+        ModifierId::upload_temporary_modifier(&mut game.db, knight, protection_from(Color::GREEN));
+    knight.apply_modifier(&mut game.db, modifier);
+    assert!(knight.protected_from(&game.db, goblin));
+    assert!(knight.protected_from(&game.db, opposing_bear));
+    assert!(!knight.can_be_targeted(&game.db, opposing_bear));
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{PendingEffects, SelectionResult},
+    in_play::CastFrom,
+    log::LogId,
+    protogen::targets::Location,
+    stack::{Selected, Stack, TargetType},
+    turns::{AutoPolicy, Phase, Turn},
+};
+
+#[test]
+fn phased_out_permanents_are_ignored() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 library: ["Forest" * 10],
+        p1 hand: ["Reality Ripple"],
+        p2 library: ["Forest" * 10],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p2, "Alpine Grizzly");
+    let ripple = game.card(game.p1, "Reality Ripple");
+
+    let mut results = PendingEffects::default();
+    results.apply_results(ripple.move_to_stack(
+        &mut game.db,
+        vec![Selected {
+            location: Some(Location::ON_BATTLEFIELD),
+            target_type: TargetType::Card(bear),
+            targeted: true,
+            restrictions: vec![],
+        }],
+        CastFrom::Hand,
+        vec![],
+    ));
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let mut results = Stack::resolve_1(&mut game.db);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert!(bear.phased_out(&game.db));
+    assert_eq!(bear.location(&game.db), Some(Location::ON_BATTLEFIELD));
+    assert!(!bear.is_in_location(&game.db, Location::ON_BATTLEFIELD));
+    assert!(!bear.passes_restrictions(&game.db, LogId::current(&game.db), ripple, &[]));
+    assert!(game.db.battlefield[game.p2].is_empty());
+
+    Turn::advance_until(&mut game.db, Phase::Upkeep, game.p2, AutoPolicy::Decline)?;
+
+    assert!(!bear.phased_out(&game.db));
+    assert_zone!(game.db, bear, battlefield);
+    assert_eq!(game.db.battlefield[game.p2].len(), 1);
+
+    Ok(())
+}
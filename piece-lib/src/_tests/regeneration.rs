@@ -0,0 +1,97 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    battlefield::Battlefields,
+    effects::{EffectBehaviors, EffectBundle, PendingEffects, SelectedStack, SelectionResult},
+    in_play::{CardId, Database},
+    protogen::{
+        effects::{DestroySelected, Regenerate},
+        targets::Location,
+    },
+    stack::{Selected, TargetType},
+};
+
+fn selected(card: CardId) -> Selected {
+    Selected {
+        location: Some(Location::ON_BATTLEFIELD),
+        target_type: TargetType::Card(card),
+        targeted: false,
+        restrictions: vec![],
+    }
+}
+
+fn regenerate(db: &mut Database, card: CardId) {
+    let _ = Regenerate::default().apply(
+        db,
+        None,
+        &mut SelectedStack::new(vec![selected(card)]),
+        false,
+    );
+}
+
+fn destroy(db: &mut Database, card: CardId) {
+    let mut results = PendingEffects::from(EffectBundle {
+        push_on_enter: Some(vec![selected(card)]),
+        effects: vec![DestroySelected::default().into()],
+        ..Default::default()
+    });
+    while results.resolve(db, None) != SelectionResult::Complete {}
+}
+
+#[test]
+fn shield_replaces_destruction() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let db = &mut game.db;
+    db[bear].attacking = Some(game.p2);
+    bear.mark_damage(db, None, 1);
+
+    regenerate(db, bear);
+    destroy(db, bear);
+    assert_zone!(*db, bear, battlefield);
+    assert!(bear.tapped(db));
+    assert_eq!(bear.attacking(db), None);
+    assert_eq!(bear.marked_damage(db), 0);
+
+    // Each shield is only used once.
+    destroy(db, bear);
+    assert_zone!(*db, bear, graveyard);
+
+    Ok(())
+}
+
+#[test]
+fn shield_replaces_lethal_damage() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let db = &mut game.db;
+
+    regenerate(db, bear);
+    bear.mark_damage(db, None, 2);
+    let mut results = Battlefields::check_sba(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+    assert_zone!(*db, bear, battlefield);
+    assert_eq!(bear.marked_damage(db), 0);
+
+    Ok(())
+}
+
+#[test]
+fn shields_expire_at_end_of_turn() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let db = &mut game.db;
+
+    regenerate(db, bear);
+    let _ = Battlefields::end_turn(db);
+    destroy(db, bear);
+    assert_zone!(*db, bear, graveyard);
+
+    Ok(())
+}
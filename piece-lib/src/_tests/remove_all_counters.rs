@@ -0,0 +1,62 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{PendingEffects, SelectionResult},
+    in_play::{CardId, CastFrom},
+    protogen::{card::Card, counters::Counter, targets::Location},
+    stack::{Selected, Stack, TargetType},
+};
+
+const TEST_SPELL: &str = r#"
+name: Test Spell
+typeline: Sorcery
+cost:
+  mana_cost: '{B}'
+targets:
+  selector: !SelectTargets
+    count:
+      count: !Fixed
+        count: 1
+    restrictions:
+      - restriction: !OnBattlefield {}
+effects:
+  - effect: !RemoveAllCounters {}
+"#;
+
+#[test]
+fn removes_every_kind() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    *game.db[bear].counters.entry(Counter::P1P1).or_default() = 2;
+    *game.db[bear].counters.entry(Counter::STUN).or_default() = 1;
+    bear.apply_modifiers_layered(&mut game.db);
+
+    let spell: Card = serde_yaml::from_str(TEST_SPELL)?;
+    let spell = CardId::upload_card_or_token(&mut game.db, game.p1, spell, false);
+
+    let mut results = PendingEffects::default();
+    results.apply_results(spell.move_to_stack(
+        &mut game.db,
+        vec![Selected {
+            location: Some(Location::ON_BATTLEFIELD),
+            target_type: TargetType::Card(bear),
+            targeted: true,
+            restrictions: vec![],
+        }],
+        CastFrom::Hand,
+        vec![],
+    ));
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let mut results = Stack::resolve_1(&mut game.db);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert!(game.db[bear].counters.values().all(|count| *count == 0));
+    assert_pt!(game.db, bear, 4 / 2);
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use pretty_assertions::assert_eq;
+
+use crate::{effects::SelectionResult, stack::Stack, turns::Phase};
+
+#[test]
+fn resolves_until_a_choice_is_needed() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Council's Judgment"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let judgment = game.card(game.p1, "Council's Judgment");
+    let bear = game.card(game.p2, "Alpine Grizzly");
+    let db = &mut game.db;
+    db.all_players[game.p1].infinite_mana();
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let mut results = Stack::move_card_to_stack_from_hand(db, judgment);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+
+    let mut results = Stack::resolve_1(db);
+    assert_eq!(
+        results.resolve_until_choice(db, None),
+        SelectionResult::PendingChoice
+    );
+    assert_eq!(results.priority(db), game.p1);
+    assert_eq!(
+        results.resolve_until_choice(db, Some(0)),
+        SelectionResult::PendingChoice
+    );
+    assert_eq!(results.priority(db), game.p2);
+
+    // The exile and the rest of the spell's resolution happen in the same call as the last vote.
+    assert_eq!(
+        results.resolve_until_choice(db, Some(0)),
+        SelectionResult::Complete
+    );
+    assert!(results.is_empty());
+    assert_zone!(*db, bear, exile);
+    assert_zone!(*db, judgment, graveyard);
+
+    Ok(())
+}
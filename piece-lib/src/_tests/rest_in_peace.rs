@@ -0,0 +1,63 @@
+use pretty_assertions::assert_eq;
+
+use crate::{effects::SelectionResult, player::Player, stack::Stack};
+
+#[test]
+fn exiles_instead_of_graveyard() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Rest in Peace", "Forest"],
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 graveyard: ["Alpine Grizzly"],
+    };
+    game.db.all_players[game.p1].infinite_mana();
+
+    let rest = game.card(game.p1, "Rest in Peace");
+    let db = &mut game.db;
+    let mut results = Player::play_card(db, game.p1, rest);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+    let mut results = Stack::resolve_1(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+    assert_zone!(*db, rest, battlefield);
+
+    let mut results = Stack::resolve_1(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+    assert_zone!(game.db, game.card(game.p2, "Alpine Grizzly"), exile);
+
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    bear.move_to_graveyard(&mut game.db);
+    assert_zone!(game.db, bear, exile);
+    assert_eq!(game.db[bear].exiled_with, Some(rest));
+
+    let forest = game.card(game.p1, "Forest");
+    forest.move_to_graveyard(&mut game.db);
+    assert_zone!(game.db, forest, exile);
+
+    Ok(())
+}
+
+#[test]
+fn stops_replacing_once_gone() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Rest in Peace"],
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    game.db.all_players[game.p1].infinite_mana();
+
+    let rest = game.card(game.p1, "Rest in Peace");
+    let db = &mut game.db;
+    let mut results = Player::play_card(db, game.p1, rest);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+    let mut results = Stack::resolve_1(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+    let mut results = Stack::resolve_1(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+
+    rest.move_to_graveyard(db);
+    assert_zone!(*db, rest, exile);
+
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    bear.move_to_graveyard(&mut game.db);
+    assert_zone!(game.db, bear, graveyard);
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use pretty_assertions::assert_eq;
+use protobuf::Enum;
+
+use crate::{
+    log::LogId,
+    protogen::{
+        empty::Empty,
+        targets::{restriction, Restriction},
+        types::Type,
+    },
+};
+
+fn of_type(ty: Type) -> Vec<Restriction> {
+    vec![Restriction {
+        restriction: Some(
+            restriction::OfType {
+                types: HashMap::from([(ty.value(), Empty::default())]),
+                ..Default::default()
+            }
+            .into(),
+        ),
+        ..Default::default()
+    }]
+}
+
+#[test]
+fn caches_within_scope() -> anyhow::Result<()> {
+    let game = game! {
+        p1 battlefield: ["Alpine Grizzly", "Forest"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let forest = game.card(game.p1, "Forest");
+    let creature = of_type(Type::CREATURE);
+    let land = of_type(Type::LAND);
+
+    let db = &game.db;
+    assert_eq!(db.restriction_cache.len(), None);
+
+    db.with_restriction_cache(|db| {
+        for _ in 0..3 {
+            assert!(bear.passes_restrictions(db, LogId::current(db), bear, &creature));
+            assert!(!bear.passes_restrictions(db, LogId::current(db), bear, &land));
+            assert!(forest.passes_restrictions(db, LogId::current(db), bear, &land));
+        }
+        assert_eq!(db.restriction_cache.len(), Some(3));
+
+        // Nested scopes share the outer cache.
+        db.with_restriction_cache(|db| {
+            assert!(!forest.passes_restrictions(db, LogId::current(db), bear, &creature));
+        });
+        assert_eq!(db.restriction_cache.len(), Some(4));
+    });
+
+    assert_eq!(db.restriction_cache.len(), None);
+
+    Ok(())
+}
+
+#[test]
+fn clears_after_panic() -> anyhow::Result<()> {
+    let game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let creature = of_type(Type::CREATURE);
+
+    let db = &game.db;
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        db.with_restriction_cache(|db| {
+            assert!(bear.passes_restrictions(db, LogId::current(db), bear, &creature));
+            panic!("checking restrictions failed");
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(db.restriction_cache.len(), None);
+
+    Ok(())
+}
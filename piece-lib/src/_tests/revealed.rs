@@ -0,0 +1,62 @@
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    effects::{EffectBehaviors, SelectedStack},
+    in_play::{CardId, Database},
+    library::Library,
+    protogen::{effects::Reveal, targets::Location},
+    stack::{Selected, TargetType},
+};
+
+fn reveal(db: &mut Database, card: CardId, location: Location) {
+    let mut selected = SelectedStack::new(vec![Selected {
+        location: Some(location),
+        target_type: TargetType::Card(card),
+        targeted: false,
+        restrictions: vec![],
+    }]);
+    let results = Reveal::default().apply(db, None, &mut selected, false);
+    assert!(results.is_empty());
+}
+
+#[test]
+fn revealed_until_zone_change() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+
+    assert!(bear.visible_to(&game.db, game.p1));
+    assert!(!bear.visible_to(&game.db, game.p2));
+
+    reveal(&mut game.db, bear, Location::IN_HAND);
+    assert!(bear.revealed_to(&game.db, game.p2));
+    assert!(bear.visible_to(&game.db, game.p2));
+
+    bear.move_to_battlefield(&mut game.db);
+    bear.move_to_hand(&mut game.db);
+    assert!(!bear.revealed_to(&game.db, game.p2));
+    assert!(!bear.visible_to(&game.db, game.p2));
+
+    Ok(())
+}
+
+#[test]
+fn revealed_until_shuffled() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 library: ["Forest" * 5],
+    };
+    let forest = game.card(game.p1, "Forest");
+
+    assert!(!forest.visible_to(&game.db, game.p1));
+    reveal(&mut game.db, forest, Location::IN_LIBRARY);
+    assert!(forest.visible_to(&game.db, game.p1));
+    assert!(forest.visible_to(&game.db, game.p2));
+    assert_zone!(game.db, forest, library);
+
+    Library::reshuffle(&mut game.db, game.p1, &mut StdRng::seed_from_u64(0));
+    assert!(!forest.visible_to(&game.db, game.p1));
+    assert!(!forest.visible_to(&game.db, game.p2));
+
+    Ok(())
+}
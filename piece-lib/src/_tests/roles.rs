@@ -0,0 +1,110 @@
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+use protobuf::Enum;
+
+use crate::{
+    battlefield::Battlefields,
+    effects::{PendingEffects, SelectionResult},
+    in_play::{CardId, CastFrom, Database},
+    player::Owner,
+    protogen::{keywords::Keyword, targets::Location},
+    stack::{Selected, Stack, TargetType},
+};
+
+fn cast_rage(db: &mut Database, rage: CardId, target: CardId) {
+    let mut results = PendingEffects::default();
+    results.apply_results(rage.move_to_stack(
+        db,
+        vec![Selected {
+            location: Some(Location::ON_BATTLEFIELD),
+            target_type: TargetType::Card(target),
+            targeted: true,
+            restrictions: vec![],
+        }],
+        CastFrom::Hand,
+        vec![],
+    ));
+    let result = results.resolve(db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let mut results = Stack::resolve_1(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+
+    let mut results = Battlefields::check_sba(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+}
+
+fn roles(db: &Database, player: Owner) -> Vec<CardId> {
+    db.battlefield[player]
+        .iter()
+        .copied()
+        .filter(|card| db[*card].token && card.name(db) == "Monster Role")
+        .collect_vec()
+}
+
+#[test]
+fn created_attached() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Monstrous Rage"],
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let rage = game.card(game.p1, "Monstrous Rage");
+    let bear = game.card(game.p1, "Alpine Grizzly");
+
+    cast_rage(&mut game.db, rage, bear);
+
+    let roles = roles(&game.db, game.p1);
+    assert_eq!(roles.len(), 1);
+    assert_eq!(game.db[roles[0]].enchanting, Some(bear));
+    assert_pt!(game.db, bear, 7 / 3);
+    assert!(game.db[bear]
+        .modified_keywords
+        .contains_key(&Keyword::TRAMPLE.value()));
+
+    Ok(())
+}
+
+#[test]
+fn newest_role_replaces_oldest() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Monstrous Rage" * 2],
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let rages = game.cards_named(game.p1, "Monstrous Rage").to_vec();
+    let bear = game.card(game.p1, "Alpine Grizzly");
+
+    cast_rage(&mut game.db, rages[0], bear);
+    let first = roles(&game.db, game.p1)[0];
+
+    cast_rage(&mut game.db, rages[1], bear);
+    let remaining = roles(&game.db, game.p1);
+    assert_eq!(remaining.len(), 1);
+    assert_ne!(remaining[0], first);
+    assert!(!first.is_in_location(&game.db, Location::ON_BATTLEFIELD));
+
+    // Only one Role's bonus applies, along with both Rages until end of turn.
+    assert_pt!(game.db, bear, 9 / 3);
+
+    Ok(())
+}
+
+#[test]
+fn roles_from_different_players_coexist() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Monstrous Rage"],
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 hand: ["Monstrous Rage"],
+    };
+    let p1_rage = game.card(game.p1, "Monstrous Rage");
+    let p2_rage = game.card(game.p2, "Monstrous Rage");
+    let bear = game.card(game.p1, "Alpine Grizzly");
+
+    cast_rage(&mut game.db, p1_rage, bear);
+    cast_rage(&mut game.db, p2_rage, bear);
+
+    assert_eq!(roles(&game.db, game.p1).len(), 1);
+    assert_eq!(roles(&game.db, game.p2).len(), 1);
+    assert_pt!(game.db, bear, 10 / 4);
+
+    Ok(())
+}
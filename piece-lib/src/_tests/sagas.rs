@@ -0,0 +1,120 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    battlefield::Battlefields,
+    effects::{PendingEffects, SelectionResult},
+    in_play::{CardId, Database},
+    player::Player,
+    protogen::{card::Card, counters::Counter},
+    stack::Stack,
+    turns::{Phase, Turn},
+};
+
+const TEST_SAGA: &str = r#"
+name: Test Saga
+typeline: Enchantment - Saga
+chapters:
+  - chapter: 1
+    ability:
+      effects:
+        - effect: !SelectEffectController {}
+        - effect: !DrawCards
+            count:
+              count: !Fixed
+                count: 1
+      oracle_text: 'I — Draw a card.'
+  - chapter: 2
+    ability:
+      effects:
+        - effect: !SelectEffectController {}
+        - effect: !DrawCards
+            count:
+              count: !Fixed
+                count: 1
+      oracle_text: 'II — Draw a card.'
+  - chapter: 3
+    ability:
+      effects:
+        - effect: !SelectEffectController {}
+        - effect: !DrawCards
+            count:
+              count: !Fixed
+                count: 1
+      oracle_text: 'III — Draw a card.'
+"#;
+
+fn resolve(db: &mut Database, mut results: PendingEffects) {
+    while results.resolve(db, None) != SelectionResult::Complete {}
+}
+
+fn check_sba(db: &mut Database) {
+    let results = Battlefields::check_sba(db);
+    resolve(db, results);
+}
+
+#[test]
+fn chapters_trigger_as_lore_counters_are_added() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 library: ["Forest" * 10],
+    };
+    let saga: Card = serde_yaml::from_str(TEST_SAGA)?;
+    let saga = CardId::upload_card_or_token(&mut game.db, game.p1, saga, false);
+    saga.move_to_hand(&mut game.db);
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+    let hand = game.db.hand[game.p1].len() - 1;
+
+    let results = Player::play_card(&mut game.db, game.p1, saga);
+    resolve(&mut game.db, results);
+    let results = Stack::resolve_1(&mut game.db);
+    resolve(&mut game.db, results);
+
+    assert_zone!(game.db, saga, battlefield);
+    assert_eq!(saga.lore(&game.db), 1);
+    assert_eq!(game.db.stack.entries().len(), 1);
+    let results = Stack::resolve_1(&mut game.db);
+    resolve(&mut game.db, results);
+    assert_eq!(game.db.hand[game.p1].len(), hand + 1);
+
+    // Another lore counter is added at the start of each of its controller's precombat main
+    // phases.
+    game.db.turn.set_phase(Phase::Draw);
+    let results = Turn::step(&mut game.db);
+    resolve(&mut game.db, results);
+    assert_eq!(saga.lore(&game.db), 2);
+    assert_eq!(game.db.stack.entries().len(), 1);
+    let results = Stack::resolve_1(&mut game.db);
+    resolve(&mut game.db, results);
+    assert_eq!(game.db.hand[game.p1].len(), hand + 2);
+
+    Ok(())
+}
+
+#[test]
+fn sacrificed_after_final_chapter() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 library: ["Forest" * 10],
+    };
+    let saga: Card = serde_yaml::from_str(TEST_SAGA)?;
+    let saga = CardId::upload_card_or_token(&mut game.db, game.p1, saga, false);
+    saga.move_to_battlefield(&mut game.db);
+    game.db[saga].counters.insert(Counter::LORE, 2);
+    let hand = game.db.hand[game.p1].len();
+
+    game.db.turn.set_phase(Phase::Draw);
+    let results = Turn::step(&mut game.db);
+    resolve(&mut game.db, results);
+    assert_eq!(saga.lore(&game.db), 3);
+
+    // The saga stays until its final chapter ability leaves the stack.
+    check_sba(&mut game.db);
+    assert_zone!(game.db, saga, battlefield);
+
+    let results = Stack::resolve_1(&mut game.db);
+    resolve(&mut game.db, results);
+    assert_eq!(game.db.hand[game.p1].len(), hand + 1);
+
+    check_sba(&mut game.db);
+    assert_zone!(game.db, saga, graveyard);
+
+    Ok(())
+}
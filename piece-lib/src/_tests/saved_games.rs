@@ -0,0 +1,156 @@
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{PendingEffects, SelectionResult},
+    in_play::{CardId, CastFrom, Database},
+    player::Controller,
+    protogen::{
+        counters::Counter,
+        mana::{Mana, ManaRestriction, ManaSource},
+        targets::Location,
+    },
+    stack::{Selected, Stack, TargetType},
+    turns::Phase,
+};
+
+#[test]
+fn round_trips_board() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly", "Sinister Strength", "Forest"],
+        p1 hand: ["Forest"],
+        p1 library: ["Mountain", "Forest"],
+        p2 graveyard: ["Hoar Shade"],
+        p2 exile: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let strength = game.card(game.p1, "Sinister Strength");
+    let forest = game.card(game.p1, "Forest");
+
+    bear.apply_aura(&mut game.db, strength);
+    *game.db[bear].counters.entry(Counter::P1P1).or_default() += 1;
+    game.db[forest].tapped = true;
+    game.db.all_players[game.p1].mana_pool.apply(
+        Mana::GREEN,
+        ManaSource::ANY,
+        ManaRestriction::NONE,
+    );
+    game.db.all_players[game.p2].life_total = 13;
+    game.db.turn.turn_count = 3;
+    game.db.turn.set_phase(Phase::PostCombatMainPhase);
+
+    let saved = game.db.serialize_state()?;
+    let db = Database::deserialize_state(&game.cards, &saved)?;
+
+    let [p1, p2] = db.all_players.all_players()[..] else {
+        panic!("Expected 2 players");
+    };
+    let names = |cards: &mut dyn Iterator<Item = CardId>| {
+        cards.map(|card| card.name(&db).clone()).collect_vec()
+    };
+
+    assert_eq!(
+        names(
+            &mut db.battlefield.battlefields[&Controller::from(p1)]
+                .iter()
+                .copied()
+        ),
+        ["Alpine Grizzly", "Sinister Strength", "Forest"]
+    );
+    assert_eq!(names(&mut db.hand[p1].iter().copied()), ["Forest"]);
+    assert_eq!(
+        names(&mut db.all_players[p1].library.cards()),
+        ["Forest", "Mountain"]
+    );
+    assert_eq!(names(&mut db.graveyard[p2].iter().copied()), ["Hoar Shade"]);
+    assert_eq!(names(&mut db.exile[p2].iter().copied()), ["Alpine Grizzly"]);
+
+    let [bear, strength, forest] = db.battlefield.battlefields[&Controller::from(p1)]
+        .iter()
+        .copied()
+        .collect_vec()[..]
+    else {
+        unreachable!()
+    };
+    assert_eq!(db[strength].enchanting, Some(bear));
+    assert_pt!(db, bear, 8 / 4);
+    assert!(forest.tapped(&db));
+    assert!(!bear.tapped(&db));
+
+    assert_eq!(
+        db.all_players[p1].mana_pool.pools_display(),
+        game.db.all_players[game.p1].mana_pool.pools_display()
+    );
+    assert_eq!(db.all_players[p2].life_total, 13);
+    assert_eq!(db.turn.turn_count, 3);
+    assert_eq!(db.turn.phase, Phase::PostCombatMainPhase);
+    assert_eq!(db.turn.active_player(), p1);
+
+    // Saving the resumed game gives back the same state.
+    assert_eq!(db.serialize_state()?, saved);
+
+    Ok(())
+}
+
+#[test]
+fn round_trips_stack() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Otherworldly Journey"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p2, "Alpine Grizzly");
+    let journey = game.card(game.p1, "Otherworldly Journey");
+
+    let mut results = PendingEffects::default();
+    results.apply_results(journey.move_to_stack(
+        &mut game.db,
+        vec![Selected {
+            location: Some(Location::ON_BATTLEFIELD),
+            target_type: TargetType::Card(bear),
+            targeted: true,
+            restrictions: vec![],
+        }],
+        CastFrom::Hand,
+        vec![],
+    ));
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let saved = game.db.serialize_state()?;
+    let mut db = Database::deserialize_state(&game.cards, &saved)?;
+    let p2 = db.all_players.all_players()[1];
+    let bear = *db.battlefield.battlefields[&Controller::from(p2)]
+        .first()
+        .unwrap();
+
+    let mut results = Stack::resolve_1(&mut db);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_zone!(db, bear, exile);
+    assert!(db.stack.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn rejects_invalid_states() -> anyhow::Result<()> {
+    let game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let saved = game.db.serialize_state()?;
+
+    assert!(Database::deserialize_state(&game.cards, "not a game").is_err());
+    assert!(Database::deserialize_state(
+        &game.cards,
+        &saved.replace("\"version\": 3", "\"version\": 0")
+    )
+    .is_err());
+    assert!(Database::deserialize_state(
+        &game.cards,
+        &saved.replace("Alpine Grizzly", "Not a card")
+    )
+    .is_err());
+
+    Ok(())
+}
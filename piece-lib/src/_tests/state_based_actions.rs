@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use pretty_assertions::assert_eq;
+use protobuf::Enum;
+
+use crate::{
+    battlefield::Battlefields,
+    effects::{EffectBehaviors, SelectedStack, SelectionResult},
+    in_play::{CardId, Database, ModifierId},
+    player::Player,
+    protogen::{
+        counters::Counter,
+        effects::{BattlefieldModifier, Duration, Equip, ModifyBattlefield},
+        empty::Empty,
+        targets::Location,
+        types::Type,
+    },
+    stack::{Selected, TargetType},
+};
+
+fn check_sba(db: &mut Database) {
+    let mut results = Battlefields::check_sba(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+}
+
+fn equipped(db: &Database, equipment: CardId, card: CardId) -> bool {
+    db.modifiers
+        .values()
+        .any(|modifier| modifier.source == equipment && modifier.modifying.contains(&card))
+}
+
+#[test]
+fn players_lose_without_life_or_with_ten_poison() -> anyhow::Result<()> {
+    let mut game = game! {};
+
+    game.db.all_players[game.p1].life_total = 0;
+    game.db.all_players[game.p2].poison_counters = 9;
+    check_sba(&mut game.db);
+    assert!(game.db.all_players[game.p1].lost);
+    assert!(!game.db.all_players[game.p2].lost);
+
+    game.db.all_players[game.p2].poison_counters = 10;
+    check_sba(&mut game.db);
+    assert!(game.db.all_players[game.p2].lost);
+
+    Ok(())
+}
+
+#[test]
+fn drawing_from_empty_library_loses_at_next_check() -> anyhow::Result<()> {
+    let mut game = game! {};
+
+    let mut results = Player::draw(game.p1, 1);
+    while results.resolve(&mut game.db, None) != SelectionResult::Complete {}
+    assert!(!game.db.all_players[game.p1].lost);
+
+    check_sba(&mut game.db);
+    assert!(game.db.all_players[game.p1].lost);
+    assert!(!game.db.all_players[game.p2].lost);
+
+    Ok(())
+}
+
+#[test]
+fn plus_and_minus_counters_annihilate() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    game.db[bear].counters.insert(Counter::P1P1, 3);
+    game.db[bear].counters.insert(Counter::M1M1, 1);
+    bear.apply_modifiers_layered(&mut game.db);
+    assert_eq!(bear.power(&game.db), Some(6));
+
+    check_sba(&mut game.db);
+    assert_eq!(
+        game.db[bear].counter_text_on(),
+        vec!["+1/+1 x2".to_string()]
+    );
+    assert_eq!(bear.power(&game.db), Some(6));
+
+    Ok(())
+}
+
+#[test]
+fn equipment_falls_off_noncreatures() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly", "+2 Mace"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let mace = game.card(game.p1, "+2 Mace");
+    let db = &mut game.db;
+
+    let _ = Equip {
+        modifiers: vec![ModifyBattlefield {
+            add_power: Some(2),
+            add_toughness: Some(2),
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+    .apply(
+        db,
+        Some(mace),
+        &mut SelectedStack::new(vec![Selected {
+            location: Some(Location::ON_BATTLEFIELD),
+            target_type: TargetType::Card(bear),
+            targeted: true,
+            restrictions: vec![],
+        }]),
+        false,
+    );
+    check_sba(db);
+    assert!(equipped(db, mace, bear));
+
+    let modifier = ModifierId::upload_temporary_modifier(
+        db,
+        bear,
+        BattlefieldModifier {
+            modifier: protobuf::MessageField::some(ModifyBattlefield {
+                remove_types: HashMap::from([(Type::CREATURE.value(), Empty::default())]),
+                ..Default::default()
+            }),
+            duration: protobuf::EnumOrUnknown::new(Duration::UNTIL_END_OF_TURN),
+            ..Default::default()
+        },
+    );
+    bear.apply_modifier(db, modifier);
+
+    check_sba(db);
+    assert!(!equipped(db, mace, bear));
+    assert_zone!(*db, mace, battlefield);
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    in_play::CardId,
+    protogen::card::Card,
+    turns::{Phase, Turn},
+};
+
+const SLOW_SILENCE: &str = r#"
+name: Slow Silence
+typeline: Enchantment
+cost:
+  mana_cost: '{W}'
+static_abilities:
+  - ability: !PreventActivatedAbilities
+      restrictions:
+        - restriction: !OfType
+            types: Creature
+      unless:
+        - restriction: !DuringControllersTurn {}
+"#;
+
+#[test]
+fn prevents_mana_abilities() -> anyhow::Result<()> {
+    let game = game! {
+        p1 battlefield: ["Abzan Banner", "Hoar Shade"],
+        p2 battlefield: ["Stony Silence"],
+    };
+    let db = &game.db;
+
+    let banner = game.card(game.p1, "Abzan Banner");
+    assert_eq!(db[banner].abilities(db).len(), 2);
+    for (source, ability) in db[banner].abilities(db) {
+        assert!(!ability.can_be_activated(db, source, game.p1, &None));
+    }
+
+    let shade = game.card(game.p1, "Hoar Shade");
+    for (source, ability) in db[shade].abilities(db) {
+        assert!(ability.can_be_activated(db, source, game.p1, &None));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn allows_activation_when_unless_passes() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Hoar Shade"],
+    };
+    let silence: Card = serde_yaml::from_str(SLOW_SILENCE)?;
+    let silence = CardId::upload_card_or_token(&mut game.db, game.p2, silence, false);
+    silence.move_to_battlefield(&mut game.db);
+
+    let shade = game.card(game.p1, "Hoar Shade");
+    let db = &mut game.db;
+    for (source, ability) in db[shade].abilities(db) {
+        assert!(ability.can_be_activated(db, source, game.p1, &None));
+    }
+
+    db.turn.set_phase(Phase::Cleanup);
+    let _ = Turn::step(db);
+    assert_eq!(db.turn.active_player(), game.p2);
+    for (source, ability) in db[shade].abilities(db) {
+        assert!(!ability.can_be_activated(db, source, game.p1, &None));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use pretty_assertions::assert_eq;
+use protobuf::Enum;
+
+use crate::{
+    battlefield::Battlefields,
+    effects::SelectionResult,
+    in_play::{CardId, Database},
+    load_cards,
+    player::{AllPlayers, Owner},
+    protogen::keywords::Keyword,
+    stack::Stack,
+    turns::Phase,
+};
+
+fn level_up(db: &mut Database, player: Owner, card: CardId) {
+    let mut results = Battlefields::activate_ability(db, &None, player, card, 0);
+    let result = results.resolve(db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let mut results = Stack::resolve_1(db);
+    let result = results.resolve(db, None);
+    assert_eq!(result, SelectionResult::Complete);
+}
+
+#[test]
+fn levels_up() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    all_players[player].infinite_mana();
+    let mut db = Database::new(all_players);
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let student = CardId::upload(&mut db, &cards, player, "Student of Warfare");
+    student.move_to_battlefield(&mut db);
+
+    level_up(&mut db, player, student);
+    assert_eq!(student.power(&db), Some(1));
+    assert_eq!(student.toughness(&db), Some(1));
+
+    level_up(&mut db, player, student);
+    assert_eq!(student.power(&db), Some(3));
+    assert_eq!(student.toughness(&db), Some(3));
+    assert!(db[student]
+        .modified_keywords
+        .contains_key(&Keyword::FIRST_STRIKE.value()));
+
+    for _ in 0..5 {
+        level_up(&mut db, player, student);
+    }
+    assert_eq!(student.power(&db), Some(4));
+    assert_eq!(student.toughness(&db), Some(4));
+    assert!(!db[student]
+        .modified_keywords
+        .contains_key(&Keyword::FIRST_STRIKE.value()));
+    assert!(db[student]
+        .modified_keywords
+        .contains_key(&Keyword::DOUBLE_STRIKE.value()));
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    battlefield::Battlefields,
+    effects::{EffectBehaviors, SelectedStack},
+    in_play::CardId,
+    protogen::{card::Card, counters::Counter, effects::Untap},
+    stack::Selected,
+};
+
+const TEST_STATUE: &str = r#"
+name: Test Statue
+typeline: Artifact Creature - Golem
+power: 4
+toughness: 4
+static_abilities:
+  - ability: !DoesntUntap {}
+"#;
+
+#[test]
+fn stun_counters_replace_untapping() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let db = &mut game.db;
+    bear.tap(db);
+    db[bear].counters.insert(Counter::STUN, 2);
+
+    Battlefields::untap(db, game.p1);
+    assert!(bear.tapped(db));
+    assert_eq!(db[bear].counters[&Counter::STUN], 1);
+
+    // Effects which untap the permanent are replaced too.
+    let mut selected = SelectedStack::new(vec![Selected::card(db, bear)]);
+    let _ = Untap::default().apply(db, None, &mut selected, false);
+    assert!(bear.tapped(db));
+    assert_eq!(db[bear].counters[&Counter::STUN], 0);
+
+    Battlefields::untap(db, game.p1);
+    assert!(!bear.tapped(db));
+
+    // Untapped permanents keep their stun counters.
+    db[bear].counters.insert(Counter::STUN, 1);
+    Battlefields::untap(db, game.p1);
+    assert_eq!(db[bear].counters[&Counter::STUN], 1);
+
+    Ok(())
+}
+
+#[test]
+fn doesnt_untap_during_untap_step() -> anyhow::Result<()> {
+    let mut game = game! {};
+    let statue: Card = serde_yaml::from_str(TEST_STATUE)?;
+    let statue = CardId::upload_card_or_token(&mut game.db, game.p1, statue, false);
+    statue.move_to_battlefield(&mut game.db);
+    let db = &mut game.db;
+    statue.tap(db);
+
+    Battlefields::untap(db, game.p1);
+    assert!(statue.tapped(db));
+
+    let mut selected = SelectedStack::new(vec![Selected::card(db, statue)]);
+    let _ = Untap::default().apply(db, None, &mut selected, false);
+    assert!(!statue.tapped(db));
+
+    Ok(())
+}
@@ -0,0 +1,257 @@
+//! Helpers for setting up games in tests without uploading every card by hand.
+//!
+//! ```ignore
+//! let game = game! {
+//!     p1 battlefield: ["Forest" * 3],
+//!     p1 hand: ["Alpine Grizzly"],
+//!     p2 library: ["Forest" * 10],
+//! };
+//! let bear = game.card(game.p1, "Alpine Grizzly");
+//! assert_zone!(game.db, bear, hand);
+//! assert_pt!(game.db, bear, 4 / 2);
+//! ```
+
+use indexmap::IndexMap;
+
+use crate::{
+    battlefield::Battlefields,
+    effects::SelectionResult,
+    in_play::{CardId, Database},
+    library::Library,
+    load_cards,
+    player::{AllPlayers, Owner},
+    protogen::{card::Card, effects::Duration},
+    stack::Stack,
+    Cards,
+};
+
+/// Builds a [Game] with two players. Each entry is `<player> <zone>: [<card name> (* <count>)?, ..]`
+/// where player is `p1` or `p2` and zone is one of `battlefield`, `hand`, `library`, `graveyard`,
+/// or `exile`. Cards are placed in the order they are listed, so the last card listed for a
+/// library is on top. Cards are placed without firing triggers or replacement effects, as with
+/// [Database::with_suspended_triggers].
+///
+/// Expands to an expression using `?`, so it must be used in a function returning a result.
+macro_rules! game {
+    ($($player:ident $zone:ident: [$($name:literal $(* $count:literal)?),* $(,)?]),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut builder = $crate::_tests::support::GameBuilder::new()?;
+        $($(
+            builder.$zone(builder.$player, $name, game!(@count $($count)?));
+        )*)*
+        builder.build()
+    }};
+    (@count) => { 1 };
+    (@count $count:literal) => { $count };
+}
+
+/// Asserts that a card is in a zone, e.g. `assert_zone!(game.db, card, graveyard)`. Phased out
+/// permanents are not considered to be on the battlefield.
+macro_rules! assert_zone {
+    ($db:expr, $card:expr, $zone:ident) => {{
+        let card: $crate::in_play::CardId = $card;
+        let expected = $crate::_tests::support::location(stringify!($zone));
+        assert!(
+            card.is_in_location(&$db, expected),
+            "{} is in {:?}, expected {:?}",
+            card.name(&$db),
+            card.location(&$db),
+            expected,
+        );
+    }};
+}
+
+/// Asserts a creature's power and toughness, e.g. `assert_pt!(game.db, card, 4 / 2)`.
+macro_rules! assert_pt {
+    ($db:expr, $card:expr, $power:literal / $toughness:literal) => {{
+        let card: $crate::in_play::CardId = $card;
+        pretty_assertions::assert_eq!(
+            (card.power(&$db), card.toughness(&$db)),
+            (Some($power), Some($toughness)),
+            "power and toughness of {}",
+            card.name(&$db),
+        );
+    }};
+}
+
+pub(crate) fn location(zone: &str) -> crate::protogen::targets::Location {
+    use crate::protogen::targets::Location;
+
+    match zone {
+        "battlefield" => Location::ON_BATTLEFIELD,
+        "hand" => Location::IN_HAND,
+        "library" => Location::IN_LIBRARY,
+        "graveyard" => Location::IN_GRAVEYARD,
+        "exile" => Location::IN_EXILE,
+        "stack" => Location::IN_STACK,
+        zone => panic!("Unknown zone {}", zone),
+    }
+}
+
+pub(crate) struct Game {
+    pub(crate) cards: Cards,
+    pub(crate) db: Database,
+    pub(crate) p1: Owner,
+    pub(crate) p2: Owner,
+    uploaded: IndexMap<(Owner, String), Vec<CardId>>,
+}
+
+impl Game {
+    /// The first card with the given name the game was set up with for the player.
+    pub(crate) fn card(&self, player: Owner, name: &str) -> CardId {
+        self.cards_named(player, name)[0]
+    }
+
+    /// Every card with the given name the game was set up with for the player, in the order they
+    /// were listed.
+    pub(crate) fn cards_named(&self, player: Owner, name: &str) -> &[CardId] {
+        self.uploaded
+            .get(&(player, name.to_string()))
+            .unwrap_or_else(|| panic!("No {} was set up for {:?}", name, player))
+    }
+
+    /// Upload a card which was not part of the initial setup without moving it anywhere.
+    pub(crate) fn upload(&mut self, player: Owner, name: &str) -> CardId {
+        CardId::upload(&mut self.db, &self.cards, player, name)
+    }
+
+    /// Upload a card defined in YAML rather than loaded from the card files, without moving it
+    /// anywhere.
+    pub(crate) fn upload_yaml(&mut self, player: Owner, yaml: &str) -> anyhow::Result<CardId> {
+        let card: Card = serde_yaml::from_str(yaml)?;
+        Ok(CardId::upload_card_or_token(
+            &mut self.db,
+            player,
+            card,
+            false,
+        ))
+    }
+
+    /// Activates the card's ability at `index` and resolves it, returning whether it could be
+    /// activated.
+    pub(crate) fn activate(&mut self, player: Owner, card: CardId, index: usize) -> bool {
+        let mut results = Battlefields::activate_ability(&mut self.db, &None, player, card, index);
+        if results.is_empty() {
+            return false;
+        }
+        while results.resolve(&mut self.db, None) != SelectionResult::Complete {}
+
+        let mut results = Stack::resolve_1(&mut self.db);
+        while results.resolve(&mut self.db, None) != SelectionResult::Complete {}
+
+        true
+    }
+}
+
+pub(crate) struct GameBuilder {
+    pub(crate) p1: Owner,
+    pub(crate) p2: Owner,
+    game: Game,
+}
+
+impl GameBuilder {
+    pub(crate) fn new() -> anyhow::Result<Self> {
+        let cards = load_cards()?;
+        let mut all_players = AllPlayers::default();
+        let p1 = all_players.new_player("Player".to_string(), 20);
+        let p2 = all_players.new_player("Opponent".to_string(), 20);
+        let mut db = Database::new(all_players);
+        db.set_card_names(&cards);
+
+        Ok(Self {
+            p1,
+            p2,
+            game: Game {
+                cards,
+                db,
+                p1,
+                p2,
+                uploaded: Default::default(),
+            },
+        })
+    }
+
+    pub(crate) fn battlefield(&mut self, player: Owner, name: &str, count: usize) {
+        let uploaded = self.upload(player, name, count);
+        self.game.db.with_suspended_triggers(|db| {
+            for card in uploaded {
+                card.move_to_battlefield(db);
+            }
+        });
+    }
+
+    pub(crate) fn hand(&mut self, player: Owner, name: &str, count: usize) {
+        let uploaded = self.upload(player, name, count);
+        self.game.db.with_suspended_triggers(|db| {
+            for card in uploaded {
+                card.move_to_hand(db);
+            }
+        });
+    }
+
+    pub(crate) fn library(&mut self, player: Owner, name: &str, count: usize) {
+        let uploaded = self.upload(player, name, count);
+        self.game.db.with_suspended_triggers(|db| {
+            for card in uploaded {
+                Library::place_on_top(db, player, card);
+            }
+        });
+    }
+
+    pub(crate) fn graveyard(&mut self, player: Owner, name: &str, count: usize) {
+        let uploaded = self.upload(player, name, count);
+        self.game.db.with_suspended_triggers(|db| {
+            for card in uploaded {
+                card.move_to_graveyard(db);
+            }
+        });
+    }
+
+    pub(crate) fn exile(&mut self, player: Owner, name: &str, count: usize) {
+        let uploaded = self.upload(player, name, count);
+        self.game.db.with_suspended_triggers(|db| {
+            for card in uploaded {
+                card.move_to_exile(db, card, None, Duration::PERMANENTLY);
+            }
+        });
+    }
+
+    pub(crate) fn build(self) -> Game {
+        self.game
+    }
+
+    fn upload(&mut self, player: Owner, name: &str, count: usize) -> Vec<CardId> {
+        let uploaded = (0..count)
+            .map(|_| self.game.upload(player, name))
+            .collect::<Vec<_>>();
+        self.game
+            .uploaded
+            .entry((player, name.to_string()))
+            .or_default()
+            .extend(uploaded.iter().copied());
+
+        uploaded
+    }
+}
+
+#[test]
+fn places_cards_in_zones() -> anyhow::Result<()> {
+    let game = game! {
+        p1 battlefield: ["Alpine Grizzly" * 2],
+        p1 graveyard: ["Forest"],
+        p2 exile: ["Alpine Grizzly"],
+        p2 library: ["Forest" * 3],
+    };
+
+    let bears = game.cards_named(game.p1, "Alpine Grizzly");
+    pretty_assertions::assert_eq!(bears.len(), 2);
+    for bear in bears {
+        assert_zone!(game.db, *bear, battlefield);
+        assert_pt!(game.db, *bear, 4 / 2);
+    }
+    assert_zone!(game.db, game.card(game.p1, "Forest"), graveyard);
+    assert_zone!(game.db, game.card(game.p2, "Alpine Grizzly"), exile);
+    pretty_assertions::assert_eq!(game.db.all_players[game.p2].library.len(), 3);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    battlefield::Battlefields, effects::SelectionResult, in_play::CardId, protogen::card::Card,
+};
+
+const TEST_WARDEN: &str = r#"
+name: Test Warden
+typeline: Enchantment
+cost:
+  mana_cost: '{U}'
+replacement_abilities:
+  - replacing: zone change
+    zone_change:
+      to: in graveyard
+      instead: in exile
+"#;
+
+#[test]
+fn setup_has_no_consequences_until_done() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 hand: ["Alpine Grizzly"],
+    };
+    let warden: Card = serde_yaml::from_str(TEST_WARDEN)?;
+    let warden = CardId::upload_card_or_token(&mut game.db, game.p1, warden, false);
+    warden.move_to_battlefield(&mut game.db);
+    game.db[warden].replacements_active = true;
+
+    let ours = game.card(game.p1, "Alpine Grizzly");
+    let theirs = game.card(game.p2, "Alpine Grizzly");
+    let p2 = game.p2;
+    game.db.with_suspended_triggers(|db| {
+        theirs.move_to_graveyard(db);
+        ours.mark_damage(db, None, 2);
+        db.all_players[p2].life_total = 0;
+
+        assert!(Battlefields::check_sba(db).is_empty());
+    });
+
+    assert_zone!(game.db, theirs, graveyard);
+    assert_zone!(game.db, ours, battlefield);
+    assert_eq!(game.db.all_players[game.p2].life_total, 0);
+
+    let mut results = Battlefields::check_sba(&mut game.db);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+    assert_zone!(game.db, ours, exile);
+
+    Ok(())
+}
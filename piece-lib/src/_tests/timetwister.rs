@@ -0,0 +1,41 @@
+use pretty_assertions::assert_eq;
+
+use crate::{effects::SelectionResult, stack::Stack, turns::Phase};
+
+#[test]
+fn shuffles_hands_and_graveyards_into_owners_libraries() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Timetwister", "Alpine Grizzly"],
+        p1 graveyard: ["Forest"],
+        p1 library: ["Forest" * 10],
+        p2 hand: ["Alpine Grizzly"],
+        p2 graveyard: ["Alpine Grizzly"],
+        p2 library: ["Forest" * 10],
+    };
+    let timetwister = game.card(game.p1, "Timetwister");
+    let (p1, p2) = (game.p1, game.p2);
+    let db = &mut game.db;
+    db.all_players[p1].infinite_mana();
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let mut results = Stack::move_card_to_stack_from_hand(db, timetwister);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+
+    let mut results = Stack::resolve_1(db);
+    while results.resolve(db, None) != SelectionResult::Complete {}
+
+    for player in [p1, p2] {
+        assert_eq!(db.hand[player].len(), 7);
+        assert_eq!(db.all_players[player].library.len(), 5);
+        for card in db.all_players[player].library.cards() {
+            assert_eq!(db[card].owner, player);
+        }
+    }
+    assert_eq!(
+        db.graveyard[p1].iter().copied().collect::<Vec<_>>(),
+        [timetwister]
+    );
+    assert!(db.graveyard[p2].is_empty());
+
+    Ok(())
+}
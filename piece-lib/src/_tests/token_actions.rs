@@ -0,0 +1,198 @@
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{EffectBundle, PendingEffects, SelectionResult},
+    in_play::{CardId, CastFrom, Database},
+    player::Owner,
+    protogen::{
+        counters::Counter,
+        effects::{count::Fixed, Amass, Count, Effect, Incubate},
+        types::{Subtype, Type},
+    },
+    stack::Stack,
+    types::{SubtypeSet, TypeSet},
+};
+
+fn fixed(count: i32) -> protobuf::MessageField<Count> {
+    protobuf::MessageField::some(Count {
+        count: Some(
+            Fixed {
+                count,
+                ..Default::default()
+            }
+            .into(),
+        ),
+        ..Default::default()
+    })
+}
+
+fn apply(db: &mut Database, source: CardId, effect: Effect) {
+    let mut results = PendingEffects::from(EffectBundle {
+        push_on_enter: Some(vec![]),
+        source: Some(source),
+        effects: vec![effect],
+        ..Default::default()
+    });
+    while results.resolve(db, None) != SelectionResult::Complete {}
+}
+
+fn tokens(db: &Database, player: Owner) -> Vec<CardId> {
+    db.battlefield[player]
+        .iter()
+        .copied()
+        .filter(|card| db[*card].token)
+        .collect_vec()
+}
+
+#[test]
+fn populate_copies_only_token() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Eyes in the Skies"],
+    };
+    let eyes = game.card(game.p1, "Eyes in the Skies");
+
+    let mut results = PendingEffects::default();
+    results.apply_results(eyes.move_to_stack(&mut game.db, vec![], CastFrom::Hand, vec![]));
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let mut results = Stack::resolve_1(&mut game.db);
+    while results.resolve(&mut game.db, None) != SelectionResult::Complete {}
+
+    let birds = tokens(&game.db, game.p1);
+    assert_eq!(birds.len(), 2);
+    for bird in birds {
+        assert_eq!(bird.name(&game.db), "Bird");
+        assert_pt!(game.db, bird, 1 / 1);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn populate_chooses_token() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Eyes in the Skies"],
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let eyes = game.card(game.p1, "Eyes in the Skies");
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let bear_token = bear.token_copy_of(&mut game.db, game.p1.into());
+    bear_token.move_to_battlefield(&mut game.db);
+
+    let mut results = PendingEffects::default();
+    results.apply_results(eyes.move_to_stack(&mut game.db, vec![], CastFrom::Hand, vec![]));
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    // The bear card isn't a token, so only the bear token and the bird can be copied.
+    let mut results = Stack::resolve_1(&mut game.db);
+    let mut result = results.resolve(&mut game.db, None);
+    while result == SelectionResult::TryAgain {
+        result = results.resolve(&mut game.db, None);
+    }
+    assert_eq!(result, SelectionResult::PendingChoice);
+    assert_eq!(results.options(&game.db).len(), 2);
+    let mut result = results.resolve(&mut game.db, Some(0));
+    while result == SelectionResult::TryAgain {
+        result = results.resolve(&mut game.db, None);
+    }
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(
+        tokens(&game.db, game.p1)
+            .into_iter()
+            .map(|card| card.name(&game.db).clone())
+            .sorted()
+            .collect_vec(),
+        vec!["Alpine Grizzly", "Alpine Grizzly", "Bird"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn amass_creates_then_grows_army() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+
+    apply(
+        &mut game.db,
+        bear,
+        Amass {
+            subtype: Subtype::ZOMBIE.into(),
+            count: fixed(2),
+            ..Default::default()
+        }
+        .into(),
+    );
+
+    let armies = tokens(&game.db, game.p1);
+    assert_eq!(armies.len(), 1);
+    let army = armies[0];
+    assert_eq!(army.name(&game.db), "Zombie Army");
+    assert_eq!(
+        game.db[army].modified_subtypes,
+        SubtypeSet::from([Subtype::ZOMBIE, Subtype::ARMY])
+    );
+    assert_pt!(game.db, army, 2 / 2);
+
+    apply(
+        &mut game.db,
+        bear,
+        Amass {
+            subtype: Subtype::ZOMBIE.into(),
+            count: fixed(1),
+            ..Default::default()
+        }
+        .into(),
+    );
+
+    assert_eq!(tokens(&game.db, game.p1), vec![army]);
+    assert_eq!(game.db[army].counters.get(&Counter::P1P1), Some(&3));
+    assert_pt!(game.db, army, 3 / 3);
+
+    Ok(())
+}
+
+#[test]
+fn incubator_transforms() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+
+    apply(
+        &mut game.db,
+        bear,
+        Incubate {
+            count: fixed(2),
+            ..Default::default()
+        }
+        .into(),
+    );
+
+    let incubators = tokens(&game.db, game.p1);
+    assert_eq!(incubators.len(), 1);
+    let incubator = incubators[0];
+    assert_eq!(incubator.name(&game.db), "Incubator");
+    assert_eq!(
+        game.db[incubator].modified_types,
+        TypeSet::from([Type::ARTIFACT])
+    );
+    assert_eq!(game.db[incubator].activated_abilities.len(), 1);
+    assert_eq!(game.db[incubator].counters.get(&Counter::P1P1), Some(&2));
+
+    incubator.transform(&mut game.db);
+    assert_eq!(incubator.name(&game.db), "Phyrexian Token");
+    assert_eq!(
+        game.db[incubator].modified_types,
+        TypeSet::from([Type::ARTIFACT, Type::CREATURE])
+    );
+    assert_pt!(game.db, incubator, 2 / 2);
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::SelectionResult,
+    in_play::{CardId, Database},
+    load_cards,
+    player::AllPlayers,
+    trace::{Trace, TraceEvent},
+    turns::{Phase, Turn},
+};
+
+#[test]
+fn records_resolution() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let _ = all_players.new_player("Opponent".to_string(), 20);
+    let mut db = Database::new(all_players);
+
+    let creature = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    creature.move_to_battlefield(&mut db);
+
+    db.turn.turn_count += db.turn.turns_per_round();
+    db.turn.set_phase(Phase::BeginCombat);
+
+    Trace::start(&mut db);
+    let mut results = Turn::step(&mut db);
+    let result = results.resolve(&mut db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(&mut db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let trace = Trace::take(&mut db).unwrap();
+    assert_eq!(trace.roots().count(), 1);
+
+    let root = &trace.nodes[trace.roots().next().unwrap()];
+    assert_eq!(
+        root.event,
+        TraceEvent::Enter {
+            source: None,
+            effects: vec!["ChooseAttackers".to_string(), "PopSelected".to_string()]
+        }
+    );
+
+    let apply = root
+        .children
+        .iter()
+        .copied()
+        .find(|child| {
+            trace.nodes[*child].event
+                == TraceEvent::Apply {
+                    effect: "ChooseAttackers".to_string(),
+                }
+        })
+        .unwrap();
+    assert_eq!(trace.nodes[apply].children.len(), 2);
+    for child in trace.nodes[apply].children.iter() {
+        assert!(matches!(
+            trace.nodes[*child].event,
+            TraceEvent::Enter { .. }
+        ));
+    }
+
+    assert!(trace.to_dot().starts_with("digraph trace {"));
+    assert!(Trace::take(&mut db).is_none());
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use crate::{
+    in_play::{CardId, CastFrom},
+    protogen::card::Card,
+    turns::{Phase, Turn},
+};
+
+const TEST_FIRST_SPELL: &str = r#"
+name: Test First Spell Watcher
+typeline: Artifact
+triggered_abilities:
+  - trigger:
+      source: cast
+      from: anywhere
+      first_each_turn: true
+    oracle_text: Whenever a player casts their first spell each turn, do nothing.
+"#;
+
+const TEST_TWICE_EACH_TURN: &str = r#"
+name: Test Twice Each Turn Watcher
+typeline: Artifact
+triggered_abilities:
+  - trigger:
+      source: cast
+      from: anywhere
+      limit_per_turn: 2
+    oracle_text: Whenever a player casts a spell, do nothing. This ability triggers only
+      twice each turn.
+"#;
+
+const TEST_ONCE_EACH_GAME: &str = r#"
+name: Test Once Each Game Watcher
+typeline: Artifact
+triggered_abilities:
+  - trigger:
+      source: cast
+      from: anywhere
+      limit_per_game: 1
+    oracle_text: Whenever a player casts a spell, do nothing. This ability triggers only
+      once each game.
+"#;
+
+fn watch(game: &mut crate::_tests::support::Game, yaml: &str) -> anyhow::Result<CardId> {
+    let card: Card = serde_yaml::from_str(yaml)?;
+    let card = CardId::upload_card_or_token(&mut game.db, game.p1, card, false);
+    card.move_to_battlefield(&mut game.db);
+    Ok(card)
+}
+
+#[test]
+fn first_spell_each_turn() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Alpine Grizzly" * 3],
+        p2 hand: ["Alpine Grizzly"],
+    };
+    watch(&mut game, TEST_FIRST_SPELL)?;
+    let [first, second, third] = game.cards_named(game.p1, "Alpine Grizzly") else {
+        unreachable!()
+    };
+    let (first, second, third) = (*first, *second, *third);
+    let opponents = game.card(game.p2, "Alpine Grizzly");
+
+    let triggers = first.move_to_stack(&mut game.db, vec![], CastFrom::Hand, vec![]);
+    assert_eq!(triggers.len(), 1);
+    let triggers = second.move_to_stack(&mut game.db, vec![], CastFrom::Hand, vec![]);
+    assert!(triggers.is_empty());
+
+    // Each player's first spell triggers separately.
+    let triggers = opponents.move_to_stack(&mut game.db, vec![], CastFrom::Hand, vec![]);
+    assert_eq!(triggers.len(), 1);
+
+    // The count starts over each turn.
+    game.db.stack.entries.clear();
+    game.db.turn.set_phase(Phase::Cleanup);
+    let _ = Turn::step(&mut game.db);
+    let triggers = third.move_to_stack(&mut game.db, vec![], CastFrom::Hand, vec![]);
+    assert_eq!(triggers.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn limited_each_turn() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Alpine Grizzly" * 4],
+    };
+    watch(&mut game, TEST_TWICE_EACH_TURN)?;
+    let bears = game.cards_named(game.p1, "Alpine Grizzly").to_vec();
+
+    let fired = bears[..3]
+        .iter()
+        .map(|bear| {
+            bear.move_to_stack(&mut game.db, vec![], CastFrom::Hand, vec![])
+                .len()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(fired, [1, 1, 0]);
+
+    game.db.stack.entries.clear();
+    game.db.turn.set_phase(Phase::Cleanup);
+    let _ = Turn::step(&mut game.db);
+    let triggers = bears[3].move_to_stack(&mut game.db, vec![], CastFrom::Hand, vec![]);
+    assert_eq!(triggers.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn limited_each_game() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Alpine Grizzly" * 3],
+    };
+    let watcher = watch(&mut game, TEST_ONCE_EACH_GAME)?;
+    let bears = game.cards_named(game.p1, "Alpine Grizzly").to_vec();
+
+    let triggers = bears[0].move_to_stack(&mut game.db, vec![], CastFrom::Hand, vec![]);
+    assert_eq!(triggers.len(), 1);
+    let triggers = bears[1].move_to_stack(&mut game.db, vec![], CastFrom::Hand, vec![]);
+    assert!(triggers.is_empty());
+
+    // The limit lasts past the end of the turn and follows the card between zones.
+    game.db.stack.entries.clear();
+    game.db.turn.set_phase(Phase::Cleanup);
+    let _ = Turn::step(&mut game.db);
+    watcher.move_to_hand(&mut game.db);
+    watcher.move_to_battlefield(&mut game.db);
+    let triggers = bears[2].move_to_stack(&mut game.db, vec![], CastFrom::Hand, vec![]);
+    assert!(triggers.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{Options, SelectionResult},
+    in_play::{CardId, Database},
+    library::Library,
+    load_cards,
+    player::AllPlayers,
+    turns::{AutoPolicy, Phase, Turn},
+};
+
+#[test]
+fn attacks() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let opponent = all_players.new_player("Opponent".to_string(), 20);
+    let mut db = Database::new(all_players);
+
+    let creature = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    creature.move_to_battlefield(&mut db);
+
+    // Get rid of summoning sickness
+    db.turn.turn_count += db.turn.turns_per_round();
+    db.turn.set_phase(Phase::BeginCombat);
+
+    let mut results = Turn::step(&mut db);
+    assert_eq!(
+        results.options(&db),
+        Options::OptionalList(vec![(0, "Alpine Grizzly".to_string())])
+    );
+    let result = results.resolve(&mut db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    assert_eq!(
+        results.options(&db),
+        Options::MandatoryList(vec![(0, "Opponent".to_string())])
+    );
+    let result = results.resolve(&mut db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(db[creature].attacking, Some(opponent));
+
+    Turn::advance_until(
+        &mut db,
+        Phase::PostCombatMainPhase,
+        player,
+        AutoPolicy::Decline,
+    )?;
+
+    assert_eq!(db.all_players[opponent].life_total, 16);
+
+    Ok(())
+}
+
+#[test]
+fn advances_to_next_upkeep() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    let opponent = all_players.new_player("Opponent".to_string(), 20);
+    let mut db = Database::new(all_players);
+
+    for _ in 0..10 {
+        let card = CardId::upload(&mut db, &cards, player, "Forest");
+        Library::place_on_top(&mut db, player, card);
+        let card = CardId::upload(&mut db, &cards, opponent, "Forest");
+        Library::place_on_top(&mut db, opponent, card);
+    }
+
+    let creature = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    creature.move_to_battlefield(&mut db);
+
+    Turn::advance_until(&mut db, Phase::Upkeep, opponent, AutoPolicy::First)?;
+    assert_eq!(db.turn.turn_count, 1);
+    assert_eq!(db.turn.active_player(), opponent);
+    // The player's creature was summoning sick, so it could not attack.
+    assert_eq!(db.all_players[opponent].life_total, 20);
+
+    Turn::advance_until(&mut db, Phase::Upkeep, player, AutoPolicy::First)?;
+    assert_eq!(db.turn.turn_count, 2);
+    assert_eq!(db.turn.phase, Phase::Upkeep);
+
+    Turn::advance_until(&mut db, Phase::Upkeep, player, AutoPolicy::First)?;
+    assert_eq!(db.turn.turn_count, 4);
+    assert_eq!(db.all_players[opponent].life_total, 16);
+    assert_eq!(db.hand[player].len(), 1);
+
+    Ok(())
+}
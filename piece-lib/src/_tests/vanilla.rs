@@ -0,0 +1,34 @@
+use std::collections::HashSet;
+
+use pretty_assertions::assert_eq;
+
+use crate::{
+    protogen::{color::Color, counters::Counter, types::Type},
+    types::TypeSet,
+};
+
+#[test]
+fn vanilla_creatures_skip_layers() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly", "Forest"],
+    };
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    let forest = game.card(game.p1, "Forest");
+
+    assert_eq!(bear.name(&game.db), "Alpine Grizzly");
+    assert_eq!(bear.power(&game.db), Some(4));
+    assert_eq!(bear.toughness(&game.db), Some(2));
+    assert_eq!(game.db[bear].modified_colors, HashSet::from([Color::GREEN]));
+    assert!(bear.types_intersect(&game.db, &TypeSet::from([Type::CREATURE])));
+
+    *game.db[bear].counters.entry(Counter::P1P1).or_default() += 2;
+    bear.apply_modifiers_layered(&mut game.db);
+    assert_eq!(bear.power(&game.db), Some(6));
+    assert_eq!(bear.toughness(&game.db), Some(4));
+
+    // Lands still get their mana abilities from the full layer system.
+    assert_eq!(game.db[forest].modified_mana_abilities.len(), 1);
+    assert_eq!(game.db[forest].modified_colors, HashSet::default());
+
+    Ok(())
+}
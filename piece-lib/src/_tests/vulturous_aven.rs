@@ -0,0 +1,105 @@
+use indexmap::IndexSet;
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::SelectionResult,
+    in_play::{CardId, Database},
+    library::Library,
+    load_cards,
+    player::AllPlayers,
+    stack::Stack,
+    turns::Phase,
+};
+
+#[test]
+fn exploits() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    all_players[player].infinite_mana();
+    let mut db = Database::new(all_players);
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let deck1 = CardId::upload(&mut db, &cards, player, "Forest");
+    Library::place_on_top(&mut db, player, deck1);
+    let deck2 = CardId::upload(&mut db, &cards, player, "Forest");
+    Library::place_on_top(&mut db, player, deck2);
+
+    let bear = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    bear.move_to_battlefield(&mut db);
+
+    let aven = CardId::upload(&mut db, &cards, player, "Vulturous Aven");
+    let mut results = Stack::move_card_to_stack_from_hand(&mut db, aven);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    // Pay the black
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::PendingChoice);
+    // Pay the generic
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let mut results = Stack::resolve_1(&mut db);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    // Exploit the bear
+    let mut results = Stack::resolve_1(&mut db);
+    let result = results.resolve(&mut db, Some(0));
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+    assert_eq!(db.graveyard[player], IndexSet::from([bear]));
+
+    // Resolve the exploit trigger
+    let mut results = Stack::resolve_1(&mut db);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(db.hand[player], IndexSet::from([deck2, deck1]));
+    assert_eq!(db.all_players[player].life_total, 18);
+
+    Ok(())
+}
+
+#[test]
+fn exploit_declined() -> anyhow::Result<()> {
+    let cards = load_cards()?;
+    let mut all_players = AllPlayers::default();
+    let player = all_players.new_player("Player".to_string(), 20);
+    all_players[player].infinite_mana();
+    let mut db = Database::new(all_players);
+    db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let bear = CardId::upload(&mut db, &cards, player, "Alpine Grizzly");
+    bear.move_to_battlefield(&mut db);
+
+    let aven = CardId::upload(&mut db, &cards, player, "Vulturous Aven");
+    let mut results = Stack::move_card_to_stack_from_hand(&mut db, aven);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    // Pay the black
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::PendingChoice);
+    // Pay the generic
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let mut results = Stack::resolve_1(&mut db);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    let mut results = Stack::resolve_1(&mut db);
+    let result = results.resolve(&mut db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert!(db.graveyard[player].is_empty());
+    assert!(db.stack.is_empty());
+    assert_eq!(db.all_players[player].life_total, 20);
+
+    Ok(())
+}
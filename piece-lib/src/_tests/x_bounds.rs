@@ -0,0 +1,129 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    effects::{Options, SelectionResult},
+    in_play::CardId,
+    player::Player,
+    protogen::{
+        card::Card,
+        mana::{Mana, ManaRestriction, ManaSource},
+    },
+    turns::Phase,
+};
+
+const TEST_FIREBALL: &str = r#"
+name: Test Fireball
+typeline: Sorcery
+cost:
+  mana_cost: '{X}{R}'
+  x:
+    minimum: 1
+"#;
+
+const TEST_OUTPOURING: &str = r#"
+name: Test Outpouring
+typeline: Sorcery
+cost:
+  mana_cost: '{X}{R}'
+  x:
+    fixed: cards in hand
+"#;
+
+fn add_red(game: &mut crate::_tests::support::Game, amount: usize) {
+    for _ in 0..amount {
+        game.db.all_players[game.p1].mana_pool.apply(
+            Mana::RED,
+            ManaSource::ANY,
+            ManaRestriction::NONE,
+        );
+    }
+}
+
+#[test]
+fn x_cant_be_zero() -> anyhow::Result<()> {
+    let mut game = game! {};
+    let player = game.p1;
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let fireball: Card = serde_yaml::from_str(TEST_FIREBALL)?;
+    let fireball = CardId::upload_card_or_token(&mut game.db, player, fireball, false);
+    fireball.move_to_hand(&mut game.db);
+
+    add_red(&mut game, 1);
+    assert!(!Player::can_afford(&game.db, player, fireball));
+    add_red(&mut game, 1);
+    assert!(Player::can_afford(&game.db, player, fireball));
+    add_red(&mut game, 1);
+
+    let mut results = Player::play_card(&mut game.db, player, fireball);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    // Pay the red
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::PendingChoice);
+    assert!(matches!(
+        results.options(&game.db),
+        Options::MandatoryList(_)
+    ));
+
+    // X can't be skipped while it is 0.
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::PendingChoice);
+
+    let result = results.resolve(&mut game.db, Some(0));
+    assert_eq!(result, SelectionResult::PendingChoice);
+    assert!(matches!(
+        results.options(&game.db),
+        Options::ListWithDefault(_)
+    ));
+
+    // Paying for X finishes once there is no more mana to pay with.
+    let result = results.resolve(&mut game.db, Some(0));
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(game.db[fireball].x_is, 2);
+    assert_zone!(game.db, fireball, stack);
+
+    Ok(())
+}
+
+#[test]
+fn fixed_x_is_paid_automatically() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Forest" * 2],
+    };
+    let player = game.p1;
+    game.db.turn.set_phase(Phase::PreCombatMainPhase);
+
+    let outpouring: Card = serde_yaml::from_str(TEST_OUTPOURING)?;
+    let outpouring = CardId::upload_card_or_token(&mut game.db, player, outpouring, false);
+    outpouring.move_to_hand(&mut game.db);
+
+    add_red(&mut game, 2);
+    assert!(!Player::can_afford(&game.db, player, outpouring));
+    add_red(&mut game, 2);
+    assert!(Player::can_afford(&game.db, player, outpouring));
+
+    let mut results = Player::play_card(&mut game.db, player, outpouring);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    // Pay the red, which pays for X since it can only be 2.
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::TryAgain);
+    let result = results.resolve(&mut game.db, None);
+    assert_eq!(result, SelectionResult::Complete);
+
+    assert_eq!(game.db[outpouring].x_is, 2);
+    assert_eq!(
+        game.db.all_players[player]
+            .mana_pool
+            .available_mana()
+            .map(|(count, _, _, _)| count)
+            .sum::<usize>(),
+        1
+    );
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use crate::library::Library;
+
+const TEST_GATEKEEPER: &str = r#"
+name: Test Gatekeeper
+typeline: Creature - Human Soldier
+cost:
+  mana_cost: '{1}{W}'
+replacement_abilities:
+  - restrictions:
+      - restriction: !Controller
+          controller: !Opponent {}
+      - restriction: !OfType
+          types: Creature
+    replacing: zone change
+    zone_change:
+      to: on battlefield
+      enters_tapped: true
+power: 2
+toughness: 2
+"#;
+
+const TEST_WARDEN: &str = r#"
+name: Test Warden
+typeline: Enchantment
+cost:
+  mana_cost: '{U}'
+replacement_abilities:
+  - restrictions:
+      - restriction: !Controller
+          controller: !Opponent {}
+    replacing: zone change
+    zone_change:
+      to: in hand
+      instead: in library
+  - replacing: zone change
+    zone_change:
+      to: in library
+      instead: in hand
+"#;
+
+#[test]
+fn enters_tapped() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 hand: ["Alpine Grizzly"],
+        p2 hand: ["Alpine Grizzly", "Forest"],
+    };
+    let gatekeeper = game.upload_yaml(game.p1, TEST_GATEKEEPER)?;
+    gatekeeper.move_to_battlefield(&mut game.db);
+    game.db[gatekeeper].replacements_active = true;
+
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    bear.move_to_battlefield(&mut game.db);
+    assert!(!game.db[bear].tapped);
+
+    let bear = game.card(game.p2, "Alpine Grizzly");
+    bear.move_to_battlefield(&mut game.db);
+    assert_zone!(game.db, bear, battlefield);
+    assert!(game.db[bear].tapped);
+
+    let forest = game.card(game.p2, "Forest");
+    forest.move_to_battlefield(&mut game.db);
+    assert!(!game.db[forest].tapped);
+
+    Ok(())
+}
+
+#[test]
+fn replaces_destination_once() -> anyhow::Result<()> {
+    let mut game = game! {
+        p1 battlefield: ["Alpine Grizzly"],
+        p2 battlefield: ["Alpine Grizzly"],
+    };
+    let warden = game.upload_yaml(game.p1, TEST_WARDEN)?;
+    warden.move_to_battlefield(&mut game.db);
+    game.db[warden].replacements_active = true;
+
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    bear.move_to_hand(&mut game.db);
+    assert_zone!(game.db, bear, hand);
+
+    let bear = game.card(game.p2, "Alpine Grizzly");
+    bear.move_to_hand(&mut game.db);
+    assert_zone!(game.db, bear, library);
+
+    let bear = game.card(game.p1, "Alpine Grizzly");
+    Library::place_on_top(&mut game.db, game.p1, bear);
+    assert_zone!(game.db, bear, hand);
+
+    Ok(())
+}
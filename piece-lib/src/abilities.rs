@@ -1,12 +1,12 @@
 use crate::{
     effects::PendingEffects,
-    in_play::{ActivatedAbilityId, CardId, Database, GainManaAbilityId},
+    in_play::{AbilityId, ActivatedAbilityId, CardId, Database, GainManaAbilityId},
     player::Owner,
     protogen::{
         cost::{ability_restriction, AbilityCost},
         effects::{
-            static_ability, ActivatedAbility, Effect, EtbAbility, GainManaAbility, PayCosts,
-            TargetSelection, TriggeredAbility,
+            pay_cost, static_ability, ActivatedAbility, Effect, EtbAbility, GainManaAbility,
+            PayCosts, TargetSelection, TriggeredAbility,
         },
     },
     turns::Phase,
@@ -30,7 +30,7 @@ impl ActivatedAbility {
                 db[*ability].ability,
                 static_ability::Ability::PreventAbilityActivation(_)
             )
-        });
+        }) || source.activation_prevented(db, false);
 
         if banned {
             return false;
@@ -61,7 +61,8 @@ impl ActivatedAbility {
             return false;
         }
 
-        if self.sorcery_speed {
+        let loyalty = self.cost.loyalty;
+        if self.sorcery_speed || loyalty.is_some() {
             if controller != db.turn.active_player() {
                 return false;
             }
@@ -78,11 +79,29 @@ impl ActivatedAbility {
             }
         }
 
+        if let Some(loyalty) = loyalty {
+            if loyalty < 0 && source.loyalty(db) < loyalty.unsigned_abs() {
+                return false;
+            }
+
+            // Only one loyalty ability of each permanent can be activated each turn.
+            if db[source]
+                .modified_activated_abilities
+                .iter()
+                .any(|ability| {
+                    db[*ability].ability.cost.loyalty.is_some()
+                        && db.turn.activated_abilities.contains(ability)
+                })
+            {
+                return false;
+            }
+        }
+
         if !passes_restrictions(db, id, self.cost.get_or_default(), source) {
             return false;
         }
 
-        true
+        can_pay_sacrifices(db, &self.additional_costs, source)
     }
 }
 
@@ -102,10 +121,25 @@ impl GainManaAbility {
             return false;
         }
 
+        if source.activation_prevented(db, true) {
+            return false;
+        }
+
         passes_restrictions(db, id, &self.cost, source)
+            && can_pay_sacrifices(db, &self.additional_costs, source)
     }
 }
 
+/// Whether there is a permanent which can be sacrificed for each sacrifice in the costs.
+fn can_pay_sacrifices(db: &Database, costs: &PayCosts, source: CardId) -> bool {
+    costs.pay_costs.iter().all(|cost| match cost.cost.as_ref() {
+        Some(pay_cost::Cost::SacrificePermanent(sacrifice)) => {
+            sacrifice.can_be_paid(db, db[source].controller, source)
+        }
+        _ => true,
+    })
+}
+
 #[derive(Debug, Clone)]
 pub enum Ability {
     Activated(ActivatedAbilityId),
@@ -218,6 +252,19 @@ pub(crate) fn passes_restrictions(
                 Ability::Mana(_) => todo!(),
                 _ => return false,
             },
+            ability_restriction::Restriction::OncePerGame(_) => match id {
+                Ability::Activated(id) => {
+                    if db.activated_this_game.contains(&AbilityId::Activated(*id)) {
+                        return false;
+                    }
+                }
+                Ability::Mana(id) => {
+                    if db.activated_this_game.contains(&AbilityId::Mana(*id)) {
+                        return false;
+                    }
+                }
+                _ => return false,
+            },
         }
     }
 
@@ -5,29 +5,80 @@ use itertools::Itertools;
 
 use crate::{
     abilities::Ability,
-    effects::{EffectBundle, PendingEffects, SelectedStack},
-    in_play::{CardId, Database},
-    player::{Controller, Owner},
+    cost::mana_cost_text,
+    effects::{EffectBundle, PendingEffects, SelectedStack, SelectionResult},
+    filter::CardFilter,
+    in_play::{AbilityId, CardId, Database, ModifierInPlay},
+    log::{Cause, Log},
+    notifications::Notifications,
+    player::{mana_pool::FloatingMana, Controller, Owner, Player},
     protogen::{
         color::Color,
+        cost::ManaCost,
+        counters::Counter,
         effects::{
+            count,
             dest::Destination,
-            pay_cost::PayMana,
+            pay_cost::{self, PayMana},
             static_ability::{self},
-            ClearSelected, Dest, Duration, MoveToBattlefield, MoveToGraveyard, MoveToStack,
-            PayCost, PayCosts, PopSelected, PushSelected, SelectDestinations, SelectSource, Tap,
+            target_selection, AddCounters, ClearSelected, Count, Dest, Duration, MoveToBattlefield,
+            MoveToGraveyard, MoveToStack, PayCost, PayCosts, PopSelected, PushSelected,
+            RemoveCounters, Sacrifice, SelectDestinations, SelectSource, Tap,
         },
         mana::{spend_reason::Activating, SpendReason},
         targets::Location,
-        types::Type,
+        types::{Subtype, Type},
     },
-    stack::{Selected, TargetType},
-    types::TypeSet,
+    stack::{Entry, Selected, TargetType},
+    types::{SubtypeSet, TypeSet},
 };
 
+/// The result of activating several mana abilities at once with
+/// [Battlefields::activate_mana_abilities].
 #[derive(Debug, Default)]
+pub struct ManaActivation {
+    /// The mana added to the activator's pool by abilities which finished resolving.
+    pub floated: Vec<FloatingMana>,
+    /// The abilities which need a choice before they can finish resolving, e.g. which color of
+    /// mana to add.
+    pub pending: PendingEffects,
+}
+
+/// What activating an ability will ask of the activator, for showing before they commit to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ActivationPreview {
+    pub mana_cost: Vec<ManaCost>,
+    /// The mana cost as mana symbols, see [crate::protogen::cost::AbilityCost::text].
+    pub mana_text: String,
+    /// Mana symbols in the cost which the activator may pay life for instead, and how much life.
+    pub life_alternatives: Vec<(ManaCost, u32)>,
+    pub tap: bool,
+    /// The loyalty counters put on or removed from the source, for a planeswalker's loyalty
+    /// abilities.
+    pub loyalty: Option<i32>,
+    /// Costs which are paid in addition to the mana cost, e.g. sacrificing a permanent.
+    pub additional_costs: Vec<String>,
+    pub targets: Option<TargetsPreview>,
+    pub can_activate: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TargetsPreview {
+    /// How many targets must be chosen.
+    pub count: usize,
+    pub optional: bool,
+    /// How many legal targets there are right now.
+    pub available: usize,
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct Battlefields {
     pub battlefields: IndexMap<Controller, IndexSet<CardId>>,
+    /// Permanents which are phased out. They are still on the battlefield, but are treated as
+    /// though they don't exist until they phase in.
+    pub phased_out: IndexSet<CardId>,
 }
 
 impl std::ops::Index<Owner> for Battlefields {
@@ -80,25 +131,55 @@ impl Battlefields {
         colors
     }
 
-    pub(crate) fn untap(db: &mut Database, player: Owner) {
-        let cards = db
+    /// Phase out the player's permanents with phasing and phase in the permanents which phased out
+    /// under their control.
+    pub(crate) fn phase(db: &mut Database, player: Owner) {
+        let phasing_in = db
             .battlefield
-            .battlefields
+            .phased_out
             .iter()
-            .flat_map(|(controller, cards)| cards.iter().map(|card| (*controller, *card)))
-            .filter_map(|(controller, card)| {
-                if controller == player
-                    || db[card].modified_static_abilities.iter().any(|ability| {
-                        matches!(
-                            db[*ability].ability,
-                            static_ability::Ability::UntapEachUntapStep(_)
-                        )
+            .copied()
+            .filter(|card| db[*card].controller == player && db[*card].enchanting.is_none())
+            .collect_vec();
+        let phasing_out = CardFilter::on_battlefield()
+            .controlled_by(player)
+            .matching(|db, card| card.phasing(db))
+            .cards(db);
+
+        for card in phasing_out {
+            card.phase_out(db);
+        }
+
+        for card in phasing_in {
+            card.phase_in(db);
+        }
+    }
+
+    pub(crate) fn untap(db: &mut Database, player: Owner) {
+        let cards = CardFilter::on_battlefield()
+            .controlled_by(player)
+            .cards(db)
+            .into_iter()
+            .chain(
+                CardFilter::on_battlefield()
+                    .matching(|db, card| {
+                        db[card].modified_static_abilities.iter().any(|ability| {
+                            matches!(
+                                db[*ability].ability,
+                                static_ability::Ability::UntapEachUntapStep(_)
+                            )
+                        })
                     })
-                {
-                    Some(card)
-                } else {
-                    None
-                }
+                    .cards(db),
+            )
+            .unique()
+            .filter(|card| {
+                !db[*card].modified_static_abilities.iter().any(|ability| {
+                    matches!(
+                        db[*ability].ability,
+                        static_ability::Ability::DoesntUntap(_)
+                    )
+                })
             })
             .collect_vec();
 
@@ -108,8 +189,9 @@ impl Battlefields {
     }
 
     pub(crate) fn end_turn(db: &mut Database) -> PendingEffects {
-        for card in db.battlefield.battlefields.values().flat_map(|b| b.iter()) {
-            db.cards.entry(*card).or_default().marked_damage = 0;
+        for card in CardFilter::on_battlefield().cards(db) {
+            card.clear_damage(db);
+            db[card].regeneration_shields = 0;
         }
 
         let mut results = PendingEffects::default();
@@ -137,40 +219,124 @@ impl Battlefields {
             ..Default::default()
         });
 
-        let all_modifiers = db
+        Self::expire_modifiers(db, |_, modifier| {
+            modifier.modifier.duration.enum_value().unwrap() == Duration::UNTIL_END_OF_TURN
+        });
+
+        results
+    }
+
+    /// Removes every creature from combat and ends "until end of combat" effects, so no combat
+    /// state outlives the end of combat step.
+    pub(crate) fn end_combat(db: &mut Database) {
+        for card in db.cards.keys().copied().collect_vec() {
+            card.remove_from_combat(db);
+        }
+
+        Self::expire_modifiers(db, |_, modifier| {
+            modifier.modifier.duration.enum_value().unwrap() == Duration::UNTIL_END_OF_COMBAT
+        });
+    }
+
+    pub(crate) fn start_turn(db: &mut Database, player: Owner) {
+        Self::expire_modifiers(db, |_, modifier| {
+            modifier.modifier.duration.enum_value().unwrap() == Duration::UNTIL_YOUR_NEXT_TURN
+                && modifier.controller == Some(Controller::from(player))
+        });
+    }
+
+    /// Deactivates every active modifier for which `expired` returns true.
+    /// Recomputes the abilities of permanents which have all the activated abilities of other
+    /// cards. Which cards those are depends on where every card is, so this needs to run whenever a
+    /// card changes zones.
+    pub(crate) fn refresh_shared_abilities(db: &mut Database) {
+        for card in CardFilter::on_battlefield()
+            .matching(|db, card| {
+                db[card]
+                    .modified_static_abilities
+                    .iter()
+                    .any(|sa| matches!(db[*sa].ability, static_ability::Ability::AllAbilitiesOf(_)))
+            })
+            .cards(db)
+        {
+            card.apply_modifiers_layered(db);
+        }
+    }
+
+    fn expire_modifiers(db: &mut Database, expired: impl Fn(&Database, &ModifierInPlay) -> bool) {
+        let expiring = db
             .modifiers
             .iter()
-            .filter_map(|(id, modifier)| {
-                if modifier.active
-                    && matches!(
-                        modifier.modifier.duration.enum_value().unwrap(),
-                        Duration::UNTIL_END_OF_TURN
-                    )
-                {
-                    Some(id)
-                } else {
-                    None
-                }
-            })
-            .copied()
+            .filter(|(_, modifier)| modifier.active && expired(db, modifier))
+            .map(|(id, _)| *id)
             .collect_vec();
 
-        for modifier in all_modifiers {
+        if expiring.is_empty() {
+            return;
+        }
+
+        for modifier in expiring {
             modifier.deactivate(db);
         }
 
         for card in db.cards.keys().copied().collect_vec() {
             card.apply_modifiers_layered(db);
         }
-
-        results
     }
 
     pub fn check_sba(db: &mut Database) -> PendingEffects {
         let mut pending = PendingEffects::default();
+        if db.triggers_suspended {
+            return pending;
+        }
+
+        Log::set_cause(db, Cause::StateBasedActions);
+
+        // A player with no life left, ten or more poison counters, or who tried to draw from an
+        // empty library since the last check loses the game.
+        for player in db.all_players.all_players() {
+            let player = &mut db.all_players[player];
+            if player.life_total <= 0
+                || player.poison_counters >= 10
+                || player.drew_from_empty_library
+            {
+                player.lost = true;
+            }
+            player.drew_from_empty_library = false;
+        }
+
+        Notifications::check_players(db);
+
+        // Zone changes already send tokens to limbo, but anything which leaves a token in another
+        // zone has it cease to exist here. Tokens on the stack are copies of spells.
+        for card in db
+            .cards
+            .keys()
+            .copied()
+            .filter(|card| db[*card].token)
+            .collect_vec()
+        {
+            if db[card].location.is_some_and(|location| {
+                !matches!(location, Location::ON_BATTLEFIELD | Location::IN_STACK)
+            }) {
+                card.move_to_limbo(db);
+            }
+        }
+
+        Self::refresh_shared_abilities(db);
+
+        Self::expire_modifiers(db, |db, modifier| {
+            modifier.modifier.duration.enum_value().unwrap() == Duration::WHILE_YOU_CONTROL_SOURCE
+                && (!modifier.source.is_in_location(db, Location::ON_BATTLEFIELD)
+                    || Some(db[modifier.source].controller) != modifier.controller)
+        });
 
         let mut legendary_cards: HashMap<String, Vec<CardId>> = HashMap::default();
+        let mut roles: HashMap<(CardId, Controller), Vec<CardId>> = HashMap::default();
+        let mut unattached = vec![];
+        let mut unequipped = vec![];
         let mut push_on_enter = vec![];
+        let mut finished_sagas = vec![];
         let mut bundle = EffectBundle {
             effects: vec![
                 MoveToGraveyard::default().into(),
@@ -179,13 +345,24 @@ impl Battlefields {
             ..Default::default()
         };
 
-        for card in db
-            .battlefield
-            .battlefields
-            .values()
-            .flat_map(|b| b.iter())
-            .copied()
-        {
+        for card in CardFilter::on_battlefield().cards(db) {
+            // +1/+1 and -1/-1 counters on the same permanent remove each other in pairs.
+            let p1p1 = db[card]
+                .counters
+                .get(&Counter::P1P1)
+                .copied()
+                .unwrap_or_default();
+            let m1m1 = db[card]
+                .counters
+                .get(&Counter::M1M1)
+                .copied()
+                .unwrap_or_default();
+            let annihilated = p1p1.min(m1m1);
+            if annihilated > 0 {
+                *db[card].counters.entry(Counter::P1P1).or_default() -= annihilated;
+                *db[card].counters.entry(Counter::M1M1).or_default() -= annihilated;
+            }
+
             if card.types_intersect(db, &TypeSet::from([Type::LEGENDARY])) {
                 legendary_cards
                     .entry(db[card].modified_name.clone())
@@ -193,12 +370,13 @@ impl Battlefields {
                     .push(card);
             }
 
-            let toughness = card.toughness(db);
-
-            if toughness.is_some()
-                && (toughness.unwrap() <= 0
-                    || ((toughness.unwrap() - card.marked_damage(db)) <= 0
-                        && !card.indestructible(db)))
+            // Lethal damage destroys the creature, which a regeneration shield can replace. Having
+            // no toughness or loyalty left can't be regenerated.
+            if card.toughness(db).is_some_and(|toughness| toughness <= 0)
+                || (card.is_planeswalker(db) && card.loyalty(db) == 0)
+                || (card.lethal_damage_remaining(db) == Some(0)
+                    && !card.indestructible(db)
+                    && !card.regenerate(db))
             {
                 push_on_enter.push(Selected {
                     location: Some(Location::ON_BATTLEFIELD),
@@ -208,24 +386,111 @@ impl Battlefields {
                 });
             }
 
-            let enchanting = db[card].enchanting;
-            if enchanting.is_some()
-                && !enchanting
-                    .unwrap()
-                    .is_in_location(db, Location::ON_BATTLEFIELD)
+            // A saga is sacrificed once it has reached its final chapter and none of its chapter
+            // abilities are still waiting to resolve.
+            if card
+                .final_chapter(db)
+                .is_some_and(|chapter| card.lore(db) >= chapter)
+                && !db.stack.entries().values().any(
+                    |entry| matches!(entry.ty, Entry::Ability { source, .. } if source == card),
+                )
             {
-                push_on_enter.push(Selected {
+                finished_sagas.push(Selected {
                     location: Some(Location::ON_BATTLEFIELD),
                     target_type: TargetType::Card(card),
                     targeted: false,
                     restrictions: vec![],
                 });
             }
+
+            // Auras fall off of permanents which have left the battlefield or which have gained
+            // protection from them.
+            let enchanting = db[card].enchanting;
+            if enchanting.is_some_and(|enchanting| {
+                !enchanting.is_in_location(db, Location::ON_BATTLEFIELD)
+                    || enchanting.protected_from(db, card)
+            }) {
+                if db[card].bestowed {
+                    unattached.push(card);
+                } else {
+                    push_on_enter.push(Selected {
+                        location: Some(Location::ON_BATTLEFIELD),
+                        target_type: TargetType::Card(card),
+                        targeted: false,
+                        restrictions: vec![],
+                    });
+                }
+            } else if let Some(enchanting) = enchanting {
+                if card.subtypes_intersect(db, &SubtypeSet::from([Subtype::ROLE])) {
+                    roles
+                        .entry((enchanting, db[card].controller))
+                        .or_default()
+                        .push(card);
+                }
+            }
+
+            // Equipment becomes unattached from permanents which are no longer creatures or which
+            // have gained protection from it, but stays on the battlefield.
+            if card.subtypes_intersect(db, &SubtypeSet::from([Subtype::EQUIPMENT])) {
+                unequipped.extend(
+                    db.modifiers
+                        .iter()
+                        .filter(|(id, modifier)| {
+                            modifier.source == card
+                                && !db[card]
+                                    .modified_static_abilities
+                                    .iter()
+                                    .any(|sa| db[*sa].owned_modifier == Some(**id))
+                                && modifier.modifying.iter().any(|equipped| {
+                                    !equipped.types_intersect(db, &TypeSet::from([Type::CREATURE]))
+                                        || equipped.protected_from(db, card)
+                                })
+                        })
+                        .map(|(modifier, _)| *modifier),
+                );
+            }
+        }
+
+        // A permanent can only have one Role from each player, so every Role but the newest is put
+        // into the graveyard.
+        for roles in roles.values() {
+            push_on_enter.extend(
+                roles
+                    .iter()
+                    .sorted_by_key(|role| role.timestamp(db))
+                    .rev()
+                    .skip(1)
+                    .map(|role| Selected {
+                        location: Some(Location::ON_BATTLEFIELD),
+                        target_type: TargetType::Card(*role),
+                        targeted: false,
+                        restrictions: vec![],
+                    }),
+            );
+        }
+
+        // Bestowed auras become creatures again rather than going to the graveyard.
+        for card in unattached {
+            db[card].bestowed = false;
+            db[card].enchanting = None;
+            card.apply_modifiers_layered(db);
+        }
+
+        for modifier in unequipped {
+            modifier.deactivate(db);
         }
 
         bundle.push_on_enter = Some(push_on_enter);
         pending.push_back(bundle);
 
+        if !finished_sagas.is_empty() {
+            pending.push_back(EffectBundle {
+                push_on_enter: Some(finished_sagas),
+                effects: vec![Sacrifice::default().into(), PopSelected::default().into()],
+                ..Default::default()
+            });
+        }
+
         for legends in legendary_cards.values() {
             if legends.len() > 1 {
                 pending.push_back(EffectBundle {
@@ -280,6 +545,8 @@ impl Battlefields {
             return PendingEffects::default();
         }
 
+        Log::set_cause(db, Cause::Ability(source));
+
         let mut results = PendingEffects::new(SelectedStack::new(vec![Selected {
             location: Some(Location::ON_BATTLEFIELD),
             target_type: TargetType::Ability {
@@ -344,9 +611,46 @@ impl Battlefields {
                 bundle.effects.push(Tap::default().into());
                 bundle.effects.push(PopSelected::default().into());
             }
+            if let Some(loyalty) = cost.loyalty {
+                let count = protobuf::MessageField::some(Count {
+                    count: Some(
+                        count::Fixed {
+                            count: loyalty.abs(),
+                            ..Default::default()
+                        }
+                        .into(),
+                    ),
+                    ..Default::default()
+                });
+                bundle.effects.push(PushSelected::default().into());
+                bundle.effects.push(ClearSelected::default().into());
+                bundle.effects.push(SelectSource::default().into());
+                if loyalty < 0 {
+                    bundle.effects.push(
+                        RemoveCounters {
+                            counter: Counter::LOYALTY.into(),
+                            count,
+                            ..Default::default()
+                        }
+                        .into(),
+                    );
+                } else {
+                    bundle.effects.push(
+                        AddCounters {
+                            counter: Counter::LOYALTY.into(),
+                            count,
+                            ..Default::default()
+                        }
+                        .into(),
+                    );
+                }
+                bundle.effects.push(PopSelected::default().into());
+            }
         }
 
         if let Ability::Mana(id) = ability {
+            // Mana abilities don't use the stack, so they count as activated as soon as they are.
+            db.activated_this_game.insert(AbilityId::Mana(id));
             bundle
                 .effects
                 .extend(db[id].ability.effects.iter().cloned());
@@ -359,16 +663,110 @@ impl Battlefields {
         results
     }
 
+    /// Describes the costs and targets of the nth ability of the card without activating it.
+    pub fn activation_preview(
+        db: &Database,
+        pending: &Option<PendingEffects>,
+        activator: Owner,
+        source: CardId,
+        index: usize,
+    ) -> Option<ActivationPreview> {
+        let (ability_source, ability) = db[source].abilities(db).into_iter().nth(index)?;
+
+        let cost = ability.cost(db).cloned().unwrap_or_default();
+        let mana_cost = cost
+            .mana_cost
+            .iter()
+            .map(|cost| cost.enum_value().unwrap())
+            .sorted()
+            .collect_vec();
+        let life_alternatives = mana_cost
+            .iter()
+            .copied()
+            .dedup()
+            .filter_map(|mana| {
+                Player::life_for_mana(db, activator, Some(source), mana).map(|life| (mana, life))
+            })
+            .collect_vec();
+
+        let additional_costs = ability
+            .additional_costs(db)
+            .map(|costs| {
+                costs
+                    .pay_costs
+                    .iter()
+                    .filter_map(|cost| cost.cost.as_ref())
+                    .map(|cost| cost_text(db, ability_source, cost))
+                    .collect_vec()
+            })
+            .unwrap_or_default();
+
+        let targets = ability
+            .targets(db)
+            .and_then(|targets| targets.selector.as_ref())
+            .and_then(|selector| match selector {
+                target_selection::Selector::SelectTargets(targets) => Some(TargetsPreview {
+                    count: targets.count.count(db, Some(ability_source), &[]).max(0) as usize,
+                    optional: targets.optional,
+                    available: targets.valid_targets(db, Some(ability_source), &[]).count(),
+                }),
+                _ => None,
+            });
+
+        Some(ActivationPreview {
+            mana_text: cost.text(),
+            mana_cost,
+            life_alternatives,
+            tap: cost.tap,
+            loyalty: cost.loyalty,
+            additional_costs,
+            targets,
+            can_activate: ability.can_be_activated(db, source, activator, pending),
+        })
+    }
+
+    /// Activates each of the mana abilities, given as a card and the index of the ability, as a
+    /// single action. Abilities which aren't mana abilities or which can't be activated are
+    /// skipped.
+    pub fn activate_mana_abilities(
+        db: &mut Database,
+        pending: &Option<PendingEffects>,
+        activator: Owner,
+        abilities: &[(CardId, usize)],
+    ) -> ManaActivation {
+        let before = db.all_players[activator].mana_pool.floating();
+
+        let mut activation = ManaActivation::default();
+        for (source, index) in abilities.iter().copied() {
+            if !matches!(
+                db[source].abilities(db).into_iter().nth(index),
+                Some((_, Ability::Mana(_)))
+            ) {
+                continue;
+            }
+
+            let mut results = Self::activate_ability(db, pending, activator, source, index);
+            while !results.wants_input(db) {
+                if results.resolve(db, None) == SelectionResult::Complete {
+                    break;
+                }
+            }
+            if activation.pending.is_empty() {
+                activation.pending = results;
+            } else {
+                activation.pending.extend(results);
+            }
+        }
+
+        activation.floated =
+            FloatingMana::added(&before, &db.all_players[activator].mana_pool.floating());
+        activation
+    }
+
     pub(crate) fn static_abilities(db: &Database) -> Vec<(&static_ability::Ability, CardId)> {
         let mut result: Vec<(&static_ability::Ability, CardId)> = Default::default();
 
-        for card in db
-            .battlefield
-            .battlefields
-            .values()
-            .flat_map(|b| b.iter())
-            .copied()
-        {
+        for card in CardFilter::on_battlefield().cards(db) {
             for ability in db[card].modified_static_abilities.iter() {
                 result.push((&db[*ability].ability, card));
             }
@@ -391,7 +789,7 @@ impl Battlefields {
             .filter_map(|(id, modifier)| {
                 if (matches!(
                     modifier.modifier.duration.enum_value().unwrap(),
-                    Duration::UNTIL_SOURCE_LEAVES_BATTLEFIELD
+                    Duration::UNTIL_SOURCE_LEAVES_BATTLEFIELD | Duration::WHILE_YOU_CONTROL_SOURCE
                 ) && modifier.source == target)
                     || (matches!(
                         modifier.modifier.duration.enum_value().unwrap(),
@@ -443,3 +841,34 @@ impl Battlefields {
         }
     }
 }
+
+fn cost_text(db: &Database, source: CardId, cost: &pay_cost::Cost) -> String {
+    match cost {
+        pay_cost::Cost::Discard(discard) => format!(
+            "Discard {} cards",
+            discard.count.count(db, Some(source), &[])
+        ),
+        pay_cost::Cost::ExileCardsSharingType(exile) => {
+            format!("Exile {} cards which share a type", exile.count)
+        }
+        pay_cost::Cost::ExilePermanents(exile) => {
+            format!("Exile {} to {} permanents", exile.minimum, exile.maximum)
+        }
+        pay_cost::Cost::ExilePermanentsCmcX(_) => {
+            "Exile permanents with total mana value X".to_string()
+        }
+        pay_cost::Cost::PayLife(life) => {
+            format!("Pay {} life", life.count.count(db, Some(source), &[]))
+        }
+        pay_cost::Cost::PayMana(mana) => format!("Pay {}", mana_cost_text(&mana.paying)),
+        pay_cost::Cost::RemoveCounters(remove) => format!(
+            "Remove {} counters",
+            remove.count.count(db, Some(source), &[])
+        ),
+        pay_cost::Cost::SacrificePermanent(_) => "Sacrifice a permanent".to_string(),
+        pay_cost::Cost::TapPermanent(_) => "Tap an untapped permanent".to_string(),
+        pay_cost::Cost::TapPermanentsPowerXOrMore(tap) => {
+            format!("Tap permanents with total power {} or more", tap.x_is)
+        }
+    }
+}
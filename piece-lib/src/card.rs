@@ -1,4 +1,7 @@
-use std::{collections::HashMap, sync::OnceLock};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::OnceLock,
+};
 
 use aho_corasick::AhoCorasick;
 use itertools::Itertools;
@@ -6,14 +9,16 @@ use protobuf::Enum;
 
 use crate::protogen::{
     card::Card,
+    color::Color,
     cost::{AbilityCost, ManaCost},
     effects::{
         count::Fixed,
         create_token::{self, Token},
         pay_cost::SacrificePermanent,
-        ActivatedAbility, Count, Effect, Explore, PayCosts, SelectTargets,
+        ActivatedAbility, Count, Effect, Explore, PayCosts, SelectSource, SelectTargets, Transform,
     },
     empty::Empty,
+    keywords::Keyword,
     targets::{
         restriction::{self, OfType},
         Restriction,
@@ -22,12 +27,63 @@ use crate::protogen::{
 };
 
 impl Card {
+    /// Whether the card has no abilities besides keywords, so its characteristics only change
+    /// when something else modifies it. Basic land types grant intrinsic mana abilities, so cards
+    /// with them are never vanilla.
+    pub(crate) fn is_vanilla(&self) -> bool {
+        self.static_abilities.is_empty()
+            && self.etb_ability.is_none()
+            && self.activated_abilities.is_empty()
+            && self.triggered_abilities.is_empty()
+            && self.mana_abilities.is_empty()
+            && self.replacement_abilities.is_empty()
+            && self.dynamic_power_toughness.is_none()
+            && self.levels.is_empty()
+            && self.chapters.is_empty()
+            && !self.typeline.subtypes.iter().any(|subtype| {
+                subtype
+                    .enum_value()
+                    .is_ok_and(|subtype| subtype.basic_land_mana().is_some())
+            })
+    }
+
+    /// The card's colors before anything modifies them. These are the colors of its mana cost and
+    /// color indicator, unless the card has devoid. Colorless cards have no colors, so this never
+    /// contains [Color::COLORLESS].
+    pub fn base_colors(&self) -> HashSet<Color> {
+        if self.keywords.contains_key(&Keyword::DEVOID.value()) {
+            return HashSet::default();
+        }
+
+        self.color_identity()
+    }
+
+    /// The colors of the mana symbols in the card's cost, its color indicator, and the colors it
+    /// was given when created as a token. Unlike [Card::base_colors], this ignores devoid.
+    pub fn color_identity(&self) -> HashSet<Color> {
+        self.colors
+            .iter()
+            .chain(self.color_indicator.iter())
+            .map(|color| color.enum_value().unwrap())
+            .chain(self.cost.colors())
+            .filter(|color| *color != Color::COLORLESS)
+            .collect()
+    }
+
     pub fn document(&self) -> String {
         let cost_text = self.cost.text();
+        let rules_text = self.rules_text();
 
-        std::iter::once(self.name.as_str())
-            .chain(std::iter::once(cost_text.as_str()))
-            .chain(std::iter::once(self.oracle_text.as_str()))
+        let parts: Vec<&str> = [self.name.as_str(), cost_text.as_str(), rules_text.as_str()]
+            .into_iter()
+            .filter(|t| !t.is_empty())
+            .collect();
+        parts.join("\n")
+    }
+
+    /// The oracle text of the card and each of its effects and abilities, one per line.
+    pub fn rules_text(&self) -> String {
+        std::iter::once(self.oracle_text.as_str())
             .chain(self.effects.iter().map(|e| e.oracle_text.as_str()))
             .chain(std::iter::once(self.etb_ability.oracle_text.as_str()))
             .chain(
@@ -141,6 +197,44 @@ impl From<Token> for Card {
                 }],
                 ..Default::default()
             },
+            Token::Incubator(_) => Self {
+                name: "Incubator".to_string(),
+                typeline: protobuf::MessageField::some(Typeline {
+                    types: vec![Type::ARTIFACT.into()],
+                    subtypes: vec![Subtype::INCUBATOR.into()],
+                    ..Default::default()
+                }),
+                activated_abilities: vec![ActivatedAbility {
+                    cost: protobuf::MessageField::some(AbilityCost {
+                        mana_cost: vec![ManaCost::GENERIC.into(), ManaCost::GENERIC.into()],
+                        ..Default::default()
+                    }),
+                    effects: vec![
+                        Effect {
+                            effect: Some(SelectSource::default().into()),
+                            ..Default::default()
+                        },
+                        Effect {
+                            effect: Some(Transform::default().into()),
+                            ..Default::default()
+                        },
+                    ],
+                    oracle_text: "{2}: Transform this artifact.".to_string(),
+                    ..Default::default()
+                }],
+                back_face: protobuf::MessageField::some(Self {
+                    name: "Phyrexian Token".to_string(),
+                    typeline: protobuf::MessageField::some(Typeline {
+                        types: vec![Type::ARTIFACT.into(), Type::CREATURE.into()],
+                        subtypes: vec![Subtype::PHYREXIAN.into()],
+                        ..Default::default()
+                    }),
+                    power: Some(0),
+                    toughness: Some(0),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
         }
     }
 }
@@ -0,0 +1,112 @@
+//! Notes, tags, and favorites the user keeps about cards, keyed by card name. Frontends decide
+//! where the store lives and use it to organize large card pools when browsing or building decks.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[non_exhaustive]
+pub struct CardMetadata {
+    pub favorite: bool,
+    pub tags: BTreeSet<String>,
+    pub notes: String,
+}
+
+impl CardMetadata {
+    fn is_empty(&self) -> bool {
+        !self.favorite && self.tags.is_empty() && self.notes.is_empty()
+    }
+}
+
+/// The metadata for every card the user has marked. Cards without any metadata aren't stored.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CardMetadataStore {
+    cards: BTreeMap<String, CardMetadata>,
+}
+
+impl CardMetadataStore {
+    pub fn load_from(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Reading card metadata: {}", path.display()))?;
+        Ok(serde_yaml::from_str(&contents)?)
+    }
+
+    pub fn save_to(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Writing card metadata: {}", path.display()))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&CardMetadata> {
+        self.cards.get(name)
+    }
+
+    pub fn is_favorite(&self, name: &str) -> bool {
+        self.get(name).is_some_and(|metadata| metadata.favorite)
+    }
+
+    pub fn set_favorite(&mut self, name: &str, favorite: bool) {
+        self.update(name, |metadata| metadata.favorite = favorite);
+    }
+
+    pub fn toggle_favorite(&mut self, name: &str) {
+        self.update(name, |metadata| metadata.favorite = !metadata.favorite);
+    }
+
+    pub fn add_tag(&mut self, name: &str, tag: &str) {
+        self.update(name, |metadata| {
+            metadata.tags.insert(tag.to_string());
+        });
+    }
+
+    pub fn remove_tag(&mut self, name: &str, tag: &str) {
+        self.update(name, |metadata| {
+            metadata.tags.remove(tag);
+        });
+    }
+
+    pub fn set_notes(&mut self, name: &str, notes: &str) {
+        self.update(name, |metadata| metadata.notes = notes.to_string());
+    }
+
+    /// The names of the favorited cards, in alphabetical order.
+    pub fn favorites(&self) -> impl Iterator<Item = &str> {
+        self.cards
+            .iter()
+            .filter(|(_, metadata)| metadata.favorite)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// The names of the cards with `tag`, in alphabetical order.
+    pub fn tagged<'s>(&'s self, tag: &'s str) -> impl Iterator<Item = &'s str> {
+        self.cards
+            .iter()
+            .filter(move |(_, metadata)| metadata.tags.contains(tag))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Every tag in use, in alphabetical order.
+    pub fn tags(&self) -> BTreeSet<&str> {
+        self.cards
+            .values()
+            .flat_map(|metadata| metadata.tags.iter().map(String::as_str))
+            .collect()
+    }
+
+    fn update(&mut self, name: &str, f: impl FnOnce(&mut CardMetadata)) {
+        let metadata = self.cards.entry(name.to_string()).or_default();
+        f(metadata);
+        if metadata.is_empty() {
+            self.cards.remove(name);
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Searching the loaded card definitions by name, type, color, mana value, and rules text, so the
+//! deck builder, the dev console, and effects which name cards all match cards the same way.
+//! Queries are built with the [CardQuery] builder methods or parsed from text with
+//! [CardQuery::parse], then run with [SearchCards::search].
+
+use std::{collections::HashSet, ops::RangeInclusive};
+
+use anyhow::{anyhow, Context};
+use convert_case::{Case, Casing};
+use protobuf::Enum;
+use regex::{Regex, RegexBuilder};
+
+use crate::{
+    protogen::{
+        card::Card,
+        color::Color,
+        types::{Subtype, Type},
+    },
+    Cards,
+};
+
+/// A set of conditions a card must meet. Every condition added to the query must pass for a card
+/// to match, and a query with no conditions matches every card.
+#[derive(Debug, Clone, Default)]
+pub struct CardQuery {
+    /// Stored lowercase, since names are matched ignoring case.
+    name: Option<String>,
+    types: Vec<Type>,
+    subtypes: Vec<Subtype>,
+    colors: HashSet<Color>,
+    cmc: Option<RangeInclusive<usize>>,
+    text: Option<Regex>,
+}
+
+impl CardQuery {
+    /// Parses a query from space separated terms. Terms with a prefix filter on a characteristic:
+    /// - `t:creature` or `t:bear` for a type or subtype.
+    /// - `c:wu` for colors, using `c:c` for colorless.
+    /// - `cmc:3` or `cmc:2-4` for a mana value or range of mana values.
+    /// - `o:draw\sa\scard` for a regex over the card's rules text, ignoring case.
+    ///
+    /// Every other term is part of the name, e.g. `alpine t:creature grizzly`.
+    pub fn parse(query: &str) -> anyhow::Result<Self> {
+        let mut result = Self::default();
+        let mut name = vec![];
+        for term in query.split_whitespace() {
+            match term.split_once(':') {
+                Some(("t" | "type", ty)) => {
+                    let screaming = ty.to_case(Case::ScreamingSnake);
+                    if let Some(ty) = Type::from_str(&screaming) {
+                        result = result.of_types([ty]);
+                    } else if let Some(subtype) = Subtype::from_str(&screaming) {
+                        result = result.of_subtypes([subtype]);
+                    } else {
+                        return Err(anyhow!("Unknown type: {}", ty));
+                    }
+                }
+                Some(("c" | "color", colors)) => {
+                    let colors = colors
+                        .chars()
+                        .map(|color| match color.to_ascii_lowercase() {
+                            'w' => Ok(Color::WHITE),
+                            'u' => Ok(Color::BLUE),
+                            'b' => Ok(Color::BLACK),
+                            'r' => Ok(Color::RED),
+                            'g' => Ok(Color::GREEN),
+                            'c' => Ok(Color::COLORLESS),
+                            color => Err(anyhow!("Unknown color: {}", color)),
+                        })
+                        .collect::<anyhow::Result<Vec<_>>>()?;
+                    result = result.of_colors(colors);
+                }
+                Some(("cmc", cmc)) => {
+                    let (min, max) = cmc.split_once('-').unwrap_or((cmc, cmc));
+                    let min = min
+                        .parse()
+                        .with_context(|| format!("Invalid mana value: {}", cmc))?;
+                    let max = max
+                        .parse()
+                        .with_context(|| format!("Invalid mana value: {}", cmc))?;
+                    result = result.cmc(min..=max);
+                }
+                Some(("o" | "oracle", text)) => {
+                    result = result.text(text)?;
+                }
+                _ => name.push(term),
+            }
+        }
+
+        if !name.is_empty() {
+            result = result.named(&name.join(" "));
+        }
+
+        Ok(result)
+    }
+
+    /// Only match cards whose name contains `name`, ignoring case.
+    pub fn named(mut self, name: &str) -> Self {
+        self.name = Some(name.to_lowercase());
+        self
+    }
+
+    /// Only match cards with every one of the types.
+    pub fn of_types(mut self, types: impl IntoIterator<Item = Type>) -> Self {
+        self.types.extend(types);
+        self
+    }
+
+    /// Only match cards with every one of the subtypes.
+    pub fn of_subtypes(mut self, subtypes: impl IntoIterator<Item = Subtype>) -> Self {
+        self.subtypes.extend(subtypes);
+        self
+    }
+
+    /// Only match cards which are every one of the colors. [Color::COLORLESS] matches cards with
+    /// no colors.
+    pub fn of_colors(mut self, colors: impl IntoIterator<Item = Color>) -> Self {
+        self.colors.extend(colors);
+        self
+    }
+
+    /// Only match cards whose mana value is in the range.
+    pub fn cmc(mut self, cmc: RangeInclusive<usize>) -> Self {
+        self.cmc = Some(cmc);
+        self
+    }
+
+    /// Only match cards whose rules text matches the regex `pattern`, ignoring case.
+    pub fn text(mut self, pattern: &str) -> anyhow::Result<Self> {
+        self.text = Some(
+            RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+                .with_context(|| format!("Invalid pattern: {}", pattern))?,
+        );
+        Ok(self)
+    }
+
+    /// Whether `name` passes the name condition of the query. This is useful when only the names
+    /// of the cards are available.
+    pub fn matches_name(&self, name: &str) -> bool {
+        self.name
+            .as_ref()
+            .map(|search| name.to_lowercase().contains(search))
+            .unwrap_or(true)
+    }
+
+    pub fn matches(&self, card: &Card) -> bool {
+        if !self.matches_name(&card.name) {
+            return false;
+        }
+
+        let has_type = |ty: &Type| {
+            card.typeline
+                .types
+                .iter()
+                .any(|card_ty| card_ty.enum_value().is_ok_and(|card_ty| card_ty == *ty))
+        };
+        let has_subtype = |subtype: &Subtype| {
+            card.typeline.subtypes.iter().any(|card_ty| {
+                card_ty
+                    .enum_value()
+                    .is_ok_and(|card_ty| card_ty == *subtype)
+            })
+        };
+        if !self.types.iter().all(has_type) || !self.subtypes.iter().all(has_subtype) {
+            return false;
+        }
+
+        let colors = card.base_colors();
+        if !self.colors.iter().all(|color| {
+            if *color == Color::COLORLESS {
+                colors.is_empty()
+            } else {
+                colors.contains(color)
+            }
+        }) {
+            return false;
+        }
+
+        self.cmc
+            .as_ref()
+            .map(|cmc| cmc.contains(&card.cost.cmc()))
+            .unwrap_or(true)
+            && self
+                .text
+                .as_ref()
+                .map(|text| text.is_match(&card.rules_text()))
+                .unwrap_or(true)
+    }
+}
+
+/// Searching [Cards], which is an alias of [indexmap::IndexMap] so it can't have inherent methods.
+pub trait SearchCards {
+    /// Every card matching `query`, in the order the cards were loaded.
+    fn search<'c>(
+        &'c self,
+        query: &'c CardQuery,
+    ) -> impl Iterator<Item = (&'c String, &'c Card)> + 'c;
+}
+
+impl SearchCards for Cards {
+    fn search<'c>(
+        &'c self,
+        query: &'c CardQuery,
+    ) -> impl Iterator<Item = (&'c String, &'c Card)> + 'c {
+        self.iter().filter(|(_, card)| query.matches(card))
+    }
+}
@@ -0,0 +1,147 @@
+//! Checkpoints of the game taken before a player starts an action, so they can back out of it
+//! partway through, e.g. after choosing targets but before paying costs, and return to exactly the
+//! state they started from. A frontend takes a [Checkpoint] before each action it starts and keeps
+//! them in a [Checkpoints] stack, so actions started while another is pending, like activating a
+//! mana ability while paying for a spell, can be backed out of one at a time.
+//!
+//! Frontends offering undo and the AI exploring lines of play can instead take snapshots of the
+//! game with [Database::snapshot], which are kept in the database and can be returned to any
+//! number of times with [Database::restore].
+//!
+//! Clocks, notifications, and traces aren't rolled back, since time spent deciding and cues which
+//! were already delivered can't be taken back. Snapshots aren't rolled back either, so restoring
+//! one never forgets the others.
+
+use std::{collections::HashMap, mem};
+
+use crate::{effects::PendingEffects, in_play::Database, player::Owner};
+
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    db: Box<Database>,
+    pending: Option<PendingEffects>,
+}
+
+impl Checkpoint {
+    /// Records the game along with the pending effects being resolved when the action started.
+    pub fn new(db: &Database, pending: &Option<PendingEffects>) -> Self {
+        Self {
+            db: Box::new(db.clone()),
+            pending: pending.clone(),
+        }
+    }
+
+    /// Whether the game can be returned to this checkpoint. Once the action has revealed hidden
+    /// information, e.g. by drawing a card or reordering a library, backing out would let the
+    /// player use what they learned, so the checkpoint can no longer be restored.
+    pub fn can_restore(&self, db: &Database) -> bool {
+        db.all_players
+            .all_players()
+            .into_iter()
+            .all(|player| same_library(&self.db, db, player))
+    }
+
+    /// Returns the game to this checkpoint, giving back the pending effects which were being
+    /// resolved when it was taken.
+    pub fn restore(self, db: &mut Database) -> Option<PendingEffects> {
+        let mut restored = *self.db;
+        mem::swap(&mut restored.clocks, &mut db.clocks);
+        mem::swap(&mut restored.notifications, &mut db.notifications);
+        mem::swap(&mut restored.trace, &mut db.trace);
+        mem::swap(&mut restored.snapshots, &mut db.snapshots);
+        *db = restored;
+
+        self.pending
+    }
+}
+
+/// Checkpoints for actions which are in progress, the most recently started last.
+#[derive(Debug, Clone, Default)]
+pub struct Checkpoints {
+    checkpoints: Vec<Checkpoint>,
+}
+
+impl Checkpoints {
+    /// Takes a checkpoint before starting an action.
+    pub fn push(&mut self, db: &Database, pending: &Option<PendingEffects>) {
+        self.checkpoints.push(Checkpoint::new(db, pending));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints.is_empty()
+    }
+
+    /// Whether the most recently started action can be backed out of.
+    pub fn can_cancel(&self, db: &Database) -> bool {
+        self.checkpoints
+            .last()
+            .is_some_and(|checkpoint| checkpoint.can_restore(db))
+    }
+
+    /// Backs out of the most recently started action, returning the pending effects from before
+    /// it was started. Returns None without changing the game if it can't be backed out of.
+    pub fn cancel(&mut self, db: &mut Database) -> Option<Option<PendingEffects>> {
+        if !self.can_cancel(db) {
+            return None;
+        }
+
+        self.checkpoints
+            .pop()
+            .map(|checkpoint| checkpoint.restore(db))
+    }
+
+    /// Forgets every checkpoint, e.g. once the actions they were taken for have finished.
+    pub fn clear(&mut self) {
+        self.checkpoints.clear();
+    }
+}
+
+/// Identifies a snapshot taken with [Database::snapshot].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SnapshotId(usize);
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Snapshots {
+    next_id: usize,
+    snapshots: HashMap<SnapshotId, Checkpoint>,
+}
+
+impl Database {
+    /// Records the game along with the pending effects being resolved, if any, so it can be
+    /// returned to later. Unlike a [Checkpoint], a snapshot can be restored after hidden
+    /// information is revealed, so frontends should only offer to restore one when the players
+    /// agree to it.
+    pub fn snapshot(&mut self, pending: &Option<PendingEffects>) -> SnapshotId {
+        // Other snapshots are left out of the copy, so they aren't copied again each time a
+        // snapshot is taken.
+        let mut snapshots = mem::take(&mut self.snapshots);
+        snapshots.next_id += 1;
+        let id = SnapshotId(snapshots.next_id);
+        snapshots
+            .snapshots
+            .insert(id, Checkpoint::new(self, pending));
+        self.snapshots = snapshots;
+
+        id
+    }
+
+    /// Returns the game to the snapshot, giving back the pending effects which were being
+    /// resolved when it was taken. The snapshot is kept, so it can be restored again. Returns None
+    /// without changing the game if the snapshot was released.
+    pub fn restore(&mut self, snapshot: SnapshotId) -> Option<Option<PendingEffects>> {
+        let checkpoint = self.snapshots.snapshots.get(&snapshot)?.clone();
+        Some(checkpoint.restore(self))
+    }
+
+    /// Forgets the snapshot, freeing the copy of the game it holds.
+    pub fn release(&mut self, snapshot: SnapshotId) {
+        self.snapshots.snapshots.remove(&snapshot);
+    }
+}
+
+fn same_library(before: &Database, after: &Database, player: Owner) -> bool {
+    before.all_players[player]
+        .library
+        .cards()
+        .eq(after.all_players[player].library.cards())
+}
@@ -0,0 +1,100 @@
+//! Optional per-player chess clocks. A player's clock runs down while they hold priority or are
+//! making a decision, and they are given their increment at the start of each of their turns.
+//! Clocks are off by default and are enabled per-player with [Clock::start]. The engine doesn't
+//! read the time itself, so the caller reports elapsed time with [Clock::elapse].
+
+use std::{collections::HashMap, time::Duration};
+
+use crate::{effects::PendingEffects, in_play::Database, player::Owner};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutPolicy {
+    /// The player keeps playing, but each decision they hold should be made with its default
+    /// choice: resolving pending effects with `None` or passing priority.
+    AutoDefault,
+    /// The player loses the game.
+    Lose,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeout {
+    pub player: Owner,
+    pub policy: TimeoutPolicy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clock {
+    pub remaining: Duration,
+    /// Time added at the start of each of the player's turns.
+    pub increment: Duration,
+    pub policy: TimeoutPolicy,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Clocks {
+    clocks: HashMap<Owner, Clock>,
+}
+
+impl Clock {
+    /// Starts a clock for the player, replacing any clock they already had.
+    pub fn start(
+        db: &mut Database,
+        player: Owner,
+        time: Duration,
+        increment: Duration,
+        policy: TimeoutPolicy,
+    ) {
+        db.clocks.clocks.insert(
+            player,
+            Clock {
+                remaining: time,
+                increment,
+                policy,
+            },
+        );
+    }
+
+    pub fn stop(db: &mut Database, player: Owner) {
+        db.clocks.clocks.remove(&player);
+    }
+
+    pub fn get(db: &Database, player: Owner) -> Option<Clock> {
+        db.clocks.clocks.get(&player).copied()
+    }
+
+    /// Charges the elapsed time to the player holding the current decision, which is the player
+    /// with priority over the pending effects if there are any, otherwise the priority player.
+    ///
+    /// Returns the timeout if that player's clock has run out. Players whose policy is
+    /// [TimeoutPolicy::Lose] lose the game immediately, and the timeout is returned for every call
+    /// made while a player with [TimeoutPolicy::AutoDefault] is out of time.
+    pub fn elapse(
+        db: &mut Database,
+        pending: Option<&PendingEffects>,
+        elapsed: Duration,
+    ) -> Option<Timeout> {
+        let player = match pending {
+            Some(pending) if !pending.is_empty() => pending.priority(db),
+            _ => db.turn.priority_player(),
+        };
+
+        let clock = db.clocks.clocks.get_mut(&player)?;
+        clock.remaining = clock.remaining.saturating_sub(elapsed);
+        if !clock.remaining.is_zero() {
+            return None;
+        }
+
+        let policy = clock.policy;
+        if policy == TimeoutPolicy::Lose {
+            db.all_players[player].lost = true;
+        }
+
+        Some(Timeout { player, policy })
+    }
+
+    pub(crate) fn new_turn(db: &mut Database, player: Owner) {
+        if let Some(clock) = db.clocks.clocks.get_mut(&player) {
+            clock.remaining += clock.increment;
+        }
+    }
+}
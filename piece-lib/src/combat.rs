@@ -0,0 +1,548 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use indexmap::IndexMap;
+use itertools::Itertools;
+
+use crate::{
+    effects::PendingEffects,
+    filter::CardFilter,
+    in_play::{CardId, Database},
+    log::LogId,
+    player::{Owner, Player},
+    protogen::{cost::ManaCost, counters::Counter, targets::Location, triggers::TriggerSource},
+    stack::Stack,
+    turns::Phase,
+};
+
+/// The most declarations [enumerate_attack_options] or [enumerate_block_options] will return.
+pub const MAX_DECLARATIONS: usize = 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AttackPreview {
+    pub attacker: CardId,
+    pub target: Owner,
+    /// The planeswalker being attacked, if the attacker isn't attacking `target` directly.
+    pub planeswalker: Option<CardId>,
+    /// The combat damage this attacker has yet to deal to the player or planeswalker it attacks this
+    /// combat, including damage trampling over its blockers.
+    pub damage: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct CombatPreview {
+    pub attacks: Vec<AttackPreview>,
+    /// The combat damage each creature will be dealt, by attackers and blockers alike.
+    pub creature_damage: IndexMap<CardId, i32>,
+    /// The creatures which will be destroyed by the combat damage, in the order they were damaged.
+    pub dying: Vec<CardId>,
+    /// The life total of each player after the remaining combat damage is dealt.
+    pub life_totals: HashMap<Owner, i32>,
+    /// Players whose life total will be 0 or less after combat damage.
+    pub losing: Vec<Owner>,
+}
+
+impl CombatPreview {
+    pub fn is_empty(&self) -> bool {
+        self.attacks.is_empty()
+    }
+}
+
+/// Predicts the outcome of the combat damage which has not yet been dealt this turn, without
+/// modifying the game. Damage is assigned the same way as when it is dealt, including to and from
+/// blockers, trample, deathtouch, and lifelink, and creatures destroyed by first strike damage
+/// don't deal or receive regular combat damage. Triggers and replacement effects are not
+/// considered.
+pub fn combat_preview(db: &Database) -> CombatPreview {
+    let steps: &[bool] = match db.turn.phase {
+        Phase::BeginCombat | Phase::DeclareAttackers | Phase::DeclareBlockers => &[true, false],
+        Phase::FirstStrike => &[false],
+        _ => &[],
+    };
+
+    let mut preview = CombatPreview {
+        attacks: db.battlefield[db.turn.active_player()]
+            .iter()
+            .copied()
+            .filter_map(|card| {
+                db[card].attacking.map(|target| AttackPreview {
+                    attacker: card,
+                    target,
+                    planeswalker: card.attacking_planeswalker(db),
+                    damage: 0,
+                })
+            })
+            .collect_vec(),
+        life_totals: db
+            .all_players
+            .all_players()
+            .into_iter()
+            .map(|player| (player, db.all_players[player].life_total))
+            .collect(),
+        ..Default::default()
+    };
+
+    let mut deathtouched = HashSet::<CardId>::default();
+    let mut dead = HashSet::<CardId>::default();
+    for first_strike_step in steps.iter().copied() {
+        let assignments = assign_damage(db, first_strike_step, &preview.creature_damage, &dead);
+        for (source, recipient, amount) in assignments {
+            let attacked = match recipient {
+                Recipient::Creature(creature) => {
+                    if creature.protected_from(db, source) {
+                        continue;
+                    }
+
+                    *preview.creature_damage.entry(creature).or_default() += amount;
+                    if source.deathtouch(db) {
+                        deathtouched.insert(creature);
+                    }
+                    false
+                }
+                Recipient::Player(player) => {
+                    *preview.life_totals.entry(player).or_default() -= amount;
+                    true
+                }
+                Recipient::Planeswalker(planeswalker) => {
+                    if planeswalker.protected_from(db, source) {
+                        continue;
+                    }
+                    true
+                }
+            };
+
+            if attacked {
+                if let Some(attack) = preview
+                    .attacks
+                    .iter_mut()
+                    .find(|attack| attack.attacker == source)
+                {
+                    attack.damage += amount;
+                }
+            }
+            if source.lifelink(db) {
+                *preview
+                    .life_totals
+                    .entry(Owner::from(db[source].controller))
+                    .or_default() += amount;
+            }
+        }
+
+        for (creature, damage) in preview.creature_damage.iter() {
+            if dead.contains(creature) || creature.indestructible(db) {
+                continue;
+            }
+
+            let lethal = creature.lethal_damage_remaining(db).unwrap_or_default();
+            if deathtouched.contains(creature) || *damage >= lethal {
+                dead.insert(*creature);
+                preview.dying.push(*creature);
+            }
+        }
+    }
+
+    preview.losing = db
+        .all_players
+        .all_players()
+        .into_iter()
+        .filter(|player| preview.life_totals[player] <= 0)
+        .collect_vec();
+
+    preview
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Recipient {
+    Creature(CardId),
+    Player(Owner),
+    Planeswalker(CardId),
+}
+
+/// Who an attacker's unblocked or trample damage is dealt to. Damage to a planeswalker which has
+/// left the battlefield isn't dealt at all.
+fn attack_recipient(db: &Database, attacker: CardId, target: Owner) -> Option<Recipient> {
+    match attacker.attacking_planeswalker(db) {
+        Some(planeswalker) if planeswalker.is_in_location(db, Location::ON_BATTLEFIELD) => {
+            Some(Recipient::Planeswalker(planeswalker))
+        }
+        Some(_) => None,
+        None => Some(Recipient::Player(target)),
+    }
+}
+
+/// Deals the combat damage of the first strike damage step if `first_strike_step` is set,
+/// otherwise of the regular combat damage step.
+///
+/// Unblocked attackers damage the player or planeswalker they attack. Blocked attackers assign
+/// lethal damage to each of their blockers in damage assignment order before moving on to the next,
+/// and any damage left over goes to the last blocker, or to the player or planeswalker if the
+/// attacker has trample. Blocked attackers whose blockers have all left combat deal no damage
+/// unless they have trample. Blockers damage the attacker they block. All of the damage is dealt at
+/// the same time.
+pub(crate) fn deal_damage(db: &mut Database, first_strike_step: bool) -> PendingEffects {
+    let assignments = assign_damage(
+        db,
+        first_strike_step,
+        &IndexMap::default(),
+        &HashSet::default(),
+    );
+
+    let mut results = PendingEffects::default();
+    for (source, recipient, amount) in assignments {
+        match recipient {
+            Recipient::Creature(creature) => {
+                creature.mark_damage(db, Some(source), amount as u32);
+            }
+            Recipient::Player(player) => {
+                db.all_players[player].life_total -= amount;
+                if source.lifelink(db) {
+                    let controller = db[source].controller;
+                    db.all_players[controller].life_total += amount;
+                    db.all_players[controller].life_gained_this_turn += amount as u32;
+                }
+
+                for (listener, trigger) in
+                    db.active_triggers_of_source(TriggerSource::DEALS_COMBAT_DAMAGE_TO_PLAYER)
+                {
+                    if source.passes_restrictions(
+                        db,
+                        LogId::current(db),
+                        listener,
+                        &trigger.trigger.restrictions,
+                    ) {
+                        results.apply_results(Stack::move_trigger_to_stack(db, listener, trigger));
+                    }
+                }
+            }
+            Recipient::Planeswalker(planeswalker) => {
+                if planeswalker.protected_from(db, source) {
+                    continue;
+                }
+
+                let loyalty = db[planeswalker]
+                    .counters
+                    .entry(Counter::LOYALTY)
+                    .or_default();
+                *loyalty = loyalty.saturating_sub(amount as u32);
+                planeswalker.apply_modifiers_layered(db);
+                if source.lifelink(db) {
+                    let controller = db[source].controller;
+                    db.all_players[controller].life_total += amount;
+                    db.all_players[controller].life_gained_this_turn += amount as u32;
+                }
+            }
+        }
+    }
+
+    results
+}
+
+/// The combat damage each creature deals in the first strike damage step if `first_strike_step`
+/// is set, otherwise in the regular combat damage step, as the source, recipient, and amount. See
+/// [deal_damage] for how it is assigned.
+///
+/// `damaged` is damage creatures will have been dealt which isn't marked on them yet, and `dead`
+/// are creatures which will have been destroyed, so they neither deal nor are dealt damage.
+fn assign_damage(
+    db: &Database,
+    first_strike_step: bool,
+    damaged: &IndexMap<CardId, i32>,
+    dead: &HashSet<CardId>,
+) -> Vec<(CardId, Recipient, i32)> {
+    let mut assignments = vec![];
+    for attacker in db.battlefield[db.turn.active_player()].iter().copied() {
+        let Some(target) = db[attacker].attacking else {
+            continue;
+        };
+        if dead.contains(&attacker) || !deals_damage_in_step(db, attacker, first_strike_step) {
+            continue;
+        }
+
+        let power = attacker.power(db).unwrap_or_default();
+        if power <= 0 {
+            continue;
+        }
+
+        if !attacker.blocked(db) {
+            if let Some(recipient) = attack_recipient(db, attacker, target) {
+                assignments.push((attacker, recipient, power));
+            }
+            continue;
+        }
+
+        let blockers = blockers(db, attacker)
+            .into_iter()
+            .filter(|blocker| !dead.contains(blocker))
+            .collect_vec();
+        let mut remaining = power;
+        for (index, blocker) in blockers.iter().copied().enumerate() {
+            let last = index + 1 == blockers.len();
+            let mut lethal = (blocker.lethal_damage_remaining(db).unwrap_or_default()
+                - damaged.get(&blocker).copied().unwrap_or_default())
+            .max(0);
+            if attacker.deathtouch(db) {
+                lethal = lethal.min(1);
+            }
+
+            let amount = if last && !attacker.trample(db) {
+                remaining
+            } else {
+                remaining.min(lethal)
+            };
+            if amount > 0 {
+                assignments.push((attacker, Recipient::Creature(blocker), amount));
+                remaining -= amount;
+            }
+        }
+
+        if remaining > 0 && attacker.trample(db) {
+            if let Some(recipient) = attack_recipient(db, attacker, target) {
+                assignments.push((attacker, recipient, remaining));
+            }
+        }
+    }
+
+    for blocker in CardFilter::on_battlefield().cards(db) {
+        let Some(attacker) = db[blocker].blocking else {
+            continue;
+        };
+        if dead.contains(&blocker)
+            || dead.contains(&attacker)
+            || attacker.attacking(db).is_none()
+            || !deals_damage_in_step(db, blocker, first_strike_step)
+        {
+            continue;
+        }
+
+        let power = blocker.power(db).unwrap_or_default();
+        if power > 0 {
+            assignments.push((blocker, Recipient::Creature(attacker), power));
+        }
+    }
+
+    assignments
+}
+
+/// The creatures still blocking `attacker`, in damage assignment order.
+pub fn blockers(db: &Database, attacker: CardId) -> Vec<CardId> {
+    db[attacker]
+        .blocked_by
+        .iter()
+        .copied()
+        .filter(|blocker| db[*blocker].blocking == Some(attacker))
+        .collect_vec()
+}
+
+/// Whether the creature deals combat damage in the first strike damage step if
+/// `first_strike_step` is set, otherwise in the regular combat damage step.
+fn deals_damage_in_step(db: &Database, card: CardId, first_strike_step: bool) -> bool {
+    if first_strike_step {
+        card.first_strike(db) || card.double_strike(db)
+    } else {
+        card.double_strike(db) || !card.first_strike(db)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct AttackDeclaration {
+    /// Each attacking creature and the player it attacks.
+    pub attacks: Vec<(CardId, Owner)>,
+    /// The attack tax owed for the declaration. Attackers whose tax goes unpaid are removed from
+    /// combat.
+    pub tax: Vec<ManaCost>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct BlockDeclaration {
+    /// Each blocking creature and the attacker it blocks.
+    pub blocks: Vec<(CardId, CardId)>,
+}
+
+/// The sets of attacks the player could declare this turn, starting with not attacking at all.
+///
+/// Creatures with the same name, power, and toughness are interchangeable, so only one of the
+/// equivalent declarations is listed, and at most [MAX_DECLARATIONS] are returned.
+pub fn enumerate_attack_options(db: &Database, player: Owner) -> Vec<AttackDeclaration> {
+    let targets = db
+        .all_players
+        .all_players()
+        .into_iter()
+        .filter(|target| *target != player)
+        .collect_vec();
+
+    enumerate_declarations(db, attack_candidates(db, player), |_| targets.clone())
+        .into_iter()
+        .map(|attacks| AttackDeclaration {
+            tax: attacks
+                .iter()
+                .flat_map(|(_, target)| Player::attack_tax(db, *target))
+                .collect_vec(),
+            attacks,
+        })
+        .collect_vec()
+}
+
+/// The sets of blocks the player could declare against the creatures attacking them, starting
+/// with not blocking at all.
+///
+/// Creatures with the same name, power, and toughness which could block the same attackers are
+/// interchangeable, so only one of the equivalent declarations is listed, and at most
+/// [MAX_DECLARATIONS] are returned.
+pub fn enumerate_block_options(db: &Database, player: Owner) -> Vec<BlockDeclaration> {
+    let attackers = CardFilter::on_battlefield()
+        .cards(db)
+        .into_iter()
+        .filter(|card| card.attacking(db) == Some(player))
+        .collect_vec();
+
+    let blockers = CardFilter::on_battlefield().controlled_by(player).cards(db);
+    enumerate_declarations(db, blockers, |blocker| {
+        attackers
+            .iter()
+            .copied()
+            .filter(|attacker| blocker.can_block(db, *attacker))
+            .collect_vec()
+    })
+    .into_iter()
+    .map(|blocks| BlockDeclaration { blocks })
+    .collect_vec()
+}
+
+/// The largest attack the player can declare which can't lose any of their creatures however the
+/// defending players block, for suggesting attacks to a player or choosing them for an AI.
+///
+/// Each attacker is checked against every creature which could block it blocking it at once, and
+/// every blocker is assumed to deal its damage even if the attacker has first strike, so some
+/// attacks which are actually safe may be left out. A creature can only block one attacker, so
+/// attacking with every safe attacker together is also safe. Triggers, combat tricks, and damage
+/// prevention aren't considered.
+pub fn safe_attacks(db: &Database, player: Owner) -> AttackDeclaration {
+    let targets = db
+        .all_players
+        .all_players()
+        .into_iter()
+        .filter(|target| *target != player)
+        .collect_vec();
+
+    let attacks = attack_candidates(db, player)
+        .into_iter()
+        .filter_map(|attacker| {
+            targets
+                .iter()
+                .copied()
+                .find(|target| survives_any_blocks(db, attacker, *target))
+                .map(|target| (attacker, target))
+        })
+        .collect_vec();
+
+    AttackDeclaration {
+        tax: attacks
+            .iter()
+            .flat_map(|(_, target)| Player::attack_tax(db, *target))
+            .collect_vec(),
+        attacks,
+    }
+}
+
+/// Whether the attacker survives combat if every creature `defender` controls which could block it
+/// does.
+fn survives_any_blocks(db: &Database, attacker: CardId, defender: Owner) -> bool {
+    if db[attacker].unblockable || attacker.indestructible(db) {
+        return true;
+    }
+
+    let blockers = CardFilter::on_battlefield()
+        .controlled_by(defender)
+        .cards(db)
+        .into_iter()
+        .filter(|blocker| blocker.can_block(db, attacker))
+        .filter(|blocker| blocker.power(db).unwrap_or_default() > 0)
+        .collect_vec();
+
+    if blockers.iter().any(|blocker| blocker.deathtouch(db)) {
+        return false;
+    }
+
+    let damage = blockers
+        .iter()
+        .map(|blocker| {
+            let power = blocker.power(db).unwrap_or_default();
+            if blocker.double_strike(db) {
+                power * 2
+            } else {
+                power
+            }
+        })
+        .sum::<i32>();
+
+    attacker
+        .lethal_damage_remaining(db)
+        .is_none_or(|remaining| damage < remaining)
+}
+
+/// The creatures the player may choose to attack with.
+pub fn attack_candidates(db: &Database, player: Owner) -> Vec<CardId> {
+    CardFilter::on_battlefield()
+        .controlled_by(player)
+        .matching(|db, card| card.can_attack(db))
+        .cards(db)
+}
+
+/// Every way of assigning each card to at most one of its choices. Cards with the same name, power,
+/// toughness, and choices are grouped so that equivalent assignments are only listed once.
+fn enumerate_declarations<T: Copy + Eq + Hash>(
+    db: &Database,
+    cards: Vec<CardId>,
+    choices: impl Fn(CardId) -> Vec<T>,
+) -> Vec<Vec<(CardId, T)>> {
+    let mut groups = IndexMap::<_, Vec<CardId>>::default();
+    for card in cards {
+        let choices = choices(card);
+        if choices.is_empty() {
+            continue;
+        }
+
+        groups
+            .entry((
+                card.name(db).clone(),
+                card.power(db),
+                card.toughness(db),
+                choices,
+            ))
+            .or_default()
+            .push(card);
+    }
+
+    if groups.is_empty() {
+        return vec![vec![]];
+    }
+
+    groups
+        .into_iter()
+        .map(|((_, _, _, choices), cards)| {
+            std::iter::once(None)
+                .chain(choices.into_iter().map(Some))
+                .combinations_with_replacement(cards.len())
+                .map(|assigned| {
+                    // Choices are sorted with not participating first, so reverse them to assign
+                    // the earlier cards first.
+                    cards
+                        .iter()
+                        .copied()
+                        .zip(assigned.into_iter().rev())
+                        .filter_map(|(card, choice)| choice.map(|choice| (card, choice)))
+                        .collect_vec()
+                })
+                .collect_vec()
+        })
+        .multi_cartesian_product()
+        .take(MAX_DECLARATIONS)
+        .map(|declaration| declaration.into_iter().flatten().collect_vec())
+        .collect_vec()
+}
@@ -0,0 +1,84 @@
+//! Rules which differ between formats, e.g. starting life totals and mulligan rules. A frontend
+//! creates players with [crate::player::AllPlayers::new] and
+//! [crate::player::AllPlayers::join], and the rest of the game reads the configuration from there.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[non_exhaustive]
+pub struct GameConfig {
+    pub starting_life: i32,
+    /// How many cards each player draws for their opening hand.
+    pub starting_hand_size: usize,
+    /// How many cards each player may keep in hand at the end of their turn.
+    pub max_hand_size: usize,
+    /// Whether the player who goes first draws a card in their first draw step.
+    pub starting_player_draws: bool,
+    /// Whether each player's first mulligan is free, i.e. they don't put a card on the bottom of
+    /// their library for it.
+    pub free_mulligan: bool,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            starting_life: 20,
+            starting_hand_size: 7,
+            max_hand_size: 7,
+            starting_player_draws: false,
+            free_mulligan: false,
+        }
+    }
+}
+
+impl GameConfig {
+    /// The rules for commander games, with 40 starting life and a free first mulligan.
+    pub fn commander() -> Self {
+        Self {
+            starting_life: 40,
+            free_mulligan: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// Adjustments to a single player's starting life and hand sizes, in the style of vanguard cards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[non_exhaustive]
+pub struct Vanguard {
+    pub life: i32,
+    pub hand_size: i32,
+}
+
+impl Vanguard {
+    pub fn new(life: i32, hand_size: i32) -> Self {
+        Self { life, hand_size }
+    }
+
+    pub(crate) fn hand_size(&self, hand_size: usize) -> usize {
+        hand_size.saturating_add_signed(self.hand_size as isize)
+    }
+}
+
+/// How a player's cards and avatar are drawn. The identifiers are opaque to the engine, which only
+/// carries them along so frontends can tell whose hidden cards are whose.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[non_exhaustive]
+pub struct Cosmetics {
+    pub sleeve: Option<String>,
+    pub card_back: Option<String>,
+    pub avatar: Option<String>,
+}
+
+impl Cosmetics {
+    pub fn new(sleeve: Option<String>, card_back: Option<String>, avatar: Option<String>) -> Self {
+        Self {
+            sleeve,
+            card_back,
+            avatar,
+        }
+    }
+}
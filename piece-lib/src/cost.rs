@@ -1,72 +1,48 @@
-use crate::protogen::{
-    color::Color,
-    cost::{CastingCost, ManaCost},
+use protobuf::EnumOrUnknown;
+
+use crate::{
+    mana::{render_symbols, Symbol},
+    protogen::{
+        color::Color,
+        cost::{AbilityCost, CastingCost, ManaCost},
+    },
 };
 
 impl CastingCost {
     pub(crate) fn colors(&self) -> Vec<Color> {
         self.mana_cost
             .iter()
-            .map(|mana| mana.enum_value().unwrap().color())
+            .flat_map(|mana| mana.enum_value().unwrap().colors())
             .collect()
     }
 
     pub fn text(&self) -> String {
-        let mut result = String::default();
+        mana_cost_text(&self.mana_cost)
+    }
 
-        let generic = self
-            .mana_cost
+    pub fn cmc(&self) -> usize {
+        self.mana_cost
             .iter()
-            .filter(|cost| matches!(cost.enum_value().unwrap(), ManaCost::GENERIC))
-            .count();
-
-        let mut pushed_generic = false;
-        for mana in self.mana_cost.iter() {
-            match mana.enum_value().unwrap() {
-                ManaCost::WHITE => result.push('\u{e600}'),
-                ManaCost::BLUE => result.push('\u{e601}'),
-                ManaCost::BLACK => result.push('\u{e602}'),
-                ManaCost::RED => result.push('\u{e603}'),
-                ManaCost::GREEN => result.push('\u{e604}'),
-                ManaCost::COLORLESS => result.push('\u{e904}'),
-                ManaCost::GENERIC => {
-                    if !pushed_generic {
-                        match generic {
-                            0 => result.push('\u{e605}'),
-                            1 => result.push('\u{e606}'),
-                            2 => result.push('\u{e607}'),
-                            3 => result.push('\u{e608}'),
-                            4 => result.push('\u{e609}'),
-                            5 => result.push('\u{e60a}'),
-                            6 => result.push('\u{e60b}'),
-                            7 => result.push('\u{e60c}'),
-                            8 => result.push('\u{e60d}'),
-                            9 => result.push('\u{e60e}'),
-                            10 => result.push('\u{e60f}'),
-                            11 => result.push('\u{e610}'),
-                            12 => result.push('\u{e611}'),
-                            13 => result.push('\u{e612}'),
-                            14 => result.push('\u{e613}'),
-                            15 => result.push('\u{e614}'),
-                            16 => result.push('\u{e62a}'),
-                            17 => result.push('\u{e62b}'),
-                            18 => result.push('\u{e62c}'),
-                            19 => result.push('\u{e62d}'),
-                            20 => result.push('\u{e62e}'),
-                            _ => result.push_str(&format!("{}", generic)),
-                        }
-                        pushed_generic = true;
-                    }
-                }
-                ManaCost::X => result.push('\u{e615}'),
-                ManaCost::TWO_X => result.push_str("\u{e615}\u{e615}"),
-            }
-        }
+            .map(|mana| mana.enum_value().unwrap().mana_value())
+            .sum()
+    }
+}
 
-        result
+impl AbilityCost {
+    pub fn text(&self) -> String {
+        mana_cost_text(&self.mana_cost)
     }
 
-    pub(crate) fn cmc(&self) -> usize {
-        self.mana_cost.len()
+    /// The symbols for the cost, including the tap symbol if the ability taps its source.
+    pub fn symbols(&self) -> Vec<Symbol> {
+        let mut symbols = Symbol::from_mana_cost(&self.mana_cost);
+        if self.tap {
+            symbols.push(Symbol::Tap);
+        }
+        symbols
     }
 }
+
+pub(crate) fn mana_cost_text(mana_cost: &[EnumOrUnknown<ManaCost>]) -> String {
+    render_symbols(Symbol::from_mana_cost(mana_cost))
+}
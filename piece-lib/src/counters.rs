@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use protobuf::Enum;
+
+use crate::{
+    effects::EffectBundle,
+    in_play::{CardId, Database},
+    protogen::{counters::Counter, keywords::Keyword},
+    stack::Stack,
+};
+
+impl Counter {
+    /// The keyword granted by a keyword counter, or None for other counters.
+    pub fn keyword(self) -> Option<Keyword> {
+        match self {
+            Counter::DEATHTOUCH => Some(Keyword::DEATHTOUCH),
+            Counter::DECAYED => Some(Keyword::DECAYED),
+            Counter::DOUBLE_STRIKE => Some(Keyword::DOUBLE_STRIKE),
+            Counter::EXALTED => Some(Keyword::EXALTED),
+            Counter::FIRST_STRIKE => Some(Keyword::FIRST_STRIKE),
+            Counter::FLYING => Some(Keyword::FLYING),
+            Counter::HASTE => Some(Keyword::HASTE),
+            Counter::HEXPROOF => Some(Keyword::HEXPROOF),
+            Counter::INDESTRUCTIBLE => Some(Keyword::INDESTRUCTIBLE),
+            Counter::LIFELINK => Some(Keyword::LIFELINK),
+            Counter::MENACE => Some(Keyword::MENACE),
+            Counter::REACH => Some(Keyword::REACH),
+            Counter::SHADOW => Some(Keyword::SHADOW),
+            Counter::TRAMPLE => Some(Keyword::TRAMPLE),
+            Counter::VIGILANCE => Some(Keyword::VIGILANCE),
+            Counter::ANY
+            | Counter::CHARGE
+            | Counter::CHORUS
+            | Counter::NET
+            | Counter::P1P1
+            | Counter::STUN
+            | Counter::TIME
+            | Counter::M1M1
+            | Counter::LEVEL
+            | Counter::LOYALTY
+            | Counter::LORE => None,
+        }
+    }
+}
+
+/// Adds the keywords granted by any keyword counters to a permanent's keywords.
+pub(crate) fn add_keyword_counters(
+    counters: &HashMap<Counter, u32>,
+    keywords: &mut HashMap<i32, u32>,
+) {
+    for (counter, count) in counters.iter() {
+        if *count == 0 {
+            continue;
+        }
+
+        if let Some(keyword) = counter.keyword() {
+            *keywords.entry(keyword.value()).or_default() += count;
+        }
+    }
+}
+
+/// Puts lore counters on a saga, triggering each of its chapter abilities whose chapter was reached
+/// by the new counters.
+pub(crate) fn add_lore_counters(db: &mut Database, saga: CardId, count: u32) -> Vec<EffectBundle> {
+    let before = saga.lore(db);
+    *db[saga].counters.entry(Counter::LORE).or_default() += count;
+    saga.apply_modifiers_layered(db);
+    let after = saga.lore(db);
+
+    let chapters = saga
+        .faceup_face(db)
+        .chapters
+        .iter()
+        .filter(|chapter| before < chapter.chapter && chapter.chapter <= after)
+        .map(|chapter| chapter.ability.get_or_default().clone())
+        .collect_vec();
+
+    chapters
+        .into_iter()
+        .flat_map(|ability| Stack::move_trigger_to_stack(db, saga, ability))
+        .collect_vec()
+}
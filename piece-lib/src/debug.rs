@@ -0,0 +1,201 @@
+//! Functions for forcing the game into a particular state. These bypass the rules entirely and are
+//! intended for reproducing complex interactions during development.
+
+use itertools::Itertools;
+
+use crate::{
+    in_play::{CardId, Database},
+    library::Library,
+    player::Owner,
+    protogen::{effects::Duration, targets::Location},
+    turns::Phase,
+    Cards,
+};
+
+/// The card added for each card in a zone whose cards weren't named, e.g. `2 cards in hand`.
+pub const FILLER: &str = "Forest";
+
+/// Creates a new copy of the named card owned by `owner` and moves it to `location`. Cards added
+/// to the library are placed on top.
+pub fn add_card(
+    db: &mut Database,
+    cards: &Cards,
+    owner: Owner,
+    name: &str,
+    location: Location,
+) -> Option<CardId> {
+    if !cards.contains_key(name) {
+        warn!("Unknown card {}", name);
+        return None;
+    }
+
+    let card = CardId::upload(db, cards, owner, name);
+    match location {
+        Location::ON_BATTLEFIELD => card.move_to_battlefield(db),
+        Location::IN_HAND => card.move_to_hand(db),
+        Location::IN_LIBRARY => Library::place_on_top(db, owner, card),
+        Location::IN_GRAVEYARD => card.move_to_graveyard(db),
+        Location::IN_EXILE => card.move_to_exile(db, card, None, Duration::PERMANENTLY),
+        Location::IN_STACK => {
+            warn!("Cannot add cards directly to the stack");
+            return None;
+        }
+    }
+
+    Some(card)
+}
+
+pub fn set_life_total(db: &mut Database, player: Owner, life_total: i32) {
+    db.all_players[player].life_total = life_total;
+}
+
+pub fn untap_all(db: &mut Database) {
+    for card in db
+        .battlefield
+        .battlefields
+        .values()
+        .flat_map(|battlefield| battlefield.iter())
+        .copied()
+        .collect_vec()
+    {
+        card.untap(db);
+    }
+}
+
+/// Moves the current turn directly to `phase` without firing any triggers or resolving the stack.
+pub fn skip_to_phase(db: &mut Database, phase: Phase) {
+    for player in db.all_players.all_players() {
+        db.all_players[player].mana_pool.drain();
+    }
+
+    db.turn.skip_to(phase);
+}
+
+/// Sets up a board from a quick plaintext description, e.g.
+/// `P1: 3 Forest (2 tapped), Alpine Grizzly; P2: 17 life, 2 cards in hand`.
+///
+/// Each player's section starts with `P<n>:`, and sections are separated by `;` or newlines. Each
+/// comma separated item in a section is one of:
+/// - `<n> life`
+/// - `<n> cards in <hand|library>`, which adds that many [FILLER] cards.
+/// - `[<n>] <card name> [(tapped)|(<m> tapped)] [in <hand|graveyard|exile|library>]`, which adds
+///   the cards to the battlefield unless another zone is given.
+///
+/// Card names are matched ignoring case. This favors speed over precision, so anything which can't
+/// be understood is skipped and described in the returned warnings.
+pub fn import_board(
+    db: &mut Database,
+    cards: &Cards,
+    players: &[Owner],
+    description: &str,
+) -> Vec<String> {
+    let mut warnings = vec![];
+    for section in description
+        .split([';', '\n'])
+        .map(str::trim)
+        .filter(|section| !section.is_empty())
+    {
+        let Some((player, items)) = section.split_once(':') else {
+            warnings.push(format!("Expected 'P<n>:' at the start of {:?}", section));
+            continue;
+        };
+
+        let Some(player) = player
+            .trim()
+            .to_ascii_lowercase()
+            .strip_prefix('p')
+            .and_then(|player| player.parse::<usize>().ok())
+            .and_then(|player| player.checked_sub(1))
+            .and_then(|player| players.get(player))
+            .copied()
+        else {
+            warnings.push(format!("Unknown player {:?}", player.trim()));
+            continue;
+        };
+
+        for item in items
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+        {
+            if let Err(warning) = import_item(db, cards, player, item) {
+                warnings.push(warning);
+            }
+        }
+    }
+
+    warnings
+}
+
+fn import_item(db: &mut Database, cards: &Cards, player: Owner, item: &str) -> Result<(), String> {
+    let lower = item.to_ascii_lowercase();
+    let (count, rest) = lower
+        .split_once(' ')
+        .and_then(|(count, rest)| Some((count.parse::<usize>().ok()?, rest.trim())))
+        .unwrap_or((1, lower.as_str()));
+
+    if rest == "life" {
+        set_life_total(db, player, count as i32);
+        return Ok(());
+    }
+
+    if let Some(zone) = rest
+        .strip_prefix("cards in ")
+        .or_else(|| rest.strip_prefix("card in "))
+    {
+        let location = parse_zone(zone)
+            .filter(|location| matches!(location, Location::IN_HAND | Location::IN_LIBRARY))
+            .ok_or_else(|| format!("Can't add unnamed cards to {:?}", zone))?;
+        for _ in 0..count {
+            add_card(db, cards, player, FILLER, location);
+        }
+        return Ok(());
+    }
+
+    // Some card names contain " in ", so it's only a zone if it names one.
+    let (rest, location) = rest
+        .rsplit_once(" in ")
+        .and_then(|(rest, zone)| Some((rest.trim(), parse_zone(zone)?)))
+        .unwrap_or((rest, Location::ON_BATTLEFIELD));
+
+    let (name, tapped) = match rest.split_once('(') {
+        Some((name, tapped)) => {
+            let tapped = tapped.trim_end_matches(')').trim();
+            let tapped = if tapped == "tapped" {
+                count
+            } else {
+                tapped
+                    .strip_suffix("tapped")
+                    .and_then(|tapped| tapped.trim().parse().ok())
+                    .ok_or_else(|| format!("Expected (tapped) or (<n> tapped) in {:?}", item))?
+            };
+            (name.trim(), tapped)
+        }
+        None => (rest, 0),
+    };
+
+    let name = cards
+        .keys()
+        .find(|card| card.eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("Unknown card {:?}", name))?;
+
+    for i in 0..count {
+        let card = add_card(db, cards, player, name, location).unwrap();
+        if i < tapped && location == Location::ON_BATTLEFIELD {
+            card.tap(db);
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_zone(zone: &str) -> Option<Location> {
+    match zone.trim() {
+        "battlefield" | "play" => Some(Location::ON_BATTLEFIELD),
+        "hand" => Some(Location::IN_HAND),
+        "graveyard" | "yard" => Some(Location::IN_GRAVEYARD),
+        "exile" => Some(Location::IN_EXILE),
+        "library" => Some(Location::IN_LIBRARY),
+        _ => None,
+    }
+}
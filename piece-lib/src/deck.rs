@@ -0,0 +1,656 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use anyhow::anyhow;
+use ariadne::{Label, Report, ReportKind, Source};
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    in_play::Database,
+    library::{DeckDefinition, Library},
+    player::Owner,
+    protogen::{
+        card::Card,
+        color::Color,
+        effects::{effect, gain_mana::Gain},
+        mana::Mana,
+        rarity::Rarity,
+        types::Type,
+    },
+    Cards,
+};
+
+/// The number of turns to compute cast probabilities for.
+pub const MAX_TURN: usize = 10;
+
+/// The number of cards in an opening hand.
+const OPENING_HAND: usize = 7;
+
+/// Spells at or below this mana value count as early plays when analyzing a hand.
+const EARLY_MANA_VALUE: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+#[non_exhaustive]
+pub struct DeckStats {
+    /// The total number of cards in the deck.
+    pub size: usize,
+    /// The number of lands in the deck.
+    pub lands: usize,
+    /// Maps mana value to the number of nonland cards with that mana value.
+    pub mana_curve: BTreeMap<usize, usize>,
+    /// The number of mana symbols of each color across all nonland cards in the deck.
+    pub color_requirements: BTreeMap<Mana, usize>,
+    /// The number of lands which can produce each color of mana.
+    pub color_sources: BTreeMap<Mana, usize>,
+    /// The number of cards of each type. Cards with multiple types are counted once per type.
+    pub type_distribution: BTreeMap<Type, usize>,
+    /// Cast probabilities for each distinct nonland card in the deck, sorted by mana value.
+    pub cast_probabilities: Vec<CastProbability>,
+    /// Card names which were in the deck definition but not in the card database.
+    pub unknown_cards: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct CastProbability {
+    pub name: String,
+    pub mana_value: usize,
+    /// The probability of having enough lands of the right colors to cast the card by turn
+    /// `idx + 1` while on the play. Drawing the card itself is not considered.
+    pub by_turn: [f64; MAX_TURN],
+}
+
+/// The result of [analyze_hand].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct HandAnalysis {
+    /// The number of lands in the hand.
+    pub lands: usize,
+    /// The number of nonland cards with mana value 3 or less.
+    pub early_plays: usize,
+    /// Colors required by nonland cards in the hand which no land in the hand can produce.
+    pub missing_colors: Vec<Mana>,
+    /// A score from 0 to 1, higher is better.
+    pub score: f64,
+    /// Whether the hand should be kept.
+    pub keep: bool,
+}
+
+/// Constraints for [random_deck].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct DeckConstraints {
+    /// The total number of cards in the deck.
+    pub size: usize,
+    /// The number of basic lands in the deck.
+    pub lands: usize,
+    /// The minimum number of creatures in the deck.
+    pub creatures: usize,
+    /// The colors nonland cards may be. Colorless cards are always allowed. Any color is allowed
+    /// if this is empty.
+    pub colors: HashSet<Color>,
+    /// The maximum number of nonland cards with each mana value. Mana values which are missing
+    /// are unrestricted.
+    pub curve: BTreeMap<usize, usize>,
+    /// The maximum number of copies of each nonland card.
+    pub max_copies: usize,
+}
+
+impl Default for DeckConstraints {
+    fn default() -> Self {
+        Self {
+            size: 40,
+            lands: 17,
+            creatures: 15,
+            colors: HashSet::default(),
+            curve: BTreeMap::default(),
+            max_copies: 4,
+        }
+    }
+}
+
+/// Builds a random deck from the loaded cards. Nonland cards are chosen uniformly from the cards
+/// matching the constraints, and basic lands are split according to the colored mana symbols of
+/// the chosen cards.
+pub fn random_deck(
+    cards: &Cards,
+    constraints: &DeckConstraints,
+    rng: &mut impl Rng,
+) -> anyhow::Result<DeckDefinition> {
+    let Some(spells) = constraints.size.checked_sub(constraints.lands) else {
+        anyhow::bail!(
+            "A deck of {} cards cannot contain {} lands",
+            constraints.size,
+            constraints.lands
+        );
+    };
+    if constraints.creatures > spells {
+        anyhow::bail!(
+            "A deck with {} nonland cards cannot contain {} creatures",
+            spells,
+            constraints.creatures
+        );
+    }
+
+    let candidates = cards
+        .iter()
+        .filter(|(_, card)| !is_land(card))
+        .filter(|(_, card)| {
+            constraints.colors.is_empty()
+                || card
+                    .color_identity()
+                    .into_iter()
+                    .all(|color| constraints.colors.contains(&color))
+        })
+        .collect_vec();
+
+    let mut counts = HashMap::<&str, usize>::default();
+    let mut curve = BTreeMap::<usize, usize>::default();
+    let mut creatures = 0;
+    for slot in 0..spells {
+        let need_creature = constraints.creatures.saturating_sub(creatures) >= spells - slot;
+        let options = candidates
+            .iter()
+            .filter(|(name, card)| {
+                counts.get(name.as_str()).copied().unwrap_or_default() < constraints.max_copies
+                    && constraints.curve.get(&card.cost.cmc()).is_none_or(|max| {
+                        curve.get(&card.cost.cmc()).copied().unwrap_or_default() < *max
+                    })
+                    && (!need_creature || is_creature(card))
+            })
+            .collect_vec();
+
+        let Some((name, card)) = options.choose(rng) else {
+            anyhow::bail!("Not enough cards match the deck constraints");
+        };
+
+        *counts.entry(name.as_str()).or_default() += 1;
+        *curve.entry(card.cost.cmc()).or_default() += 1;
+        if is_creature(card) {
+            creatures += 1;
+        }
+    }
+
+    let mut pips = BTreeMap::<Mana, usize>::default();
+    for (name, count) in counts.iter() {
+        for (mana, card_pips) in self::pips(&cards[*name]) {
+            if mana != Mana::COLORLESS {
+                *pips.entry(mana).or_default() += card_pips * count;
+            }
+        }
+    }
+    if pips.is_empty() {
+        let basics = BASICS
+            .iter()
+            .filter(|(_, color, _)| {
+                constraints.colors.is_empty() || constraints.colors.contains(color)
+            })
+            .collect_vec();
+        if let Some((mana, _, _)) = basics.choose(rng) {
+            pips.insert(*mana, 1);
+        }
+    }
+
+    let mut def = DeckDefinition::default();
+    for (name, count) in counts {
+        def.add_card(name.to_string(), count);
+    }
+    for (mana, count) in split_lands(constraints.lands, &pips) {
+        let (_, _, basic) = BASICS.iter().find(|(basic, _, _)| *basic == mana).unwrap();
+        def.add_card(basic.to_string(), count);
+    }
+
+    Ok(def)
+}
+
+/// Builds a sealed pool of `size` random cards, excluding basic lands. Cards are opened in
+/// boosters of ten commons, three uncommons, and a rare which is a mythic in one of every eight
+/// boosters. A slot whose rarity has no cards is filled from every card.
+pub fn sealed_pool(cards: &Cards, size: usize, rng: &mut impl Rng) -> DeckDefinition {
+    let candidates = cards
+        .iter()
+        .filter(|(_, card)| {
+            !card
+                .typeline
+                .types
+                .iter()
+                .any(|ty| ty.enum_value().unwrap() == Type::BASIC)
+        })
+        .map(|(name, card)| (name, rarity(card)))
+        .collect_vec();
+    let by_rarity = candidates
+        .iter()
+        .map(|(name, rarity)| (*rarity, *name))
+        .into_group_map();
+    let all = candidates.iter().map(|(name, _)| *name).collect_vec();
+
+    let mut counts = BTreeMap::<&String, usize>::default();
+    let mut slots = BOOSTER.iter().cycle();
+    for _ in 0..size {
+        let mut rarity = *slots.next().unwrap();
+        if rarity == Rarity::RARE && rng.gen_ratio(1, MYTHIC_ODDS) {
+            rarity = Rarity::MYTHIC;
+        }
+
+        let pool = by_rarity.get(&rarity).unwrap_or(&all);
+        if let Some(name) = pool.choose(rng) {
+            *counts.entry(*name).or_default() += 1;
+        }
+    }
+
+    let mut def = DeckDefinition::default();
+    for (name, count) in counts {
+        def.add_card(name.clone(), count);
+    }
+
+    def
+}
+
+/// Cards without a rarity are opened as commons.
+fn rarity(card: &Card) -> Rarity {
+    card.rarity
+        .map(|rarity| rarity.enum_value().unwrap())
+        .unwrap_or(Rarity::COMMON)
+}
+
+/// Parses a decklist in the standard text format, with one `<count> <name>` entry per line, e.g.
+/// `4 Forest` or `4x Forest`. Set codes and collector numbers after the name, comments starting
+/// with `//` or `#`, and headings like `Deck` are ignored, as is everything after the `Sideboard`
+/// heading. Entries for the same card are added together.
+///
+/// Every unknown card and malformed line is printed in a single report, with `source` naming the
+/// decklist, before an error listing them is returned.
+pub fn parse_decklist(
+    source: &str,
+    contents: &str,
+    cards: &Cards,
+) -> anyhow::Result<DeckDefinition> {
+    let span = |text: &str| {
+        let start = text.as_ptr() as usize - contents.as_ptr() as usize;
+        start..start + text.len()
+    };
+
+    let mut counts = BTreeMap::<&str, usize>::default();
+    let mut problems = vec![];
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with("//") || line.starts_with('#') {
+            continue;
+        }
+
+        let heading = line.trim_end_matches(':').to_lowercase();
+        if SIDEBOARD_HEADINGS.contains(&heading.as_str()) {
+            break;
+        } else if DECK_HEADINGS.contains(&heading.as_str()) {
+            continue;
+        }
+
+        let Some((count, name)) = line.split_once(' ').and_then(|(count, name)| {
+            count
+                .trim_end_matches(['x', 'X'])
+                .parse::<usize>()
+                .ok()
+                .map(|count| (count, name))
+        }) else {
+            problems.push((
+                span(line),
+                format!("Expected '<count> <name>', got: {}", line),
+            ));
+            continue;
+        };
+
+        // Set codes and collector numbers, e.g. `Forest (M21) 274`.
+        let name = name
+            .split_once(" (")
+            .map(|(name, _)| name)
+            .unwrap_or(name)
+            .trim();
+        if !cards.contains_key(name) {
+            problems.push((span(name), format!("Unknown card: {}", name)));
+            continue;
+        }
+
+        *counts.entry(name).or_default() += count;
+    }
+
+    if let Some((first, _)) = problems.first() {
+        Report::build(ReportKind::Error, source, first.start)
+            .with_message(format!("Invalid decklist {}", source))
+            .with_labels(
+                problems.iter().map(|(span, message)| {
+                    Label::new((source, span.clone())).with_message(message)
+                }),
+            )
+            .finish()
+            .eprint((source, Source::from(contents)))
+            .unwrap();
+
+        return Err(anyhow!(
+            "Invalid decklist {}:\n{}",
+            source,
+            problems.iter().map(|(_, message)| message).join("\n")
+        ));
+    }
+
+    let mut def = DeckDefinition::default();
+    for (name, count) in counts {
+        def.add_card(name.to_string(), count);
+    }
+
+    Ok(def)
+}
+
+/// Parses a decklist with [parse_decklist] and builds a shuffled library for `player` from it.
+pub fn import_library(
+    db: &mut Database,
+    cards: &Cards,
+    player: Owner,
+    source: &str,
+    contents: &str,
+) -> anyhow::Result<Library> {
+    let def = parse_decklist(source, contents, cards)?;
+    let mut library = def.build_deck(db, cards, player);
+    library.shuffle();
+
+    Ok(library)
+}
+
+/// The rarity of each slot in a booster, excluding its basic land.
+const BOOSTER: [Rarity; 14] = [
+    Rarity::COMMON,
+    Rarity::COMMON,
+    Rarity::COMMON,
+    Rarity::COMMON,
+    Rarity::COMMON,
+    Rarity::COMMON,
+    Rarity::COMMON,
+    Rarity::COMMON,
+    Rarity::COMMON,
+    Rarity::COMMON,
+    Rarity::UNCOMMON,
+    Rarity::UNCOMMON,
+    Rarity::UNCOMMON,
+    Rarity::RARE,
+];
+
+/// One in this many boosters has a mythic in place of its rare.
+const MYTHIC_ODDS: u32 = 8;
+
+/// Decklist headings for the cards in the deck.
+const DECK_HEADINGS: [&str; 4] = ["deck", "main", "maindeck", "mainboard"];
+
+/// Decklist headings for cards which aren't in the deck. Everything after them is ignored.
+const SIDEBOARD_HEADINGS: [&str; 2] = ["sideboard", "maybeboard"];
+
+const BASICS: [(Mana, Color, &str); 5] = [
+    (Mana::WHITE, Color::WHITE, "Plains"),
+    (Mana::BLUE, Color::BLUE, "Island"),
+    (Mana::BLACK, Color::BLACK, "Swamp"),
+    (Mana::RED, Color::RED, "Mountain"),
+    (Mana::GREEN, Color::GREEN, "Forest"),
+];
+
+/// Splits `lands` proportionally to `pips`, giving any leftover lands to the colors with the
+/// largest remainders.
+pub(crate) fn split_lands(lands: usize, pips: &BTreeMap<Mana, usize>) -> BTreeMap<Mana, usize> {
+    let total = pips.values().sum::<usize>();
+    if total == 0 {
+        return BTreeMap::default();
+    }
+
+    let mut split = pips
+        .iter()
+        .map(|(mana, pips)| (*mana, lands * pips / total))
+        .collect::<BTreeMap<_, _>>();
+    let remaining = lands - split.values().sum::<usize>();
+    for (mana, _) in pips
+        .iter()
+        .sorted_by_key(|(_, pips)| std::cmp::Reverse((lands * *pips) % total))
+        .take(remaining)
+    {
+        *split.get_mut(mana).unwrap() += 1;
+    }
+
+    // Colors with too few pips to earn a land get no entry, rather than zero copies of a basic.
+    split.retain(|_, count| *count > 0);
+    split
+}
+
+pub fn stats(deck: &DeckDefinition, cards: &Cards) -> DeckStats {
+    let mut stats = DeckStats::default();
+
+    let mut known = vec![];
+    for (name, count) in deck.cards().sorted() {
+        let Some(card) = cards.get(name) else {
+            stats.unknown_cards.push(name.to_string());
+            continue;
+        };
+
+        stats.size += count;
+        for ty in card.typeline.types.iter() {
+            *stats
+                .type_distribution
+                .entry(ty.enum_value().unwrap())
+                .or_default() += count;
+        }
+
+        if is_land(card) {
+            stats.lands += count;
+            for mana in produces(card) {
+                *stats.color_sources.entry(mana).or_default() += count;
+            }
+        } else {
+            *stats.mana_curve.entry(card.cost.cmc()).or_default() += count;
+            for (mana, pips) in pips(card) {
+                *stats.color_requirements.entry(mana).or_default() += pips * count;
+            }
+            known.push((name, card));
+        }
+    }
+
+    for (name, card) in known {
+        let mana_value = card.cost.cmc();
+        let requirements = pips(card);
+
+        let mut by_turn = [0.0; MAX_TURN];
+        for (idx, probability) in by_turn.iter_mut().enumerate() {
+            let turn = idx + 1;
+            if turn < mana_value {
+                continue;
+            }
+
+            let seen = OPENING_HAND + turn - 1;
+            *probability = requirements.iter().fold(
+                at_least(stats.size, stats.lands, seen, mana_value),
+                |probability, (mana, pips)| {
+                    probability
+                        * at_least(
+                            stats.size,
+                            stats.color_sources.get(mana).copied().unwrap_or_default(),
+                            seen,
+                            *pips,
+                        )
+                },
+            );
+        }
+
+        stats.cast_probabilities.push(CastProbability {
+            name: name.to_string(),
+            mana_value,
+            by_turn,
+        });
+    }
+
+    stats
+        .cast_probabilities
+        .sort_by(|l, r| l.mana_value.cmp(&r.mana_value).then(l.name.cmp(&r.name)));
+
+    stats
+}
+
+/// Scores an opening hand drawn from a deck with the given stats, weighing the number of lands
+/// against what the deck is expected to provide, how many cheap spells the hand has, and whether
+/// the lands in hand can produce the colors the spells need.
+pub fn analyze_hand(hand: &[&Card], stats: &DeckStats) -> HandAnalysis {
+    let (lands, spells): (Vec<_>, Vec<_>) = hand.iter().copied().partition(|card| is_land(card));
+    let sources = lands
+        .iter()
+        .flat_map(|card| produces(card))
+        .collect::<HashSet<_>>();
+
+    let expected = if stats.size == 0 {
+        0.0
+    } else {
+        hand.len() as f64 * stats.lands as f64 / stats.size as f64
+    };
+    let land_score = 1.0 - ((lands.len() as f64 - expected).abs() / 3.0).min(1.0);
+
+    let early_plays = spells
+        .iter()
+        .filter(|card| card.cost.cmc() <= EARLY_MANA_VALUE)
+        .count();
+    let curve_score = early_plays.min(2) as f64 / 2.0;
+
+    let missing_colors = spells
+        .iter()
+        .flat_map(|card| pips(card).into_keys())
+        .filter(|mana| !sources.contains(mana))
+        .unique()
+        .sorted()
+        .collect_vec();
+    let color_score = if spells.is_empty() {
+        0.0
+    } else {
+        spells
+            .iter()
+            .filter(|card| pips(card).keys().all(|mana| sources.contains(mana)))
+            .count() as f64
+            / spells.len() as f64
+    };
+
+    let score = 0.5 * land_score + 0.25 * curve_score + 0.25 * color_score;
+    HandAnalysis {
+        lands: lands.len(),
+        early_plays,
+        missing_colors,
+        score,
+        keep: (2..=5).contains(&lands.len()) && score >= 0.5,
+    }
+}
+
+/// Chooses `count` cards to put on the bottom of the library after a mulligan, returning their
+/// indices into `hand`. Cards are removed one at a time, each time removing whichever card leaves
+/// the best scoring hand.
+pub fn choose_bottom(hand: &[&Card], stats: &DeckStats, count: usize) -> Vec<usize> {
+    let mut remaining = (0..hand.len()).collect_vec();
+    let mut bottom = vec![];
+    for _ in 0..count.min(hand.len()) {
+        let (position, _) = remaining
+            .iter()
+            .enumerate()
+            .map(|(position, _)| {
+                let rest = remaining
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, _)| *other != position)
+                    .map(|(_, idx)| hand[*idx])
+                    .collect_vec();
+                (position, analyze_hand(&rest, stats).score)
+            })
+            .max_by(|(_, l), (_, r)| l.total_cmp(r))
+            .unwrap();
+
+        bottom.push(remaining.remove(position));
+    }
+
+    bottom
+}
+
+fn is_land(card: &Card) -> bool {
+    card.typeline
+        .types
+        .iter()
+        .any(|ty| ty.enum_value().unwrap() == Type::LAND)
+}
+
+pub(crate) fn is_creature(card: &Card) -> bool {
+    card.typeline
+        .types
+        .iter()
+        .any(|ty| ty.enum_value().unwrap() == Type::CREATURE)
+}
+
+fn produces(card: &Card) -> Vec<Mana> {
+    card.mana_abilities
+        .iter()
+        .flat_map(|ability| ability.effects.iter())
+        .filter_map(|effect| match effect.effect.as_ref() {
+            Some(effect::Effect::GainMana(gain)) => gain.gain.as_ref(),
+            _ => None,
+        })
+        .flat_map(|gain| match gain {
+            Gain::Specific(specific) => specific
+                .gain
+                .iter()
+                .map(|mana| mana.enum_value().unwrap())
+                .collect_vec(),
+            Gain::Choice(choice) => choice
+                .choices
+                .iter()
+                .flat_map(|choice| choice.gains.iter())
+                .map(|mana| mana.enum_value().unwrap())
+                .collect_vec(),
+        })
+        .chain(
+            card.typeline
+                .subtypes
+                .iter()
+                .filter_map(|subtype| subtype.enum_value().unwrap().basic_land_mana()),
+        )
+        .unique()
+        .collect_vec()
+}
+
+fn pips(card: &Card) -> BTreeMap<Mana, usize> {
+    let mut pips = BTreeMap::<Mana, usize>::default();
+    for mana in card
+        .cost
+        .mana_cost
+        .iter()
+        .filter_map(|cost| cost.enum_value().unwrap().mana())
+    {
+        *pips.entry(mana).or_default() += 1;
+    }
+
+    pips
+}
+
+/// The hypergeometric probability of drawing at least `wanted` of `successes` cards when drawing
+/// `draws` cards from a deck of `population` cards.
+pub(crate) fn at_least(population: usize, successes: usize, draws: usize, wanted: usize) -> f64 {
+    if wanted == 0 {
+        return 1.0;
+    }
+
+    let draws = draws.min(population);
+    if successes < wanted || draws < wanted {
+        return 0.0;
+    }
+
+    let total = choose(population, draws);
+    let below = (0..wanted)
+        .map(|hits| {
+            choose(successes, hits) * choose(population - successes, draws.saturating_sub(hits))
+        })
+        .sum::<f64>();
+
+    (1.0 - below / total).clamp(0.0, 1.0)
+}
+
+fn choose(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+
+    let k = k.min(n - k);
+    (0..k).fold(1.0, |result, i| result * (n - i) as f64 / (i + 1) as f64)
+}
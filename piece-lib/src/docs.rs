@@ -0,0 +1,99 @@
+//! Documentation for the effects the engine supports, generated from the proto definitions so that
+//! it never falls out of date with what card files can express.
+
+use convert_case::{Case, Casing};
+use protobuf::{
+    reflect::{FieldDescriptor, RuntimeFieldType, RuntimeType},
+    MessageFull,
+};
+
+use crate::protogen::{comment, effects::Effect};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct EffectDoc {
+    /// The tag used to select this effect in card files, e.g. `!AddCounters`.
+    pub tag: String,
+    /// The name of the proto message describing the effect.
+    pub message: String,
+    pub description: Option<String>,
+    pub fields: Vec<FieldDoc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FieldDoc {
+    pub name: String,
+    /// A human-readable name for the type of the field, e.g. `Count`, `repeated Restriction`.
+    pub ty: String,
+    pub description: Option<String>,
+}
+
+/// Enumerates every effect which can be used in a card definition, sorted by tag.
+pub fn effects() -> Vec<EffectDoc> {
+    let descriptor = Effect::descriptor();
+    let oneof = descriptor.oneof_by_name("effect").unwrap();
+
+    let mut effects = oneof
+        .fields()
+        .filter(|field| !hidden(field))
+        .map(|field| {
+            let message = match field.singular_runtime_type() {
+                RuntimeType::Message(message) => message,
+                ty => unreachable!("Effect {} has non-message type {}", field.name(), ty),
+            };
+
+            EffectDoc {
+                tag: field.name().to_case(Case::Pascal),
+                message: message.name().to_string(),
+                description: description(&field),
+                fields: message
+                    .fields()
+                    .filter(|field| !hidden(field))
+                    .map(|field| FieldDoc {
+                        name: field.name().to_string(),
+                        ty: type_name(&field),
+                        description: description(&field),
+                    })
+                    .collect(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    effects.sort_by(|l, r| l.tag.cmp(&r.tag));
+    effects
+}
+
+fn hidden(field: &FieldDescriptor) -> bool {
+    comment::exts::hidden
+        .get(field.proto().options.get_or_default())
+        .unwrap_or_default()
+}
+
+fn description(field: &FieldDescriptor) -> Option<String> {
+    field
+        .proto()
+        .options
+        .as_ref()
+        .and_then(|options| comment::exts::comment.get(options))
+}
+
+fn type_name(field: &FieldDescriptor) -> String {
+    match field.runtime_field_type() {
+        RuntimeFieldType::Singular(ty) => runtime_type_name(&ty),
+        RuntimeFieldType::Repeated(ty) => format!("repeated {}", runtime_type_name(&ty)),
+        RuntimeFieldType::Map(key, value) => format!(
+            "map<{}, {}>",
+            runtime_type_name(&key),
+            runtime_type_name(&value)
+        ),
+    }
+}
+
+fn runtime_type_name(ty: &RuntimeType) -> String {
+    match ty {
+        RuntimeType::Enum(descriptor) => descriptor.name().to_string(),
+        RuntimeType::Message(descriptor) => descriptor.name().to_string(),
+        ty => ty.to_string(),
+    }
+}
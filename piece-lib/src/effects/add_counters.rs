@@ -1,7 +1,8 @@
 use crate::{
+    counters::add_lore_counters,
     effects::{EffectBehaviors, EffectBundle, SelectedStack},
     in_play::{CardId, Database},
-    protogen::effects::AddCounters,
+    protogen::{counters::Counter, effects::AddCounters},
 };
 
 impl EffectBehaviors for AddCounters {
@@ -12,17 +13,22 @@ impl EffectBehaviors for AddCounters {
         selected: &mut SelectedStack,
         _skip_replacement: bool,
     ) -> Vec<EffectBundle> {
+        let counter = self.counter.enum_value().unwrap();
+        let mut results = vec![];
         for target in selected.iter() {
             if let Some(id) = target.id(db) {
-                *db[id]
-                    .counters
-                    .entry(self.counter.enum_value().unwrap())
-                    .or_default() += self.count.count(db, source, selected) as u32;
+                let count = self.count.count(db, source, selected) as u32;
+                if counter == Counter::LORE {
+                    results.extend(add_lore_counters(db, id, count));
+                } else {
+                    *db[id].counters.entry(counter).or_default() += count;
+                    id.apply_modifiers_layered(db);
+                }
             } else {
                 todo!("Handle counters on players");
             }
         }
 
-        vec![]
+        results
     }
 }
@@ -0,0 +1,168 @@
+use convert_case::{Case, Casing};
+use itertools::Itertools;
+
+use crate::{
+    effects::{controller, EffectBehaviors, EffectBundle, Options, SelectedStack, SelectionResult},
+    in_play::{CardId, Database},
+    protogen::{
+        color::Color,
+        counters::Counter,
+        effects::{
+            create_token::{self, Token},
+            AddCounters, Amass, CreateToken, Effect,
+        },
+        targets::Location,
+        types::{Subtype, Type, Typeline},
+    },
+    stack::{Selected, TargetType},
+    types::{SubtypeSet, TypeSet},
+};
+
+impl EffectBehaviors for Amass {
+    fn description(
+        &self,
+        _db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> String {
+        "Select an Army to put counters on".to_string()
+    }
+
+    fn wants_input(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> bool {
+        self.army.is_none() && armies(db, source).len() > 1
+    }
+
+    fn options(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> Options {
+        Options::MandatoryList(
+            armies(db, source)
+                .into_iter()
+                .map(|card| card.name(db).clone())
+                .enumerate()
+                .collect_vec(),
+        )
+    }
+
+    fn select(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        option: Option<usize>,
+        _selected: &mut SelectedStack,
+    ) -> SelectionResult {
+        if let Some(option) = option {
+            let card = armies(db, source)[option];
+            self.army = protobuf::MessageField::some(card.into());
+            SelectionResult::Complete
+        } else {
+            SelectionResult::PendingChoice
+        }
+    }
+
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        _selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        let army = if let Some(army) = self.army.take() {
+            Some(CardId::from(army))
+        } else {
+            armies(db, source).into_iter().exactly_one().ok()
+        };
+
+        let add_counters: Effect = AddCounters {
+            counter: Counter::P1P1.into(),
+            count: self.count.clone(),
+            ..Default::default()
+        }
+        .into();
+
+        if let Some(army) = army {
+            vec![EffectBundle {
+                push_on_enter: Some(vec![Selected {
+                    location: Some(Location::ON_BATTLEFIELD),
+                    target_type: TargetType::Card(army),
+                    targeted: false,
+                    restrictions: vec![],
+                }]),
+                source,
+                effects: vec![add_counters],
+                ..Default::default()
+            }]
+        } else {
+            // Creating the token selects it, so the counters are put on the new Army.
+            vec![EffectBundle {
+                push_on_enter: Some(vec![Selected {
+                    location: None,
+                    target_type: TargetType::Player(controller(db, source)),
+                    targeted: false,
+                    restrictions: vec![],
+                }]),
+                source,
+                effects: vec![
+                    CreateToken {
+                        token: Some(Token::Creature(self.token())),
+                        ..Default::default()
+                    }
+                    .into(),
+                    add_counters,
+                ],
+                ..Default::default()
+            }]
+        }
+    }
+}
+
+impl Amass {
+    fn token(&self) -> create_token::Creature {
+        let subtype = self.subtype.enum_value().unwrap();
+        let name = if subtype == Subtype::ARMY {
+            "Army".to_string()
+        } else {
+            format!("{} Army", subtype.as_ref().to_case(Case::Title))
+        };
+
+        create_token::Creature {
+            name,
+            typeline: protobuf::MessageField::some(Typeline {
+                types: vec![Type::CREATURE.into()],
+                subtypes: [subtype, Subtype::ARMY]
+                    .into_iter()
+                    .unique()
+                    .map(Into::into)
+                    .collect_vec(),
+                ..Default::default()
+            }),
+            colors: vec![Color::BLACK.into()],
+            power: 0,
+            toughness: 0,
+            ..Default::default()
+        }
+    }
+}
+
+/// The Armies the source's controller controls.
+fn armies(db: &Database, source: Option<CardId>) -> Vec<CardId> {
+    db.battlefield[controller(db, source)]
+        .iter()
+        .copied()
+        .filter(|card| {
+            card.types_intersect(db, &TypeSet::from([Type::CREATURE]))
+                && card.subtypes_intersect(db, &SubtypeSet::from([Subtype::ARMY]))
+        })
+        .collect_vec()
+}
@@ -32,7 +32,11 @@ impl EffectBehaviors for Cascade {
                 Duration::PERMANENTLY,
             );
 
-            if !card.is_land(db) && card.faceup_face(db).cost.cmc() < mana_value {
+            if !card.is_land(db)
+                && card
+                    .characteristics(db, Location::IN_EXILE)
+                    .is_some_and(|exiled| exiled.mana_value < mana_value)
+            {
                 casting.push(Selected {
                     location: Some(Location::IN_EXILE),
                     target_type: TargetType::Card(card),
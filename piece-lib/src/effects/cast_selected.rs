@@ -1,7 +1,7 @@
 use crate::{
     effects::{EffectBehaviors, EffectBundle, SelectedStack},
     in_play::{CardId, Database},
-    protogen::effects::CastSelected,
+    protogen::{effects::CastSelected, targets::Location},
     stack::Stack,
 };
 
@@ -16,7 +16,12 @@ impl EffectBehaviors for CastSelected {
         let mut results = vec![];
         for target in selected.iter() {
             let card = target.id(db).unwrap();
-            results.push(Stack::prepare_card_for_stack(db, card, self.pay_costs));
+            results.push(Stack::prepare_card_for_stack(
+                db,
+                card,
+                Location::IN_HAND,
+                self.pay_costs,
+            ));
         }
 
         results
@@ -3,8 +3,15 @@ use itertools::Itertools;
 use crate::{
     effects::{EffectBehaviors, EffectBundle, Options, SelectedStack, SelectionResult},
     in_play::{CardId, Database},
+    player::{Owner, Player},
     protogen::{
-        effects::{ChooseAttackers, DeclareAttacking, PopSelected},
+        effects::{
+            choose_attackers::{target, Target},
+            pay_cost::PayMana,
+            pay_costs::OrElse,
+            ChooseAttackers, DeclareAttacking, PayCost, PayCosts, PopSelected,
+        },
+        mana::{spend_reason::Reason, SpendReason},
         targets::Location,
     },
     stack::{Selected, TargetType},
@@ -26,9 +33,11 @@ impl EffectBehaviors for ChooseAttackers {
         db: &Database,
         _source: Option<CardId>,
         already_selected: &[Selected],
-        _modes: &[usize],
+        modes: &[usize],
     ) -> Options {
-        if self.attackers.len() == self.targets.len() {
+        if let Some(tax) = self.tax.as_ref() {
+            tax.options(db, self.taxed_attackers(db).next(), already_selected, modes)
+        } else if self.attackers.len() == self.targets.len() {
             Options::OptionalList(
                 self.valid_attackers(db, already_selected)
                     .map(|card| card.name(db).clone())
@@ -37,16 +46,40 @@ impl EffectBehaviors for ChooseAttackers {
             )
         } else {
             Options::MandatoryList(
-                already_selected
-                    .iter()
-                    .filter_map(|selected| selected.player())
-                    .map(|player| db.all_players[player].name.clone())
+                valid_targets(db, already_selected)
+                    .map(|selected| match selected.target_type {
+                        TargetType::Player(player) => db.all_players[player].name.clone(),
+                        _ => selected.id(db).unwrap().name(db).clone(),
+                    })
                     .enumerate()
                     .collect_vec(),
             )
         }
     }
 
+    fn target_for_option(
+        &self,
+        db: &Database,
+        _source: Option<CardId>,
+        already_selected: &[Selected],
+        option: usize,
+    ) -> Option<Selected> {
+        if self.tax.is_some() {
+            None
+        } else if self.attackers.len() == self.targets.len() {
+            self.valid_attackers(db, already_selected)
+                .nth(option)
+                .map(|card| Selected {
+                    location: Some(Location::ON_BATTLEFIELD),
+                    target_type: TargetType::Card(card),
+                    targeted: false,
+                    restrictions: vec![],
+                })
+        } else {
+            valid_targets(db, already_selected).nth(option).cloned()
+        }
+    }
+
     fn select(
         &mut self,
         db: &mut Database,
@@ -54,6 +87,15 @@ impl EffectBehaviors for ChooseAttackers {
         option: Option<usize>,
         selected: &mut SelectedStack,
     ) -> SelectionResult {
+        if self.tax.is_some() {
+            let source = self.taxed_attackers(db).next();
+            return self
+                .tax
+                .as_mut()
+                .unwrap()
+                .select(db, source, option, selected);
+        }
+
         if let Some(option) = option {
             if selected.is_empty() {
                 return SelectionResult::Complete;
@@ -68,19 +110,47 @@ impl EffectBehaviors for ChooseAttackers {
 
                 self.attackers.push(attacker);
             } else {
-                self.targets.push(
-                    selected
-                        .iter()
-                        .filter_map(|selected| selected.player())
-                        .nth(option)
-                        .unwrap()
-                        .into(),
-                );
+                let target = valid_targets(db, selected).nth(option).unwrap();
+                self.targets.push(Target {
+                    target: Some(match target.target_type {
+                        TargetType::Player(player) => target::Target::Player(player.into()),
+                        _ => target::Target::Planeswalker(target.id(db).unwrap().into()),
+                    }),
+                    ..Default::default()
+                });
             }
 
             SelectionResult::PendingChoice
         } else if self.attackers.len() == self.targets.len() {
-            SelectionResult::Complete
+            let tax = self
+                .targets
+                .iter()
+                .flat_map(|target| Player::attack_tax(db, defender(db, target)))
+                .collect_vec();
+            if tax.is_empty() {
+                return SelectionResult::Complete;
+            }
+
+            self.tax = protobuf::MessageField::some(PayCosts {
+                pay_costs: vec![PayCost {
+                    cost: Some(
+                        PayMana {
+                            paying: tax.into_iter().map(protobuf::EnumOrUnknown::new).collect(),
+                            reason: protobuf::MessageField::some(SpendReason {
+                                reason: Some(Reason::Other(Default::default())),
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        }
+                        .into(),
+                    ),
+                    ..Default::default()
+                }],
+                or_else: protobuf::MessageField::some(OrElse::default()),
+                ..Default::default()
+            });
+
+            SelectionResult::PendingChoice
         } else {
             SelectionResult::PendingChoice
         }
@@ -88,26 +158,33 @@ impl EffectBehaviors for ChooseAttackers {
 
     fn apply(
         &mut self,
-        _db: &mut Database,
+        db: &mut Database,
         _source: Option<CardId>,
-        _selected: &mut SelectedStack,
-        _skip_replacement: bool,
+        selected: &mut SelectedStack,
+        skip_replacement: bool,
     ) -> Vec<EffectBundle> {
-        vec![
-            EffectBundle {
-                push_on_enter: Some(
-                    self.targets
-                        .iter()
-                        .map(|target| Selected {
-                            location: None,
-                            target_type: TargetType::Player(target.clone().into()),
-                            targeted: false,
-                            restrictions: vec![],
-                        })
-                        .collect_vec(),
-                ),
-                ..Default::default()
-            },
+        let mut tax_paid = vec![];
+        let taxed = self.taxed_attackers(db).collect_vec();
+        if let Some(tax) = self.tax.as_mut() {
+            if tax.apply_or_else {
+                // Attackers whose tax wasn't paid are removed before attacks are declared.
+                let (attackers, targets) = self
+                    .attackers
+                    .iter()
+                    .cloned()
+                    .zip(self.targets.iter().cloned())
+                    .filter(|(attacker, _)| !taxed.contains(&attacker.clone().into()))
+                    .unzip();
+                self.attackers = attackers;
+                self.targets = targets;
+            } else {
+                tax_paid = tax.apply(db, taxed.first().copied(), selected, skip_replacement);
+            }
+        }
+
+        // Bundles are applied in reverse order, so the targets are pushed before the attackers,
+        // and the tax is spent before either.
+        let mut results = vec![
             EffectBundle {
                 push_on_enter: Some(
                     self.attackers
@@ -126,7 +203,32 @@ impl EffectBehaviors for ChooseAttackers {
                 ],
                 ..Default::default()
             },
-        ]
+            EffectBundle {
+                push_on_enter: Some(
+                    self.targets
+                        .iter()
+                        .map(|target| match target.target.as_ref().unwrap() {
+                            target::Target::Player(player) => Selected {
+                                location: None,
+                                target_type: TargetType::Player(player.clone().into()),
+                                targeted: false,
+                                restrictions: vec![],
+                            },
+                            target::Target::Planeswalker(planeswalker) => Selected {
+                                location: Some(Location::ON_BATTLEFIELD),
+                                target_type: TargetType::Card(planeswalker.clone().into()),
+                                targeted: false,
+                                restrictions: vec![],
+                            },
+                        })
+                        .collect_vec(),
+                ),
+                ..Default::default()
+            },
+        ];
+        results.extend(tax_paid);
+
+        results
     }
 }
 
@@ -139,6 +241,41 @@ impl ChooseAttackers {
         selected
             .iter()
             .filter_map(|selected| selected.id(db))
+            .filter(|card| db[*card].controller == db.turn.active_player())
             .filter(|selected| !self.attackers.iter().any(|card| *selected == *card))
     }
+
+    fn taxed_attackers<'db>(&'db self, db: &'db Database) -> impl Iterator<Item = CardId> + 'db {
+        self.attackers
+            .iter()
+            .zip(self.targets.iter())
+            .filter(|(_, target)| !Player::attack_tax(db, defender(db, target)).is_empty())
+            .map(|(attacker, _)| attacker.clone().into())
+    }
+}
+
+/// The players and planeswalkers which can be attacked, which are pushed after the creatures which
+/// can attack. Planeswalkers are told apart from attackers by being controlled by another player.
+fn valid_targets<'db>(
+    db: &'db Database,
+    selected: &'db [Selected],
+) -> impl Iterator<Item = &'db Selected> + 'db {
+    selected
+        .iter()
+        .filter(|selected| match selected.target_type {
+            TargetType::Player(_) => true,
+            TargetType::Card(card) => db[card].controller != db.turn.active_player(),
+            _ => false,
+        })
+}
+
+/// The player defending against an attack on the target, who controls the planeswalker if a
+/// planeswalker is attacked.
+fn defender(db: &Database, target: &Target) -> Owner {
+    match target.target.as_ref().unwrap() {
+        target::Target::Player(player) => player.clone().into(),
+        target::Target::Planeswalker(planeswalker) => {
+            db[CardId::from(planeswalker.clone())].controller.into()
+        }
+    }
 }
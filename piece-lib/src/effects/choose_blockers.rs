@@ -0,0 +1,238 @@
+use itertools::Itertools;
+
+use crate::{
+    effects::{EffectBehaviors, EffectBundle, Options, SelectedStack, SelectionResult},
+    filter::CardFilter,
+    in_play::{CardId, Database},
+    player::Owner,
+    protogen::{
+        effects::{ChooseBlockers, OrderBlockers},
+        targets::Location,
+    },
+    stack::{Selected, TargetType},
+};
+
+impl EffectBehaviors for ChooseBlockers {
+    fn priority(
+        &self,
+        _db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> Owner {
+        self.defender.clone().unwrap().into()
+    }
+
+    fn description(
+        &self,
+        db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> String {
+        if let Some(blocker) = self.choosing_attacker() {
+            format!("Select the attacker {} blocks", blocker.name(db))
+        } else {
+            "Select blockers".to_string()
+        }
+    }
+
+    fn wants_input(
+        &self,
+        db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> bool {
+        self.choosing_attacker().is_some() || !self.valid_blockers(db).is_empty()
+    }
+
+    fn options(
+        &self,
+        db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> Options {
+        if let Some(blocker) = self.choosing_attacker() {
+            Options::MandatoryList(
+                self.blockable(db, blocker)
+                    .into_iter()
+                    .map(|attacker| attacker.name(db).clone())
+                    .enumerate()
+                    .collect_vec(),
+            )
+        } else {
+            Options::OptionalList(
+                self.valid_blockers(db)
+                    .into_iter()
+                    .map(|blocker| blocker.name(db).clone())
+                    .enumerate()
+                    .collect_vec(),
+            )
+        }
+    }
+
+    fn target_for_option(
+        &self,
+        db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        option: usize,
+    ) -> Option<Selected> {
+        let card = if let Some(blocker) = self.choosing_attacker() {
+            self.blockable(db, blocker).get(option).copied()
+        } else {
+            self.valid_blockers(db).get(option).copied()
+        };
+
+        card.map(|card| Selected {
+            location: Some(Location::ON_BATTLEFIELD),
+            target_type: TargetType::Card(card),
+            targeted: false,
+            restrictions: vec![],
+        })
+    }
+
+    fn select(
+        &mut self,
+        db: &mut Database,
+        _source: Option<CardId>,
+        option: Option<usize>,
+        _selected: &mut SelectedStack,
+    ) -> SelectionResult {
+        if let Some(blocker) = self.choosing_attacker() {
+            if let Some(attacker) =
+                option.and_then(|option| self.blockable(db, blocker).get(option).copied())
+            {
+                self.blocking.push(attacker.into());
+            }
+            SelectionResult::PendingChoice
+        } else if let Some(option) = option {
+            if let Some(blocker) = self.valid_blockers(db).get(option).copied() {
+                self.blockers.push(blocker.into());
+            }
+            SelectionResult::PendingChoice
+        } else if self.remove_lone_menace_blocks(db) {
+            // A creature with menace can't be blocked except by two or more creatures, so the
+            // declaration is rejected and the lone blockers have to be declared again.
+            SelectionResult::TryAgain
+        } else {
+            SelectionResult::Complete
+        }
+    }
+
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        _source: Option<CardId>,
+        _selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        let mut results = vec![];
+        for (blocker, attacker) in self.blocks() {
+            db[blocker].blocking = Some(attacker);
+            db[attacker].blocked_by.push(blocker);
+            db[attacker].blocked = true;
+        }
+
+        // The attacking player chooses the damage assignment order of attackers with more than
+        // one blocker.
+        for attacker in self.blocks().map(|(_, attacker)| attacker).unique() {
+            if db[attacker].blocked_by.len() > 1 {
+                results.push(EffectBundle {
+                    effects: vec![OrderBlockers {
+                        attacker: protobuf::MessageField::some(attacker.into()),
+                        ..Default::default()
+                    }
+                    .into()],
+                    ..Default::default()
+                });
+            }
+        }
+
+        results
+    }
+}
+
+impl ChooseBlockers {
+    /// The blocker whose attacker is being chosen, if one was chosen without an attacker.
+    fn choosing_attacker(&self) -> Option<CardId> {
+        if self.blockers.len() > self.blocking.len() {
+            self.blockers.last().cloned().map(CardId::from)
+        } else {
+            None
+        }
+    }
+
+    fn blocks(&self) -> impl Iterator<Item = (CardId, CardId)> + '_ {
+        self.blockers
+            .iter()
+            .cloned()
+            .map(CardId::from)
+            .zip(self.blocking.iter().cloned().map(CardId::from))
+    }
+
+    /// Removes the blocks of attackers with menace which only one creature is blocking, returning
+    /// whether there were any.
+    fn remove_lone_menace_blocks(&mut self, db: &Database) -> bool {
+        let blocks = self.blocks().collect_vec();
+        let lone = blocks
+            .iter()
+            .copied()
+            .filter(|(_, attacker)| {
+                attacker.menace(db)
+                    && blocks
+                        .iter()
+                        .filter(|(_, blocked)| blocked == attacker)
+                        .count()
+                        < 2
+            })
+            .map(|(blocker, _)| blocker)
+            .collect_vec();
+
+        let (blockers, blocking) = blocks
+            .into_iter()
+            .filter(|(blocker, _)| !lone.contains(blocker))
+            .map(|(blocker, attacker)| (blocker.into(), attacker.into()))
+            .unzip();
+        self.blockers = blockers;
+        self.blocking = blocking;
+
+        !lone.is_empty()
+    }
+
+    fn attackers(&self, db: &Database) -> Vec<CardId> {
+        let defender = Owner::from(self.defender.clone().unwrap());
+        CardFilter::on_battlefield()
+            .cards(db)
+            .into_iter()
+            .filter(|card| card.attacking(db) == Some(defender))
+            .collect_vec()
+    }
+
+    /// The attackers `blocker` could block.
+    fn blockable(&self, db: &Database, blocker: CardId) -> Vec<CardId> {
+        self.attackers(db)
+            .into_iter()
+            .filter(|attacker| blocker.can_block(db, *attacker))
+            .collect_vec()
+    }
+
+    /// The defender's creatures which haven't been declared as blockers and could block one of the
+    /// attackers.
+    fn valid_blockers(&self, db: &Database) -> Vec<CardId> {
+        let attackers = self.attackers(db);
+        CardFilter::on_battlefield()
+            .controlled_by(Owner::from(self.defender.clone().unwrap()))
+            .cards(db)
+            .into_iter()
+            .filter(|blocker| !self.blockers.iter().any(|declared| *blocker == *declared))
+            .filter(|blocker| {
+                attackers
+                    .iter()
+                    .any(|attacker| blocker.can_block(db, *attacker))
+            })
+            .collect_vec()
+    }
+}
@@ -0,0 +1,78 @@
+use itertools::Itertools;
+
+use crate::{
+    card_query::CardQuery,
+    effects::{EffectBehaviors, EffectBundle, Options, SelectedStack, SelectionResult},
+    in_play::{CardId, Database},
+    protogen::effects::ChooseCardName,
+    stack::Selected,
+};
+
+impl EffectBehaviors for ChooseCardName {
+    fn description(
+        &self,
+        _db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> String {
+        "Choose a card name".to_string()
+    }
+
+    fn wants_input(
+        &self,
+        _db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> bool {
+        true
+    }
+
+    fn options(
+        &self,
+        db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> Options {
+        Options::MandatoryList(self.matching(db).cloned().enumerate().collect_vec())
+    }
+
+    fn select(
+        &mut self,
+        db: &mut Database,
+        _source: Option<CardId>,
+        option: Option<usize>,
+        _selected: &mut SelectedStack,
+    ) -> SelectionResult {
+        if let Some(name) = option.and_then(|option| self.matching(db).nth(option)) {
+            self.chosen = name.clone();
+            SelectionResult::Complete
+        } else {
+            SelectionResult::PendingChoice
+        }
+    }
+
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        _selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        db[source.unwrap()].chosen_name = Some(self.chosen.clone());
+
+        vec![]
+    }
+}
+
+impl ChooseCardName {
+    /// The names which contain the search, ignoring case.
+    fn matching<'db>(&self, db: &'db Database) -> impl Iterator<Item = &'db String> + 'db {
+        let query = CardQuery::default().named(&self.search);
+        db.card_names
+            .iter()
+            .filter(move |name| query.matches_name(name))
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{
+    effects::{controller, handle_replacements, EffectBehaviors, EffectBundle, SelectedStack},
+    in_play::{CardId, Database},
+    log::LogId,
+    protogen::{
+        abilities::Enchant,
+        card::Card,
+        effects::{replacement_effect::Replacing, CreateRole, MoveToBattlefield},
+        targets::Location,
+        types::{Subtype, Type, Typeline},
+    },
+    stack::{Selected, TargetType},
+};
+
+impl EffectBehaviors for CreateRole {
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        selected: &mut SelectedStack,
+        skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        let owner = controller(db, source);
+        if skip_replacement {
+            // The Role is created attached to the creature, so there's nothing to create if the
+            // creature is gone.
+            if !selected
+                .first()
+                .and_then(|target| target.id(db))
+                .is_some_and(|target| target.is_in_location(db, Location::ON_BATTLEFIELD))
+            {
+                return vec![];
+            }
+
+            let card = CardId::upload_card_or_token(db, owner, self.token(), true);
+
+            // Moving the token to the battlefield attaches it to the currently selected creature.
+            vec![EffectBundle {
+                push_on_enter: Some(vec![Selected {
+                    location: None,
+                    target_type: TargetType::Card(card),
+                    targeted: false,
+                    restrictions: vec![],
+                }]),
+                source,
+                effects: vec![MoveToBattlefield::default().into()],
+                ..Default::default()
+            }]
+        } else {
+            handle_replacements(
+                db,
+                source,
+                Replacing::TOKEN_CREATION,
+                self.clone(),
+                |source, restrictions| {
+                    owner.passes_restrictions(
+                        db,
+                        LogId::current(db),
+                        db[source].controller,
+                        restrictions,
+                    )
+                },
+            )
+        }
+    }
+}
+
+impl CreateRole {
+    fn token(&self) -> Card {
+        Card {
+            name: self.name.clone(),
+            typeline: protobuf::MessageField::some(Typeline {
+                types: vec![Type::ENCHANTMENT.into()],
+                subtypes: vec![Subtype::AURA.into(), Subtype::ROLE.into()],
+                ..Default::default()
+            }),
+            oracle_text: self.oracle_text.clone(),
+            enchant: protobuf::MessageField::some(Enchant {
+                modifiers: self.modifiers.clone(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+}
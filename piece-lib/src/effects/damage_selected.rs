@@ -1,9 +1,16 @@
+use itertools::Itertools;
+
 use crate::{
-    effects::{EffectBehaviors, EffectBundle, SelectedStack},
+    effects::{handle_replacements, EffectBehaviors, EffectBundle, SelectedStack},
     in_play::{CardId, Database},
     log::LogId,
-    protogen::{effects::DamageSelected, targets::Location},
-    stack::TargetType,
+    protogen::{
+        effects::{
+            count::Fixed, replacement_effect::Replacing, Count, DamageSelected, PopSelected,
+        },
+        targets::{Location, Restriction},
+    },
+    stack::{Selected, TargetType},
 };
 
 impl EffectBehaviors for DamageSelected {
@@ -12,32 +19,120 @@ impl EffectBehaviors for DamageSelected {
         db: &mut Database,
         source: Option<CardId>,
         selected: &mut SelectedStack,
-        _skip_replacement: bool,
+        skip_replacement: bool,
     ) -> Vec<EffectBundle> {
         let count = self.count.count(db, source, selected);
-        for target in selected.iter().filter(|target| {
-            (matches!(target.location, Some(Location::ON_BATTLEFIELD)))
-                || matches!(target.target_type, TargetType::Player(_))
-        }) {
-            match &target.target_type {
-                TargetType::Card(card) => {
-                    if !target.targeted
-                        || (card.can_be_targeted(db, db[source.unwrap()].controller)
-                            && card.passes_restrictions(
-                                db,
-                                LogId::current(db),
-                                source.unwrap(),
-                                &target.restrictions,
-                            ))
-                    {
-                        card.mark_damage(db, count as u32)
+        let targets = selected
+            .iter()
+            .filter(|target| can_be_damaged(db, source, target))
+            .cloned()
+            .collect_vec();
+
+        if skip_replacement {
+            for target in targets.iter() {
+                deal_damage(db, source, target, count);
+            }
+
+            return vec![];
+        }
+
+        let fixed = DamageSelected {
+            count: protobuf::MessageField::some(Count {
+                count: Some(
+                    Fixed {
+                        count,
+                        ..Default::default()
                     }
-                }
-                TargetType::Player(player) => db.all_players[*player].life_total -= count,
-                _ => unreachable!(),
+                    .into(),
+                ),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        if let [target] = targets.as_slice() {
+            if is_replaced(db, target) {
+                return handle_replacements(
+                    db,
+                    source,
+                    Replacing::DAMAGE,
+                    fixed,
+                    |replacing, restrictions| {
+                        passes_restrictions(db, target, replacing, restrictions)
+                    },
+                );
+            }
+        }
+
+        // Each target may be watched by different replacements, so they are handled one at a time.
+        let mut results = vec![];
+        for target in targets {
+            if is_replaced(db, &target) {
+                results.push(EffectBundle {
+                    push_on_enter: Some(vec![target]),
+                    source,
+                    effects: vec![fixed.clone().into(), PopSelected::default().into()],
+                    ..Default::default()
+                });
+            } else {
+                deal_damage(db, source, &target, count);
             }
         }
 
-        vec![]
+        results
+    }
+}
+
+fn can_be_damaged(db: &Database, source: Option<CardId>, target: &Selected) -> bool {
+    match &target.target_type {
+        TargetType::Card(card) => {
+            matches!(target.location, Some(Location::ON_BATTLEFIELD))
+                && (!target.targeted
+                    || (card.can_be_targeted(db, source.unwrap())
+                        && card.passes_restrictions(
+                            db,
+                            LogId::current(db),
+                            source.unwrap(),
+                            &target.restrictions,
+                        )))
+        }
+        TargetType::Player(_) => true,
+        _ => false,
+    }
+}
+
+fn deal_damage(db: &mut Database, source: Option<CardId>, target: &Selected, count: i32) {
+    match &target.target_type {
+        TargetType::Card(card) => card.mark_damage(db, source, count as u32),
+        TargetType::Player(player) => db.all_players[*player].life_total -= count,
+        _ => unreachable!(),
+    }
+}
+
+fn is_replaced(db: &Database, target: &Selected) -> bool {
+    db.replacement_abilities_watching(Replacing::DAMAGE)
+        .into_iter()
+        .any(|(replacing, replacement)| {
+            passes_restrictions(db, target, replacing, &replacement.restrictions)
+        })
+}
+
+fn passes_restrictions(
+    db: &Database,
+    target: &Selected,
+    replacing: CardId,
+    restrictions: &[Restriction],
+) -> bool {
+    match &target.target_type {
+        TargetType::Card(card) => {
+            card.passes_restrictions(db, LogId::current(db), replacing, restrictions)
+        }
+        TargetType::Player(player) => player.passes_restrictions(
+            db,
+            LogId::current(db),
+            db[replacing].controller,
+            restrictions,
+        ),
+        _ => false,
     }
 }
@@ -14,7 +14,7 @@ use crate::{
         },
         empty::Empty,
         targets::{restriction, Location, Restriction},
-        triggers::TriggerSource,
+        triggers::{Trigger, TriggerSource},
         types::Type,
     },
     stack::{Selected, Stack, TargetType},
@@ -32,10 +32,17 @@ impl EffectBehaviors for DeclareAttacking {
 
         let mut results = vec![];
 
-        for (attacker, target) in attackers
+        for (attacker, (target, planeswalker)) in attackers
             .into_iter()
             .map(|attacker| attacker.id(db).unwrap())
-            .zip(selected.iter().map(|target| target.player().unwrap()))
+            .zip(selected.iter().map(|target| match target.target_type {
+                TargetType::Player(player) => (player, None),
+                // Attacking a planeswalker attacks the player who controls it.
+                _ => {
+                    let planeswalker = target.id(db).unwrap();
+                    (db[planeswalker].controller.into(), Some(planeswalker))
+                }
+            }))
             .collect_vec()
         {
             for (listener, trigger) in db.active_triggers_of_source(TriggerSource::ATTACKS) {
@@ -45,7 +52,7 @@ impl EffectBehaviors for DeclareAttacking {
                     listener,
                     &trigger.trigger.restrictions,
                 ) {
-                    results.push(Stack::move_trigger_to_stack(db, listener, trigger));
+                    results.extend(Stack::move_trigger_to_stack(db, listener, trigger));
                 }
             }
 
@@ -83,11 +90,14 @@ impl EffectBehaviors for DeclareAttacking {
                     },
                 ];
 
-                results.push(Stack::move_trigger_to_stack(
+                results.extend(Stack::move_trigger_to_stack(
                     db,
                     attacker,
                     TriggeredAbility {
-                        trigger: protobuf::MessageField::none(),
+                        trigger: protobuf::MessageField::some(Trigger {
+                            source: TriggerSource::ATTACKS.into(),
+                            ..Default::default()
+                        }),
                         effects: vec![
                             Effect {
                                 effect: Some(
@@ -130,6 +140,7 @@ impl EffectBehaviors for DeclareAttacking {
             }
 
             db[attacker].attacking = Some(target);
+            db[attacker].attacking_planeswalker = planeswalker;
 
             if !attacker.vigilance(db) {
                 results.push(EffectBundle {
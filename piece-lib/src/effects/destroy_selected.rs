@@ -1,7 +1,7 @@
 use crate::{
     effects::{EffectBehaviors, EffectBundle, SelectedStack},
     in_play::{CardId, Database},
-    protogen::effects::{DestroySelected, Effect, MoveToGraveyard},
+    protogen::effects::{DestroySelected, MoveToGraveyard, PopSelected},
     stack::TargetType,
 };
 
@@ -13,22 +13,23 @@ impl EffectBehaviors for DestroySelected {
         selected: &mut SelectedStack,
         _skip_replacement: bool,
     ) -> Vec<EffectBundle> {
-        let mut effects = vec![];
+        let mut destroyed = vec![];
         for target in selected.iter() {
             let TargetType::Card(card) = target.target_type else {
                 unreachable!()
             };
 
-            if !card.indestructible(db) {
-                effects.push(Effect {
-                    effect: Some(MoveToGraveyard::default().into()),
-                    ..Default::default()
-                })
+            if !card.indestructible(db) && !card.regenerate(db) {
+                destroyed.push(target.clone());
             }
         }
 
         vec![EffectBundle {
-            effects,
+            push_on_enter: Some(destroyed),
+            effects: vec![
+                MoveToGraveyard::default().into(),
+                PopSelected::default().into(),
+            ],
             source,
             ..Default::default()
         }]
@@ -0,0 +1,125 @@
+use itertools::Itertools;
+
+use crate::{
+    effects::{
+        move_to_graveyard::move_card_to_graveyard, EffectBehaviors, EffectBundle, Options,
+        SelectedStack, SelectionResult,
+    },
+    in_play::{CardId, Database},
+    protogen::{counters::Counter, effects::Devour, types::Type},
+    stack::{Selected, TargetType},
+    types::TypeSet,
+};
+
+impl EffectBehaviors for Devour {
+    fn description(
+        &self,
+        _db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> String {
+        "Select creatures to devour".to_string()
+    }
+
+    fn wants_input(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> bool {
+        self.compute_targets(db, source.unwrap()).next().is_some()
+    }
+
+    fn options(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> Options {
+        Options::OptionalList(
+            self.compute_targets(db, source.unwrap())
+                .map(|card| card.name(db).clone())
+                .enumerate()
+                .collect_vec(),
+        )
+    }
+
+    fn select(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        option: Option<usize>,
+        _selected: &mut SelectedStack,
+    ) -> SelectionResult {
+        if let Some(option) = option {
+            let card = self
+                .compute_targets(db, source.unwrap())
+                .nth(option)
+                .unwrap();
+            self.selected.push(card.into());
+
+            if self.compute_targets(db, source.unwrap()).next().is_some() {
+                SelectionResult::PendingChoice
+            } else {
+                SelectionResult::Complete
+            }
+        } else {
+            SelectionResult::Complete
+        }
+    }
+
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        _selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        let source = source.unwrap();
+        let devoured = self
+            .selected
+            .drain(..)
+            .map(CardId::from)
+            .map(|card| Selected {
+                location: card.location(db),
+                target_type: TargetType::Card(card),
+                targeted: false,
+                restrictions: vec![],
+            })
+            .collect_vec();
+
+        *db[source].counters.entry(Counter::P1P1).or_default() +=
+            devoured.len() as u32 * self.multiplier;
+
+        let results = move_card_to_graveyard(db, &mut SelectedStack::new(devoured), Some(source));
+        for card in db.cards.keys().copied().collect_vec() {
+            card.apply_modifiers_layered(db);
+        }
+
+        results
+    }
+}
+
+impl Devour {
+    fn compute_targets<'db>(
+        &'db self,
+        db: &'db Database,
+        source: CardId,
+    ) -> impl Iterator<Item = CardId> + 'db {
+        db.battlefield[db[source].controller]
+            .iter()
+            .copied()
+            .filter(move |card| {
+                *card != source
+                    && card.types_intersect(db, &TypeSet::from([Type::CREATURE]))
+                    && !card.sacrifice_prevented(db)
+                    && !self
+                        .selected
+                        .iter()
+                        .any(|selected| CardId::from(selected.clone()) == *card)
+            })
+    }
+}
@@ -40,6 +40,19 @@ impl EffectBehaviors for Discard {
         )
     }
 
+    fn target_for_option(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        already_selected: &[Selected],
+        option: usize,
+    ) -> Option<Selected> {
+        let in_hand = &db.hand[already_selected.first().unwrap().player().unwrap()];
+        self.valid_targets(db, source, in_hand)
+            .nth(option)
+            .map(|card| Selected::card(db, card))
+    }
+
     fn select(
         &mut self,
         db: &mut Database,
@@ -32,7 +32,11 @@ impl EffectBehaviors for Discover {
                 Duration::PERMANENTLY,
             );
 
-            if !card.is_land(db) && card.faceup_face(db).cost.cmc() < discover_value as usize {
+            if !card.is_land(db)
+                && card
+                    .characteristics(db, Location::IN_EXILE)
+                    .is_some_and(|exiled| exiled.mana_value < discover_value as usize)
+            {
                 casting.push(Selected {
                     location: Some(Location::IN_EXILE),
                     target_type: TargetType::Card(card),
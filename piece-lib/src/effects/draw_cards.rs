@@ -2,8 +2,7 @@ use crate::{
     effects::{handle_replacements, EffectBehaviors, EffectBundle, SelectedStack},
     in_play::{CardId, Database},
     log::LogId,
-    protogen::effects::{replacement_effect::Replacing, DrawCards, Effect, PlayerLoses},
-    stack::{Selected, TargetType},
+    protogen::effects::{count::Fixed, replacement_effect::Replacing, Count, DrawCards},
 };
 
 impl EffectBehaviors for DrawCards {
@@ -21,27 +20,27 @@ impl EffectBehaviors for DrawCards {
                 if let Some(card) = db.all_players[target].library.draw() {
                     card.move_to_hand(db);
                 } else {
-                    results.push(EffectBundle {
-                        push_on_enter: Some(vec![Selected {
-                            location: None,
-                            target_type: TargetType::Player(target),
-                            targeted: false,
-                            restrictions: vec![],
-                        }]),
-                        effects: vec![Effect {
-                            effect: Some(PlayerLoses::default().into()),
-                            ..Default::default()
-                        }],
-                        source,
-                        ..Default::default()
-                    });
+                    db.all_players[target].drew_from_empty_library = true;
                 }
             } else {
+                // Each draw is replaced separately, so each replacement only draws a single card.
                 results.extend(handle_replacements(
                     db,
                     source,
                     Replacing::DRAW,
-                    self.clone(),
+                    DrawCards {
+                        count: protobuf::MessageField::some(Count {
+                            count: Some(
+                                Fixed {
+                                    count: 1,
+                                    ..Default::default()
+                                }
+                                .into(),
+                            ),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    },
                     |source, restrictions| {
                         target.passes_restrictions(
                             db,
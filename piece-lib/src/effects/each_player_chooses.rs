@@ -0,0 +1,161 @@
+use itertools::Itertools;
+
+use crate::{
+    effects::{controller, EffectBehaviors, EffectBundle, Options, SelectedStack, SelectionResult},
+    in_play::{CardId, Database},
+    log::{Log, LogId},
+    player::Owner,
+    protogen::effects::EachPlayerChooses,
+    stack::Selected,
+};
+
+impl EffectBehaviors for EachPlayerChooses {
+    fn priority(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> Owner {
+        self.current(db, source)
+            .map(|(_, player)| player)
+            .unwrap_or_else(|| controller(db, source))
+    }
+
+    fn description(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> String {
+        if let Some((_, player)) = self.current(db, source) {
+            format!("{} chooses a card", db.all_players[player].name)
+        } else {
+            "Each player chooses a card".to_string()
+        }
+    }
+
+    fn wants_input(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> bool {
+        self.current(db, source).is_some()
+    }
+
+    fn options(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> Options {
+        let Some((_, player)) = self.current(db, source) else {
+            return Options::MandatoryList(vec![]);
+        };
+
+        Options::MandatoryList(
+            self.candidates(db, source, player)
+                .map(|card| card.name(db).clone())
+                .enumerate()
+                .collect_vec(),
+        )
+    }
+
+    fn target_for_option(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        option: usize,
+    ) -> Option<Selected> {
+        let (_, player) = self.current(db, source)?;
+        self.candidates(db, source, player)
+            .nth(option)
+            .map(|card| Selected::card(db, card))
+    }
+
+    fn select(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        option: Option<usize>,
+        _selected: &mut SelectedStack,
+    ) -> SelectionResult {
+        let Some((index, player)) = self.current(db, source) else {
+            return SelectionResult::Complete;
+        };
+
+        let Some(card) = option.and_then(|option| self.candidates(db, source, player).nth(option))
+        else {
+            return SelectionResult::PendingChoice;
+        };
+
+        self.chosen.push(card.into());
+        self.choosing = index as u32 + 1;
+        if self.current(db, source).is_some() {
+            SelectionResult::PendingChoice
+        } else {
+            SelectionResult::Complete
+        }
+    }
+
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        _source: Option<CardId>,
+        selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        selected.clear();
+        for card in self.chosen.iter() {
+            let card = CardId::from(card.clone());
+            Log::card_chosen(db, card);
+            selected.push(Selected::card(db, card));
+        }
+
+        vec![]
+    }
+}
+
+impl EachPlayerChooses {
+    /// The players choosing, in order.
+    fn choosers(&self, db: &Database, source: Option<CardId>) -> Vec<Owner> {
+        let controller = controller(db, source);
+        db.all_players
+            .starting_with(controller)
+            .into_iter()
+            .filter(|player| !self.opponents_only || *player != controller)
+            .collect_vec()
+    }
+
+    /// The next player to choose and their position in [Self::choosers]. Players without anything
+    /// to choose are skipped.
+    fn current(&self, db: &Database, source: Option<CardId>) -> Option<(usize, Owner)> {
+        self.choosers(db, source)
+            .into_iter()
+            .enumerate()
+            .skip(self.choosing as usize)
+            .find(|(_, player)| self.candidates(db, source, *player).next().is_some())
+    }
+
+    fn candidates<'db>(
+        &'db self,
+        db: &'db Database,
+        source: Option<CardId>,
+        player: Owner,
+    ) -> impl Iterator<Item = CardId> + 'db {
+        db.cards.keys().copied().filter(move |card| {
+            Owner::from(db[*card].controller) == player
+                && card.passes_restrictions(
+                    db,
+                    LogId::current(db),
+                    source.unwrap(),
+                    &self.restrictions,
+                )
+        })
+    }
+}
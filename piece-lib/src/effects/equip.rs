@@ -26,7 +26,7 @@ impl EffectBehaviors for Equip {
         }
 
         let target = target.id(db).unwrap();
-        if !target.can_be_targeted(db, db[source].controller) {
+        if !target.can_be_targeted(db, source) {
             return vec![];
         }
 
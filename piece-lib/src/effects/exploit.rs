@@ -0,0 +1,115 @@
+use itertools::Itertools;
+
+use crate::{
+    effects::{
+        move_to_graveyard::move_card_to_graveyard, EffectBehaviors, EffectBundle, Options,
+        SelectedStack, SelectionResult,
+    },
+    in_play::{CardId, Database},
+    protogen::{effects::Exploit, triggers::TriggerSource, types::Type},
+    stack::{Selected, Stack, TargetType},
+    types::TypeSet,
+};
+
+impl EffectBehaviors for Exploit {
+    fn description(
+        &self,
+        _db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> String {
+        "Select a creature to exploit".to_string()
+    }
+
+    fn wants_input(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> bool {
+        self.selected.is_none() && compute_targets(db, source.unwrap()).next().is_some()
+    }
+
+    fn options(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> Options {
+        Options::OptionalList(
+            compute_targets(db, source.unwrap())
+                .map(|card| card.name(db).clone())
+                .enumerate()
+                .collect_vec(),
+        )
+    }
+
+    fn select(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        option: Option<usize>,
+        _selected: &mut SelectedStack,
+    ) -> SelectionResult {
+        if let Some(option) = option {
+            let card = compute_targets(db, source.unwrap()).nth(option).unwrap();
+            self.selected = protobuf::MessageField::some(card.into());
+        }
+
+        SelectionResult::Complete
+    }
+
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        _selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        let Some(card) = self.selected.take().map(CardId::from) else {
+            return vec![];
+        };
+        let source = source.unwrap();
+
+        // Exploit triggers still fire if the creature exploits itself, so gather them before the
+        // sacrifice.
+        let triggers = db
+            .active_triggers_of_source(TriggerSource::EXPLOITS)
+            .into_iter()
+            .filter(|(listener, _)| *listener == source)
+            .collect_vec();
+
+        let mut results = move_card_to_graveyard(
+            db,
+            &mut SelectedStack::new(vec![Selected {
+                location: card.location(db),
+                target_type: TargetType::Card(card),
+                targeted: false,
+                restrictions: vec![],
+            }]),
+            Some(source),
+        );
+        for card in db.cards.keys().copied().collect_vec() {
+            card.apply_modifiers_layered(db);
+        }
+
+        for (listener, trigger) in triggers {
+            results.extend(Stack::move_trigger_to_stack(db, listener, trigger));
+        }
+
+        results
+    }
+}
+
+fn compute_targets(db: &Database, source: CardId) -> impl Iterator<Item = CardId> + '_ {
+    db.battlefield[db[source].controller]
+        .iter()
+        .copied()
+        .filter(|card| {
+            card.types_intersect(db, &TypeSet::from([Type::CREATURE]))
+                && !card.sacrifice_prevented(db)
+        })
+}
@@ -33,14 +33,15 @@ impl EffectBehaviors for Explore {
             .iter()
             .filter(|target| {
                 matches!(target.location, Some(Location::ON_BATTLEFIELD))
-                    && (!target.targeted || target.id(db).unwrap().can_be_targeted(db, controller))
+                    && (!target.targeted
+                        || target.id(db).unwrap().can_be_targeted(db, source.unwrap()))
             })
             .collect_vec()
         {
             let explorer = target.id(db).unwrap();
 
             if let Some(card) = db.all_players[controller].library.draw() {
-                db[card].revealed = true;
+                card.reveal(db);
                 if card.types_intersect(db, &TypeSet::from([Type::LAND])) {
                     card.move_to_hand(db);
                 } else {
@@ -65,7 +66,7 @@ impl EffectBehaviors for Explore {
                         listener,
                         &trigger.trigger.restrictions,
                     ) {
-                        results.push(Stack::move_trigger_to_stack(db, listener, trigger));
+                        results.extend(Stack::move_trigger_to_stack(db, listener, trigger));
                     }
                 });
         }
@@ -0,0 +1,86 @@
+use itertools::Itertools;
+
+use crate::{
+    effects::{EffectBehaviors, EffectBundle, SelectedStack},
+    in_play::{CardId, Database},
+    protogen::{
+        effects::{count::Fixed, Count, DamageSelected, Fight, PopSelected},
+        targets::Location,
+        types::Type,
+    },
+    stack::{Selected, TargetType},
+    types::TypeSet,
+};
+
+impl EffectBehaviors for Fight {
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        let mut fighters = selected
+            .iter()
+            .filter_map(|selected| selected.id(db))
+            .take(2)
+            .collect_vec();
+        if fighters.len() == 1 {
+            fighters.extend(source);
+        }
+
+        let [left, right] = fighters.as_slice() else {
+            return vec![];
+        };
+
+        // If either creature is no longer on the battlefield, neither deals damage.
+        if !can_fight(db, *left) || !can_fight(db, *right) {
+            return vec![];
+        }
+
+        // Both creatures deal damage at the same time, so their power is fixed before either is
+        // damaged.
+        let left_power = left.power(db).unwrap_or_default().max(0);
+        let right_power = right.power(db).unwrap_or_default().max(0);
+
+        vec![
+            damage(*left, *right, left_power),
+            damage(*right, *left, right_power),
+        ]
+    }
+}
+
+fn can_fight(db: &Database, card: CardId) -> bool {
+    card.is_in_location(db, Location::ON_BATTLEFIELD)
+        && card.types_intersect(db, &TypeSet::from([Type::CREATURE]))
+}
+
+fn damage(source: CardId, target: CardId, count: i32) -> EffectBundle {
+    EffectBundle {
+        push_on_enter: Some(vec![Selected {
+            location: Some(Location::ON_BATTLEFIELD),
+            target_type: TargetType::Card(target),
+            targeted: false,
+            restrictions: vec![],
+        }]),
+        source: Some(source),
+        effects: vec![
+            DamageSelected {
+                count: protobuf::MessageField::some(Count {
+                    count: Some(
+                        Fixed {
+                            count,
+                            ..Default::default()
+                        }
+                        .into(),
+                    ),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }
+            .into(),
+            PopSelected::default().into(),
+        ],
+        ..Default::default()
+    }
+}
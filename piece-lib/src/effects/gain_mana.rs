@@ -1,6 +1,7 @@
 use crate::{
     effects::{EffectBehaviors, EffectBundle, Options, SelectedStack, SelectionResult},
     in_play::{CardId, Database},
+    player::mana_pool::ManaProducer,
     protogen::effects::{gain_mana::Gain, GainMana},
     stack::Selected,
 };
@@ -69,10 +70,12 @@ impl EffectBehaviors for GainMana {
             Gain::Specific(gain) => {
                 let controller = db[source.unwrap()].controller;
                 for gain in gain.gain.iter() {
-                    db.all_players[controller].mana_pool.apply(
+                    let producer = ManaProducer::new(db, source.unwrap());
+                    db.all_players[controller].mana_pool.apply_produced(
                         gain.enum_value().unwrap(),
                         self.mana_source.enum_value().unwrap(),
                         self.mana_restriction.enum_value().unwrap(),
+                        producer,
                     );
                 }
             }
@@ -81,10 +84,12 @@ impl EffectBehaviors for GainMana {
                 let chosen = &choice.choices[*mode];
                 let controller = db[source.unwrap()].controller;
                 for gain in chosen.gains.iter() {
-                    db.all_players[controller].mana_pool.apply(
+                    let producer = ManaProducer::new(db, source.unwrap());
+                    db.all_players[controller].mana_pool.apply_produced(
                         gain.enum_value().unwrap(),
                         self.mana_source.enum_value().unwrap(),
                         self.mana_restriction.enum_value().unwrap(),
+                        producer,
                     );
                 }
             }
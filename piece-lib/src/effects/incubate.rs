@@ -0,0 +1,43 @@
+use crate::{
+    effects::{controller, EffectBehaviors, EffectBundle, SelectedStack},
+    in_play::{CardId, Database},
+    protogen::{
+        counters::Counter,
+        effects::{create_token::Token, AddCounters, CreateToken, Incubate},
+    },
+    stack::{Selected, TargetType},
+};
+
+impl EffectBehaviors for Incubate {
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        _selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        vec![EffectBundle {
+            push_on_enter: Some(vec![Selected {
+                location: None,
+                target_type: TargetType::Player(controller(db, source)),
+                targeted: false,
+                restrictions: vec![],
+            }]),
+            source,
+            effects: vec![
+                CreateToken {
+                    token: Some(Token::Incubator(Default::default())),
+                    ..Default::default()
+                }
+                .into(),
+                AddCounters {
+                    counter: Counter::P1P1.into(),
+                    count: self.count.clone(),
+                    ..Default::default()
+                }
+                .into(),
+            ],
+            ..Default::default()
+        }]
+    }
+}
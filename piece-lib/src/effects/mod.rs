@@ -1,4 +1,5 @@
 mod add_counters;
+mod amass;
 mod apply_modifier;
 mod apply_to_each_target;
 mod attack_selected;
@@ -6,29 +7,37 @@ mod ban_attacking_this_turn;
 mod cascade;
 mod cast_selected;
 mod choose_attackers;
+mod choose_blockers;
+mod choose_card_name;
 mod choose_cast;
 mod clear_selected;
 mod clone_selected;
 mod complete_spell_resolution;
 mod copy_spell_or_ability;
 mod counter_spell;
+mod create_role;
 mod create_token;
 mod create_token_clone_of_selected;
 mod cycling;
 mod damage_selected;
 mod declare_attacking;
 mod destroy_selected;
+mod devour;
 mod discard;
 mod discard_selected;
 mod discover;
 mod draw_cards;
+mod each_player_chooses;
 mod equip;
 mod exile_graveyard;
+mod exploit;
 mod explore;
+mod fight;
 mod for_each_mana_of_source;
 mod gain_life;
 mod gain_mana;
 mod if_then_else;
+mod incubate;
 mod lose_life;
 mod manifest;
 mod mill;
@@ -42,15 +51,24 @@ mod move_to_stack;
 mod move_to_top_of_library;
 mod multiply_tokens;
 mod nothing;
+mod order_blockers;
 mod ovewrite;
 mod pay_costs;
+mod phase_out;
 mod player_loses;
 mod pop_selected;
+mod populate;
+mod proliferate;
 mod push_selected;
+mod redirect_damage;
+mod regenerate;
+mod remove_all_counters;
 mod remove_counters;
+mod remove_from_combat;
 mod reorder_selected;
 mod reveal;
 mod sacrifice;
+mod schedule_delayed;
 mod scry;
 mod select_all;
 mod select_all_players;
@@ -64,6 +82,7 @@ mod select_source;
 mod select_target_controller;
 mod select_targets;
 mod select_top_of_library;
+mod shuffle_into_library;
 mod shuffle_selected;
 mod spend_mana;
 mod tap;
@@ -71,6 +90,7 @@ mod transform;
 mod tutor_library;
 mod unless;
 mod untap;
+mod vote;
 
 use std::{collections::VecDeque, fmt::Debug, vec};
 
@@ -78,9 +98,13 @@ use derive_more::{Deref, DerefMut};
 use itertools::Itertools;
 
 use crate::{
+    filter::CardFilter,
     in_play::{CardId, Database},
     log::LogId,
-    player::Owner,
+    player::{
+        mana_pool::{FloatingMana, ManaPool},
+        Owner,
+    },
     protogen::{
         cost::XIs,
         effects::{
@@ -90,9 +114,24 @@ use crate::{
         targets::{Location, Restriction},
         triggers,
     },
+    record::GameRecord,
     stack::{Selected, TargetType},
+    trace::{Trace, TraceEvent},
 };
 
+/// The most choices [PendingEffects::affected_by_option] will let resolve on their own after the
+/// option is chosen.
+const MAX_SIMULATED_CHOICES: usize = 100;
+
+/// The controller of the effect's source, or the player with priority if it has no source.
+fn controller(db: &Database, source: Option<CardId>) -> Owner {
+    if let Some(source) = source {
+        db[source].controller.into()
+    } else {
+        db.turn.priority_player()
+    }
+}
+
 impl PartialEq<triggers::Location> for Location {
     fn eq(&self, other: &triggers::Location) -> bool {
         match self {
@@ -123,13 +162,73 @@ pub enum SelectionResult {
     PendingChoice,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum Options {
     MandatoryList(Vec<(usize, String)>),
     OptionalList(Vec<(usize, String)>),
     ListWithDefault(Vec<(usize, String)>),
 }
 
+/// What kind of thing an option refers to, for grouping options in a UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OptionCategory {
+    /// A card, grouped by the zone it is in.
+    Card(Location),
+    Player,
+    /// A spell or ability on the stack.
+    Stack,
+    /// An activated, triggered, or replacement ability which isn't on the stack.
+    Ability,
+    /// Anything else, such as a mode, a mana payment, or a card name.
+    Other,
+}
+
+/// Structured information about a single option, so a UI can render previews and group options
+/// without parsing their descriptions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct OptionDetail {
+    pub index: usize,
+    pub description: String,
+    pub category: OptionCategory,
+    /// The card the option refers to, including spells on the stack.
+    pub card: Option<CardId>,
+    pub player: Option<Owner>,
+    /// Whether this is the option which will be chosen if the choice is left to the engine. Only
+    /// mandatory choices have a default option, since optional choices can be declined and lists
+    /// with a default use a choice which isn't listed.
+    pub is_default: bool,
+}
+
+/// Everything which choosing an option would affect, see [PendingEffects::affected_by_option].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AffectedObjects {
+    /// Cards which would be targeted, or which would change zones, controllers, counters, power
+    /// and toughness, or become tapped or untapped.
+    pub cards: Vec<CardId>,
+    /// Players who would be targeted or whose life total would change.
+    pub players: Vec<Owner>,
+}
+
+impl AffectedObjects {
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty() && self.players.is_empty()
+    }
+
+    fn add_card(&mut self, card: CardId) {
+        if !self.cards.contains(&card) {
+            self.cards.push(card);
+        }
+    }
+
+    fn add_player(&mut self, player: Owner) {
+        if !self.players.contains(&player) {
+            self.players.push(player);
+        }
+    }
+}
+
 impl Options {
     pub fn is_empty(&self) -> bool {
         match self {
@@ -146,11 +245,20 @@ impl Options {
             | Options::ListWithDefault(opts) => opts.len(),
         }
     }
+
+    pub fn into_vec(self) -> Vec<(usize, String)> {
+        match self {
+            Options::MandatoryList(opts)
+            | Options::OptionalList(opts)
+            | Options::ListWithDefault(opts) => opts,
+        }
+    }
 }
 
 #[enum_delegate::implement_for(crate::protogen::effects::effect::Effect,
     enum Effect {
         AddCounters(AddCounters),
+        Amass(Amass),
         ApplyModifier(ApplyModifier),
         ApplyToEachTarget(ApplyToEachTarget),
         AttackSelected(AttackSelected),
@@ -158,29 +266,37 @@ impl Options {
         Cascade(Cascade),
         CastSelected(CastSelected),
         ChooseAttackers(ChooseAttackers),
+        ChooseBlockers(ChooseBlockers),
+        ChooseCardName(ChooseCardName),
         ChooseCast(ChooseCast),
         ClearSelected(ClearSelected),
         CloneSelected(CloneSelected),
         CompleteSpellResolution(CompleteSpellResolution),
         CopySpellOrAbility(CopySpellOrAbility),
         CounterSpell(CounterSpell),
+        CreateRole(CreateRole),
         CreateToken(CreateToken),
         CreateTokenCloneOfSelected(CreateTokenCloneOfSelected),
         Cycling(Cycling),
         DamageSelected(DamageSelected),
         DeclareAttacking(DeclareAttacking),
         DestroySelected(DestroySelected),
+        Devour(Devour),
         Discard(Discard),
         DiscardSelected(DiscardSelected),
         Discover(Discover),
         DrawCards(DrawCards),
+        EachPlayerChooses(EachPlayerChooses),
         Equip(Equip),
         ExileGraveyard(ExileGraveyard),
+        Exploit(Exploit),
         Explore(Explore),
+        Fight(Fight),
         ForEachManaOfSource(ForEachManaOfSource),
         GainLife(GainLife),
         GainMana(GainMana),
         IfThenElse(IfThenElse),
+        Incubate(Incubate),
         LoseLife(LoseLife),
         Manifest(Manifest),
         Mill(Mill),
@@ -194,15 +310,24 @@ impl Options {
         MoveToTopOfLibrary(MoveToTopOfLibrary),
         MultiplyTokens(MultiplyTokens),
         Nothing(Nothing),
+        OrderBlockers(OrderBlockers),
         Overwrite(Overwrite),
         PayCosts(PayCosts),
+        PhaseOut(PhaseOut),
         PlayerLoses(PlayerLoses),
         PopSelected(PopSelected),
+        Populate(Populate),
+        Proliferate(Proliferate),
         PushSelected(PushSelected),
+        RedirectDamage(RedirectDamage),
+        Regenerate(Regenerate),
+        RemoveAllCounters(RemoveAllCounters),
         RemoveCounters(RemoveCounters),
+        RemoveFromCombat(RemoveFromCombat),
         ReorderSelected(ReorderSelected),
         Reveal(Reveal),
         Sacrifice(Sacrifice),
+        ScheduleDelayed(ScheduleDelayed),
         Scry(Scry),
         SelectAll(SelectAll),
         SelectAllPlayers(SelectAllPlayers),
@@ -216,6 +341,7 @@ impl Options {
         SelectTargetController(SelectTargetController),
         SelectTargets(SelectTargets),
         SelectTopOfLibrary(SelectTopOfLibrary),
+        ShuffleIntoLibrary(ShuffleIntoLibrary),
         ShuffleSelected(ShuffleSelected),
         SpendMana(SpendMana),
         Tap(Tap),
@@ -223,6 +349,7 @@ impl Options {
         TutorLibrary(TutorLibrary),
         Unless(Unless),
         Untap(Untap),
+        Vote(Vote),
     }
 )]
 #[enum_delegate::implement_for(crate::protogen::effects::dest::Destination,
@@ -261,11 +388,7 @@ pub(crate) trait EffectBehaviors {
         let _ = already_selected;
         let _ = modes;
 
-        if let Some(source) = source {
-            db[source].controller.into()
-        } else {
-            db.turn.priority_player()
-        }
+        controller(db, source)
     }
 
     fn description(
@@ -312,6 +435,7 @@ pub(crate) trait EffectBehaviors {
         Options::OptionalList(vec![])
     }
 
+    /// The card, player, or stack entry the nth option refers to, if any.
     fn target_for_option(
         &self,
         db: &Database,
@@ -321,8 +445,19 @@ pub(crate) trait EffectBehaviors {
     ) -> Option<Selected> {
         let _ = db;
         let _ = source;
+        let _ = already_selected;
+        let _ = option;
 
-        already_selected.get(option).cloned()
+        None
+    }
+
+    /// The mana which would be left in the paying player's pool once the mana chosen so far is
+    /// spent, if this effect is paying mana.
+    fn floating_after_payment(&self, db: &Database, source: Option<CardId>) -> Option<ManaPool> {
+        let _ = db;
+        let _ = source;
+
+        None
     }
 
     /// Select the nth option.
@@ -350,8 +485,10 @@ pub(crate) trait EffectBehaviors {
         skip_replacement: bool,
     ) -> Vec<EffectBundle>;
 
-    /// Apply the replacement effects to the bundle.
-    fn apply_replacement(&self, effect: Effect) -> Vec<Effect> {
+    /// Apply the replacement effects to the bundle. The source is the card with the replacement
+    /// ability.
+    fn apply_replacement(&self, source: CardId, effect: Effect) -> Vec<Effect> {
+        let _ = source;
         vec![effect]
     }
 }
@@ -388,7 +525,7 @@ impl SelectedStack {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct EffectBundle {
     pub(crate) push_on_enter: Option<Vec<Selected>>,
     pub(crate) source: Option<CardId>,
@@ -396,9 +533,12 @@ pub struct EffectBundle {
     pub(crate) skip_replacement: bool,
     pub(crate) effects: Vec<Effect>,
     pub(crate) resolving: usize,
+
+    pub(crate) trace_parent: Option<usize>,
+    pub(crate) trace_node: Option<usize>,
 }
 
-#[derive(Default, Debug)]
+#[derive(Clone, Default, Debug)]
 #[must_use]
 pub struct PendingEffects {
     pub(crate) selected: SelectedStack,
@@ -457,6 +597,13 @@ impl PendingEffects {
         }
     }
 
+    fn apply_traced_results(&mut self, parent: Option<usize>, results: Vec<EffectBundle>) {
+        self.apply_results(results.into_iter().map(|mut result| {
+            result.trace_parent = parent;
+            result
+        }));
+    }
+
     pub fn extend(&mut self, other: PendingEffects) {
         self.bundles.extend(other.bundles);
     }
@@ -471,10 +618,34 @@ impl PendingEffects {
                 .effect
                 .as_ref()
                 .unwrap()
-                .target_for_option(db, first.source, &self.selected, option)
+                .target_for_option(db, first.source, self.selected_for(first), option)
         })
     }
 
+    /// The unspent mana in the player's pool, less any mana already chosen to pay for the current
+    /// effect. Mana isn't removed from the pool until a payment finishes, so this is what the pool
+    /// should be shown as while paying.
+    pub fn floating_mana(&self, db: &Database, player: Owner) -> Vec<FloatingMana> {
+        self.bundles
+            .front()
+            .filter(|first| {
+                first
+                    .source
+                    .is_some_and(|source| Owner::from(db[source].controller) == player)
+            })
+            .and_then(|first| {
+                first
+                    .effects
+                    .get(first.resolving)?
+                    .effect
+                    .as_ref()
+                    .unwrap()
+                    .floating_after_payment(db, first.source)
+            })
+            .unwrap_or_else(|| db.all_players[player].mana_pool.clone())
+            .floating()
+    }
+
     pub fn priority(&self, db: &Database) -> Owner {
         self.bundles
             .front()
@@ -503,7 +674,12 @@ impl PendingEffects {
                     .effect
                     .as_ref()
                     .unwrap()
-                    .description(db, first.source, &self.selected, &self.selected.modes)
+                    .description(
+                        db,
+                        first.source,
+                        self.selected_for(first),
+                        &self.selected.modes,
+                    )
             })
             .unwrap_or_default()
     }
@@ -527,19 +703,17 @@ impl PendingEffects {
     pub fn resolve(&mut self, db: &mut Database, option: Option<usize>) -> SelectionResult {
         let mut applied = false;
         if option.is_none() {
-            loop {
-                let Some(first) = self.bundles.front_mut() else {
-                    break;
-                };
-
+            while let Some(first) = self.bundles.front_mut() {
                 if first.resolving == 0 && first.push_on_enter.is_some() {
                     self.selected.save();
                     self.selected.clear();
                     self.selected.extend(first.push_on_enter.take().unwrap());
                 }
+                Trace::enter(db, first);
 
                 let first_len = first.effects.len();
                 let Some(effect) = first.effects.get_mut(first.resolving) else {
+                    Trace::record(db, first.trace_node, TraceEvent::Exit);
                     self.bundles.pop_front();
                     continue;
                 };
@@ -554,15 +728,18 @@ impl PendingEffects {
                 }
 
                 applied = true;
+                let name = effect.as_ref().to_string();
                 let results =
                     effect.apply(db, first.source, &mut self.selected, first.skip_replacement);
+                let node = Trace::record(db, first.trace_node, TraceEvent::Apply { effect: name });
 
                 first.resolving += 1;
                 if first.resolving == first_len {
+                    Trace::record(db, first.trace_node, TraceEvent::Exit);
                     self.bundles.pop_front();
                 }
 
-                self.apply_results(results);
+                self.apply_traced_results(node, results);
             }
 
             if applied {
@@ -580,20 +757,47 @@ impl PendingEffects {
                 self.selected.clear();
                 self.selected.extend(first.push_on_enter.take().unwrap());
             }
+            Trace::enter(db, first);
 
             let effect = first.effects[first.resolving].effect.as_mut().unwrap();
-
-            match effect.select(db, first.source, option, &mut self.selected) {
+            let name = effect.as_ref().to_string();
+
+            GameRecord::decision(
+                db,
+                effect.priority(db, first.source, &self.selected, &self.selected.modes),
+                name.clone(),
+                effect.description(db, first.source, &self.selected, &self.selected.modes),
+                effect
+                    .options(db, first.source, &self.selected, &self.selected.modes)
+                    .into_vec(),
+                option,
+            );
+
+            let result = effect.select(db, first.source, option, &mut self.selected);
+            Trace::record(
+                db,
+                first.trace_node,
+                TraceEvent::Select {
+                    effect: name.clone(),
+                    option,
+                    complete: result == SelectionResult::Complete,
+                },
+            );
+
+            match result {
                 SelectionResult::Complete => {
                     let results =
                         effect.apply(db, first.source, &mut self.selected, first.skip_replacement);
+                    let node =
+                        Trace::record(db, first.trace_node, TraceEvent::Apply { effect: name });
 
                     first.resolving += 1;
                     if first.resolving == first.effects.len() {
+                        Trace::record(db, first.trace_node, TraceEvent::Exit);
                         let _ = self.bundles.pop_front().unwrap();
                     }
 
-                    self.apply_results(results);
+                    self.apply_traced_results(node, results);
 
                     if self.bundles.is_empty() {
                         SelectionResult::Complete
@@ -608,24 +812,169 @@ impl PendingEffects {
         }
     }
 
+    /// Like [Self::resolve], but keeps resolving for as long as nothing needs a choice, so it only
+    /// returns once a player has to choose or everything has resolved. Never returns
+    /// [SelectionResult::TryAgain].
+    pub fn resolve_until_choice(
+        &mut self,
+        db: &mut Database,
+        option: Option<usize>,
+    ) -> SelectionResult {
+        let mut result = self.resolve(db, option);
+        while result == SelectionResult::TryAgain {
+            if self.wants_input(db) {
+                return SelectionResult::PendingChoice;
+            }
+            result = self.resolve(db, None);
+        }
+
+        result
+    }
+
+    /// Narrows the options of a pending name choice to the names containing the query. Has no
+    /// effect on other choices.
+    pub fn search(&mut self, query: &str) {
+        if let Some(first) = self.bundles.front_mut() {
+            if let Some(effect::Effect::ChooseCardName(choose)) = first
+                .effects
+                .get_mut(first.resolving)
+                .and_then(|effect| effect.effect.as_mut())
+            {
+                choose.search = query.to_string();
+            }
+        }
+    }
+
     pub fn options(&self, db: &Database) -> Options {
-        self.bundles
-            .front()
-            .and_then(|first| {
-                first
-                    .effects
-                    .get(first.resolving)
-                    .map(|effect| (first.source, effect))
-            })
-            .map(|(source, effect)| {
-                effect.effect.as_ref().unwrap().options(
-                    db,
-                    source,
-                    &self.selected,
-                    &self.selected.modes,
-                )
+        // Listing options checks every candidate against the same restrictions, often several
+        // times over.
+        db.with_restriction_cache(|db| {
+            self.bundles
+                .front()
+                .and_then(|first| {
+                    first
+                        .effects
+                        .get(first.resolving)
+                        .map(|effect| (first, effect))
+                })
+                .map(|(first, effect)| {
+                    effect.effect.as_ref().unwrap().options(
+                        db,
+                        first.source,
+                        self.selected_for(first),
+                        &self.selected.modes,
+                    )
+                })
+                .unwrap_or_else(|| Options::OptionalList(vec![]))
+        })
+    }
+
+    /// The options for the current choice along with what each option refers to.
+    pub fn option_details(&self, db: &Database) -> Vec<OptionDetail> {
+        let options = self.options(db);
+        let mandatory = matches!(options, Options::MandatoryList(_));
+
+        options
+            .into_vec()
+            .into_iter()
+            .enumerate()
+            .map(|(position, (index, description))| {
+                let target = self.target_for_option(db, index);
+                let (category, card, player) = match target.as_ref().map(|t| &t.target_type) {
+                    Some(TargetType::Card(card)) => (
+                        card.location(db)
+                            .map(OptionCategory::Card)
+                            .unwrap_or(OptionCategory::Other),
+                        Some(*card),
+                        None,
+                    ),
+                    Some(TargetType::Player(player)) => {
+                        (OptionCategory::Player, None, Some(*player))
+                    }
+                    Some(TargetType::Stack(_)) => (
+                        OptionCategory::Stack,
+                        target.as_ref().and_then(|target| target.id(db)),
+                        None,
+                    ),
+                    Some(TargetType::Ability { .. } | TargetType::ReplacementAbility { .. }) => {
+                        (OptionCategory::Ability, None, None)
+                    }
+                    None => (OptionCategory::Other, None, None),
+                };
+
+                OptionDetail {
+                    index,
+                    description,
+                    category,
+                    card,
+                    player,
+                    is_default: mandatory && position == 0,
+                }
             })
-            .unwrap_or_else(|| Options::OptionalList(vec![]))
+            .collect_vec()
+    }
+
+    /// Every object choosing the option would affect, found by choosing it in a copy of the game
+    /// and applying effects until the next choice. Unlike [Self::target_for_option], this
+    /// includes everything an effect touches, e.g. every creature destroyed by a mode which
+    /// destroys all creatures.
+    pub fn affected_by_option(&self, db: &Database, option: usize) -> AffectedObjects {
+        let mut affected = AffectedObjects::default();
+        if let Some(target) = self.target_for_option(db, option) {
+            match target.target_type {
+                TargetType::Player(player) => affected.add_player(player),
+                _ => {
+                    if let Some(card) = target.id(db) {
+                        affected.add_card(card);
+                    }
+                }
+            }
+        }
+
+        let mut simulated = db.clone();
+        let mut pending = self.clone();
+        let mut result = pending.resolve(&mut simulated, Some(option));
+        for _ in 0..MAX_SIMULATED_CHOICES {
+            if result == SelectionResult::Complete
+                || pending.is_empty()
+                || pending.wants_input(&simulated)
+            {
+                break;
+            }
+            result = pending.resolve(&mut simulated, None);
+        }
+
+        for (card, before) in db.cards.iter() {
+            let Some(after) = simulated.cards.get(card) else {
+                continue;
+            };
+
+            if before.location != after.location
+                || before.controller != after.controller
+                || before.tapped != after.tapped
+                || before.counters != after.counters
+                || card.power(db) != card.power(&simulated)
+                || card.toughness(db) != card.toughness(&simulated)
+            {
+                affected.add_card(*card);
+            }
+        }
+
+        for player in db.all_players.all_players() {
+            if db.all_players[player].life_total != simulated.all_players[player].life_total {
+                affected.add_player(player);
+            }
+        }
+
+        affected
+    }
+
+    /// The targets the bundle will see once entered, accounting for any targets it has yet to push.
+    fn selected_for<'s>(&'s self, bundle: &'s EffectBundle) -> &'s [Selected] {
+        match bundle.push_on_enter.as_ref() {
+            Some(push_on_enter) if bundle.resolving == 0 => push_on_enter,
+            _ => &self.selected,
+        }
     }
 }
 
@@ -664,6 +1013,20 @@ impl Count {
                     0
                 }
             }
+            count::Count::NumberOfCardsExiledWith(exiled) => {
+                let source = source.unwrap();
+                source
+                    .exiled_with(db)
+                    .filter(|card| {
+                        card.passes_restrictions(
+                            db,
+                            LogId::current(db),
+                            source,
+                            &exiled.restrictions,
+                        )
+                    })
+                    .count() as i32
+            }
             count::Count::NumberOfCountersOnSelected(counters) => {
                 if let Some(first) = selected.first() {
                     if let Some(card) = first.id(db) {
@@ -680,18 +1043,9 @@ impl Count {
                     0
                 }
             }
-            count::Count::NumberOfPermanentsMatching(matching) => db
-                .cards
-                .keys()
-                .filter(|card| {
-                    card.passes_restrictions(
-                        db,
-                        LogId::current(db),
-                        source.unwrap(),
-                        &matching.restrictions,
-                    )
-                })
-                .count() as i32,
+            count::Count::NumberOfPermanentsMatching(matching) => CardFilter::any()
+                .passing(source.unwrap(), &matching.restrictions)
+                .count(db) as i32,
             count::Count::X(x) => match x.x_is.enum_value().unwrap() {
                 XIs::MANA_VALUE_OF_SELECTED => db[selected.first().unwrap().id(db).unwrap()]
                     .modified_cost
@@ -713,7 +1067,10 @@ fn handle_replacements<T: Into<effect::Effect>>(
     let replacements = replacements
         .into_iter()
         .filter(|(card, replacing)| passes_restrictions(*card, &replacing.restrictions))
-        .map(|(_, replacement)| TargetType::ReplacementAbility(replacement))
+        .map(|(source, replacement)| TargetType::ReplacementAbility {
+            source,
+            replacement,
+        })
         .map(|target| Selected {
             location: None,
             target_type: target,
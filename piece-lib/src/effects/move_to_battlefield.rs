@@ -3,6 +3,7 @@ use itertools::Itertools;
 use crate::{
     abilities::Ability,
     battlefield::Battlefields,
+    counters::add_lore_counters,
     effects::{handle_replacements, EffectBehaviors, EffectBundle, SelectedStack},
     in_play::{CardId, Database},
     log::LogId,
@@ -42,11 +43,25 @@ impl EffectBehaviors for MoveToBattlefield {
                     )
                 {
                     let target_card = add_to_battlefield.id(db).unwrap();
-                    if let Some(aura_target) = aura_target.and_then(|target| target.id(db)) {
+                    let mut aura_target = aura_target.and_then(|target| target.id(db));
+                    // A bestowed spell whose target has gone resolves as a creature instead.
+                    if db[target_card].bestowed
+                        && !aura_target.is_some_and(|target| {
+                            target.is_in_location(db, Location::ON_BATTLEFIELD)
+                        })
+                    {
+                        db[target_card].bestowed = false;
+                        aura_target = None;
+                    }
+                    if let Some(aura_target) = aura_target {
                         aura_target.apply_aura(db, target_card);
                     }
 
-                    if let Some(etb) = db[target_card].modified_etb_ability.as_ref() {
+                    if let Some(etb) = db[target_card]
+                        .modified_etb_ability
+                        .as_ref()
+                        .filter(|_| !db.triggers_suspended)
+                    {
                         let mut to_trigger = vec![
                             Effect::from(PushSelected::default()),
                             Effect::from(ClearSelected::default()),
@@ -89,7 +104,7 @@ impl EffectBehaviors for MoveToBattlefield {
                                 &trigger.trigger.restrictions,
                             )
                         {
-                            pending.push(Stack::move_trigger_to_stack(db, listener, trigger));
+                            pending.extend(Stack::move_trigger_to_stack(db, listener, trigger));
                         }
                     }
 
@@ -115,6 +130,11 @@ impl EffectBehaviors for MoveToBattlefield {
                         target_card.tap(db);
                     }
                     target_card.move_to_battlefield(db);
+                    if target_card.final_chapter(db).is_some()
+                        && target_card.is_in_location(db, Location::ON_BATTLEFIELD)
+                    {
+                        pending.extend(add_lore_counters(db, target_card, 1));
+                    }
                 }
             }
 
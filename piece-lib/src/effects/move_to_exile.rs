@@ -51,7 +51,7 @@ impl EffectBehaviors for MoveToExile {
                                 &trigger.trigger.restrictions,
                             )
                         {
-                            pending.push(Stack::move_trigger_to_stack(db, listener, trigger));
+                            pending.extend(Stack::move_trigger_to_stack(db, listener, trigger));
                         }
                     }
                 }
@@ -58,7 +58,7 @@ pub(crate) fn move_card_to_graveyard(
                         &trigger.trigger.restrictions,
                     )
                 {
-                    pending.push(Stack::move_trigger_to_stack(db, listener, trigger));
+                    pending.extend(Stack::move_trigger_to_stack(db, listener, trigger));
                 }
             }
 
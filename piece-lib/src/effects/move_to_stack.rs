@@ -1,7 +1,7 @@
 use crate::{
     abilities::Ability,
     effects::{EffectBehaviors, EffectBundle, SelectedStack},
-    in_play::{CardId, CastFrom, Database},
+    in_play::{AbilityId, CardId, CastFrom, Database},
     log::Log,
     protogen::{
         effects::{Cascade, MoveToStack, TriggeredAbility},
@@ -52,6 +52,9 @@ impl EffectBehaviors for MoveToStack {
                 match ability {
                     Ability::Activated(activated) => {
                         Log::activated(db, *source, *activated);
+                        db.turn.activated_abilities.insert(*activated);
+                        db.activated_this_game
+                            .insert(AbilityId::Activated(*activated));
                     }
                     Ability::Etb(_) | Ability::TriggeredAbility(_) => {
                         Log::etb_or_triggered(db, *source);
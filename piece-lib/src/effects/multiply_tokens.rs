@@ -15,7 +15,7 @@ impl EffectBehaviors for MultiplyTokens {
         unreachable!()
     }
 
-    fn apply_replacement(&self, effect: Effect) -> Vec<Effect> {
+    fn apply_replacement(&self, _source: CardId, effect: Effect) -> Vec<Effect> {
         let mut replaced = vec![];
         for _ in 0..self.multiplier {
             replaced.push(effect.clone());
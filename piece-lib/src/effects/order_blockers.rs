@@ -0,0 +1,116 @@
+use itertools::Itertools;
+
+use crate::{
+    combat,
+    effects::{EffectBehaviors, EffectBundle, Options, SelectedStack, SelectionResult},
+    in_play::{CardId, Database},
+    player::Owner,
+    protogen::effects::OrderBlockers,
+    stack::Selected,
+};
+
+impl EffectBehaviors for OrderBlockers {
+    fn priority(
+        &self,
+        db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> Owner {
+        db[self.attacker()].controller.into()
+    }
+
+    fn description(
+        &self,
+        db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> String {
+        format!(
+            "Select the next creature {} assigns combat damage to",
+            self.attacker().name(db)
+        )
+    }
+
+    fn wants_input(
+        &self,
+        db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> bool {
+        self.unordered(db).len() > 1
+    }
+
+    fn options(
+        &self,
+        db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> Options {
+        Options::ListWithDefault(
+            self.unordered(db)
+                .into_iter()
+                .map(|blocker| blocker.name(db).clone())
+                .enumerate()
+                .collect_vec(),
+        )
+    }
+
+    fn select(
+        &mut self,
+        db: &mut Database,
+        _source: Option<CardId>,
+        option: Option<usize>,
+        _selected: &mut SelectedStack,
+    ) -> SelectionResult {
+        // Not choosing keeps the remaining blockers in the order they were declared.
+        let Some(blocker) = option.and_then(|option| self.unordered(db).get(option).copied())
+        else {
+            return SelectionResult::Complete;
+        };
+
+        self.ordered.push(blocker.into());
+        if self.unordered(db).len() > 1 {
+            SelectionResult::PendingChoice
+        } else {
+            SelectionResult::Complete
+        }
+    }
+
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        _source: Option<CardId>,
+        _selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        let attacker = self.attacker();
+        let ordered = self.ordered.iter().cloned().map(CardId::from).collect_vec();
+        let rest = db[attacker]
+            .blocked_by
+            .iter()
+            .copied()
+            .filter(|blocker| !ordered.contains(blocker))
+            .collect_vec();
+        db[attacker].blocked_by = ordered.into_iter().chain(rest).collect_vec();
+
+        vec![]
+    }
+}
+
+impl OrderBlockers {
+    fn attacker(&self) -> CardId {
+        self.attacker.clone().unwrap().into()
+    }
+
+    /// The creatures blocking the attacker which haven't been given a place in the order yet.
+    fn unordered(&self, db: &Database) -> Vec<CardId> {
+        combat::blockers(db, self.attacker())
+            .into_iter()
+            .filter(|blocker| !self.ordered.iter().any(|ordered| *blocker == *ordered))
+            .collect_vec()
+    }
+}
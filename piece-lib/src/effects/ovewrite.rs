@@ -15,7 +15,7 @@ impl EffectBehaviors for Overwrite {
         unreachable!()
     }
 
-    fn apply_replacement(&self, _effect: Effect) -> Vec<Effect> {
+    fn apply_replacement(&self, _source: CardId, _effect: Effect) -> Vec<Effect> {
         self.effects.clone()
     }
 }
@@ -12,7 +12,7 @@ mod tap_permanents_power_x_or_more;
 use crate::{
     effects::{EffectBehaviors, EffectBundle, Options, SelectedStack, SelectionResult},
     in_play::{CardId, Database},
-    player::Owner,
+    player::{mana_pool::ManaPool, Owner},
     protogen::effects::PayCosts,
     stack::Selected,
 };
@@ -25,8 +25,9 @@ impl EffectBehaviors for PayCosts {
         already_selected: &[Selected],
         modes: &[usize],
     ) -> bool {
-        !self.pay_costs.is_empty()
-            && self.pay_costs[self.paying as usize].wants_input(db, source, already_selected, modes)
+        self.pay_costs
+            .get(self.paying as usize)
+            .is_some_and(|pay| pay.wants_input(db, source, already_selected, modes))
     }
 
     fn priority(
@@ -52,7 +53,16 @@ impl EffectBehaviors for PayCosts {
         already_selected: &[Selected],
         modes: &[usize],
     ) -> Options {
-        self.pay_costs[self.paying as usize].options(db, source, already_selected, modes)
+        self.pay_costs
+            .get(self.paying as usize)
+            .map(|pay| pay.options(db, source, already_selected, modes))
+            .unwrap_or_else(|| Options::OptionalList(vec![]))
+    }
+
+    fn floating_after_payment(&self, db: &Database, source: Option<CardId>) -> Option<ManaPool> {
+        self.pay_costs
+            .get(self.paying as usize)?
+            .floating_after_payment(db, source)
     }
 
     fn select(
@@ -68,13 +78,14 @@ impl EffectBehaviors for PayCosts {
             return SelectionResult::Complete;
         }
 
-        if let SelectionResult::Complete =
-            self.pay_costs[self.paying as usize].select(db, source, option, selected)
-        {
-            self.paying += 1;
+        // There's nothing left to pay if every cost has been paid, or there were none to begin with.
+        if let Some(pay) = self.pay_costs.get_mut(self.paying as usize) {
+            if let SelectionResult::Complete = pay.select(db, source, option, selected) {
+                self.paying += 1;
+            }
         }
 
-        if (self.paying as usize) == self.pay_costs.len() {
+        if (self.paying as usize) >= self.pay_costs.len() {
             SelectionResult::Complete
         } else {
             SelectionResult::PendingChoice
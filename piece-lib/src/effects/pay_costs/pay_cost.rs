@@ -1,6 +1,7 @@
 use crate::{
     effects::{EffectBehaviors, EffectBundle, Options, SelectedStack, SelectionResult},
     in_play::{CardId, Database},
+    player::mana_pool::ManaPool,
     protogen::effects::{pay_cost::Cost, PayCost},
     stack::Selected,
 };
@@ -32,6 +33,13 @@ impl EffectBehaviors for PayCost {
             .options(db, source, already_selected, modes)
     }
 
+    fn floating_after_payment(&self, db: &Database, source: Option<CardId>) -> Option<ManaPool> {
+        self.cost
+            .as_ref()
+            .unwrap()
+            .floating_after_payment(db, source)
+    }
+
     fn select(
         &mut self,
         db: &mut Database,
@@ -6,8 +6,9 @@ use crate::{
     effects::{EffectBehaviors, EffectBundle, Options, SelectedStack, SelectionResult},
     in_play::{CardId, Database},
     log::LogId,
+    player::{mana_pool::ManaPool, Player},
     protogen::{
-        cost::{cost_reducer::When, ManaCost},
+        cost::{cost_reducer::When, xbounds::Fixed, ManaCost, XBounds},
         effects::{pay_cost::PayMana, Effect, SpendMana},
         mana::{
             spend_reason::{Other, Reason},
@@ -36,6 +37,8 @@ impl EffectBehaviors for PayMana {
         _modes: &[usize],
     ) -> Options {
         let (mana, sources) = self.paying();
+        let other = Reason::Other(Other::default());
+        let reason = self.reason.reason.as_ref().unwrap_or(&other);
         let pool_post_paid = db.all_players[db[source.unwrap()].controller].pool_post_pay(
             db,
             &mana.iter().map(|e| e.enum_value().unwrap()).collect_vec(),
@@ -43,27 +46,40 @@ impl EffectBehaviors for PayMana {
                 .iter()
                 .map(|e| e.enum_value().unwrap())
                 .collect_vec(),
-            self.reason.reason.as_ref().unwrap(),
+            reason,
         );
-        if pool_post_paid.is_none()
-            || pool_post_paid
-                .as_ref()
-                .unwrap()
-                .max(db, self.reason.reason.as_ref().unwrap())
-                .is_none()
-        {
-            return Options::OptionalList(vec![]);
+        let life_option = self.life_alternative(db, source.unwrap()).map(|life| {
+            (
+                pool_post_paid
+                    .as_ref()
+                    .map(|pool| pool.available_mana().count())
+                    .unwrap_or_default(),
+                format!("Pay {} life", life),
+            )
+        });
+
+        if pool_post_paid.is_none() || pool_post_paid.as_ref().unwrap().max(db, reason).is_none() {
+            return if let Some(life_option) = life_option {
+                Options::ListWithDefault(vec![life_option])
+            } else {
+                Options::OptionalList(vec![])
+            };
         }
 
         let pool_post_paid = pool_post_paid.unwrap();
-        let display = pool_post_paid
+        let mut display = pool_post_paid
             .available_pool_display()
             .into_iter()
             .enumerate()
             .collect_vec();
+        display.extend(life_option.clone());
 
-        match self.first_unpaid_x_always_unpaid() {
-            Some(ManaCost::GENERIC | ManaCost::X) => Options::ListWithDefault(display),
+        match self.first_unpaid_including_x(db, source.unwrap()) {
+            Some(ManaCost::GENERIC) => Options::ListWithDefault(display),
+            Some(ManaCost::X) if !self.x_below_minimum(db, source.unwrap()) => {
+                Options::ListWithDefault(display)
+            }
+            Some(ManaCost::X) => Options::MandatoryList(display),
             Some(ManaCost::TWO_X) => {
                 if self
                     .paid
@@ -74,16 +90,31 @@ impl EffectBehaviors for PayMana {
                     .sum::<u32>()
                     % 2
                     == 0
+                    && !self.x_below_minimum(db, source.unwrap())
                 {
                     Options::ListWithDefault(display)
                 } else {
                     Options::MandatoryList(display)
                 }
             }
-            _ => Options::ListWithDefault(vec![]),
+            _ => Options::ListWithDefault(life_option.into_iter().collect_vec()),
         }
     }
 
+    fn floating_after_payment(&self, db: &Database, source: Option<CardId>) -> Option<ManaPool> {
+        let (mana, sources) = self.paying();
+        let other = Reason::Other(Other::default());
+        db.all_players[db[source?].controller].pool_post_pay(
+            db,
+            &mana.iter().map(|e| e.enum_value().unwrap()).collect_vec(),
+            &sources
+                .iter()
+                .map(|e| e.enum_value().unwrap())
+                .collect_vec(),
+            self.reason.reason.as_ref().unwrap_or(&other),
+        )
+    }
+
     fn select(
         &mut self,
         db: &mut Database,
@@ -91,9 +122,9 @@ impl EffectBehaviors for PayMana {
         option: Option<usize>,
         selected: &mut SelectedStack,
     ) -> SelectionResult {
-        if !self.reduced && self.reducer.when.is_some() {
+        if let Some(when) = self.reducer.when.as_ref().filter(|_| !self.reduced) {
             self.reduced = true;
-            match self.reducer.when.as_ref().unwrap() {
+            match when {
                 When::TargetMatches(matcher) => {
                     if selected
                         .iter()
@@ -143,6 +174,39 @@ impl EffectBehaviors for PayMana {
             }
         }
 
+        if let (Some(option), Some(life)) =
+            (option, self.life_alternative(db, source_card.unwrap()))
+        {
+            let (mana, sources) = self.paying();
+            let available = db.all_players[db[source_card.unwrap()].controller]
+                .pool_post_pay(
+                    db,
+                    &mana.iter().map(|e| e.enum_value().unwrap()).collect_vec(),
+                    &sources
+                        .iter()
+                        .map(|e| e.enum_value().unwrap())
+                        .collect_vec(),
+                    self.reason.reason.as_ref().unwrap(),
+                )
+                .map(|pool| pool.available_mana().count())
+                .unwrap_or_default();
+
+            if option == available {
+                let cost = self.first_unpaid(db, source_card.unwrap()).unwrap();
+                *self.paid_with_life.entry(cost.value()).or_default() += 1;
+                self.life_paid += life;
+
+                return if self
+                    .first_unpaid_including_x(db, source_card.unwrap())
+                    .is_none()
+                {
+                    SelectionResult::Complete
+                } else {
+                    SelectionResult::PendingChoice
+                };
+            }
+        }
+
         if option.is_none() {
             if self
                 .paid
@@ -169,8 +233,8 @@ impl EffectBehaviors for PayMana {
                     self.reason.reason.as_ref().unwrap(),
                 )
                 .unwrap();
-            let Some(first_unpaid) = self.first_unpaid() else {
-                return SelectionResult::Complete;
+            let Some(first_unpaid) = self.first_unpaid(db, source_card.unwrap()) else {
+                return self.finish_x(db, source_card.unwrap(), &mut pool_post_pay);
             };
 
             if pool_post_pay.can_spend(
@@ -179,22 +243,17 @@ impl EffectBehaviors for PayMana {
                 ManaSource::ANY,
                 self.reason.reason.as_ref().unwrap(),
             ) {
-                let mana = match first_unpaid {
-                    ManaCost::WHITE => Mana::WHITE,
-                    ManaCost::BLUE => Mana::BLUE,
-                    ManaCost::BLACK => Mana::BLACK,
-                    ManaCost::RED => Mana::RED,
-                    ManaCost::GREEN => Mana::GREEN,
-                    ManaCost::COLORLESS => Mana::COLORLESS,
+                let mut mana = match first_unpaid {
                     ManaCost::GENERIC => {
-                        while matches!(self.first_unpaid(), Some(ManaCost::GENERIC))
-                            && pool_post_pay.can_spend(
-                                db,
-                                ManaCost::GENERIC,
-                                ManaSource::ANY,
-                                self.reason.reason.as_ref().unwrap(),
-                            )
-                        {
+                        while matches!(
+                            self.first_unpaid(db, source_card.unwrap()),
+                            Some(ManaCost::GENERIC)
+                        ) && pool_post_pay.can_spend(
+                            db,
+                            ManaCost::GENERIC,
+                            ManaSource::ANY,
+                            self.reason.reason.as_ref().unwrap(),
+                        ) {
                             let max = pool_post_pay
                                 .max(db, self.reason.reason.as_ref().unwrap())
                                 .unwrap();
@@ -216,40 +275,69 @@ impl EffectBehaviors for PayMana {
                                 .or_default() += 1;
                         }
 
-                        return if matches!(
-                            self.first_unpaid_x_always_unpaid(),
+                        return match self.first_unpaid_including_x(db, source_card.unwrap()) {
                             Some(ManaCost::X | ManaCost::TWO_X)
-                        ) {
-                            SelectionResult::PendingChoice
-                        } else {
-                            SelectionResult::Complete
+                                if self.x_forced(db, source_card.unwrap()) =>
+                            {
+                                self.finish_x(db, source_card.unwrap(), &mut pool_post_pay)
+                            }
+                            Some(ManaCost::X | ManaCost::TWO_X) => SelectionResult::PendingChoice,
+                            _ => SelectionResult::Complete,
                         };
                     }
                     ManaCost::X => unreachable!(),
                     ManaCost::TWO_X => unreachable!(),
+                    cost => pool_post_pay
+                        .mana_for(
+                            db,
+                            cost,
+                            ManaSource::ANY,
+                            self.reason.reason.as_ref().unwrap(),
+                        )
+                        .unwrap(),
                 };
 
-                let (_, source) = pool_post_pay.spend(
-                    db,
-                    mana,
-                    ManaSource::ANY,
-                    self.reason.reason.as_ref().unwrap(),
-                );
-                *self
-                    .paid
-                    .entry(first_unpaid.value())
-                    .or_default()
-                    .mana_to_source
-                    .entry(mana.value())
-                    .or_default()
-                    .source_to_count
-                    .entry(source.value())
-                    .or_default() += 1;
-
-                return if self.first_unpaid_x_always_unpaid().is_some() {
-                    SelectionResult::PendingChoice
-                } else {
-                    SelectionResult::Complete
+                loop {
+                    let (_, source) = pool_post_pay.spend(
+                        db,
+                        mana,
+                        ManaSource::ANY,
+                        self.reason.reason.as_ref().unwrap(),
+                    );
+                    *self
+                        .paid
+                        .entry(first_unpaid.value())
+                        .or_default()
+                        .mana_to_source
+                        .entry(mana.value())
+                        .or_default()
+                        .source_to_count
+                        .entry(source.value())
+                        .or_default() += 1;
+
+                    // A {2/W}-style symbol paid with other types of mana takes two of them.
+                    if first_unpaid.two_hybrid().is_some_and(|two| two != mana)
+                        && self.amount_paid(first_unpaid) % 2 == 1
+                    {
+                        if let Some(max) =
+                            pool_post_pay.max(db, self.reason.reason.as_ref().unwrap())
+                        {
+                            mana = max;
+                            continue;
+                        }
+                    }
+
+                    break;
+                }
+
+                return match self.first_unpaid_including_x(db, source_card.unwrap()) {
+                    None => SelectionResult::Complete,
+                    Some(ManaCost::X | ManaCost::TWO_X)
+                        if self.x_forced(db, source_card.unwrap()) =>
+                    {
+                        self.finish_x(db, source_card.unwrap(), &mut pool_post_pay)
+                    }
+                    Some(_) => SelectionResult::PendingChoice,
                 };
             } else {
                 return SelectionResult::PendingChoice;
@@ -274,7 +362,9 @@ impl EffectBehaviors for PayMana {
             .available_mana()
             .nth(option.unwrap())
         {
-            let cost = self.first_unpaid_x_always_unpaid().unwrap();
+            let cost = self
+                .first_unpaid_including_x(db, source_card.unwrap())
+                .unwrap();
             *self
                 .paid
                 .entry(cost.value())
@@ -286,7 +376,10 @@ impl EffectBehaviors for PayMana {
                 .entry(source.value())
                 .or_default() += 1;
 
-            if self.first_unpaid_x_always_unpaid().is_none() {
+            if self
+                .first_unpaid_including_x(db, source_card.unwrap())
+                .is_none()
+            {
                 SelectionResult::Complete
             } else {
                 SelectionResult::PendingChoice
@@ -305,6 +398,9 @@ impl EffectBehaviors for PayMana {
     ) -> Vec<EffectBundle> {
         db[source.unwrap()].x_is = self.x_paid() as usize;
 
+        let controller = db[source.unwrap()].controller;
+        db.all_players[controller].life_total -= self.life_paid as i32;
+
         let (mana_paid, mana_sources) = self.paying();
         source.unwrap().mana_from_source(db, &mana_sources);
 
@@ -327,8 +423,34 @@ impl EffectBehaviors for PayMana {
     }
 }
 
+impl XBounds {
+    /// The smallest and largest values X may be announced as, ignoring whether they can be paid.
+    pub(crate) fn range(&self, db: &Database, source: CardId) -> (u32, u32) {
+        let mut min = self.minimum;
+        let mut max = self.maximum.unwrap_or(u32::MAX);
+        if let Some(fixed) = self.fixed {
+            let fixed = match fixed.enum_value().unwrap() {
+                Fixed::CARDS_IN_HAND => db.hand[db[source].controller]
+                    .iter()
+                    .filter(|card| **card != source)
+                    .count() as u32,
+            };
+            min = min.max(fixed);
+            max = max.min(fixed);
+        }
+
+        (min, max)
+    }
+}
+
 impl PayMana {
-    pub(crate) fn first_unpaid_x_always_unpaid(&self) -> Option<ManaCost> {
+    /// The first mana symbol which still needs to be paid. X is unpaid until it reaches the largest
+    /// value that can be paid for it, or forever if no legal value of X can be paid.
+    pub(crate) fn first_unpaid_including_x(
+        &self,
+        db: &Database,
+        source: CardId,
+    ) -> Option<ManaCost> {
         let paying = self
             .paying
             .iter()
@@ -342,49 +464,207 @@ impl PayMana {
             .into_iter()
             .find(|(paying, required)| {
                 let required = match paying {
-                    ManaCost::X => u32::MAX,
-                    ManaCost::TWO_X => u32::MAX,
-                    _ => *required,
+                    ManaCost::X | ManaCost::TWO_X => {
+                        let (min, max) = self.x_range(db, source);
+                        let max = if min > max { u32::MAX } else { max };
+                        if *paying == ManaCost::TWO_X {
+                            max.saturating_mul(2)
+                        } else {
+                            max
+                        }
+                    }
+                    _ => return self.unpaid(*paying, *required) > 0,
                 };
 
-                self.paid
+                let paid = self
+                    .paid
                     .get(&paying.value())
                     .map(|paid| {
-                        let paid = paid
-                            .mana_to_source
+                        paid.mana_to_source
                             .values()
                             .flat_map(|sourced| sourced.source_to_count.values())
-                            .sum::<u32>();
-                        paid < required
+                            .sum::<u32>()
                     })
-                    .unwrap_or(true)
+                    .unwrap_or_default();
+                let paid_with_life = self
+                    .paid_with_life
+                    .get(&paying.value())
+                    .copied()
+                    .unwrap_or_default();
+
+                paid + paid_with_life < required
             })
             .map(|(paying, _)| paying)
     }
 
-    pub(crate) fn first_unpaid(&self) -> Option<ManaCost> {
-        self.first_unpaid_x_always_unpaid()
+    /// How much has been paid towards the symbols of `cost`. Mana of the type a {2/W}-style symbol
+    /// names counts twice, since one of it pays for a symbol which otherwise takes two mana.
+    fn amount_paid(&self, cost: ManaCost) -> u32 {
+        let weight = |mana: i32| match cost.two_hybrid() {
+            Some(two) if two.value() == mana => 2,
+            _ => 1,
+        };
+        let paid = self
+            .paid
+            .get(&cost.value())
+            .iter()
+            .flat_map(|paid| paid.mana_to_source.iter())
+            .map(|(mana, sourced)| weight(*mana) * sourced.source_to_count.values().sum::<u32>())
+            .sum::<u32>();
+        let paid_with_life = self
+            .paid_with_life
+            .get(&cost.value())
+            .copied()
+            .unwrap_or_default();
+
+        paid + paid_with_life * if cost.two_hybrid().is_some() { 2 } else { 1 }
+    }
+
+    /// How many of the `symbols` symbols of `cost` still need to be paid for.
+    fn unpaid(&self, cost: ManaCost, symbols: u32) -> u32 {
+        if cost.two_hybrid().is_some() {
+            (symbols * 2)
+                .saturating_sub(self.amount_paid(cost))
+                .div_ceil(2)
+        } else {
+            symbols.saturating_sub(self.amount_paid(cost))
+        }
+    }
+
+    pub(crate) fn first_unpaid(&self, db: &Database, source: CardId) -> Option<ManaCost> {
+        self.first_unpaid_including_x(db, source)
             .filter(|unpaid| !matches!(unpaid, ManaCost::X | ManaCost::TWO_X))
     }
 
-    fn x_paid(&mut self) -> u32 {
-        u32::max(
+    /// The life which may be paid rather than paying the next unpaid mana symbol, if the player
+    /// has enough life left to pay it.
+    fn life_alternative(&self, db: &Database, source: CardId) -> Option<u32> {
+        let controller = db[source].controller;
+        let card = self
+            .reason
+            .reason
+            .as_ref()
+            .map_or(Some(source), |reason| reason.card());
+
+        let life =
+            Player::life_for_mana(db, controller.into(), card, self.first_unpaid(db, source)?)?;
+        if db.all_players[controller].life_total >= (self.life_paid + life) as i32 {
+            Some(life)
+        } else {
+            None
+        }
+    }
+
+    fn x_paid(&self) -> u32 {
+        let paid = |cost: ManaCost| {
             self.paid
-                .entry(ManaCost::X.value())
-                .or_default()
-                .mana_to_source
-                .values()
+                .get(&cost.value())
+                .iter()
+                .flat_map(|m| m.mana_to_source.values())
                 .flat_map(|m| m.source_to_count.values())
-                .sum::<u32>(),
-            self.paid
-                .entry(ManaCost::TWO_X.value())
+                .sum::<u32>()
+        };
+
+        u32::max(paid(ManaCost::X), paid(ManaCost::TWO_X) / 2)
+    }
+
+    /// The smallest and largest legal values of X, limited by the mana left to pay for it. The
+    /// smallest value is larger than the largest if no legal value of X can be paid.
+    fn x_range(&self, db: &Database, source: CardId) -> (u32, u32) {
+        let (min, max) = self.x.range(db, source);
+
+        let other = Reason::Other(Other::default());
+        let reason = self.reason.reason.as_ref().unwrap_or(&other);
+        let (mana, sources) = self.paying();
+        let spendable = db.all_players[db[source].controller]
+            .pool_post_pay(
+                db,
+                &mana.iter().map(|e| e.enum_value().unwrap()).collect_vec(),
+                &sources
+                    .iter()
+                    .map(|e| e.enum_value().unwrap())
+                    .collect_vec(),
+                reason,
+            )
+            .map(|pool| u32::try_from(pool.spendable_count(db, reason)).unwrap_or(u32::MAX))
+            .unwrap_or_default();
+        // Mana spent on the rest of the cost can't also be spent on X.
+        let unpaid_other = self
+            .paying
+            .iter()
+            .map(|cost| cost.enum_value().unwrap())
+            .filter(|cost| !matches!(cost, ManaCost::X | ManaCost::TWO_X))
+            .counts()
+            .into_iter()
+            .map(|(cost, symbols)| self.unpaid(cost, symbols as u32))
+            .sum::<u32>();
+        let spendable = spendable.saturating_sub(unpaid_other);
+
+        let payable = if self
+            .paying
+            .iter()
+            .any(|cost| cost.enum_value().unwrap() == ManaCost::TWO_X)
+        {
+            (self.x_paid() * 2).saturating_add(spendable) / 2
+        } else {
+            self.x_paid().saturating_add(spendable)
+        };
+
+        (min, max.min(payable))
+    }
+
+    /// Whether exactly one value of X can be paid, in which case it doesn't need to be announced.
+    fn x_forced(&self, db: &Database, source: CardId) -> bool {
+        let (min, max) = self.x_range(db, source);
+        min == max
+    }
+
+    /// Whether X is still below its minimum and must be paid for before paying can finish. X is
+    /// never below its minimum if it is forced, since it will be paid automatically.
+    fn x_below_minimum(&self, db: &Database, source: CardId) -> bool {
+        let (min, max) = self.x_range(db, source);
+        min != max && self.x_paid() < min
+    }
+
+    /// Finishes paying once only X is left, paying for it automatically if it is forced. Paying
+    /// can't finish while X is below its minimum.
+    fn finish_x(&mut self, db: &Database, source: CardId, pool: &mut ManaPool) -> SelectionResult {
+        if self.x_forced(db, source) {
+            self.pay_forced_x(db, source, pool);
+        }
+
+        if self.x_paid() < self.x_range(db, source).0 {
+            SelectionResult::PendingChoice
+        } else {
+            SelectionResult::Complete
+        }
+    }
+
+    /// Pays for X from the pool until it reaches its only legal value.
+    fn pay_forced_x(&mut self, db: &Database, source: CardId, pool: &mut ManaPool) {
+        let reason = self.reason.reason.clone().unwrap();
+        while let Some(cost @ (ManaCost::X | ManaCost::TWO_X)) =
+            self.first_unpaid_including_x(db, source)
+        {
+            let Some(max) = pool.max(db, &reason) else {
+                break;
+            };
+            let (spent, mana_source) = pool.spend(db, max, ManaSource::ANY, &reason);
+            if !spent {
+                break;
+            }
+
+            *self
+                .paid
+                .entry(cost.value())
                 .or_default()
                 .mana_to_source
-                .values()
-                .flat_map(|m| m.source_to_count.values())
-                .sum::<u32>()
-                / 2,
-        )
+                .entry(max.value())
+                .or_default()
+                .source_to_count
+                .entry(mana_source.value())
+                .or_default() += 1;
+        }
     }
 
     fn paying(
@@ -93,6 +93,18 @@ impl EffectBehaviors for SacrificePermanent {
 }
 
 impl SacrificePermanent {
+    /// Whether the controller has a permanent they can sacrifice to pay the cost.
+    pub(crate) fn can_be_paid(
+        &self,
+        db: &Database,
+        controller: Controller,
+        source: CardId,
+    ) -> bool {
+        self.compute_targets(db, controller, Some(source), &[])
+            .next()
+            .is_some()
+    }
+
     fn compute_targets<'db>(
         &'db self,
         db: &'db Database,
@@ -104,18 +116,20 @@ impl SacrificePermanent {
             .iter()
             .copied()
             .filter(move |card| {
-                card.passes_restrictions(
-                    db,
-                    LogId::current(db),
-                    source.unwrap(),
-                    &self.restrictions,
-                ) && !already_selected.iter().any(|selected| {
-                    if let Some(id) = selected.id(db) {
-                        id == *card
-                    } else {
-                        false
-                    }
-                })
+                !card.sacrifice_prevented(db)
+                    && card.passes_restrictions(
+                        db,
+                        LogId::current(db),
+                        source.unwrap(),
+                        &self.restrictions,
+                    )
+                    && !already_selected.iter().any(|selected| {
+                        if let Some(id) = selected.id(db) {
+                            id == *card
+                        } else {
+                            false
+                        }
+                    })
             })
     }
 }
@@ -0,0 +1,24 @@
+use crate::{
+    effects::{EffectBehaviors, EffectBundle, SelectedStack},
+    in_play::{CardId, Database},
+    protogen::effects::PhaseOut,
+};
+
+impl EffectBehaviors for PhaseOut {
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        _source: Option<CardId>,
+        selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        for target in selected.iter() {
+            let target = target.id(db).unwrap();
+            if !target.phased_out(db) {
+                target.phase_out(db);
+            }
+        }
+
+        vec![]
+    }
+}
@@ -0,0 +1,116 @@
+use itertools::Itertools;
+
+use crate::{
+    effects::{controller, EffectBehaviors, EffectBundle, Options, SelectedStack, SelectionResult},
+    in_play::{CardId, Database},
+    protogen::{
+        effects::{CreateTokenCloneOfSelected, Populate},
+        targets::Location,
+        types::Type,
+    },
+    stack::{Selected, TargetType},
+    types::TypeSet,
+};
+
+impl EffectBehaviors for Populate {
+    fn description(
+        &self,
+        _db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> String {
+        "Select a creature token to copy".to_string()
+    }
+
+    fn wants_input(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> bool {
+        self.chosen.is_none() && candidates(db, source).len() > 1
+    }
+
+    fn options(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> Options {
+        Options::MandatoryList(
+            candidates(db, source)
+                .into_iter()
+                .map(|card| card.name(db).clone())
+                .enumerate()
+                .collect_vec(),
+        )
+    }
+
+    fn select(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        option: Option<usize>,
+        _selected: &mut SelectedStack,
+    ) -> SelectionResult {
+        if let Some(option) = option {
+            let card = candidates(db, source)[option];
+            self.chosen = protobuf::MessageField::some(card.into());
+            SelectionResult::Complete
+        } else {
+            SelectionResult::PendingChoice
+        }
+    }
+
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        _selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        let copying = if let Some(chosen) = self.chosen.take() {
+            Some(CardId::from(chosen))
+        } else {
+            candidates(db, source).into_iter().exactly_one().ok()
+        };
+
+        let Some(copying) = copying else {
+            return vec![];
+        };
+
+        vec![EffectBundle {
+            push_on_enter: Some(vec![
+                Selected {
+                    location: Some(Location::ON_BATTLEFIELD),
+                    target_type: TargetType::Card(copying),
+                    targeted: false,
+                    restrictions: vec![],
+                },
+                Selected {
+                    location: None,
+                    target_type: TargetType::Player(controller(db, source)),
+                    targeted: false,
+                    restrictions: vec![],
+                },
+            ]),
+            source,
+            effects: vec![CreateTokenCloneOfSelected::default().into()],
+            ..Default::default()
+        }]
+    }
+}
+
+/// The creature tokens the source's controller controls.
+fn candidates(db: &Database, source: Option<CardId>) -> Vec<CardId> {
+    db.battlefield[controller(db, source)]
+        .iter()
+        .copied()
+        .filter(|card| {
+            db[*card].token && card.types_intersect(db, &TypeSet::from([Type::CREATURE]))
+        })
+        .collect_vec()
+}
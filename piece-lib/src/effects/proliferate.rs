@@ -0,0 +1,111 @@
+use itertools::Itertools;
+
+use crate::{
+    counters::add_lore_counters,
+    effects::{EffectBehaviors, EffectBundle, Options, SelectedStack, SelectionResult},
+    in_play::{CardId, Database},
+    protogen::{counters::Counter, effects::Proliferate},
+    stack::Selected,
+};
+
+impl EffectBehaviors for Proliferate {
+    fn description(
+        &self,
+        _db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> String {
+        "Select permanents to proliferate".to_string()
+    }
+
+    fn wants_input(
+        &self,
+        db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> bool {
+        self.candidates(db).next().is_some()
+    }
+
+    fn options(
+        &self,
+        db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> Options {
+        Options::OptionalList(
+            self.candidates(db)
+                .map(|card| card.name(db).clone())
+                .enumerate()
+                .collect_vec(),
+        )
+    }
+
+    fn select(
+        &mut self,
+        db: &mut Database,
+        _source: Option<CardId>,
+        option: Option<usize>,
+        _selected: &mut SelectedStack,
+    ) -> SelectionResult {
+        if let Some(option) = option {
+            let card = self.candidates(db).nth(option).unwrap();
+            self.selected.push(card.into());
+
+            if self.candidates(db).next().is_some() {
+                SelectionResult::PendingChoice
+            } else {
+                SelectionResult::Complete
+            }
+        } else {
+            SelectionResult::Complete
+        }
+    }
+
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        _source: Option<CardId>,
+        _selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        let mut results = vec![];
+        for card in self.selected.drain(..).map(CardId::from).collect_vec() {
+            for (_, count) in db[card]
+                .counters
+                .iter_mut()
+                .filter(|(counter, count)| **counter != Counter::LORE && **count > 0)
+            {
+                *count += 1;
+            }
+            card.apply_modifiers_layered(db);
+
+            if card.lore(db) > 0 {
+                results.extend(add_lore_counters(db, card, 1));
+            }
+        }
+
+        results
+    }
+}
+
+impl Proliferate {
+    /// The permanents with counters on them which haven't been chosen yet.
+    fn candidates<'db>(&'db self, db: &'db Database) -> impl Iterator<Item = CardId> + 'db {
+        db.battlefield
+            .battlefields
+            .values()
+            .flat_map(|battlefield| battlefield.iter())
+            .copied()
+            .filter(move |card| {
+                db[*card].counters.values().any(|count| *count > 0)
+                    && !self
+                        .selected
+                        .iter()
+                        .any(|selected| CardId::from(selected.clone()) == *card)
+            })
+    }
+}
@@ -0,0 +1,139 @@
+use itertools::Itertools;
+
+use crate::{
+    effects::{EffectBehaviors, EffectBundle, Options, SelectedStack, SelectionResult},
+    filter::CardFilter,
+    in_play::{CardId, Database},
+    player::Owner,
+    protogen::effects::{count::Fixed, effect, Count, DamageSelected, Effect, RedirectDamage},
+    stack::Selected,
+};
+
+impl EffectBehaviors for RedirectDamage {
+    fn priority(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> Owner {
+        if !self.damage_source_chooses {
+            db[CardId::from(self.source.clone().unwrap())]
+                .controller
+                .into()
+        } else if let Some(source) = source {
+            db[source].controller.into()
+        } else {
+            db.turn.priority_player()
+        }
+    }
+
+    fn wants_input(
+        &self,
+        db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> bool {
+        let candidates = self.candidates(db).len();
+        candidates > 1 || (candidates == 1 && self.optional)
+    }
+
+    fn options(
+        &self,
+        db: &Database,
+        _source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> Options {
+        let candidates = self
+            .candidates(db)
+            .into_iter()
+            .map(|card| card.name(db).clone())
+            .enumerate()
+            .collect_vec();
+
+        if self.optional {
+            Options::OptionalList(candidates)
+        } else {
+            Options::MandatoryList(candidates)
+        }
+    }
+
+    fn select(
+        &mut self,
+        db: &mut Database,
+        _source: Option<CardId>,
+        option: Option<usize>,
+        _selected: &mut SelectedStack,
+    ) -> SelectionResult {
+        if let Some(option) = option {
+            let card = self.candidates(db)[option];
+            self.redirect_to = protobuf::MessageField::some(card.into());
+            SelectionResult::Complete
+        } else if self.optional {
+            SelectionResult::Complete
+        } else {
+            SelectionResult::PendingChoice
+        }
+    }
+
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        let count = self.count.count(db, source, selected);
+
+        let redirect_to = if let Some(redirect_to) = self.redirect_to.as_ref() {
+            Some(CardId::from(redirect_to.clone()))
+        } else if !self.optional {
+            self.candidates(db).into_iter().exactly_one().ok()
+        } else {
+            None
+        };
+
+        if let Some(redirect_to) = redirect_to {
+            redirect_to.mark_damage(db, source, count as u32);
+            vec![]
+        } else {
+            DamageSelected {
+                count: protobuf::MessageField::some(Count {
+                    count: Some(
+                        Fixed {
+                            count,
+                            ..Default::default()
+                        }
+                        .into(),
+                    ),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }
+            .apply(db, source, selected, true)
+        }
+    }
+
+    fn apply_replacement(&self, source: CardId, effect: Effect) -> Vec<Effect> {
+        match effect.effect {
+            Some(effect::Effect::DamageSelected(damage)) => vec![RedirectDamage {
+                count: damage.count,
+                source: protobuf::MessageField::some(source.into()),
+                ..self.clone()
+            }
+            .into()],
+            _ => vec![effect],
+        }
+    }
+}
+
+impl RedirectDamage {
+    /// The permanents the damage may be redirected to.
+    fn candidates(&self, db: &Database) -> Vec<CardId> {
+        CardFilter::on_battlefield()
+            .passing(self.source.clone().unwrap().into(), &self.restrictions)
+            .cards(db)
+    }
+}
@@ -0,0 +1,23 @@
+use crate::{
+    effects::{EffectBehaviors, EffectBundle, SelectedStack},
+    in_play::{CardId, Database},
+    protogen::effects::Regenerate,
+};
+
+impl EffectBehaviors for Regenerate {
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        _source: Option<CardId>,
+        selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        for target in selected.iter() {
+            if let Some(card) = target.id(db) {
+                db[card].regeneration_shields += 1;
+            }
+        }
+
+        vec![]
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{
+    effects::{EffectBehaviors, EffectBundle, SelectedStack},
+    in_play::{CardId, Database},
+    protogen::{counters::Counter, effects::RemoveAllCounters},
+};
+
+impl EffectBehaviors for RemoveAllCounters {
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        _source: Option<CardId>,
+        selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        for target in selected.iter() {
+            let Some(card) = target.id(db) else {
+                continue;
+            };
+
+            match self.counter.enum_value().unwrap() {
+                Counter::ANY => db[card].counters.clear(),
+                counter => {
+                    db[card].counters.remove(&counter);
+                }
+            }
+            card.apply_modifiers_layered(db);
+        }
+
+        vec![]
+    }
+}
@@ -22,6 +22,7 @@ impl EffectBehaviors for RemoveCounters {
             .entry(self.counter.enum_value().unwrap())
             .or_default()
             .saturating_sub(count as u32);
+        target.apply_modifiers_layered(db);
 
         vec![]
     }
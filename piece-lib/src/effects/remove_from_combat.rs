@@ -0,0 +1,21 @@
+use crate::{
+    effects::{EffectBehaviors, EffectBundle, SelectedStack},
+    in_play::{CardId, Database},
+    protogen::effects::RemoveFromCombat,
+};
+
+impl EffectBehaviors for RemoveFromCombat {
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        _source: Option<CardId>,
+        selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        for target in selected.iter() {
+            target.id(db).unwrap().remove_from_combat(db);
+        }
+
+        vec![]
+    }
+}
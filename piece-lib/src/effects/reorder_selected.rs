@@ -10,12 +10,12 @@ use crate::{
 impl EffectBehaviors for ReorderSelected {
     fn wants_input(
         &self,
-        _db: &Database,
+        db: &Database,
         _source: Option<CardId>,
         already_selected: &[Selected],
         _modes: &[usize],
     ) -> bool {
-        already_selected.len() > 1
+        already_selected.len() > 1 && !all_identical(db, already_selected)
     }
 
     fn options(
@@ -32,18 +32,37 @@ impl EffectBehaviors for ReorderSelected {
         let start_at = self.reordering as usize;
         let (_, options) = already_selected.split_at(start_at);
 
-        let mut results = vec![];
+        // Identical entries are listed once, since it doesn't matter which of them goes first.
+        let mut results: Vec<(usize, String, usize)> = vec![];
         for (idx, option) in options.iter().enumerate() {
-            let idx = idx + start_at;
-            results.push((idx, option.display(db)))
+            if let Some((_, _, count)) = results
+                .iter_mut()
+                .find(|(first, _, _)| already_selected[*first].is_identical_stack_entry(db, option))
+            {
+                *count += 1;
+                continue;
+            }
+
+            results.push((idx + start_at, option.display(db), 1));
         }
 
+        let results = results
+            .into_iter()
+            .map(|(idx, display, count)| {
+                if count > 1 {
+                    (idx, format!("{} (x{})", display, count))
+                } else {
+                    (idx, display)
+                }
+            })
+            .collect_vec();
+
         Options::ListWithDefault(results)
     }
 
     fn select(
         &mut self,
-        _db: &mut Database,
+        db: &mut Database,
         _source: Option<CardId>,
         option: Option<usize>,
         selected: &mut SelectedStack,
@@ -51,7 +70,9 @@ impl EffectBehaviors for ReorderSelected {
         if let Some(option) = option {
             selected.swap(self.reordering as usize, option);
             self.reordering += 1;
-            if self.reordering as usize == selected.len() {
+            if self.reordering as usize == selected.len()
+                || all_identical(db, &selected[self.reordering as usize..])
+            {
                 SelectionResult::Complete
             } else {
                 SelectionResult::PendingChoice
@@ -77,7 +98,10 @@ impl EffectBehaviors for ReorderSelected {
                     db.stack.entries.swap_indices(target_stack_index, swapping);
                     target_stack_index += 1;
                 }
-                TargetType::ReplacementAbility(replacement) => {
+                TargetType::ReplacementAbility {
+                    source: replacing,
+                    replacement,
+                } => {
                     for replacement in replacement.effects.iter() {
                         replaced = replaced
                             .into_iter()
@@ -86,7 +110,7 @@ impl EffectBehaviors for ReorderSelected {
                                     .effect
                                     .as_ref()
                                     .unwrap()
-                                    .apply_replacement(effect)
+                                    .apply_replacement(*replacing, effect)
                             })
                             .collect_vec()
                     }
@@ -104,3 +128,11 @@ impl EffectBehaviors for ReorderSelected {
         }]
     }
 }
+
+/// Whether every selection is an identical stack entry, in which case their order doesn't matter.
+fn all_identical(db: &Database, selected: &[Selected]) -> bool {
+    selected
+        .iter()
+        .tuple_windows()
+        .all(|(lhs, rhs)| lhs.is_identical_stack_entry(db, rhs))
+}
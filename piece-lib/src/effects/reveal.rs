@@ -14,7 +14,7 @@ impl EffectBehaviors for Reveal {
     ) -> Vec<EffectBundle> {
         for target in selected.iter() {
             let target = target.id(db).unwrap();
-            db[target].revealed = true;
+            target.reveal(db);
         }
 
         vec![]
@@ -14,6 +14,11 @@ impl EffectBehaviors for Sacrifice {
         selected: &mut SelectedStack,
         _skip_replacement: bool,
     ) -> Vec<EffectBundle> {
+        selected.retain(|target| {
+            target
+                .id(db)
+                .is_none_or(|card| !card.sacrifice_prevented(db))
+        });
         move_card_to_graveyard(db, selected, source)
     }
 }
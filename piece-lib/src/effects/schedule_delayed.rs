@@ -0,0 +1,66 @@
+use itertools::Itertools;
+
+use crate::{
+    effects::{controller, EffectBehaviors, EffectBundle, SelectedStack},
+    in_play::{CardId, Database},
+    player::Owner,
+    protogen::effects::{DelayedTriggerPoint, ScheduleDelayed, TriggeredAbility},
+    turns::Phase,
+};
+
+impl EffectBehaviors for ScheduleDelayed {
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        let (player, phase) = self.fires_on(db, source);
+        let trigger = TriggeredAbility {
+            effects: self.effects.clone(),
+            oracle_text: self.oracle_text.clone(),
+            ..Default::default()
+        };
+
+        let mut listeners = selected
+            .iter()
+            .filter_map(|selected| selected.id(db))
+            .collect_vec();
+        if listeners.is_empty() {
+            listeners.extend(source);
+        }
+
+        let scheduled = db
+            .delayed_triggers
+            .entry(player)
+            .or_default()
+            .entry(phase)
+            .or_default();
+        for listener in listeners {
+            scheduled.push((listener, trigger.clone()));
+        }
+
+        vec![]
+    }
+}
+
+impl ScheduleDelayed {
+    /// The player whose turn the effects happen on and the phase they happen in.
+    fn fires_on(&self, db: &Database, source: Option<CardId>) -> (Owner, Phase) {
+        let next = |phase: Phase| {
+            if db.turn.phase < phase {
+                (db.turn.active_player(), phase)
+            } else {
+                (db.turn.next_player(), phase)
+            }
+        };
+
+        match self.at.enum_value().unwrap() {
+            DelayedTriggerPoint::NEXT_END_STEP => next(Phase::EndStep),
+            DelayedTriggerPoint::NEXT_UPKEEP => next(Phase::Upkeep),
+            DelayedTriggerPoint::YOUR_NEXT_END_STEP => (controller(db, source), Phase::EndStep),
+            DelayedTriggerPoint::YOUR_NEXT_UPKEEP => (controller(db, source), Phase::Upkeep),
+        }
+    }
+}
@@ -38,6 +38,16 @@ impl EffectBehaviors for SelectDestinations {
         }
     }
 
+    fn target_for_option(
+        &self,
+        _db: &Database,
+        _source: Option<CardId>,
+        already_selected: &[Selected],
+        option: usize,
+    ) -> Option<Selected> {
+        already_selected.get(option).cloned()
+    }
+
     fn select(
         &mut self,
         db: &mut Database,
@@ -39,6 +39,18 @@ impl EffectBehaviors for SelectForEachPlayer {
         }
     }
 
+    fn target_for_option(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        already_selected: &[Selected],
+        option: usize,
+    ) -> Option<Selected> {
+        self.valid_targets(db, already_selected, source)
+            .nth(option)
+            .map(|card| Selected::card(db, card))
+    }
+
     fn select(
         &mut self,
         db: &mut Database,
@@ -75,7 +87,7 @@ impl EffectBehaviors for SelectForEachPlayer {
                         source.unwrap(),
                         &self.restrictions,
                     )
-                    && (!self.targeted || card.can_be_targeted(db, db[source.unwrap()].controller))
+                    && (!self.targeted || card.can_be_targeted(db, source.unwrap()))
             })
         {
             SelectionResult::Complete
@@ -116,7 +128,7 @@ impl SelectForEachPlayer {
                     source.unwrap(),
                     &self.restrictions,
                 )
-                && (!self.targeted || card.can_be_targeted(db, db[source.unwrap()].controller))
+                && (!self.targeted || card.can_be_targeted(db, source.unwrap()))
         })
     }
 }
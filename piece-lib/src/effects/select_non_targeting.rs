@@ -27,25 +27,25 @@ impl EffectBehaviors for SelectNonTargeting {
         _modes: &[usize],
     ) -> Options {
         Options::OptionalList(
-            db.cards
-                .keys()
-                .copied()
-                .filter(|card| {
-                    card.passes_restrictions(
-                        db,
-                        LogId::current(db),
-                        source.unwrap(),
-                        &self.restrictions,
-                    ) && !already_selected
-                        .iter()
-                        .any(|selected| selected.id(db).unwrap() == *card)
-                })
+            self.valid_targets(db, source, already_selected)
                 .map(|card| card.name(db).clone())
                 .enumerate()
                 .collect_vec(),
         )
     }
 
+    fn target_for_option(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        already_selected: &[Selected],
+        option: usize,
+    ) -> Option<Selected> {
+        self.valid_targets(db, source, already_selected)
+            .nth(option)
+            .map(|card| Selected::card(db, card))
+    }
+
     fn select(
         &mut self,
         db: &mut Database,
@@ -54,20 +54,8 @@ impl EffectBehaviors for SelectNonTargeting {
         selected: &mut SelectedStack,
     ) -> SelectionResult {
         if let Some(option) = option {
-            let card = db
-                .cards
-                .keys()
-                .copied()
-                .filter(|card| {
-                    card.passes_restrictions(
-                        db,
-                        LogId::current(db),
-                        source.unwrap(),
-                        &self.restrictions,
-                    ) && !selected
-                        .iter()
-                        .any(|selected| selected.id(db).unwrap() == *card)
-                })
+            let card = self
+                .valid_targets(db, source, selected)
                 .nth(option)
                 .unwrap();
 
@@ -101,3 +89,19 @@ impl EffectBehaviors for SelectNonTargeting {
         vec![]
     }
 }
+
+impl SelectNonTargeting {
+    fn valid_targets<'db>(
+        &'db self,
+        db: &'db Database,
+        source: Option<CardId>,
+        already_selected: &'db [Selected],
+    ) -> impl Iterator<Item = CardId> + 'db {
+        db.cards.keys().copied().filter(move |card| {
+            card.passes_restrictions(db, LogId::current(db), source.unwrap(), &self.restrictions)
+                && !already_selected
+                    .iter()
+                    .any(|selected| selected.id(db).unwrap() == *card)
+        })
+    }
+}
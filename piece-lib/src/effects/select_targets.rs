@@ -4,7 +4,7 @@ use crate::{
     effects::{EffectBehaviors, EffectBundle, Options, SelectedStack, SelectionResult},
     in_play::{CardId, Database},
     log::{Log, LogId},
-    protogen::effects::SelectTargets,
+    protogen::{effects::SelectTargets, targets::Location},
     stack::{Selected, TargetType},
 };
 
@@ -26,36 +26,9 @@ impl EffectBehaviors for SelectTargets {
         already_selected: &[Selected],
         _modes: &[usize],
     ) -> Options {
-        let options = db
-            .cards
-            .keys()
-            .copied()
-            .filter(|card| {
-                card.passes_restrictions(
-                    db,
-                    LogId::current(db),
-                    source.unwrap(),
-                    &self.restrictions,
-                ) && !already_selected
-                    .iter()
-                    .filter_map(|selected| selected.id(db))
-                    .any(|selected| selected == *card)
-            })
-            .map(|card| card.name(db).clone())
-            .chain(
-                db.all_players
-                    .all_players()
-                    .into_iter()
-                    .filter(|player| {
-                        player.passes_restrictions(
-                            db,
-                            LogId::current(db),
-                            db[source.unwrap()].controller,
-                            &self.restrictions,
-                        )
-                    })
-                    .map(|player| db.all_players[player].name.clone()),
-            )
+        let options = self
+            .valid_targets(db, source, already_selected)
+            .map(|target| target.display(db))
             .enumerate()
             .collect_vec();
 
@@ -66,6 +39,16 @@ impl EffectBehaviors for SelectTargets {
         }
     }
 
+    fn target_for_option(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        already_selected: &[Selected],
+        option: usize,
+    ) -> Option<Selected> {
+        self.valid_targets(db, source, already_selected).nth(option)
+    }
+
     fn select(
         &mut self,
         db: &mut Database,
@@ -73,51 +56,10 @@ impl EffectBehaviors for SelectTargets {
         option: Option<usize>,
         selected: &mut SelectedStack,
     ) -> SelectionResult {
-        let mut targets = db
-            .cards
-            .keys()
-            .copied()
-            .filter(|card| {
-                {
-                    card.passes_restrictions(
-                        db,
-                        LogId::current(db),
-                        source.unwrap(),
-                        &self.restrictions,
-                    ) && !selected
-                        .iter()
-                        .filter_map(|target| target.id(db))
-                        .any(|target| target == *card)
-                }
-            })
-            .map(|card| Selected {
-                location: card.location(db),
-                target_type: TargetType::Card(card),
-                targeted: true,
-                restrictions: self.restrictions.clone(),
-            })
-            .chain(
-                db.all_players
-                    .all_players()
-                    .into_iter()
-                    .filter(|player| {
-                        player.passes_restrictions(
-                            db,
-                            LogId::current(db),
-                            db[source.unwrap()].controller,
-                            &self.restrictions,
-                        )
-                    })
-                    .map(|player| Selected {
-                        location: None,
-                        target_type: TargetType::Player(player),
-                        targeted: true,
-                        restrictions: self.restrictions.clone(),
-                    }),
-            );
+        let mut targets = self.valid_targets(db, source, selected).collect_vec();
 
         if let Some(option) = option {
-            let target = targets.nth(option).unwrap();
+            let target = targets.swap_remove(option);
 
             selected.push(target);
 
@@ -127,7 +69,7 @@ impl EffectBehaviors for SelectTargets {
             } else {
                 SelectionResult::PendingChoice
             }
-        } else if self.optional || targets.next().is_none() {
+        } else if self.optional || targets.is_empty() {
             SelectionResult::Complete
         } else {
             SelectionResult::PendingChoice
@@ -150,3 +92,54 @@ impl EffectBehaviors for SelectTargets {
         vec![]
     }
 }
+
+impl SelectTargets {
+    pub(crate) fn valid_targets<'db>(
+        &'db self,
+        db: &'db Database,
+        source: Option<CardId>,
+        already_selected: &'db [Selected],
+    ) -> impl Iterator<Item = Selected> + 'db {
+        db.cards
+            .keys()
+            .copied()
+            .filter(move |card| {
+                card.passes_restrictions(
+                    db,
+                    LogId::current(db),
+                    source.unwrap(),
+                    &self.restrictions,
+                ) && (!card.is_in_location(db, Location::ON_BATTLEFIELD)
+                    || card.can_be_targeted(db, source.unwrap()))
+                    && !already_selected
+                        .iter()
+                        .filter_map(|selected| selected.id(db))
+                        .any(|selected| selected == *card)
+            })
+            .map(move |card| Selected {
+                location: card.location(db),
+                target_type: TargetType::Card(card),
+                targeted: true,
+                restrictions: self.restrictions.clone(),
+            })
+            .chain(
+                db.all_players
+                    .all_players()
+                    .into_iter()
+                    .filter(move |player| {
+                        player.passes_restrictions(
+                            db,
+                            LogId::current(db),
+                            db[source.unwrap()].controller,
+                            &self.restrictions,
+                        )
+                    })
+                    .map(move |player| Selected {
+                        location: None,
+                        target_type: TargetType::Player(player),
+                        targeted: true,
+                        restrictions: self.restrictions.clone(),
+                    }),
+            )
+    }
+}
@@ -0,0 +1,50 @@
+use indexmap::IndexSet;
+use rand::thread_rng;
+
+use crate::{
+    battlefield::Battlefields,
+    effects::{EffectBehaviors, EffectBundle, SelectedStack},
+    in_play::{CardId, Database},
+    library::Library,
+    log::LogId,
+    protogen::effects::ShuffleIntoLibrary,
+};
+
+impl EffectBehaviors for ShuffleIntoLibrary {
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        let mut pending = vec![];
+        let mut shuffling = IndexSet::new();
+        for target in selected.iter() {
+            let Some(card) = target.id(db) else {
+                continue;
+            };
+
+            if !target.targeted
+                || card.passes_restrictions(
+                    db,
+                    LogId::current(db),
+                    source.unwrap(),
+                    &target.restrictions,
+                )
+            {
+                let owner = db[card].owner;
+                pending.extend(Battlefields::maybe_leave_battlefield(db, card));
+                Library::place_on_top(db, owner, card);
+                shuffling.insert(owner);
+            }
+        }
+
+        // Shuffle once all the cards have moved, so no library is shuffled more than once.
+        for owner in shuffling {
+            Library::reshuffle(db, owner, &mut thread_rng());
+        }
+
+        pending
+    }
+}
@@ -26,7 +26,7 @@ impl EffectBehaviors for Tap {
                     listener,
                     &trigger.trigger.restrictions,
                 ) {
-                    pending.push(Stack::move_trigger_to_stack(db, listener, trigger));
+                    pending.extend(Stack::move_trigger_to_stack(db, listener, trigger));
                 }
             }
         }
@@ -34,6 +34,18 @@ impl EffectBehaviors for TutorLibrary {
         )
     }
 
+    fn target_for_option(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        already_selected: &[Selected],
+        option: usize,
+    ) -> Option<Selected> {
+        self.valid_targets(db, source, already_selected)
+            .nth(option)
+            .map(|card| Selected::card(db, card))
+    }
+
     fn select(
         &mut self,
         db: &mut Database,
@@ -79,7 +91,7 @@ impl EffectBehaviors for TutorLibrary {
         {
             let card: CardId = card.clone().into();
             if self.reveal {
-                db[card].revealed = true;
+                card.reveal(db);
             }
 
             results.push(EffectBundle {
@@ -19,7 +19,7 @@ impl EffectBehaviors for Unless {
             }
             crate::stack::TargetType::Stack(_) => todo!(),
             crate::stack::TargetType::Ability { .. } => todo!(),
-            crate::stack::TargetType::ReplacementAbility(_) => todo!(),
+            crate::stack::TargetType::ReplacementAbility { .. } => todo!(),
             crate::stack::TargetType::Player(player) => player.passes_restrictions(
                 db,
                 LogId::current(db),
@@ -1,7 +1,7 @@
 use crate::{
     effects::{EffectBehaviors, EffectBundle, SelectedStack},
     in_play::{CardId, Database},
-    protogen::{counters::Counter, effects::Untap},
+    protogen::effects::Untap,
 };
 
 impl EffectBehaviors for Untap {
@@ -13,14 +13,7 @@ impl EffectBehaviors for Untap {
         _skip_replacement: bool,
     ) -> Vec<EffectBundle> {
         for target in selected.iter() {
-            let target = target.id(db).unwrap();
-
-            let stun = db[target].counters.entry(Counter::STUN).or_default();
-            if *stun > 0 {
-                *stun -= 1;
-            } else {
-                target.untap(db);
-            }
+            target.id(db).unwrap().untap(db);
         }
 
         vec![]
@@ -0,0 +1,153 @@
+use itertools::Itertools;
+
+use crate::{
+    effects::{controller, EffectBehaviors, EffectBundle, Options, SelectedStack, SelectionResult},
+    in_play::{CardId, Database},
+    log::{Log, LogId},
+    player::Owner,
+    protogen::effects::Vote,
+    stack::Selected,
+};
+
+impl EffectBehaviors for Vote {
+    fn priority(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> Owner {
+        self.voter(db, source)
+            .unwrap_or_else(|| controller(db, source))
+    }
+
+    fn description(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> String {
+        if let Some(player) = self.voter(db, source) {
+            format!("{} votes", db.all_players[player].name)
+        } else {
+            "Each player votes".to_string()
+        }
+    }
+
+    fn wants_input(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> bool {
+        self.voter(db, source).is_some() && self.candidates(db, source).next().is_some()
+    }
+
+    fn options(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        _modes: &[usize],
+    ) -> Options {
+        Options::MandatoryList(
+            self.candidates(db, source)
+                .map(|card| {
+                    let votes = self.votes_for(card);
+                    if votes == 0 {
+                        card.name(db).clone()
+                    } else {
+                        format!("{} ({} votes)", card.name(db), votes)
+                    }
+                })
+                .enumerate()
+                .collect_vec(),
+        )
+    }
+
+    fn target_for_option(
+        &self,
+        db: &Database,
+        source: Option<CardId>,
+        _already_selected: &[Selected],
+        option: usize,
+    ) -> Option<Selected> {
+        self.candidates(db, source)
+            .nth(option)
+            .map(|card| Selected::card(db, card))
+    }
+
+    fn select(
+        &mut self,
+        db: &mut Database,
+        source: Option<CardId>,
+        option: Option<usize>,
+        _selected: &mut SelectedStack,
+    ) -> SelectionResult {
+        let Some(card) = option.and_then(|option| self.candidates(db, source).nth(option)) else {
+            return SelectionResult::PendingChoice;
+        };
+
+        self.votes.push(card.into());
+        if self.voter(db, source).is_some() {
+            SelectionResult::PendingChoice
+        } else {
+            SelectionResult::Complete
+        }
+    }
+
+    fn apply(
+        &mut self,
+        db: &mut Database,
+        _source: Option<CardId>,
+        selected: &mut SelectedStack,
+        _skip_replacement: bool,
+    ) -> Vec<EffectBundle> {
+        let tally = self
+            .votes
+            .iter()
+            .map(|card| CardId::from(card.clone()))
+            .counts();
+        let most = tally.values().max().copied().unwrap_or_default();
+
+        selected.clear();
+        for card in self
+            .votes
+            .iter()
+            .map(|card| CardId::from(card.clone()))
+            .unique()
+            .filter(|card| tally[card] == most)
+        {
+            Log::card_chosen(db, card);
+            selected.push(Selected::card(db, card));
+        }
+
+        vec![]
+    }
+}
+
+impl Vote {
+    /// The player casting the next vote, if anyone has yet to vote.
+    fn voter(&self, db: &Database, source: Option<CardId>) -> Option<Owner> {
+        db.all_players
+            .starting_with(controller(db, source))
+            .get(self.votes.len())
+            .copied()
+    }
+
+    fn votes_for(&self, card: CardId) -> usize {
+        self.votes.iter().filter(|vote| **vote == card).count()
+    }
+
+    fn candidates<'db>(
+        &'db self,
+        db: &'db Database,
+        source: Option<CardId>,
+    ) -> impl Iterator<Item = CardId> + 'db {
+        db.cards.keys().copied().filter(move |card| {
+            card.passes_restrictions(db, LogId::current(db), source.unwrap(), &self.restrictions)
+        })
+    }
+}
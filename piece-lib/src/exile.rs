@@ -5,7 +5,7 @@ use crate::{
     player::{Controller, Owner},
 };
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct Exiles {
     pub(crate) exile_zones: IndexMap<Owner, IndexSet<CardId>>,
 }
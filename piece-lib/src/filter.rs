@@ -0,0 +1,110 @@
+//! Matching cards by location, controller, and restrictions using the same rules the engine uses
+//! internally.
+
+use itertools::Itertools;
+
+use crate::{
+    in_play::{CardId, Database},
+    log::LogId,
+    player::Controller,
+    protogen::targets::{Location, Restriction},
+};
+
+/// A set of conditions a card must meet. Every condition added to the filter must pass for a card
+/// to match, so filters are built up by chaining the `in_`, `controlled_by`, `passing`, and
+/// `matching` methods.
+///
+/// Phased out permanents never match a filter looking at the battlefield.
+#[derive(Debug, Clone, Default)]
+pub struct CardFilter {
+    locations: Vec<Location>,
+    controller: Option<Controller>,
+    restrictions: Option<(CardId, Vec<Restriction>)>,
+    predicates: Vec<fn(&Database, CardId) -> bool>,
+}
+
+impl CardFilter {
+    /// Matches every card in the game.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Matches every permanent on the battlefield.
+    pub fn on_battlefield() -> Self {
+        Self::any().in_locations([Location::ON_BATTLEFIELD])
+    }
+
+    /// Only match cards in one of the locations.
+    pub fn in_locations(mut self, locations: impl IntoIterator<Item = Location>) -> Self {
+        self.locations.extend(locations);
+        self
+    }
+
+    /// Only match cards controlled by the player.
+    pub fn controlled_by(mut self, controller: impl Into<Controller>) -> Self {
+        self.controller = Some(controller.into());
+        self
+    }
+
+    /// Only match cards which pass the restrictions. Restrictions which are relative to a card,
+    /// such as `NotSelf`, are evaluated relative to `source`.
+    pub fn passing(mut self, source: CardId, restrictions: &[Restriction]) -> Self {
+        self.restrictions = Some((source, restrictions.to_vec()));
+        self
+    }
+
+    /// Only match cards for which the predicate returns true.
+    pub fn matching(mut self, predicate: fn(&Database, CardId) -> bool) -> Self {
+        self.predicates.push(predicate);
+        self
+    }
+
+    pub fn matches(&self, db: &Database, card: CardId) -> bool {
+        (self.locations.is_empty()
+            || self
+                .locations
+                .iter()
+                .any(|location| card.is_in_location(db, *location)))
+            && self
+                .controller
+                .map(|controller| db[card].controller == controller)
+                .unwrap_or(true)
+            && self
+                .restrictions
+                .as_ref()
+                .map(|(source, restrictions)| {
+                    card.passes_restrictions(db, LogId::current(db), *source, restrictions)
+                })
+                .unwrap_or(true)
+            && self.predicates.iter().all(|predicate| predicate(db, card))
+    }
+
+    /// The matching cards. Permanents are listed in battlefield order when the filter only looks
+    /// at the battlefield.
+    pub fn cards(&self, db: &Database) -> Vec<CardId> {
+        if self.locations == [Location::ON_BATTLEFIELD] {
+            db.battlefield
+                .battlefields
+                .iter()
+                .filter(|(controller, _)| {
+                    self.controller
+                        .map(|expected| expected == **controller)
+                        .unwrap_or(true)
+                })
+                .flat_map(|(_, cards)| cards.iter())
+                .copied()
+                .filter(|card| self.matches(db, *card))
+                .collect_vec()
+        } else {
+            db.cards
+                .keys()
+                .copied()
+                .filter(|card| self.matches(db, *card))
+                .collect_vec()
+        }
+    }
+
+    pub fn count(&self, db: &Database) -> usize {
+        self.cards(db).len()
+    }
+}
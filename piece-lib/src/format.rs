@@ -0,0 +1,189 @@
+//! Deck construction rules for formats, e.g. which cards are banned and how many copies of a card
+//! a deck may contain. Formats are data files so they can be maintained without code changes. A
+//! frontend loads them with [Format::load_dir], checks decks with [Format::check], and starts
+//! games with [Format::game].
+
+use std::{collections::BTreeSet, fmt::Display, path::Path};
+
+use anyhow::Context;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{config::GameConfig, library::DeckDefinition, protogen::types::Type, Cards};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[non_exhaustive]
+pub struct Format {
+    pub name: String,
+    /// The sets cards must have been printed in. Any set is allowed if this is empty.
+    pub sets: BTreeSet<String>,
+    /// Cards which may not be in a deck.
+    pub banned: BTreeSet<String>,
+    /// Cards which may only have a single copy in a deck.
+    pub restricted: BTreeSet<String>,
+    /// The most copies of each card a deck may contain. Basic lands are unlimited.
+    pub max_copies: usize,
+    pub min_deck_size: usize,
+    pub max_deck_size: Option<usize>,
+    /// The rules games in this format are played with.
+    pub game: GameConfig,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Self {
+            name: String::default(),
+            sets: BTreeSet::default(),
+            banned: BTreeSet::default(),
+            restricted: BTreeSet::default(),
+            max_copies: 4,
+            min_deck_size: 60,
+            max_deck_size: None,
+            game: GameConfig::default(),
+        }
+    }
+}
+
+/// A reason a deck isn't legal in a format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Violation {
+    UnknownCard(String),
+    Banned(String),
+    NotInSets(String),
+    TooManyCopies {
+        name: String,
+        count: usize,
+        max: usize,
+    },
+    TooFewCards {
+        size: usize,
+        min: usize,
+    },
+    TooManyCards {
+        size: usize,
+        max: usize,
+    },
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Violation::UnknownCard(name) => write!(f, "{} is not a known card", name),
+            Violation::Banned(name) => write!(f, "{} is banned", name),
+            Violation::NotInSets(name) => write!(f, "{} is not in an allowed set", name),
+            Violation::TooManyCopies { name, count, max } => {
+                write!(
+                    f,
+                    "{} has {} copies, at most {} are allowed",
+                    name, count, max
+                )
+            }
+            Violation::TooFewCards { size, min } => {
+                write!(
+                    f,
+                    "The deck has {} cards, at least {} are required",
+                    size, min
+                )
+            }
+            Violation::TooManyCards { size, max } => {
+                write!(
+                    f,
+                    "The deck has {} cards, at most {} are allowed",
+                    size, max
+                )
+            }
+        }
+    }
+}
+
+impl Format {
+    pub fn load_from(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Reading format: {}", path.display()))?;
+        Ok(serde_yaml::from_str(&contents)?)
+    }
+
+    /// Loads every yaml file in `dir` as a format, sorted by name.
+    pub fn load_dir(dir: &Path) -> anyhow::Result<Vec<Self>> {
+        let mut formats = vec![];
+        for entry in
+            std::fs::read_dir(dir).with_context(|| format!("Reading formats: {}", dir.display()))?
+        {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_some_and(|ext| ext == "yaml" || ext == "yml")
+            {
+                formats.push(Self::load_from(&path)?);
+            }
+        }
+
+        formats.sort_by(|l, r| l.name.cmp(&r.name));
+        Ok(formats)
+    }
+
+    /// The most copies of `name` a deck may contain.
+    pub fn max_copies(&self, name: &str, cards: &Cards) -> Option<usize> {
+        if self.banned.contains(name) {
+            Some(0)
+        } else if self.restricted.contains(name) {
+            Some(1)
+        } else if cards.get(name).is_some_and(|card| {
+            card.typeline
+                .types
+                .iter()
+                .any(|ty| ty.enum_value().unwrap() == Type::BASIC)
+        }) {
+            None
+        } else {
+            Some(self.max_copies)
+        }
+    }
+
+    /// Every reason `deck` isn't legal in this format. The deck is legal if this is empty.
+    pub fn check(&self, deck: &DeckDefinition, cards: &Cards) -> Vec<Violation> {
+        let mut violations = vec![];
+        for (name, count) in deck.cards().sorted() {
+            let Some(card) = cards.get(name) else {
+                violations.push(Violation::UnknownCard(name.to_string()));
+                continue;
+            };
+
+            if self.banned.contains(name) {
+                violations.push(Violation::Banned(name.to_string()));
+                continue;
+            }
+
+            if !self.sets.is_empty() && card.sets.iter().all(|set| !self.sets.contains(set)) {
+                violations.push(Violation::NotInSets(name.to_string()));
+            }
+
+            if let Some(max) = self.max_copies(name, cards) {
+                if count > max {
+                    violations.push(Violation::TooManyCopies {
+                        name: name.to_string(),
+                        count,
+                        max,
+                    });
+                }
+            }
+        }
+
+        let size = deck.cards().map(|(_, count)| count).sum::<usize>();
+        if size < self.min_deck_size {
+            violations.push(Violation::TooFewCards {
+                size,
+                min: self.min_deck_size,
+            });
+        }
+        if let Some(max) = self.max_deck_size {
+            if size > max {
+                violations.push(Violation::TooManyCards { size, max });
+            }
+        }
+
+        violations
+    }
+}
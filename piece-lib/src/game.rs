@@ -0,0 +1,282 @@
+//! A single entry point for driving a match, for frontends and bots which would otherwise have to
+//! coordinate the [Database], the turn structure, and pending choices themselves.
+//!
+//! ```ignore
+//! let mut game = Game::new(&cards, [("Alice".to_string(), alice), ("Bob".to_string(), bob)])?;
+//! loop {
+//!     let player = game.player_to_act();
+//!     let action = choose(game.legal_actions(player));
+//!     game.apply(action)?;
+//! }
+//! ```
+
+use anyhow::bail;
+use itertools::Itertools;
+
+use crate::{
+    battlefield::Battlefields,
+    config::{GameConfig, Vanguard},
+    effects::{OptionDetail, Options, PendingEffects, SelectionResult},
+    in_play::Database,
+    library::DeckDefinition,
+    player::{Action, AllPlayers, Owner, Player},
+    turns::Turn,
+    Cards,
+};
+
+/// Something a player can do through [Game::apply]. See [Game::legal_actions].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GameAction {
+    /// Take one of the [Player::actions] available to the player. While a choice is pending, only
+    /// mana abilities can be activated.
+    Act(Action),
+    /// Pass priority. Once every player passes in succession, the top of the stack resolves or the
+    /// game moves on to the next phase.
+    PassPriority,
+    /// Choose an option of the [PendingChoice] by its index, or None to decline an optional choice
+    /// or use the default of a list with one.
+    Choose(Option<usize>),
+}
+
+/// A choice a player has to make before the game can continue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PendingChoice {
+    /// The player making the choice.
+    pub player: Owner,
+    pub description: String,
+    pub options: Vec<OptionDetail>,
+    /// Whether [GameAction::Choose] accepts None for this choice.
+    pub allows_none: bool,
+}
+
+pub struct Game {
+    db: Database,
+    players: Vec<Owner>,
+    pending: Option<PendingEffects>,
+    /// Whether the pending choice is ordering the stack, so the stack isn't ordered again once the
+    /// choice is made.
+    organizing_stack: bool,
+}
+
+impl Game {
+    /// Starts a game under the default [GameConfig] with a player for each named deck, in turn
+    /// order. See [Game::with_config].
+    pub fn new(
+        cards: &Cards,
+        decks: impl IntoIterator<Item = (String, DeckDefinition)>,
+    ) -> anyhow::Result<Self> {
+        Self::with_config(cards, GameConfig::default(), decks)
+    }
+
+    /// Starts a game with a player for each named deck, in turn order. Each library is shuffled
+    /// and every player draws their opening hand. Mulligans are left to the frontend, see
+    /// [Player::mulligan].
+    pub fn with_config(
+        cards: &Cards,
+        config: GameConfig,
+        decks: impl IntoIterator<Item = (String, DeckDefinition)>,
+    ) -> anyhow::Result<Self> {
+        let decks = decks.into_iter().collect_vec();
+        if decks.len() < 2 {
+            bail!("A game needs at least 2 players, got {}", decks.len());
+        }
+
+        for (name, def) in decks.iter() {
+            if let Some((card, _)) = def.cards().find(|(card, _)| !cards.contains_key(*card)) {
+                bail!("{}'s deck contains an unknown card: {}", name, card);
+            }
+
+            let size = def.cards().map(|(_, count)| count).sum::<usize>();
+            if size < config.starting_hand_size {
+                bail!(
+                    "{}'s deck has {} cards, which is too few to draw an opening hand of {}",
+                    name,
+                    size,
+                    config.starting_hand_size
+                );
+            }
+        }
+
+        let mut all_players = AllPlayers::new(config);
+        let players = decks
+            .iter()
+            .map(|(name, _)| all_players.join(name.clone(), Vanguard::default()))
+            .collect_vec();
+
+        let mut db = Database::new(all_players);
+        db.set_card_names(cards);
+        for (player, (_, def)) in players.iter().copied().zip(decks.iter()) {
+            db.all_players[player].library = def.build_deck(&mut db, cards, player);
+            db.all_players[player].library.shuffle();
+            Player::draw_initial_hand(&mut db, player);
+        }
+
+        Ok(Self {
+            db,
+            players,
+            pending: None,
+            organizing_stack: false,
+        })
+    }
+
+    pub fn db(&self) -> &Database {
+        &self.db
+    }
+
+    /// Direct access to the game, e.g. for mulligans or for an AI. Changes made here bypass the
+    /// checks in [Game::apply].
+    pub fn db_mut(&mut self) -> &mut Database {
+        &mut self.db
+    }
+
+    /// Every player, in turn order.
+    pub fn players(&self) -> &[Owner] {
+        &self.players
+    }
+
+    /// The player who has to make the pending choice, or who has priority if there isn't one.
+    pub fn player_to_act(&self) -> Owner {
+        self.pending
+            .as_ref()
+            .map(|pending| pending.priority(&self.db))
+            .unwrap_or_else(|| self.db.turn.priority_player())
+    }
+
+    pub fn pending_choice(&self) -> Option<PendingChoice> {
+        let pending = self.pending.as_ref()?;
+        let options = pending.option_details(&self.db);
+        Some(PendingChoice {
+            player: pending.priority(&self.db),
+            description: pending.description(&self.db),
+            allows_none: allows_none(&self.db, pending, &options),
+            options,
+        })
+    }
+
+    /// Everything the player can do right now. This is empty unless the player is the
+    /// [Game::player_to_act].
+    pub fn legal_actions(&self, player: Owner) -> Vec<GameAction> {
+        if player != self.player_to_act() {
+            return vec![];
+        }
+
+        let mut actions = vec![];
+        if let Some(pending) = self.pending.as_ref() {
+            let options = pending.option_details(&self.db);
+            if allows_none(&self.db, pending, &options) {
+                actions.push(GameAction::Choose(None));
+            }
+            actions.extend(
+                options
+                    .iter()
+                    .map(|option| GameAction::Choose(Some(option.index))),
+            );
+            actions.extend(
+                Player::actions(&self.db, player, &self.pending)
+                    .into_iter()
+                    .filter(|action| matches!(action, Action::Activate { mana: true, .. }))
+                    .map(GameAction::Act),
+            );
+        } else {
+            actions.push(GameAction::PassPriority);
+            actions.extend(
+                Player::actions(&self.db, player, &None)
+                    .into_iter()
+                    .map(GameAction::Act),
+            );
+        }
+
+        actions
+    }
+
+    /// Takes the action for the [Game::player_to_act], resolving everything which follows from it
+    /// until a player has to make a choice or take an action. Returns an error without changing
+    /// the game if the action isn't one of their [Game::legal_actions].
+    pub fn apply(&mut self, action: GameAction) -> anyhow::Result<()> {
+        let player = self.player_to_act();
+        if !self.legal_actions(player).contains(&action) {
+            bail!(
+                "{:?} is not a legal action for {}",
+                action,
+                self.db.all_players[player].name
+            );
+        }
+
+        match action {
+            GameAction::Act(Action::Play(card)) => {
+                let pending = Player::play_card(&mut self.db, player, card);
+                self.settle(pending);
+            }
+            GameAction::Act(Action::Activate { card, index, .. }) => {
+                self.db.turn.reset_passes();
+                let mut pending = Battlefields::activate_ability(
+                    &mut self.db,
+                    &self.pending,
+                    player,
+                    card,
+                    index,
+                );
+                resolve_until_input(&mut self.db, &mut pending);
+                if let Some(resolving) = self.pending.take() {
+                    pending.extend(resolving);
+                }
+                self.settle(pending);
+            }
+            GameAction::PassPriority => {
+                self.db.turn.pass_priority();
+                if self.db.turn.passed_full_priority_round() {
+                    let pending = Turn::step(&mut self.db);
+                    self.settle(pending);
+                }
+            }
+            GameAction::Choose(choice) => {
+                let mut pending = self.pending.take().unwrap();
+                match pending.resolve_until_choice(&mut self.db, choice) {
+                    SelectionResult::Complete => self.settle(PendingEffects::default()),
+                    SelectionResult::TryAgain | SelectionResult::PendingChoice => {
+                        self.pending = Some(pending);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resolves `pending` until a player has to make a choice, then checks state-based actions and
+    /// has the active player order the stack if several entries were added to it at once.
+    fn settle(&mut self, mut pending: PendingEffects) {
+        resolve_until_input(&mut self.db, &mut pending);
+        if pending.is_empty() {
+            pending = Battlefields::check_sba(&mut self.db);
+            resolve_until_input(&mut self.db, &mut pending);
+        }
+
+        if !pending.is_empty() {
+            self.pending = Some(pending);
+            self.organizing_stack = false;
+        } else if !self.organizing_stack && self.db.stack.entries_unsettled().len() > 1 {
+            self.pending = Some(PendingEffects::organize_stack(&self.db));
+            self.organizing_stack = true;
+        } else {
+            self.pending = None;
+            self.organizing_stack = false;
+        }
+    }
+}
+
+fn resolve_until_input(db: &mut Database, pending: &mut PendingEffects) {
+    while !pending.wants_input(db) {
+        if pending.resolve(db, None) == SelectionResult::Complete {
+            break;
+        }
+    }
+}
+
+/// Whether the pending choice can be made without picking an option. Mandatory choices with no
+/// options are allowed to continue this way so the game can't get stuck on them.
+fn allows_none(db: &Database, pending: &PendingEffects, options: &[OptionDetail]) -> bool {
+    options.is_empty() || !matches!(pending.options(db), Options::MandatoryList(_))
+}
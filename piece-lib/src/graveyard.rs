@@ -7,7 +7,7 @@ use crate::{
     player::{Controller, Owner},
 };
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct Graveyards {
     pub(crate) graveyards: IndexMap<Owner, IndexSet<CardId>>,
     pub(crate) descended_this_turn: HashMap<Owner, usize>,
@@ -5,7 +5,7 @@ use crate::{
     player::{Controller, Owner},
 };
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct Hands {
     pub(crate) hands: IndexMap<Owner, IndexSet<CardId>>,
 }
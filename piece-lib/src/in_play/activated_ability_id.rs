@@ -9,7 +9,7 @@ use crate::{
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, From, Into)]
 pub struct ActivatedAbilityId(Uuid);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ActivatedAbilityInPlay {
     pub(crate) source: CardId,
     pub(crate) ability: ActivatedAbility,
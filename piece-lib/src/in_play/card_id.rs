@@ -11,11 +11,15 @@ use uuid::Uuid;
 use crate::{
     abilities::Ability,
     battlefield::Battlefields,
+    config::Cosmetics,
+    counters::add_keyword_counters,
     effects::EffectBundle,
+    filter::CardFilter,
     in_play::{
         ActivatedAbilityId, CastFrom, Database, ExileReason, GainManaAbilityId, ModifierId,
         StaticAbilityId,
     },
+    library::Library,
     log::{LeaveReason, Log, LogEntry, LogId},
     player::{Controller, Owner},
     protogen::{
@@ -29,9 +33,10 @@ use crate::{
             create_token::Token,
             replacement_effect::Replacing,
             static_ability::{
-                self, AddKeywordsIf, AllAbilitiesOfExiledWith, GreenCannotBeCountered,
+                self, AddKeywordsIf, AllAbilitiesOf, AllAbilitiesOfExiledWith,
+                GreenCannotBeCountered,
             },
-            Count, Duration, EtbAbility, ReplacementEffect, TriggeredAbility,
+            Count, Duration, EtbAbility, Protection, ReplacementEffect, TriggeredAbility,
         },
         ids::UUID,
         keywords::Keyword,
@@ -98,18 +103,23 @@ impl PartialEq<CardId> for protogen::ids::CardId {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct CardInPlay {
-    pub card: Card,
+    pub(crate) card: Card,
     pub cloning: Option<Card>,
     pub(crate) cloned_id: Option<CardId>,
 
     pub(crate) object_id: usize,
+    /// When the card entered its current zone. Later entries have larger timestamps.
+    pub(crate) timestamp: usize,
     pub(crate) location: Option<Location>,
 
     pub(crate) static_abilities: HashSet<StaticAbilityId>,
     pub(crate) activated_abilities: IndexSet<ActivatedAbilityId>,
     pub(crate) mana_abilities: IndexSet<GainManaAbilityId>,
+    /// The mana abilities granted by each basic land type the card has had, uploaded the first
+    /// time the card has the type.
+    pub(crate) intrinsic_mana_abilities: HashMap<Subtype, GainManaAbilityId>,
 
     pub(crate) owner: Owner,
     pub(crate) controller: Controller,
@@ -121,6 +131,7 @@ pub struct CardInPlay {
     pub(crate) cast_from: Option<CastFrom>,
 
     pub(crate) exiling: HashSet<CardId>,
+    pub(crate) exiled_with: Option<CardId>,
     pub(crate) exile_reason: Option<ExileReason>,
     pub(crate) exile_duration: Option<Duration>,
 
@@ -129,9 +140,21 @@ pub struct CardInPlay {
     pub(crate) x_is: usize,
 
     pub(crate) enchanting: Option<CardId>,
-    pub(crate) revealed: bool,
+    pub(crate) bestowed: bool,
+    pub(crate) revealed_to: IndexSet<Owner>,
+    pub(crate) chosen_name: Option<String>,
     pub(crate) tapped: bool,
+    pub(crate) phased_out: bool,
     pub(crate) attacking: Option<Owner>,
+    /// The planeswalker this creature is attacking, which is controlled by the player in
+    /// `attacking`.
+    pub(crate) attacking_planeswalker: Option<CardId>,
+    /// The attacker this creature is blocking.
+    pub(crate) blocking: Option<CardId>,
+    /// The creatures blocking this attacker, in damage assignment order.
+    pub(crate) blocked_by: Vec<CardId>,
+    /// Whether this attacker was blocked. It stays blocked even if its blockers leave combat.
+    pub(crate) blocked: bool,
     pub manifested: bool,
     pub(crate) facedown: bool,
     pub(crate) transformed: bool,
@@ -139,7 +162,7 @@ pub struct CardInPlay {
 
     pub(crate) replacements_active: bool,
 
-    pub modified_name: String,
+    pub(crate) modified_name: String,
     pub modified_cost: CastingCost,
     pub(crate) modified_base_power: Option<Count>,
     pub(crate) modified_base_toughness: Option<Count>,
@@ -149,6 +172,7 @@ pub struct CardInPlay {
     pub modified_subtypes: SubtypeSet,
     pub(crate) modified_colors: HashSet<Color>,
     pub modified_keywords: HashMap<i32, u32>,
+    pub(crate) modified_protection: Option<Protection>,
     pub(crate) modified_replacement_abilities: HashMap<Replacing, Vec<ReplacementEffect>>,
     pub modified_triggers: HashMap<TriggerSource, Vec<TriggeredAbility>>,
     pub modified_etb_ability: protobuf::MessageField<EtbAbility>,
@@ -158,13 +182,32 @@ pub struct CardInPlay {
     pub(crate) unblockable: bool,
 
     pub(crate) marked_damage: i32,
+    pub(crate) damaged_by_deathtouch: bool,
+    /// The number of times the permanent will regenerate instead of being destroyed this turn.
+    pub(crate) regeneration_shields: u32,
 
     pub(crate) counters: HashMap<Counter, u32>,
 }
 
+/// A card's characteristics as they are in the zone it is in. These are kept up to date in every
+/// zone, so they can be checked the same way for permanents and for cards in hands, graveyards,
+/// libraries, exile, or on the stack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Characteristics {
+    pub name: String,
+    pub mana_value: usize,
+    pub power: Option<i32>,
+    pub toughness: Option<i32>,
+    pub types: TypeSet,
+    pub subtypes: SubtypeSet,
+    pub colors: HashSet<Color>,
+    pub keywords: HashMap<i32, u32>,
+}
+
 impl CardInPlay {
     fn reset(&mut self, preserve_exiled: bool) {
         let object_id = self.object_id;
+        let timestamp = self.timestamp;
 
         let mut card = Card::default();
         std::mem::swap(&mut card, &mut self.card);
@@ -178,6 +221,12 @@ impl CardInPlay {
         let mut mana_abilities = IndexSet::default();
         std::mem::swap(&mut mana_abilities, &mut self.mana_abilities);
 
+        let mut intrinsic_mana_abilities = HashMap::default();
+        std::mem::swap(
+            &mut intrinsic_mana_abilities,
+            &mut self.intrinsic_mana_abilities,
+        );
+
         let mut exiling = HashSet::default();
         if preserve_exiled {
             std::mem::swap(&mut exiling, &mut self.exiling);
@@ -187,10 +236,12 @@ impl CardInPlay {
         *self = Self {
             card,
             object_id,
+            timestamp,
             owner,
             static_abilities,
             activated_abilities,
             mana_abilities,
+            intrinsic_mana_abilities,
             controller: owner.into(),
             exiling,
             ..Default::default()
@@ -212,7 +263,7 @@ impl CardInPlay {
     pub fn counter_text_on(&self) -> Vec<String> {
         let mut results = vec![];
 
-        for counter in Counter::iter() {
+        for counter in Counter::iter().filter(|counter| counter.keyword().is_none()) {
             let amount = self.counters.get(&counter).copied().unwrap_or_default();
             if amount > 0 {
                 results.push(match counter {
@@ -226,6 +277,26 @@ impl CardInPlay {
 
         results
     }
+
+    /// The keyword counters on the card, which are shown apart from [Self::counter_text_on]
+    /// since they grant abilities rather than counting something.
+    pub fn keyword_counter_text_on(&self) -> Vec<String> {
+        Counter::iter()
+            .filter(|counter| counter.keyword().is_some())
+            .filter_map(|counter| {
+                let amount = self.counters.get(&counter).copied().unwrap_or_default();
+                match amount {
+                    0 => None,
+                    1 => Some(counter.as_ref().to_case(Case::Title)),
+                    amount => Some(format!(
+                        "{} x{}",
+                        counter.as_ref().to_case(Case::Title),
+                        amount
+                    )),
+                }
+            })
+            .collect()
+    }
 }
 
 impl CardId {
@@ -287,8 +358,54 @@ impl CardId {
         Self::upload_card_or_token(db, player, token.into(), true)
     }
 
+    /// When the card entered its current zone. A card with a larger timestamp entered its zone
+    /// more recently.
+    pub fn timestamp(self, db: &Database) -> usize {
+        db[self].timestamp
+    }
+
+    /// Phased out permanents are treated as though they don't exist, so they aren't in any
+    /// location until they phase back in.
     pub fn is_in_location(self, db: &Database, location: Location) -> bool {
-        db[self].location == Some(location)
+        db[self].location == Some(location) && !db[self].phased_out
+    }
+
+    /// Whether `player` is allowed to see the face of this card in its current zone. Cards in
+    /// hand are only visible to their owner and cards in the library are hidden from everyone,
+    /// unless they have been revealed.
+    pub fn visible_to(self, db: &Database, player: Owner) -> bool {
+        match db[self].location {
+            Some(Location::IN_LIBRARY) => self.revealed_to(db, player),
+            Some(Location::IN_HAND) => db[self].owner == player || self.revealed_to(db, player),
+            _ => true,
+        }
+    }
+
+    /// Whether this card has been revealed to `player` since it last changed zones or its library
+    /// was shuffled.
+    pub fn revealed_to(self, db: &Database, player: Owner) -> bool {
+        db[self].revealed_to.contains(&player)
+    }
+
+    /// Reveal this card to every player.
+    pub(crate) fn reveal(self, db: &mut Database) {
+        for player in db.all_players.all_players() {
+            db[self].revealed_to.insert(player);
+        }
+    }
+
+    pub(crate) fn conceal(self, db: &mut Database) {
+        db[self].revealed_to.clear();
+    }
+
+    /// The card becomes a new object, so it no longer has any memory of its previous existence,
+    /// including which players it was revealed to. It is given a new timestamp, since it is
+    /// entering a new zone.
+    fn new_object(self, db: &mut Database) {
+        db[self].object_id = db[self].object_id.wrapping_add(1);
+        db.timestamp += 1;
+        db[self].timestamp = db.timestamp;
+        self.conceal(db);
     }
 
     pub(crate) fn transform(self, db: &mut Database) {
@@ -361,8 +478,19 @@ impl CardId {
         })
     }
 
+    /// The cards still in exile which were exiled with this card.
+    pub(crate) fn exiled_with(self, db: &Database) -> impl Iterator<Item = CardId> + '_ {
+        db[self].exiling.iter().copied().filter(move |card| {
+            db[*card].exiled_with == Some(self) && card.is_in_location(db, Location::IN_EXILE)
+        })
+    }
+
     pub fn move_to_hand(self, db: &mut Database) {
-        db[self].object_id = db[self].object_id.wrapping_add(1);
+        if self.replace_zone_change(db, Location::IN_HAND) {
+            return;
+        }
+
+        self.new_object(db);
 
         if self.is_in_location(db, Location::ON_BATTLEFIELD) {
             Log::left_battlefield(db, LeaveReason::ReturnedToHand, self);
@@ -397,6 +525,7 @@ impl CardId {
             }
 
             self.apply_modifiers_layered(db);
+            Battlefields::refresh_shared_abilities(db);
         }
     }
 
@@ -412,7 +541,7 @@ impl CardId {
             return vec![];
         }
 
-        db[self].object_id = db[self].object_id.wrapping_add(1);
+        self.new_object(db);
 
         if db[self].token {
             self.move_to_limbo(db);
@@ -430,13 +559,18 @@ impl CardId {
             view.exile.shift_remove(&self);
             view.library.remove(self);
             view.hand.shift_remove(&self);
+            Battlefields::refresh_shared_abilities(db);
 
             Stack::push_card(db, self, targets, chosen_modes)
         }
     }
 
     pub(crate) fn move_to_battlefield(self, db: &mut Database) {
-        db[self].object_id = db[self].object_id.wrapping_add(1);
+        if self.replace_zone_change(db, Location::ON_BATTLEFIELD) {
+            return;
+        }
+
+        self.new_object(db);
         db[self].location = Some(Location::ON_BATTLEFIELD);
 
         db.stack.remove(self);
@@ -461,11 +595,20 @@ impl CardId {
         db[self].came_under_control_turn = Some(db.turn.turn_count);
         db[self].entered_battlefield_turn = Some(db.turn.turn_count);
 
+        if let Some(loyalty) = db[self].card.loyalty {
+            *db[self].counters.entry(Counter::LOYALTY).or_default() += loyalty;
+        }
+
         self.apply_modifiers_layered(db);
+        Battlefields::refresh_shared_abilities(db);
     }
 
     pub(crate) fn move_to_graveyard(self, db: &mut Database) {
-        db[self].object_id = db[self].object_id.wrapping_add(1);
+        if self.replace_zone_change(db, Location::IN_GRAVEYARD) {
+            return;
+        }
+
+        self.new_object(db);
 
         if self.is_in_location(db, Location::ON_BATTLEFIELD) {
             Log::left_battlefield(db, LeaveReason::PutIntoGraveyard, self);
@@ -505,11 +648,79 @@ impl CardId {
             }
 
             self.apply_modifiers_layered(db);
+            Battlefields::refresh_shared_abilities(db);
+        }
+    }
+
+    /// Applies zone change replacements watching for the card being put into `to`. Every move goes
+    /// through here, so the effects moving the card don't need to know about the replacements.
+    ///
+    /// Returns true if the card was put somewhere else instead, in which case the original move
+    /// must not happen. A card moved by a replacement isn't replaced again during that move, so
+    /// replacements can't loop.
+    fn replace_zone_change(self, db: &mut Database, to: Location) -> bool {
+        if db.triggers_suspended || db.replacing_zone_change.contains(&self) {
+            return false;
         }
+
+        let replacements = db
+            .replacement_abilities_watching(Replacing::ZONE_CHANGE)
+            .into_iter()
+            .filter(|(source, replacement)| {
+                replacement.zone_change.to.enum_value_or_default() == to
+                    && self.passes_restrictions(
+                        db,
+                        LogId::current(db),
+                        *source,
+                        &replacement.restrictions,
+                    )
+            })
+            .collect_vec();
+
+        let instead = replacements.iter().find_map(|(source, replacement)| {
+            replacement
+                .zone_change
+                .instead
+                .and_then(|instead| instead.enum_value().ok())
+                .filter(|instead| *instead != to && *instead != Location::IN_STACK)
+                .map(|instead| (*source, instead))
+        });
+
+        if let Some((source, instead)) = instead {
+            db.replacing_zone_change.insert(self);
+            match instead {
+                Location::ON_BATTLEFIELD => self.move_to_battlefield(db),
+                Location::IN_HAND => self.move_to_hand(db),
+                Location::IN_LIBRARY => {
+                    let owner = db[self].owner;
+                    Library::place_on_top(db, owner, self);
+                }
+                Location::IN_GRAVEYARD => self.move_to_graveyard(db),
+                Location::IN_EXILE => self.move_to_exile(db, source, None, Duration::PERMANENTLY),
+                Location::IN_STACK => unreachable!(),
+            }
+            db.replacing_zone_change.remove(&self);
+
+            return true;
+        }
+
+        if to == Location::ON_BATTLEFIELD
+            && replacements
+                .iter()
+                .any(|(_, replacement)| replacement.zone_change.enters_tapped)
+        {
+            self.tap(db);
+        }
+
+        false
     }
 
     pub(crate) fn move_to_library(self, db: &mut Database) -> bool {
-        db[self].object_id = db[self].object_id.wrapping_add(1);
+        if self.replace_zone_change(db, Location::IN_LIBRARY) {
+            return false;
+        }
+
+        self.new_object(db);
 
         if self.is_in_location(db, Location::ON_BATTLEFIELD) {
             Log::left_battlefield(db, LeaveReason::ReturnedToLibrary, self);
@@ -542,6 +753,7 @@ impl CardId {
             }
 
             self.apply_modifiers_layered(db);
+            Battlefields::refresh_shared_abilities(db);
             true
         }
     }
@@ -553,7 +765,11 @@ impl CardId {
         reason: Option<ExileReason>,
         duration: Duration,
     ) {
-        db[self].object_id = db[self].object_id.wrapping_add(1);
+        if self.replace_zone_change(db, Location::IN_EXILE) {
+            return;
+        }
+
+        self.new_object(db);
 
         if self.is_in_location(db, Location::ON_BATTLEFIELD) {
             Log::left_battlefield(db, LeaveReason::Exiled, self);
@@ -569,6 +785,7 @@ impl CardId {
             db[self].reset(matches!(reason, Some(ExileReason::Craft)));
             db[self].location = Some(Location::IN_EXILE);
 
+            db[self].exiled_with = Some(source);
             db[self].exile_reason = reason;
             db[self].exile_duration = Some(duration);
 
@@ -593,11 +810,12 @@ impl CardId {
             }
 
             self.apply_modifiers_layered(db);
+            Battlefields::refresh_shared_abilities(db);
         }
     }
 
     pub(crate) fn move_to_limbo(self, db: &mut Database) {
-        db[self].object_id = db[self].object_id.wrapping_add(1);
+        self.new_object(db);
 
         self.remove_all_modifiers(db);
 
@@ -622,6 +840,7 @@ impl CardId {
         }
 
         self.apply_modifiers_layered(db);
+        Battlefields::refresh_shared_abilities(db);
     }
 
     pub(crate) fn cleanup_tokens_in_limbo(db: &mut Database) {
@@ -655,6 +874,20 @@ impl CardId {
             .collect_vec();
 
         let facedown = db[self].facedown && !db[self].transformed;
+        if modifiers.is_empty()
+            && !facedown
+            && !db[self].bestowed
+            && db[self].cloning.is_none()
+            && db[self].static_abilities.is_empty()
+            && db[self].activated_abilities.is_empty()
+            && db[self].mana_abilities.is_empty()
+            && db[self].modified_static_abilities.is_empty()
+            && self.faceup_face(db).is_vanilla()
+        {
+            self.apply_vanilla(db);
+            return;
+        }
+
         let source = if let Some(cloning) = db[self].cloning.as_ref() {
             cloning
         } else {
@@ -745,15 +978,19 @@ impl CardId {
             source.keywords.clone()
         };
 
-        let mut colors: HashSet<Color> = if facedown {
+        let mut protection = if facedown {
+            None
+        } else {
+            source.protection.clone().into_option()
+        };
+        if protection.is_some() {
+            keywords.entry(Keyword::PROTECTION.value()).or_insert(1);
+        }
+
+        let mut colors = if facedown {
             HashSet::default()
         } else {
-            source
-                .colors
-                .iter()
-                .map(|c| c.enum_value().unwrap())
-                .chain(source.cost.colors())
-                .collect()
+            source.base_colors()
         };
 
         let mut triggers: HashMap<TriggerSource, Vec<TriggeredAbility>> = if facedown {
@@ -806,6 +1043,63 @@ impl CardId {
             abilities
         };
 
+        if !facedown {
+            let level = db[self]
+                .counters
+                .get(&Counter::LEVEL)
+                .copied()
+                .unwrap_or_default();
+            if let Some(tier) = source.levels.iter().find(|tier| {
+                tier.minimum <= level && tier.maximum.is_none_or(|maximum| level <= maximum)
+            }) {
+                if let Some(power) = tier.power {
+                    base_power = Some(Count {
+                        count: Some(
+                            Fixed {
+                                count: power,
+                                ..Default::default()
+                            }
+                            .into(),
+                        ),
+                        ..Default::default()
+                    });
+                }
+                if let Some(toughness) = tier.toughness {
+                    base_toughness = Some(Count {
+                        count: Some(
+                            Fixed {
+                                count: toughness,
+                                ..Default::default()
+                            }
+                            .into(),
+                        ),
+                        ..Default::default()
+                    });
+                }
+                for (keyword, count) in tier.keywords.iter() {
+                    *keywords.entry(*keyword).or_default() += count;
+                }
+                for ability in tier.triggered_abilities.iter() {
+                    triggers
+                        .entry(ability.trigger.source.enum_value().unwrap())
+                        .or_default()
+                        .push(ability.clone());
+                }
+            }
+        }
+
+        add_keyword_counters(&db[self].counters, &mut keywords);
+
+        // A bestowed card is an aura spell or permanent, not a creature, until it becomes
+        // unattached.
+        if db[self].bestowed && !facedown {
+            types.shift_remove(&Type::CREATURE);
+            subtypes.retain(|ty| !ty.is_creature_type());
+            subtypes.insert(Subtype::AURA);
+            base_power = None;
+            base_toughness = None;
+        }
+
         let mut applied_modifiers: HashSet<ModifierId> = Default::default();
 
         // TODO control changing effects go here
@@ -920,6 +1214,32 @@ impl CardId {
             }
         }
 
+        // Lands have a mana ability for each of their basic land types. These are added before
+        // abilities are modified, so effects which remove abilities remove them too.
+        if types.contains(&Type::LAND) {
+            for subtype in subtypes.iter().copied().collect_vec() {
+                let Some(ability) = subtype.intrinsic_mana_ability() else {
+                    continue;
+                };
+                if mana_abilities.iter().any(|id| {
+                    db[*id].ability.cost == ability.cost
+                        && db[*id].ability.effects == ability.effects
+                }) {
+                    continue;
+                }
+
+                let id = match db[self].intrinsic_mana_abilities.get(&subtype) {
+                    Some(id) => *id,
+                    None => {
+                        let id = GainManaAbilityId::upload(db, self, ability);
+                        db[self].intrinsic_mana_abilities.insert(subtype, id);
+                        id
+                    }
+                };
+                mana_abilities.insert(id);
+            }
+        }
+
         for id in modifiers.iter().copied() {
             let modifier = &db[id];
             if !applied_modifiers.contains(&id) {
@@ -985,9 +1305,8 @@ impl CardId {
             }
         }
 
-        if colors.len() != 1 {
-            colors.remove(&Color::COLORLESS);
-        }
+        // Modifiers can't add colorless as a color, colorless objects just have no colors.
+        colors.remove(&Color::COLORLESS);
 
         let add_keywords = static_abilities
             .iter()
@@ -1069,6 +1388,24 @@ impl CardId {
                         add.extend(db[card].activated_abilities.iter().copied());
                     }
 
+                    Some((activation_restrictions.clone(), add))
+                } else if let static_ability::Ability::AllAbilitiesOf(AllAbilitiesOf {
+                    restrictions,
+                    activation_restrictions,
+                    ..
+                }) = &db[*sa].ability
+                {
+                    if !on_battlefield {
+                        return None;
+                    }
+
+                    let mut add = vec![];
+                    for card in db.cards.keys().copied().filter(|card| *card != self) {
+                        if card.passes_restrictions(db, LogId::current(db), self, restrictions) {
+                            add.extend(db[card].activated_abilities.iter().copied());
+                        }
+                    }
+
                     Some((activation_restrictions.clone(), add))
                 } else {
                     None
@@ -1076,6 +1413,8 @@ impl CardId {
             })
             .collect_vec();
 
+        // Shared abilities are copied with this card as their source rather than reusing the other
+        // cards' abilities, so activating them pays costs and resolves effects for this card.
         for (restrictions, to_add) in add_abilities {
             activated_abilities.extend(to_add.into_iter().map(|id| {
                 let mut ability = db[id].ability.clone();
@@ -1174,6 +1513,9 @@ impl CardId {
 
                 keywords
                     .retain(|kw, _| !modifier.modifier.modifier.remove_keywords.contains_key(kw));
+                if !keywords.contains_key(&Keyword::PROTECTION.value()) {
+                    protection = None;
+                }
             }
 
             if !modifier.modifier.modifier.add_keywords.is_empty() {
@@ -1181,6 +1523,17 @@ impl CardId {
 
                 keywords.extend(modifier.modifier.modifier.add_keywords.clone());
             }
+
+            if let Some(add) = modifier.modifier.modifier.add_protection.as_ref() {
+                applied_modifiers.insert(id);
+
+                // Protection from several sources protects from the qualities of all of them.
+                let protection = protection.get_or_insert_with(Protection::default);
+                protection.colors.extend(add.colors.iter().copied());
+                protection.types.extend(add.types.clone());
+                protection.everything |= add.everything;
+                keywords.entry(Keyword::PROTECTION.value()).or_insert(1);
+            }
         }
 
         let mut add_power = 0;
@@ -1319,6 +1672,7 @@ impl CardId {
         db[self].modified_subtypes = subtypes;
         db[self].modified_triggers = triggers;
         db[self].modified_keywords = keywords;
+        db[self].modified_protection = protection;
         db[self].modified_etb_ability = etb_ability;
         db[self].modified_mana_abilities = mana_abilities;
         db[self].modified_activated_abilities = activated_abilities;
@@ -1386,6 +1740,60 @@ impl CardId {
         });
     }
 
+    /// Sets the characteristics of a card with no abilities and nothing modifying it, which are
+    /// just the ones printed on it and its counters, without going through the layers.
+    fn apply_vanilla(self, db: &mut Database) {
+        let fixed = |count: i32| Count {
+            count: Some(
+                Fixed {
+                    count,
+                    ..Default::default()
+                }
+                .into(),
+            ),
+            ..Default::default()
+        };
+
+        let source = self.faceup_face(db);
+        let base_power = source.power.map(fixed);
+        let base_toughness = source.toughness.map(fixed);
+        let cost = source.cost.get_or_default().clone();
+        let name = source.name.clone();
+        let types = TypeSet::from(&source.typeline.types);
+        let subtypes = SubtypeSet::from(&source.typeline.subtypes);
+        let colors = source.base_colors();
+        let mut keywords = source.keywords.clone();
+        let protection = source.protection.clone().into_option();
+        if protection.is_some() {
+            keywords.entry(Keyword::PROTECTION.value()).or_insert(1);
+        }
+
+        let counters = &db[self].counters;
+        add_keyword_counters(counters, &mut keywords);
+        let p1p1 = counters.get(&Counter::P1P1).copied().unwrap_or_default() as i32;
+        let m1m1 = counters.get(&Counter::M1M1).copied().unwrap_or_default() as i32;
+
+        let card = &mut db[self];
+        card.modified_base_power = base_power;
+        card.modified_base_toughness = base_toughness;
+        card.add_power = p1p1 - m1m1;
+        card.add_toughness = p1p1 - m1m1;
+        card.unblockable = false;
+        card.modified_cost = cost;
+        card.modified_name = name;
+        card.modified_types = types;
+        card.modified_colors = colors;
+        card.modified_subtypes = subtypes;
+        card.modified_triggers = Default::default();
+        card.modified_keywords = keywords;
+        card.modified_protection = protection;
+        card.modified_etb_ability = Default::default();
+        card.modified_mana_abilities = Default::default();
+        card.modified_activated_abilities = Default::default();
+        card.modified_replacement_abilities = Default::default();
+        card.modified_static_abilities = Default::default();
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn dynamic_power_toughness_given_types(
         self,
@@ -1406,6 +1814,12 @@ impl CardId {
                     card.passes_restrictions(db, LogId::current(db), self, &left.restrictions)
                 })
                 .count() as i32,
+            count::Count::NumberOfCardsExiledWith(exiled) => source
+                .exiled_with(db)
+                .filter(|card| {
+                    card.passes_restrictions(db, LogId::current(db), source, &exiled.restrictions)
+                })
+                .count() as i32,
             count::Count::NumberOfCountersOnSelected(counter) => {
                 if let Counter::ANY = counter.type_.enum_value().unwrap() {
                     db[source].counters.values().sum::<u32>() as i32
@@ -1461,9 +1875,20 @@ impl CardId {
         source: CardId,
         restrictions: &[Restriction],
     ) -> bool {
+        if db[self].phased_out {
+            return false;
+        }
+
+        if let Some(passes) = db
+            .restriction_cache
+            .get(db, self, log_session, source, restrictions)
+        {
+            return passes;
+        }
+
         let power = self.power(db);
         let toughness = self.toughness(db);
-        self.passes_restrictions_given_attributes(
+        let passes = self.passes_restrictions_given_attributes(
             db,
             log_session,
             source,
@@ -1476,7 +1901,11 @@ impl CardId {
             &db[self].modified_activated_abilities,
             power,
             toughness,
-        )
+        );
+
+        db.restriction_cache
+            .insert(db, self, log_session, source, restrictions, passes);
+        passes
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -1508,8 +1937,7 @@ impl CardId {
                     }
                 }
                 restriction::Restriction::AttackingOrBlocking(_) => {
-                    /*TODO blocking */
-                    if db[self].attacking.is_none() {
+                    if db[self].attacking.is_none() && db[self].blocking.is_none() {
                         return false;
                     }
                 }
@@ -1636,6 +2064,13 @@ impl CardId {
                         return false;
                     }
                 }
+                restriction::Restriction::ExiledWith(_) => {
+                    if db[self].exiled_with != Some(source)
+                        || !self.is_in_location(db, Location::IN_EXILE)
+                    {
+                        return false;
+                    }
+                }
                 restriction::Restriction::HasActivatedAbility(_) => {
                     if self_activated_abilities.is_empty() {
                         return false;
@@ -1738,6 +2173,11 @@ impl CardId {
                         return false;
                     }
                 }
+                restriction::Restriction::HasChosenName(_) => {
+                    if db[source].chosen_name.as_ref() != Some(self.name(db)) {
+                        return false;
+                    }
+                }
                 restriction::Restriction::NotSelf(_) => {
                     if source == self {
                         return false;
@@ -1769,10 +2209,10 @@ impl CardId {
                     }
                 }
                 restriction::Restriction::OfColor(OfColor { colors, .. }) => {
-                    if !colors
-                        .iter()
-                        .any(|c| self_colors.contains(&c.enum_value().unwrap()))
-                    {
+                    if !colors.iter().any(|c| match c.enum_value().unwrap() {
+                        Color::COLORLESS => self_colors.is_empty(),
+                        color => self_colors.contains(&color),
+                    }) {
                         return false;
                     }
                 }
@@ -1887,22 +2327,84 @@ impl CardId {
         self.apply_modifiers_layered(db);
     }
 
-    pub(crate) fn marked_damage(self, db: &Database) -> i32 {
+    pub fn marked_damage(self, db: &Database) -> i32 {
         db[self].marked_damage
     }
 
-    pub(crate) fn mark_damage(self, db: &mut Database, amount: u32) {
+    pub(crate) fn mark_damage(self, db: &mut Database, source: Option<CardId>, amount: u32) {
+        if source.is_some_and(|source| self.protected_from(db, source)) {
+            return;
+        }
+
         db[self].marked_damage += amount as i32;
+        if amount > 0 && source.is_some_and(|source| source.deathtouch(db)) {
+            db[self].damaged_by_deathtouch = true;
+        }
+        if let Some(source) = source.filter(|source| amount > 0 && source.lifelink(db)) {
+            let controller = db[source].controller;
+            db.all_players[controller].life_total += amount as i32;
+            db.all_players[controller].life_gained_this_turn += amount;
+        }
+    }
+
+    pub(crate) fn clear_damage(self, db: &mut Database) {
+        db[self].marked_damage = 0;
+        db[self].damaged_by_deathtouch = false;
+    }
+
+    /// Uses up a regeneration shield in place of destroying the permanent, tapping it, removing it
+    /// from combat, and removing all damage from it. Returns false if it has no shields.
+    pub(crate) fn regenerate(self, db: &mut Database) -> bool {
+        if db[self].regeneration_shields == 0 {
+            return false;
+        }
+
+        db[self].regeneration_shields -= 1;
+        self.tap(db);
+        self.remove_from_combat(db);
+        self.clear_damage(db);
+        true
+    }
+
+    /// The damage which must still be marked on the card before state-based actions destroy it,
+    /// accounting for damage from sources with deathtouch. Zero means it will be destroyed unless
+    /// it is indestructible. None if the card has no toughness.
+    pub fn lethal_damage_remaining(self, db: &Database) -> Option<i32> {
+        let toughness = self.toughness(db)?;
+        if db[self].damaged_by_deathtouch {
+            Some(0)
+        } else {
+            Some((toughness - self.marked_damage(db)).max(0))
+        }
     }
 
-    pub(crate) fn power(self, db: &Database) -> Option<i32> {
+    /// The characteristics of the card in `zone`, or None if the card isn't in that zone.
+    pub fn characteristics(self, db: &Database, zone: Location) -> Option<Characteristics> {
+        if !self.is_in_location(db, zone) {
+            return None;
+        }
+
+        let card = &db[self];
+        Some(Characteristics {
+            name: card.modified_name.clone(),
+            mana_value: card.modified_cost.cmc(),
+            power: self.power(db),
+            toughness: self.toughness(db),
+            types: card.modified_types.clone(),
+            subtypes: card.modified_subtypes.clone(),
+            colors: card.modified_colors.clone(),
+            keywords: card.modified_keywords.clone(),
+        })
+    }
+
+    pub fn power(self, db: &Database) -> Option<i32> {
         db[self]
             .modified_base_power
             .as_ref()
             .map(|power| self.dynamic_power_toughness(db, power) + db[self].add_power)
     }
 
-    pub(crate) fn toughness(self, db: &Database) -> Option<i32> {
+    pub fn toughness(self, db: &Database) -> Option<i32> {
         db[self]
             .modified_base_toughness
             .as_ref()
@@ -1917,6 +2419,12 @@ impl CardId {
                     card.passes_restrictions(db, LogId::current(db), self, &left.restrictions)
                 })
                 .count() as i32,
+            count::Count::NumberOfCardsExiledWith(exiled) => self
+                .exiled_with(db)
+                .filter(|card| {
+                    card.passes_restrictions(db, LogId::current(db), self, &exiled.restrictions)
+                })
+                .count() as i32,
             count::Count::NumberOfCountersOnSelected(counter) => {
                 if let Counter::ANY = counter.type_.enum_value().unwrap() {
                     db[self].counters.values().sum::<u32>() as i32
@@ -1928,15 +2436,9 @@ impl CardId {
                         .unwrap_or_default() as i32
                 }
             }
-            count::Count::NumberOfPermanentsMatching(matching) => db
-                .battlefield
-                .battlefields
-                .values()
-                .flat_map(|battlefield| battlefield.iter())
-                .filter(|card| {
-                    card.passes_restrictions(db, LogId::current(db), self, &matching.restrictions)
-                })
-                .count() as i32,
+            count::Count::NumberOfPermanentsMatching(matching) => CardFilter::on_battlefield()
+                .passing(self, &matching.restrictions)
+                .count(db) as i32,
             _ => unreachable!(),
         }
     }
@@ -1979,12 +2481,10 @@ impl CardId {
                 static_ability::Ability::GreenCannotBeCountered(GreenCannotBeCountered {
                     restrictions,
                     ..
-                }) => {
-                    if db[self].modified_colors.contains(&Color::GREEN)
-                        && self.passes_restrictions(db, log_session, source, restrictions)
-                    {
-                        return false;
-                    }
+                }) if db[self].modified_colors.contains(&Color::GREEN)
+                    && self.passes_restrictions(db, log_session, source, restrictions) =>
+                {
+                    return false;
                 }
                 _ => {}
             }
@@ -1993,30 +2493,95 @@ impl CardId {
         true
     }
 
-    pub(crate) fn can_be_targeted(self, db: &Database, caster: Controller) -> bool {
+    pub(crate) fn can_be_targeted(self, db: &Database, source: CardId) -> bool {
         if self.shroud(db) {
             return false;
         }
 
-        if self.hexproof(db) && db[self].controller != caster {
+        if self.hexproof(db) && db[self].controller != db[source].controller {
             return false;
         }
 
-        // TODO protection
+        !self.protected_from(db, source)
+    }
 
-        true
+    /// Whether the card has protection from a quality of the source, so the source can't damage,
+    /// enchant, equip, block, or target it.
+    pub(crate) fn protected_from(self, db: &Database, source: CardId) -> bool {
+        if !db[self]
+            .modified_keywords
+            .contains_key(&Keyword::PROTECTION.value())
+        {
+            return false;
+        }
+        let Some(protection) = db[self].modified_protection.as_ref() else {
+            return false;
+        };
+
+        protection.everything
+            || protection.colors.iter().any(|color| {
+                db[source]
+                    .modified_colors
+                    .contains(&color.enum_value().unwrap())
+            })
+            || (!protection.types.is_empty()
+                && source.types_intersect(db, &TypeSet::from(&protection.types)))
     }
 
     pub fn tapped(self, db: &Database) -> bool {
         db[self].tapped
     }
 
+    pub fn attacking(self, db: &Database) -> Option<Owner> {
+        db[self].attacking
+    }
+
+    /// The planeswalker the creature is attacking, if it is attacking one rather than a player.
+    pub fn attacking_planeswalker(self, db: &Database) -> Option<CardId> {
+        db[self].attacking_planeswalker
+    }
+
+    /// The attacker the creature is blocking.
+    pub fn blocking(self, db: &Database) -> Option<CardId> {
+        db[self].blocking
+    }
+
+    /// Whether the creature is an attacking creature which was blocked this combat.
+    pub fn blocked(self, db: &Database) -> bool {
+        db[self].blocked
+    }
+
+    pub(crate) fn remove_from_combat(self, db: &mut Database) {
+        if let Some(attacker) = db[self].blocking.take() {
+            db[attacker].blocked_by.retain(|blocker| *blocker != self);
+        }
+        for blocker in std::mem::take(&mut db[self].blocked_by) {
+            db[blocker].blocking = None;
+        }
+        db[self].attacking = None;
+        db[self].attacking_planeswalker = None;
+        db[self].blocked = false;
+    }
+
     pub(crate) fn tap(self, db: &mut Database) {
         Log::tapped(db, self);
         db[self].tapped = true;
     }
 
+    /// Untaps the permanent. If it is tapped and has a stun counter, a stun counter is removed
+    /// instead.
     pub fn untap(self, db: &mut Database) {
+        if db[self].tapped {
+            if let Some(stun) = db[self]
+                .counters
+                .get_mut(&Counter::STUN)
+                .filter(|stun| **stun > 0)
+            {
+                *stun -= 1;
+                return;
+            }
+        }
+
         db[self].tapped = false;
 
         let mut entities = vec![];
@@ -2040,6 +2605,99 @@ impl CardId {
         self.apply_modifiers_layered(db);
     }
 
+    pub fn phased_out(self, db: &Database) -> bool {
+        db[self].phased_out
+    }
+
+    /// Phase out this permanent along with the auras and equipment attached to it. This isn't a
+    /// zone change, so the permanent keeps its counters, modifiers, and attachments.
+    pub(crate) fn phase_out(self, db: &mut Database) {
+        if db[self].phased_out || !self.is_in_location(db, Location::ON_BATTLEFIELD) {
+            return;
+        }
+
+        for card in std::iter::once(self)
+            .chain(self.attached(db))
+            .filter(|card| !db[*card].phased_out)
+            .collect_vec()
+        {
+            let controller = db[card].controller;
+            card.remove_from_combat(db);
+            db[card].phased_out = true;
+            db[card].replacements_active = false;
+            db.battlefield[controller].shift_remove(&card);
+            db.battlefield.phased_out.insert(card);
+
+            for modifier in db[card]
+                .modified_static_abilities
+                .iter()
+                .filter_map(|sa| db[*sa].owned_modifier)
+                .collect_vec()
+            {
+                modifier.deactivate(db);
+            }
+        }
+
+        for card in db.cards.keys().copied().collect_vec() {
+            card.apply_modifiers_layered(db);
+        }
+    }
+
+    /// Phase in this permanent along with the auras and equipment which phased out with it.
+    /// Nothing enters the battlefield, so no enter the battlefield triggers or replacements apply.
+    pub(crate) fn phase_in(self, db: &mut Database) {
+        if !db[self].phased_out {
+            return;
+        }
+
+        for card in std::iter::once(self)
+            .chain(self.attached(db))
+            .filter(|card| db[*card].phased_out)
+            .collect_vec()
+        {
+            let controller = db[card].controller;
+            db[card].phased_out = false;
+            db[card].replacements_active = true;
+            db.battlefield.phased_out.shift_remove(&card);
+            db.battlefield[controller].insert(card);
+
+            for modifier in db[card]
+                .modified_static_abilities
+                .iter()
+                .filter_map(|sa| db[*sa].owned_modifier)
+                .collect_vec()
+            {
+                modifier.activate(&mut db.modifiers);
+            }
+        }
+
+        for card in db.cards.keys().copied().collect_vec() {
+            card.apply_modifiers_layered(db);
+        }
+    }
+
+    /// The auras and equipment attached to this card, whether or not they are phased out.
+    fn attached(self, db: &Database) -> Vec<CardId> {
+        db.cards
+            .keys()
+            .copied()
+            .filter(|card| db[*card].location == Some(Location::ON_BATTLEFIELD))
+            .filter(|card| {
+                db[*card].enchanting == Some(self)
+                    || (card.subtypes_intersect(db, &SubtypeSet::from([Subtype::EQUIPMENT]))
+                        && db.modifiers.values().any(|modifier| {
+                            modifier.source == *card && modifier.modifying.contains(&self)
+                        }))
+            })
+            .collect_vec()
+    }
+
+    pub(crate) fn phasing(self, db: &Database) -> bool {
+        db[self]
+            .modified_keywords
+            .contains_key(&Keyword::PHASING.value())
+    }
+
     pub(crate) fn token_copy_of(self, db: &mut Database, controller: Controller) -> CardId {
         let card = clone_card(db, self);
 
@@ -2066,6 +2724,37 @@ impl CardId {
         self.types_intersect(db, &TypeSet::from([Type::LAND]))
     }
 
+    pub fn is_planeswalker(self, db: &Database) -> bool {
+        self.types_intersect(db, &TypeSet::from([Type::PLANESWALKER]))
+    }
+
+    /// The number of loyalty counters on the permanent.
+    pub fn loyalty(self, db: &Database) -> u32 {
+        db[self]
+            .counters
+            .get(&Counter::LOYALTY)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The number of lore counters on the permanent.
+    pub fn lore(self, db: &Database) -> u32 {
+        db[self]
+            .counters
+            .get(&Counter::LORE)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The last chapter of a saga, or None if the card has no chapter abilities.
+    pub fn final_chapter(self, db: &Database) -> Option<u32> {
+        self.faceup_face(db)
+            .chapters
+            .iter()
+            .map(|chapter| chapter.chapter)
+            .max()
+    }
+
     pub(crate) fn is_permanent(self, db: &Database) -> bool {
         !self.types_intersect(db, &TypeSet::from([Type::INSTANT, Type::SORCERY]))
     }
@@ -2082,13 +2771,42 @@ impl CardId {
             .contains_key(&Keyword::HEXPROOF.value())
     }
 
-    #[allow(unused)]
     pub(crate) fn flying(self, db: &Database) -> bool {
         db[self]
             .modified_keywords
             .contains_key(&Keyword::FLYING.value())
     }
 
+    pub(crate) fn reach(self, db: &Database) -> bool {
+        db[self]
+            .modified_keywords
+            .contains_key(&Keyword::REACH.value())
+    }
+
+    pub(crate) fn deathtouch(self, db: &Database) -> bool {
+        db[self]
+            .modified_keywords
+            .contains_key(&Keyword::DEATHTOUCH.value())
+    }
+
+    pub(crate) fn lifelink(self, db: &Database) -> bool {
+        db[self]
+            .modified_keywords
+            .contains_key(&Keyword::LIFELINK.value())
+    }
+
+    pub(crate) fn menace(self, db: &Database) -> bool {
+        db[self]
+            .modified_keywords
+            .contains_key(&Keyword::MENACE.value())
+    }
+
+    pub(crate) fn trample(self, db: &Database) -> bool {
+        db[self]
+            .modified_keywords
+            .contains_key(&Keyword::TRAMPLE.value())
+    }
+
     pub(crate) fn first_strike(self, db: &Database) -> bool {
         db[self]
             .modified_keywords
@@ -2117,6 +2835,11 @@ impl CardId {
         &db[self].modified_name
     }
 
+    /// The cosmetics of the card's owner, for drawing the card while it is hidden.
+    pub fn cosmetics(self, db: &Database) -> &Cosmetics {
+        &db.all_players[db[self].owner].cosmetics
+    }
+
     pub(crate) fn has_flash(self, db: &Database) -> bool {
         db[self]
             .modified_keywords
@@ -2192,6 +2915,102 @@ impl CardId {
             && !self.summoning_sick(db)
     }
 
+    /// Whether a static ability prevents the card from being cast.
+    pub(crate) fn cast_prevented(self, db: &Database) -> bool {
+        Battlefields::static_abilities(db)
+            .into_iter()
+            .any(|(ability, source)| match ability {
+                static_ability::Ability::CantCast(cant) => {
+                    self.passes_restrictions(db, LogId::current(db), source, &cant.restrictions)
+                }
+                _ => false,
+            })
+    }
+
+    /// Whether a static ability controlled by the card's owner lets them play the card from its
+    /// graveyard or exile as though it were in their hand.
+    pub(crate) fn play_permitted(self, db: &Database) -> bool {
+        if !self.is_in_location(db, Location::IN_GRAVEYARD)
+            && !self.is_in_location(db, Location::IN_EXILE)
+        {
+            return false;
+        }
+
+        Battlefields::static_abilities(db)
+            .into_iter()
+            .any(|(ability, source)| match ability {
+                static_ability::Ability::PlayAsThoughInHand(play) => {
+                    Owner::from(db[source].controller) == db[self].owner
+                        && self.passes_restrictions(
+                            db,
+                            LogId::current(db),
+                            source,
+                            &play.restrictions,
+                        )
+                }
+                _ => false,
+            })
+    }
+
+    /// Whether the card's owner may play it as though it were in their hand, either because it is
+    /// in their hand or because a static ability lets them play it from another zone. Whether it
+    /// can be played right now also depends on timing, see [crate::turns::Turn::can_cast].
+    pub fn can_play_as_though_in_hand(self, db: &Database) -> bool {
+        self.is_in_location(db, Location::IN_HAND) || self.play_permitted(db)
+    }
+
+    /// Whether a static ability prevents the card from being sacrificed.
+    pub(crate) fn sacrifice_prevented(self, db: &Database) -> bool {
+        Battlefields::static_abilities(db)
+            .into_iter()
+            .any(|(ability, source)| match ability {
+                static_ability::Ability::CantBeSacrificed(cant) => {
+                    self.passes_restrictions(db, LogId::current(db), source, &cant.restrictions)
+                }
+                _ => false,
+            })
+    }
+
+    /// Whether a static ability prevents the card's activated abilities from being activated.
+    /// Mana abilities are only prevented by abilities which include them.
+    pub(crate) fn activation_prevented(self, db: &Database, mana_ability: bool) -> bool {
+        Battlefields::static_abilities(db)
+            .into_iter()
+            .any(|(ability, source)| match ability {
+                static_ability::Ability::PreventActivatedAbilities(prevent) => {
+                    (!mana_ability || prevent.mana_abilities)
+                        && self.passes_restrictions(
+                            db,
+                            LogId::current(db),
+                            source,
+                            &prevent.restrictions,
+                        )
+                        && (prevent.unless.is_empty()
+                            || !self.passes_restrictions(
+                                db,
+                                LogId::current(db),
+                                source,
+                                &prevent.unless,
+                            ))
+                }
+                _ => false,
+            })
+    }
+
+    pub(crate) fn can_block(self, db: &Database, attacker: CardId) -> bool {
+        self.types_intersect(db, &TypeSet::from([Type::CREATURE]))
+            && !self.tapped(db)
+            && !db[self].modified_static_abilities.iter().any(|ability| {
+                matches!(
+                    db[*ability].ability,
+                    static_ability::Ability::PreventBlocks(_)
+                )
+            })
+            && !db[attacker].unblockable
+            && !attacker.protected_from(db, self)
+            && (!attacker.flying(db) || self.flying(db) || self.reach(db))
+    }
+
     pub(crate) fn battle_cry(self, db: &Database) -> u32 {
         db[self]
             .modified_keywords
@@ -2243,6 +3062,15 @@ fn clone_card(db: &mut Database, cloning: CardId) -> Card {
         etb_tapped,
         keywords,
         back_face,
+        bestow,
+        levels,
+        companion,
+        sets,
+        color_indicator,
+        loyalty,
+        chapters,
+        protection,
+        rarity,
         special_fields,
     } = cloning.faceup_face(db);
 
@@ -2271,6 +3099,15 @@ fn clone_card(db: &mut Database, cloning: CardId) -> Card {
         etb_tapped: *etb_tapped,
         keywords: keywords.clone(),
         back_face: back_face.clone(),
+        bestow: bestow.clone(),
+        levels: levels.clone(),
+        companion: companion.clone(),
+        sets: sets.clone(),
+        color_indicator: color_indicator.clone(),
+        loyalty: *loyalty,
+        chapters: chapters.clone(),
+        protection: protection.clone(),
+        rarity: *rarity,
         special_fields: special_fields.clone(),
     }
 }
@@ -8,7 +8,7 @@ use crate::{
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct GainManaAbilityId(Uuid);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GainManaAbilityInPlay {
     pub(crate) source: CardId,
     pub(crate) ability: GainManaAbility,
@@ -4,41 +4,57 @@ mod gain_mana_ability_id;
 mod modifier_id;
 mod static_ability_id;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use indexmap::{IndexMap, IndexSet};
 use itertools::Itertools;
 
 pub use activated_ability_id::{ActivatedAbilityId, ActivatedAbilityInPlay};
-pub use card_id::CardId;
 pub(crate) use card_id::CardInPlay;
+pub use card_id::{CardId, Characteristics};
 pub use gain_mana_ability_id::{GainManaAbilityId, GainManaAbilityInPlay};
 pub(crate) use modifier_id::{ModifierId, ModifierInPlay};
 pub(crate) use static_ability_id::{StaticAbilityId, StaticAbilityInPlay};
 
 use crate::{
     battlefield::Battlefields,
+    checkpoint::Snapshots,
+    clock::Clocks,
     exile::Exiles,
     graveyard::Graveyards,
     hand::Hands,
     library::Library,
     log::Log,
+    notifications::Notifications,
     player::{AllPlayers, Controller, Owner},
     protogen::{
         effects::{replacement_effect::Replacing, ReplacementEffect, TriggeredAbility},
         triggers::{self, TriggerSource},
     },
+    record::Recording,
+    restriction_cache::RestrictionCache,
     stack::Stack,
+    trace::Trace,
     turns::{Phase, Turn},
+    Cards,
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::EnumIter)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, strum::EnumIter,
+)]
 pub(crate) enum CastFrom {
     Hand,
     Exile,
     Graveyard,
 }
 
+/// Identifies activated and mana abilities together, for limits which count both kinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum AbilityId {
+    Activated(ActivatedAbilityId),
+    Mana(GainManaAbilityId),
+}
+
 impl PartialEq<triggers::Location> for CastFrom {
     fn eq(&self, other: &triggers::Location) -> bool {
         match self {
@@ -58,9 +74,9 @@ pub(crate) enum ExileReason {
     Craft,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Database {
-    pub log: Log,
+    pub(crate) log: Log,
 
     pub(crate) cards: IndexMap<CardId, CardInPlay>,
     pub(crate) modifiers: IndexMap<ModifierId, ModifierInPlay>,
@@ -83,8 +99,41 @@ pub struct Database {
 
     pub turn: Turn,
     pub all_players: AllPlayers,
+
+    pub(crate) trace: Option<Trace>,
+    pub(crate) record: Recording,
+    pub(crate) clocks: Clocks,
+    pub(crate) notifications: Notifications,
+
+    /// Every card name which may be chosen by effects like Pithing Needle.
+    pub(crate) card_names: Vec<String>,
+
+    /// Cards currently being moved by a zone change replacement, which can't be replaced again.
+    pub(crate) replacing_zone_change: HashSet<CardId>,
+
+    /// Set while a scenario is being set up by [Database::with_suspended_triggers].
+    pub(crate) triggers_suspended: bool,
+
+    pub(crate) restriction_cache: RestrictionCache,
+
+    /// The most recent timestamp given to a card entering a zone.
+    pub(crate) timestamp: usize,
+
+    /// Every activated or mana ability which has been activated this game. Cards keep their
+    /// abilities when they change zones, so once each game limits follow the card.
+    pub(crate) activated_this_game: HashSet<AbilityId>,
+    /// Every time a trigger with a per game limit fired, along with the card it fired for.
+    pub(crate) limited_triggers_this_game: Vec<(CardId, TriggeredAbility)>,
+
+    pub(crate) snapshots: Snapshots,
 }
 
+// The AI plays out its options on copies of the database on other threads.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Database>();
+};
+
 pub struct OwnerViewMut<'db> {
     pub(crate) battlefield: &'db mut IndexSet<CardId>,
     pub(crate) graveyard: &'db mut IndexSet<CardId>,
@@ -198,10 +247,66 @@ impl Database {
             hand,
             stack: Default::default(),
             turn,
+            trace: None,
+            record: Default::default(),
+            clocks: Default::default(),
+            notifications: Default::default(),
+            card_names: Default::default(),
+            replacing_zone_change: Default::default(),
+            triggers_suspended: false,
+            restriction_cache: Default::default(),
+            timestamp: 0,
+            activated_this_game: Default::default(),
+            limited_triggers_this_game: Default::default(),
+            snapshots: Default::default(),
         }
     }
 
-    pub(crate) fn owner_view_mut(&mut self, owner: Owner) -> OwnerViewMut {
+    /// Makes the names of the loaded cards available to effects which choose a card name.
+    pub fn set_card_names(&mut self, cards: &Cards) {
+        self.card_names = cards.keys().cloned().sorted().collect_vec();
+    }
+
+    /// Runs `f` without firing triggers, zone change replacements, or state-based actions, so
+    /// cards can be placed, counters set, and life totals adjusted one at a time without any of
+    /// the intermediate states having consequences. The characteristics of every card are
+    /// recomputed once when `f` returns.
+    pub fn with_suspended_triggers<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        let suspended = std::mem::replace(&mut self.triggers_suspended, true);
+        let result = f(self);
+        self.triggers_suspended = suspended;
+
+        if !suspended {
+            for card in self.cards.keys().copied().collect_vec() {
+                card.apply_modifiers_layered(self);
+            }
+        }
+
+        result
+    }
+
+    /// Every card in a graveyard, in the order they were put there.
+    pub fn graveyard_order(&self) -> Vec<CardId> {
+        self.entry_order(self.graveyard.graveyards.values().flatten().copied())
+    }
+
+    /// Every permanent on the battlefield, in the order they entered it.
+    pub fn battlefield_entry_order(&self) -> Vec<CardId> {
+        self.entry_order(self.battlefield.battlefields.values().flatten().copied())
+    }
+
+    /// Every card in exile, in the order they were exiled.
+    pub fn exile_order(&self) -> Vec<CardId> {
+        self.entry_order(self.exile.exile_zones.values().flatten().copied())
+    }
+
+    fn entry_order(&self, cards: impl Iterator<Item = CardId>) -> Vec<CardId> {
+        cards
+            .sorted_by_key(|card| self[*card].timestamp)
+            .collect_vec()
+    }
+
+    pub(crate) fn owner_view_mut(&mut self, owner: Owner) -> OwnerViewMut<'_> {
         OwnerViewMut {
             battlefield: &mut self.battlefield[owner],
             graveyard: &mut self.graveyard[owner],
@@ -7,6 +7,7 @@ use uuid::Uuid;
 
 use crate::{
     in_play::{ActivatedAbilityId, CardId, Database, GainManaAbilityId, StaticAbilityId},
+    player::Controller,
     protogen::effects::BattlefieldModifier,
 };
 
@@ -16,6 +17,9 @@ pub(crate) struct ModifierId(Uuid);
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ModifierInPlay {
     pub(crate) source: CardId,
+    /// The controller of the source when the modifier was created, used for durations relative
+    /// to "you". None if the modifier was created while the source was being uploaded.
+    pub(crate) controller: Option<Controller>,
     pub(crate) temporary: bool,
     pub(crate) active: bool,
     pub(crate) modifier: BattlefieldModifier,
@@ -61,6 +65,7 @@ impl ModifierId {
             id,
             ModifierInPlay {
                 source,
+                controller: db.cards.get(&source).map(|card| card.controller),
                 temporary: true,
                 active: false,
                 modifier,
@@ -95,11 +100,11 @@ impl ModifierId {
             }
 
             for id in modifier.add_static_abilities.iter() {
-                db.static_abilities.remove(id);
+                db.static_abilities.swap_remove(id);
             }
 
             for id in modifier.add_mana_abilities.iter() {
-                db.mana_abilities.remove(id);
+                db.mana_abilities.swap_remove(id);
             }
 
             db.modifiers.shift_remove(&self);
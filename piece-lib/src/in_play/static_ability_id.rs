@@ -8,7 +8,7 @@ use crate::{
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub(crate) struct StaticAbilityId(Uuid);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct StaticAbilityInPlay {
     pub(crate) source: CardId,
     pub(crate) ability: static_ability::Ability,
@@ -1,9 +1,34 @@
+//! The rules engine for piece.
+//!
+//! The supported integration points are:
+//! - Loading card definitions into [Cards] with [load_cards] and friends.
+//! - Creating a [in_play::Database] for the rules in a [config::GameConfig] and driving the game
+//!   with [turns::Turn], [player::Player], and [battlefield::Battlefields].
+//! - Driving a whole match through [game::Game], for frontends and bots which don't need finer
+//!   control over the pieces below.
+//! - Playing a match between two instances over the network with [net::Host] and [net::Client].
+//! - Resolving player choices through [effects::PendingEffects], optionally under a
+//!   [clock::Clock]. [effects::PendingEffects::option_details] describes what each option refers
+//!   to, and [checkpoint::Checkpoints] lets a player back out of an action they started.
+//! - Draining semantic cues for sounds or OS notifications with
+//!   [notifications::Notifications].
+//! - Exporting a full record of a finished game with [record::GameRecord].
+//! - Saving a game to disk and resuming it later with [in_play::Database::serialize_state] and
+//!   [in_play::Database::deserialize_state].
+//! - Explaining why something happened, e.g. with [log::Log::left_battlefield_cause].
+//! - Checking decks against data-driven format rules with [format::Format].
+//! - Read-only queries such as [combat::combat_preview], [combat::enumerate_attack_options],
+//!   [deck::stats], [docs::effects], and [filter::CardFilter].
+//!
+//! Types which are expected to grow new fields or variants are marked `#[non_exhaustive]`.
+//! Everything else is subject to change without notice.
+
 #![allow(clippy::single_match)]
 
 #[macro_use]
 extern crate tracing;
 
-use std::{borrow::Cow, collections::HashMap, marker::PhantomData};
+use std::{borrow::Cow, collections::HashMap, marker::PhantomData, path::Path};
 
 use anyhow::{anyhow, Context};
 
@@ -35,20 +60,39 @@ mod _tests;
 pub mod abilities;
 pub mod battlefield;
 pub mod card;
-pub mod cost;
+pub mod card_metadata;
+pub mod card_query;
+pub mod checkpoint;
+pub mod clock;
+pub mod combat;
+pub mod config;
+pub(crate) mod cost;
+pub(crate) mod counters;
+pub mod debug;
+pub mod deck;
+pub mod docs;
 pub mod effects;
-pub mod exile;
-pub mod graveyard;
-pub mod hand;
+pub(crate) mod exile;
+pub mod filter;
+pub mod format;
+pub mod game;
+pub(crate) mod graveyard;
+pub(crate) mod hand;
 pub mod in_play;
 pub mod library;
 pub mod log;
 pub mod mana;
+pub mod net;
+pub mod notifications;
 pub mod player;
 pub mod protogen;
+pub mod record;
+pub(crate) mod restriction_cache;
 pub mod stack;
+pub mod state;
+pub mod trace;
 pub mod turns;
-pub mod types;
+pub(crate) mod types;
 
 #[derive(RustEmbed)]
 #[folder = "cards/"]
@@ -61,31 +105,61 @@ pub fn load_protos() -> anyhow::Result<Vec<(Card, Cow<'static, str>)>> {
 
     for card_file in CardDefs::iter() {
         let contents = CardDefs::get(&card_file).unwrap();
+        let card = parse_card(&card_file, &contents.data)?;
+        results.push((card, card_file));
+    }
 
-        let card: protogen::card::Card = serde_yaml::from_slice(&contents.data)
-            .map_err(|e| {
-                let location = e.location().unwrap();
-                Report::build(ReportKind::Error, &card_file, location.index())
-                    .with_label(Label::new((
-                        &card_file,
-                        location.index()..location.index() + 1,
-                    )))
-                    .with_message(e.to_string())
-                    .finish()
-                    .eprint((
-                        &card_file,
-                        Source::from(std::str::from_utf8(&contents.data).expect("Invalid utf8")),
-                    ))
-                    .unwrap();
+    Ok(results)
+}
 
-                anyhow!(e.to_string())
-            })
-            .with_context(|| format!("Parsing file: {}", card_file))?;
+/// Loads every card which parses successfully. Unlike [load_protos], a malformed card does not
+/// stop loading: a report is printed for each failing file and its error is returned alongside
+/// the cards which did load.
+pub fn load_protos_lenient() -> (Vec<(Card, Cow<'static, str>)>, Vec<anyhow::Error>) {
+    parse_protos(
+        CardDefs::iter()
+            .map(|card_file| (card_file.clone(), CardDefs::get(&card_file).unwrap().data)),
+    )
+}
 
-        results.push((card, card_file));
+pub(crate) fn parse_protos<'a>(
+    files: impl Iterator<Item = (Cow<'static, str>, Cow<'a, [u8]>)>,
+) -> (Vec<(Card, Cow<'static, str>)>, Vec<anyhow::Error>) {
+    let mut results = vec![];
+    let mut errors = vec![];
+
+    for (card_file, contents) in files {
+        match parse_card(&card_file, &contents) {
+            Ok(card) => results.push((card, card_file)),
+            Err(e) => errors.push(e),
+        }
     }
 
-    Ok(results)
+    (results, errors)
+}
+
+/// Loads a single card from a yaml file on disk rather than from the embedded card definitions,
+/// printing a report if it fails to parse.
+pub fn load_card_file(path: &Path) -> anyhow::Result<Card> {
+    let contents =
+        std::fs::read(path).with_context(|| format!("Reading card: {}", path.display()))?;
+    parse_card(&path.display().to_string(), &contents)
+}
+
+fn parse_card(card_file: &str, contents: &[u8]) -> anyhow::Result<Card> {
+    serde_yaml::from_slice(contents)
+        .map_err(|e| {
+            let location = e.location().map(|l| l.index()).unwrap_or_default();
+            Report::build(ReportKind::Error, card_file, location)
+                .with_label(Label::new((card_file, location..location + 1)))
+                .with_message(e.to_string())
+                .finish()
+                .eprint((card_file, Source::from(String::from_utf8_lossy(contents))))
+                .unwrap();
+
+            anyhow!(e.to_string())
+        })
+        .with_context(|| format!("Parsing file: {}", card_file))
 }
 
 pub fn load_cards() -> anyhow::Result<Cards> {
@@ -114,6 +188,90 @@ pub fn load_cards() -> anyhow::Result<Cards> {
     Ok(cards)
 }
 
+/// Loads all cards, then applies any patches found in the `overrides` directory.
+pub fn load_cards_with_overrides(overrides: &Path) -> anyhow::Result<Cards> {
+    let mut cards = load_cards()?;
+    apply_overrides(&mut cards, overrides)?;
+    Ok(cards)
+}
+
+/// Applies the patches in the `overrides` directory to the loaded cards.
+///
+/// Each patch is a yaml file containing the `name` of the card to patch and any fields to replace.
+/// Nested fields are merged recursively, while lists and tagged values are replaced wholesale.
+pub fn apply_overrides(cards: &mut Cards, overrides: &Path) -> anyhow::Result<()> {
+    let timer = std::time::Instant::now();
+    let mut patched = 0;
+    for entry in std::fs::read_dir(overrides)
+        .with_context(|| format!("Reading overrides: {}", overrides.display()))?
+    {
+        let path = entry?.path();
+        if !path
+            .extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml")
+        {
+            continue;
+        }
+
+        let file = path.display().to_string();
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Reading override: {}", file))?;
+
+        let patch: serde_yaml::Value = serde_yaml::from_str(&contents)
+            .map_err(|e| {
+                let location = e.location().map(|l| l.index()).unwrap_or_default();
+                Report::build(ReportKind::Error, &file, location)
+                    .with_label(Label::new((&file, location..location + 1)))
+                    .with_message(e.to_string())
+                    .finish()
+                    .eprint((&file, Source::from(&contents)))
+                    .unwrap();
+
+                anyhow!(e.to_string())
+            })
+            .with_context(|| format!("Parsing override: {}", file))?;
+
+        let name = patch
+            .get("name")
+            .and_then(|name| name.as_str())
+            .ok_or_else(|| anyhow!("Override {} does not specify a card name", file))?;
+
+        let card = cards
+            .get_mut(name)
+            .ok_or_else(|| anyhow!("Override {} patches unknown card {}", file, name))?;
+
+        let mut merged = serde_yaml::to_value(&*card)?;
+        merge_yaml(&mut merged, patch);
+        *card = serde_yaml::from_value(merged)
+            .with_context(|| format!("Applying override: {}", file))?;
+
+        patched += 1;
+    }
+
+    info!(
+        "Applied {} overrides in {}ms",
+        patched,
+        timer.elapsed().as_millis()
+    );
+
+    Ok(())
+}
+
+fn merge_yaml(base: &mut serde_yaml::Value, patch: serde_yaml::Value) {
+    match (base, patch) {
+        (serde_yaml::Value::Mapping(base), serde_yaml::Value::Mapping(patch)) => {
+            for (key, value) in patch {
+                if let Some(existing) = base.get_mut(&key) {
+                    merge_yaml(existing, value);
+                } else {
+                    base.insert(key, value);
+                }
+            }
+        }
+        (base, patch) => *base = patch,
+    }
+}
+
 fn is_default_value<T: Default + PartialEq>(t: &T) -> bool {
     *t == T::default()
 }
@@ -255,7 +413,10 @@ where
         type Value = Vec<protobuf::EnumOrUnknown<ManaCost>>;
 
         fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-            formatter.write_str("expected a sequence of {W}, {U}, {B}, {R}, {G}, {C}, or {#}")
+            formatter.write_str(
+                "expected a sequence of {W}, {U}, {B}, {R}, {G}, {C}, {#}, hybrid symbols like \
+                 {G/W} or {2/W}, or Phyrexian symbols like {W/P}",
+            )
         }
 
         fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
@@ -283,7 +444,10 @@ where
         type Value = Vec<protobuf::EnumOrUnknown<ManaCost>>;
 
         fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-            formatter.write_str("expected a sequence of {W}, {U}, {B}, {R}, {G}, {C}, or {#}")
+            formatter.write_str(
+                "expected a sequence of {W}, {U}, {B}, {R}, {G}, {C}, {#}, hybrid symbols like \
+                 {G/W} or {2/W}, or Phyrexian symbols like {W/P}",
+            )
         }
 
         fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
@@ -319,17 +483,8 @@ where
                 results.push(ManaCost::GENERIC);
             }
         } else {
-            let cost = match symbol {
-                "W" => ManaCost::WHITE,
-                "U" => ManaCost::BLUE,
-                "B" => ManaCost::BLACK,
-                "R" => ManaCost::RED,
-                "G" => ManaCost::GREEN,
-                "X" => ManaCost::X,
-                "C" => ManaCost::COLORLESS,
-                s => {
-                    return Err(E::custom(format!("Invalid mana cost {}", s)));
-                }
+            let Some(cost) = ManaCost::from_symbol(symbol) else {
+                return Err(E::custom(format!("Invalid mana cost {}", symbol)));
             };
 
             if matches!(cost, ManaCost::X) && matches!(results.last(), Some(ManaCost::X)) {
@@ -361,12 +516,6 @@ where
     let mut pushed_generic = false;
     for mana in value.iter() {
         match mana.enum_value().unwrap() {
-            ManaCost::WHITE => result.push_str("{W}"),
-            ManaCost::BLUE => result.push_str("{U}"),
-            ManaCost::BLACK => result.push_str("{B}"),
-            ManaCost::RED => result.push_str("{R}"),
-            ManaCost::GREEN => result.push_str("{G}"),
-            ManaCost::COLORLESS => result.push_str("{C}"),
             ManaCost::GENERIC => {
                 if !pushed_generic {
                     match generic {
@@ -396,8 +545,8 @@ where
                     pushed_generic = true;
                 }
             }
-            ManaCost::X => result.push_str("{X}"),
             ManaCost::TWO_X => result.push_str("{X}{X}"),
+            cost => result.push_str(&format!("{{{}}}", cost.symbol().unwrap())),
         }
     }
 
@@ -620,13 +769,13 @@ where
         &value
             .iter()
             .flat_map(|(kw, count)| {
-                std::iter::repeat(
+                std::iter::repeat_n(
                     Keyword::from_i32(*kw)
                         .unwrap()
                         .as_ref()
                         .to_case(Case::Title),
+                    (*count) as usize,
                 )
-                .take((*count) as usize)
             })
             .sorted()
             .join(", "),
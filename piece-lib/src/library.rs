@@ -1,6 +1,7 @@
 use std::collections::{HashMap, VecDeque};
 
-use rand::{seq::SliceRandom, thread_rng};
+use itertools::Itertools;
+use rand::{seq::SliceRandom, thread_rng, Rng};
 
 use crate::{
     in_play::{CardId, Database},
@@ -18,9 +19,15 @@ impl DeckDefinition {
         self.cards.insert(name, count);
     }
 
+    pub fn cards(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+        self.cards
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+    }
+
     pub fn build_deck(&self, db: &mut Database, cards: &Cards, player: Owner) -> Library {
         let mut deck = VecDeque::default();
-        for (card, count) in self.cards.iter() {
+        for (card, count) in self.cards.iter().sorted() {
             for _ in 0..*count {
                 let id = CardId::upload(db, cards, player, card);
                 deck.push_back(id);
@@ -31,7 +38,7 @@ impl DeckDefinition {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct Library {
     pub(crate) cards: VecDeque<CardId>,
 }
@@ -48,10 +55,28 @@ impl Library {
     }
 
     pub fn shuffle(&mut self) {
-        self.cards.make_contiguous().shuffle(&mut thread_rng())
+        self.shuffle_with(&mut thread_rng())
+    }
+
+    pub fn shuffle_with(&mut self, rng: &mut impl Rng) {
+        self.cards.make_contiguous().shuffle(rng)
+    }
+
+    /// Shuffles the player's library. Cards which were revealed while in the library are no longer
+    /// known to anyone.
+    pub fn reshuffle(db: &mut Database, player: Owner, rng: &mut impl Rng) {
+        db.all_players[player].library.shuffle_with(rng);
+        for card in db.all_players[player]
+            .library
+            .cards
+            .iter()
+            .copied()
+            .collect_vec()
+        {
+            card.conceal(db);
+        }
     }
 
-    #[cfg(test)]
     pub(crate) fn place_on_top(db: &mut Database, player: Owner, card: CardId) {
         if card.move_to_library(db) {
             db.all_players[player].library.cards.push_back(card);
@@ -75,7 +100,11 @@ impl Library {
         self.cards.pop_back()
     }
 
-    #[cfg(test)]
+    /// Iterates over the cards in the library, starting from the top.
+    pub fn cards(&self) -> impl Iterator<Item = CardId> + '_ {
+        self.cards.iter().rev().copied()
+    }
+
     pub(crate) fn len(&self) -> usize {
         self.cards.len()
     }
@@ -8,7 +8,7 @@ use crate::{
     protogen::counters::Counter,
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct LogId(usize);
 
 impl LogId {
@@ -34,6 +34,32 @@ pub enum LeaveReason {
     ReturnedToLibrary,
 }
 
+/// What was happening when a log entry was recorded, so frontends can explain why a change
+/// happened (e.g. which spell destroyed a creature).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cause {
+    /// A spell resolving.
+    Spell(CardId),
+    /// An activated or triggered ability of the card resolving or being activated.
+    Ability(CardId),
+    /// A player taking an action, such as playing a land or casting a spell.
+    Player(Owner),
+    StateBasedActions,
+    TurnBasedActions,
+}
+
+impl Cause {
+    pub fn describe(&self, db: &Database) -> String {
+        match self {
+            Cause::Spell(card) => card.name(db).clone(),
+            Cause::Ability(card) => format!("{}'s ability", card.name(db)),
+            Cause::Player(player) => db.all_players[*player].name.clone(),
+            Cause::StateBasedActions => "State-based actions".to_string(),
+            Cause::TurnBasedActions => "Turn-based actions".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum LogEntry {
     NewTurn {
@@ -62,6 +88,10 @@ pub enum LogEntry {
     Cast {
         card: CardId,
     },
+    LandPlayed {
+        player: Owner,
+        card: CardId,
+    },
     Activated {
         card: CardId,
         ability: ActivatedAbilityId,
@@ -77,19 +107,92 @@ pub enum LogEntry {
     },
 }
 
-#[derive(Debug, Default)]
+impl LogEntry {
+    /// A readable description of the entry, or None for entries which aren't interesting on their
+    /// own (e.g. a permanent becoming tapped) or which frontends display specially (a new turn).
+    pub fn describe(&self, db: &Database) -> Option<String> {
+        let name = |card: CardId| card.name(db).clone();
+        let controller = |card: CardId| {
+            db.all_players[Owner::from(db[card].controller)]
+                .name
+                .clone()
+        };
+
+        Some(match self {
+            LogEntry::LeftBattlefield { reason, name, .. } => match reason {
+                LeaveReason::Exiled => format!("{} is exiled", name),
+                LeaveReason::PutIntoGraveyard => format!("{} is put into a graveyard", name),
+                LeaveReason::ReturnedToHand => format!("{} returns to its owner's hand", name),
+                LeaveReason::ReturnedToLibrary => {
+                    format!("{} is put into its owner's library", name)
+                }
+            },
+            LogEntry::SpellResolved { spell, .. } => format!("{} resolves", name(*spell)),
+            LogEntry::Cast { card } => format!("{} casts {}", controller(*card), name(*card)),
+            LogEntry::LandPlayed { player, card } => {
+                format!("{} plays {}", db.all_players[*player].name, name(*card))
+            }
+            LogEntry::Activated { card, .. } => {
+                format!("{} activates {}", controller(*card), name(*card))
+            }
+            LogEntry::EtbOrTriggered { card } => format!("{} triggers", name(*card)),
+            LogEntry::CardChosen { card } => format!("{} is chosen", name(*card)),
+            LogEntry::Discarded { card } => {
+                format!("{} discards {}", controller(*card), name(*card))
+            }
+            LogEntry::NewTurn { .. }
+            | LogEntry::AbilityResolved { .. }
+            | LogEntry::Tapped { .. } => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct Log {
     pub entries: Vec<(LogId, LogEntry)>,
+    /// The cause of each entry in `entries`, if one was known when it was recorded.
+    causes: Vec<Option<Cause>>,
+    cause: Option<Cause>,
     last_turn: usize,
 
     current_id: usize,
 }
 
 impl Log {
+    /// Attributes every entry recorded from now on to `cause`, until a new cause is set.
+    pub(crate) fn set_cause(db: &mut Database, cause: Cause) {
+        db.log.cause = Some(cause);
+    }
+
+    /// Every entry along with what caused it, oldest first.
+    pub fn attributed(db: &Database) -> impl Iterator<Item = (&LogEntry, Option<Cause>)> {
+        db.log
+            .entries
+            .iter()
+            .map(|(_, entry)| entry)
+            .zip(db.log.causes.iter().copied())
+    }
+
+    /// What caused `card` to most recently leave the battlefield.
+    pub fn left_battlefield_cause(db: &Database, card: CardId) -> Option<Cause> {
+        Self::attributed(db)
+            .filter(|(entry, _)| {
+                matches!(entry, LogEntry::LeftBattlefield { card: left, .. } if *left == card)
+            })
+            .last()
+            .and_then(|(_, cause)| cause)
+    }
+
+    fn push(db: &mut Database, id: LogId, entry: LogEntry) {
+        event!(Level::INFO, ?id, ?entry, cause = ?db.log.cause);
+        db.log.entries.push((id, entry));
+        db.log.causes.push(db.log.cause);
+    }
+
     pub(crate) fn card_chosen(db: &mut Database, chosen: CardId) {
         let entry = LogEntry::CardChosen { card: chosen };
-        event!(Level::INFO, ?entry);
-        db.log.entries.push((LogId::current(db), entry))
+        let id = LogId::current(db);
+        Self::push(db, id, entry);
     }
 
     pub(crate) fn ability_resolved(db: &mut Database, source: CardId) {
@@ -97,8 +200,7 @@ impl Log {
             controller: db[source].controller,
         };
         let id = LogId::new(db);
-        event!(Level::INFO, ?id, ?entry);
-        db.log.entries.push((id, entry))
+        Self::push(db, id, entry);
     }
 
     pub(crate) fn spell_resolved(db: &mut Database, spell: CardId) {
@@ -107,15 +209,13 @@ impl Log {
             controller: db[spell].controller,
         };
         let id = LogId::new(db);
-        event!(Level::INFO, ?id, ?entry);
-        db.log.entries.push((id, entry))
+        Self::push(db, id, entry);
     }
 
     pub(crate) fn new_turn(db: &mut Database, player: Owner) {
         let entry = LogEntry::NewTurn { player };
         let id = LogId::new(db);
-        event!(Level::INFO, ?id, ?entry);
-        db.log.entries.push((id, entry));
+        Self::push(db, id, entry);
         db.log.last_turn = db.log.entries.len();
     }
 
@@ -156,29 +256,31 @@ impl Log {
         let entry = LogEntry::Tapped { card };
 
         let id = LogId::current(db);
-        event!(Level::INFO, ?id, ?entry);
-        db.log.entries.push((id, entry));
+        Self::push(db, id, entry);
     }
 
     pub(crate) fn cast(db: &mut Database, card: CardId) {
         let entry = LogEntry::Cast { card };
         let id = LogId::new(db);
-        event!(Level::INFO, ?id, ?entry);
-        db.log.entries.push((id, entry));
+        Self::push(db, id, entry);
+    }
+
+    pub(crate) fn land_played(db: &mut Database, player: Owner, card: CardId) {
+        let entry = LogEntry::LandPlayed { player, card };
+        let id = LogId::current(db);
+        Self::push(db, id, entry);
     }
 
     pub(crate) fn activated(db: &mut Database, card: CardId, ability: ActivatedAbilityId) {
         let entry = LogEntry::Activated { card, ability };
         let id = LogId::new(db);
-        event!(Level::INFO, ?id, ?entry);
-        db.log.entries.push((id, entry));
+        Self::push(db, id, entry);
     }
 
     pub(crate) fn etb_or_triggered(db: &mut Database, card: CardId) {
         let entry = LogEntry::EtbOrTriggered { card };
         let id = LogId::new(db);
-        event!(Level::INFO, ?id, ?entry);
-        db.log.entries.push((id, entry));
+        Self::push(db, id, entry);
     }
 
     pub(crate) fn left_battlefield(db: &mut Database, reason: LeaveReason, card: CardId) {
@@ -194,14 +296,12 @@ impl Log {
         };
 
         let id = LogId::current(db);
-        event!(Level::INFO, ?id, ?entry);
-        db.log.entries.push((id, entry));
+        Self::push(db, id, entry);
     }
 
     pub(crate) fn discarded(db: &mut Database, card: CardId) {
         let entry = LogEntry::Discarded { card };
         let id = LogId::current(db);
-        event!(Level::INFO, ?id, ?entry);
-        db.log.entries.push((id, entry));
+        Self::push(db, id, entry);
     }
 }
@@ -1,30 +1,277 @@
+//! Rendering costs with the glyphs of the bundled mana font. Frontends should display the
+//! results of [render_symbols] with the font loaded so every cost is drawn the same way.
+
+use itertools::Itertools;
+use protobuf::EnumOrUnknown;
+
 use crate::protogen::{color::Color, cost::ManaCost, mana::Mana};
 
-impl Mana {
-    pub(crate) fn push_mana_symbol(self, result: &mut String) {
+/// A symbol which can appear in a cost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Symbol {
+    Mana(Mana),
+    Generic(usize),
+    X,
+    /// One mana of either type.
+    Hybrid(Mana, Mana),
+    /// Two generic mana or one mana of the type.
+    TwoHybrid(Mana),
+    /// One mana of the type or 2 life.
+    Phyrexian(Mana),
+    Snow,
+    Tap,
+    Untap,
+}
+
+impl Symbol {
+    /// The symbols for `cost`, with all of its generic mana combined into a single symbol.
+    pub fn from_mana_cost(cost: &[EnumOrUnknown<ManaCost>]) -> Vec<Self> {
+        let generic = cost
+            .iter()
+            .filter(|cost| matches!(cost.enum_value().unwrap(), ManaCost::GENERIC))
+            .count();
+
+        let mut pushed_generic = false;
+        let mut symbols = vec![];
+        for mana in cost.iter() {
+            match mana.enum_value().unwrap() {
+                ManaCost::GENERIC => {
+                    if !pushed_generic {
+                        symbols.push(Symbol::Generic(generic));
+                        pushed_generic = true;
+                    }
+                }
+                ManaCost::X => symbols.push(Symbol::X),
+                ManaCost::TWO_X => symbols.extend([Symbol::X, Symbol::X]),
+                cost => symbols.push(match cost.mana_options()[..] {
+                    [mana] if cost.two_hybrid().is_some() => Symbol::TwoHybrid(mana),
+                    [mana] if cost.is_phyrexian() => Symbol::Phyrexian(mana),
+                    [left, right] => Symbol::Hybrid(left, right),
+                    _ => Symbol::Mana(cost.mana().unwrap()),
+                }),
+            }
+        }
+
+        symbols
+    }
+
+    pub fn push_glyphs(self, result: &mut String) {
         match self {
-            Mana::WHITE => result.push('\u{e600}'),
-            Mana::BLUE => result.push('\u{e601}'),
-            Mana::BLACK => result.push('\u{e602}'),
-            Mana::RED => result.push('\u{e603}'),
-            Mana::GREEN => result.push('\u{e604}'),
-            Mana::COLORLESS => result.push('\u{e904}'),
+            Symbol::Mana(Mana::WHITE) => result.push('\u{e600}'),
+            Symbol::Mana(Mana::BLUE) => result.push('\u{e601}'),
+            Symbol::Mana(Mana::BLACK) => result.push('\u{e602}'),
+            Symbol::Mana(Mana::RED) => result.push('\u{e603}'),
+            Symbol::Mana(Mana::GREEN) => result.push('\u{e604}'),
+            Symbol::Mana(Mana::COLORLESS) => result.push('\u{e904}'),
+            Symbol::Generic(generic) => match generic {
+                0..=15 => result.push(char::from_u32(0xe605 + generic as u32).unwrap()),
+                16..=20 => result.push(char::from_u32(0xe62a + generic as u32 - 16).unwrap()),
+                _ => result.push_str(&format!("{}", generic)),
+            },
+            Symbol::X => result.push('\u{e615}'),
+            // The font draws hybrid symbols by overlapping two glyphs, which plain text can't do,
+            // so the halves are separated by a slash like they are in oracle text.
+            Symbol::Hybrid(left, right) => {
+                Symbol::Mana(left).push_glyphs(result);
+                result.push('/');
+                Symbol::Mana(right).push_glyphs(result);
+            }
+            Symbol::TwoHybrid(mana) => {
+                Symbol::Generic(2).push_glyphs(result);
+                result.push('/');
+                Symbol::Mana(mana).push_glyphs(result);
+            }
+            Symbol::Phyrexian(mana) => {
+                Symbol::Mana(mana).push_glyphs(result);
+                result.push('/');
+                result.push('\u{e618}');
+            }
+            Symbol::Snow => result.push('\u{e619}'),
+            Symbol::Tap => result.push('\u{e61a}'),
+            Symbol::Untap => result.push('\u{e61b}'),
         }
     }
 }
 
+/// Renders `symbols` as glyphs in the bundled mana font.
+pub fn render_symbols(symbols: impl IntoIterator<Item = Symbol>) -> String {
+    let mut result = String::default();
+    for symbol in symbols {
+        symbol.push_glyphs(&mut result);
+    }
+
+    result
+}
+
+impl Mana {
+    pub(crate) fn push_mana_symbol(self, result: &mut String) {
+        Symbol::Mana(self).push_glyphs(result)
+    }
+}
+
 impl ManaCost {
-    pub(crate) fn color(&self) -> Color {
+    /// The cost for a symbol written without its braces, like `W`, `G/W`, `2/W`, or `W/P`. Generic
+    /// mana is written as a number instead, see [ManaCost::GENERIC].
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        let cost = match symbol {
+            "W" => ManaCost::WHITE,
+            "U" => ManaCost::BLUE,
+            "B" => ManaCost::BLACK,
+            "R" => ManaCost::RED,
+            "G" => ManaCost::GREEN,
+            "C" => ManaCost::COLORLESS,
+            "X" => ManaCost::X,
+            "W/U" => ManaCost::WHITE_BLUE,
+            "W/B" => ManaCost::WHITE_BLACK,
+            "U/B" => ManaCost::BLUE_BLACK,
+            "U/R" => ManaCost::BLUE_RED,
+            "B/R" => ManaCost::BLACK_RED,
+            "B/G" => ManaCost::BLACK_GREEN,
+            "R/W" => ManaCost::RED_WHITE,
+            "R/G" => ManaCost::RED_GREEN,
+            "G/W" => ManaCost::GREEN_WHITE,
+            "G/U" => ManaCost::GREEN_BLUE,
+            "W/P" => ManaCost::PHYREXIAN_WHITE,
+            "U/P" => ManaCost::PHYREXIAN_BLUE,
+            "B/P" => ManaCost::PHYREXIAN_BLACK,
+            "R/P" => ManaCost::PHYREXIAN_RED,
+            "G/P" => ManaCost::PHYREXIAN_GREEN,
+            "2/W" => ManaCost::TWO_WHITE,
+            "2/U" => ManaCost::TWO_BLUE,
+            "2/B" => ManaCost::TWO_BLACK,
+            "2/R" => ManaCost::TWO_RED,
+            "2/G" => ManaCost::TWO_GREEN,
+            _ => return None,
+        };
+
+        Some(cost)
+    }
+
+    /// The symbol for the cost without its braces, the inverse of [ManaCost::from_symbol]. None
+    /// for generic mana and {X}{X}, which aren't written as a single fixed symbol.
+    pub fn symbol(&self) -> Option<&'static str> {
+        let symbol = match self {
+            ManaCost::WHITE => "W",
+            ManaCost::BLUE => "U",
+            ManaCost::BLACK => "B",
+            ManaCost::RED => "R",
+            ManaCost::GREEN => "G",
+            ManaCost::COLORLESS => "C",
+            ManaCost::X => "X",
+            ManaCost::WHITE_BLUE => "W/U",
+            ManaCost::WHITE_BLACK => "W/B",
+            ManaCost::BLUE_BLACK => "U/B",
+            ManaCost::BLUE_RED => "U/R",
+            ManaCost::BLACK_RED => "B/R",
+            ManaCost::BLACK_GREEN => "B/G",
+            ManaCost::RED_WHITE => "R/W",
+            ManaCost::RED_GREEN => "R/G",
+            ManaCost::GREEN_WHITE => "G/W",
+            ManaCost::GREEN_BLUE => "G/U",
+            ManaCost::PHYREXIAN_WHITE => "W/P",
+            ManaCost::PHYREXIAN_BLUE => "U/P",
+            ManaCost::PHYREXIAN_BLACK => "B/P",
+            ManaCost::PHYREXIAN_RED => "R/P",
+            ManaCost::PHYREXIAN_GREEN => "G/P",
+            ManaCost::TWO_WHITE => "2/W",
+            ManaCost::TWO_BLUE => "2/U",
+            ManaCost::TWO_BLACK => "2/B",
+            ManaCost::TWO_RED => "2/R",
+            ManaCost::TWO_GREEN => "2/G",
+            ManaCost::GENERIC | ManaCost::TWO_X => return None,
+        };
+
+        Some(symbol)
+    }
+
+    /// The mana needed to pay for this symbol, if it requires a specific type of mana.
+    pub fn mana(&self) -> Option<Mana> {
         match self {
-            ManaCost::WHITE => Color::WHITE,
-            ManaCost::BLUE => Color::BLUE,
-            ManaCost::BLACK => Color::BLACK,
-            ManaCost::RED => Color::RED,
-            ManaCost::GREEN => Color::GREEN,
-            ManaCost::COLORLESS => Color::COLORLESS,
-            ManaCost::GENERIC => Color::COLORLESS,
-            ManaCost::X => Color::COLORLESS,
-            ManaCost::TWO_X => Color::COLORLESS,
+            ManaCost::WHITE => Some(Mana::WHITE),
+            ManaCost::BLUE => Some(Mana::BLUE),
+            ManaCost::BLACK => Some(Mana::BLACK),
+            ManaCost::RED => Some(Mana::RED),
+            ManaCost::GREEN => Some(Mana::GREEN),
+            ManaCost::COLORLESS => Some(Mana::COLORLESS),
+            _ => None,
+        }
+    }
+
+    /// The types of mana which can pay for this symbol with a single mana. Empty for symbols any
+    /// type of mana can pay for.
+    pub fn mana_options(&self) -> Vec<Mana> {
+        match self {
+            ManaCost::WHITE_BLUE => vec![Mana::WHITE, Mana::BLUE],
+            ManaCost::WHITE_BLACK => vec![Mana::WHITE, Mana::BLACK],
+            ManaCost::BLUE_BLACK => vec![Mana::BLUE, Mana::BLACK],
+            ManaCost::BLUE_RED => vec![Mana::BLUE, Mana::RED],
+            ManaCost::BLACK_RED => vec![Mana::BLACK, Mana::RED],
+            ManaCost::BLACK_GREEN => vec![Mana::BLACK, Mana::GREEN],
+            ManaCost::RED_WHITE => vec![Mana::RED, Mana::WHITE],
+            ManaCost::RED_GREEN => vec![Mana::RED, Mana::GREEN],
+            ManaCost::GREEN_WHITE => vec![Mana::GREEN, Mana::WHITE],
+            ManaCost::GREEN_BLUE => vec![Mana::GREEN, Mana::BLUE],
+            ManaCost::PHYREXIAN_WHITE | ManaCost::TWO_WHITE => vec![Mana::WHITE],
+            ManaCost::PHYREXIAN_BLUE | ManaCost::TWO_BLUE => vec![Mana::BLUE],
+            ManaCost::PHYREXIAN_BLACK | ManaCost::TWO_BLACK => vec![Mana::BLACK],
+            ManaCost::PHYREXIAN_RED | ManaCost::TWO_RED => vec![Mana::RED],
+            ManaCost::PHYREXIAN_GREEN | ManaCost::TWO_GREEN => vec![Mana::GREEN],
+            cost => cost.mana().into_iter().collect(),
+        }
+    }
+
+    /// For symbols which can also be paid with two mana of any type, the type of mana which pays
+    /// for them on its own.
+    pub fn two_hybrid(&self) -> Option<Mana> {
+        match self {
+            ManaCost::TWO_WHITE => Some(Mana::WHITE),
+            ManaCost::TWO_BLUE => Some(Mana::BLUE),
+            ManaCost::TWO_BLACK => Some(Mana::BLACK),
+            ManaCost::TWO_RED => Some(Mana::RED),
+            ManaCost::TWO_GREEN => Some(Mana::GREEN),
+            _ => None,
+        }
+    }
+
+    /// Whether 2 life can be paid rather than mana.
+    pub fn is_phyrexian(&self) -> bool {
+        matches!(
+            self,
+            ManaCost::PHYREXIAN_WHITE
+                | ManaCost::PHYREXIAN_BLUE
+                | ManaCost::PHYREXIAN_BLACK
+                | ManaCost::PHYREXIAN_RED
+                | ManaCost::PHYREXIAN_GREEN
+        )
+    }
+
+    /// How much the symbol adds to the mana value of a card.
+    pub(crate) fn mana_value(&self) -> usize {
+        if self.two_hybrid().is_some() {
+            2
+        } else {
+            1
+        }
+    }
+
+    pub(crate) fn colors(&self) -> Vec<Color> {
+        let colors = self
+            .mana_options()
+            .into_iter()
+            .map(|mana| match mana {
+                Mana::WHITE => Color::WHITE,
+                Mana::BLUE => Color::BLUE,
+                Mana::BLACK => Color::BLACK,
+                Mana::RED => Color::RED,
+                Mana::GREEN => Color::GREEN,
+                Mana::COLORLESS => Color::COLORLESS,
+            })
+            .collect_vec();
+        if colors.is_empty() {
+            vec![Color::COLORLESS]
+        } else {
+            colors
         }
     }
 }
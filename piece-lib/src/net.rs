@@ -0,0 +1,398 @@
+//! Playing a match over the network. One instance is the [Host], which runs the [Game] and is the
+//! only one to apply actions. The other is a [Client], which is sent an [Update] with what its
+//! player can see after every action and answers with the index of the action it takes.
+//!
+//! Messages are JSON, one per line, over TCP. Since the client only ever submits indices into the
+//! actions it was offered, it can't take an action the host wouldn't allow or see hidden
+//! information the host didn't send it.
+//!
+//! ```ignore
+//! // On the host.
+//! let listener = TcpListener::bind("0.0.0.0:7878")?;
+//! let mut host = Host::accept(&listener, &cards, "Alice".to_string(), alice)?;
+//! // On the client.
+//! let mut client = Client::connect("host:7878", "Bob".to_string(), &bob)?;
+//! ```
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+};
+
+use anyhow::{bail, Context};
+use itertools::Itertools;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    game::{Game, GameAction},
+    in_play::CardId,
+    library::DeckDefinition,
+    player::{Action, Controller, Owner},
+    turns::Phase,
+    Cards,
+};
+
+/// Sent from the client to the host.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum ClientMessage {
+    /// The first message sent after connecting, with the client's name and the name and count of
+    /// each card in their deck.
+    Join {
+        name: String,
+        deck: Vec<(String, usize)>,
+    },
+    /// Take the action at this index of [Update::actions].
+    Choose(usize),
+}
+
+/// Sent from the host to the client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum ServerMessage {
+    Update(Update),
+    /// The client's last message couldn't be used, e.g. because its deck was invalid or it chose
+    /// an action which wasn't offered. A rejected choice can be made again.
+    Rejected {
+        reason: String,
+    },
+}
+
+/// What a player can see of the game after an action, along with the actions they can take.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Update {
+    /// What happened since the previous update, see [crate::log::LogEntry::describe].
+    pub events: Vec<String>,
+    pub turn: usize,
+    pub phase: Phase,
+    pub active_player: String,
+    /// Every player, in turn order.
+    pub players: Vec<PlayerView>,
+    /// The stack, from the bottom up.
+    pub stack: Vec<String>,
+    /// The choice the player is making, if they have one pending.
+    pub prompt: Option<String>,
+    /// A description of each action the player can take. This is empty while waiting on another
+    /// player.
+    pub actions: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct PlayerView {
+    pub name: String,
+    pub life_total: i32,
+    pub lost: bool,
+    pub battlefield: Vec<PermanentView>,
+    /// The cards in hand, which are only sent to the player whose hand it is.
+    pub hand: Vec<String>,
+    pub cards_in_hand: usize,
+    pub cards_in_library: usize,
+    pub graveyard: Vec<String>,
+    pub exile: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct PermanentView {
+    pub name: String,
+    pub tapped: bool,
+    /// The counters on the permanent, e.g. `+1/+1 x2`.
+    pub counters: Vec<String>,
+}
+
+impl Update {
+    /// What `player` can see of the game, with the events logged after the first `since` log
+    /// entries.
+    pub fn new(game: &Game, player: Owner, since: usize) -> Self {
+        let db = game.db();
+        let names = |cards: &mut dyn Iterator<Item = CardId>| {
+            cards.map(|card| card.name(db).clone()).collect_vec()
+        };
+
+        let actions = game.legal_actions(player);
+        Self {
+            events: db
+                .log
+                .entries
+                .iter()
+                .skip(since)
+                .filter_map(|(_, entry)| entry.describe(db))
+                .collect_vec(),
+            turn: db.turn.turn_count,
+            phase: db.turn.phase.clone(),
+            active_player: db.all_players[db.turn.active_player()].name.clone(),
+            players: game
+                .players()
+                .iter()
+                .copied()
+                .map(|viewed| PlayerView {
+                    name: db.all_players[viewed].name.clone(),
+                    life_total: db.all_players[viewed].life_total,
+                    lost: db.all_players[viewed].lost,
+                    battlefield: db.battlefield.battlefields[&Controller::from(viewed)]
+                        .iter()
+                        .map(|card| PermanentView {
+                            name: card.name(db).clone(),
+                            tapped: card.tapped(db),
+                            counters: db[*card].counter_text_on(),
+                        })
+                        .collect_vec(),
+                    hand: if viewed == player {
+                        names(&mut db.hand[viewed].iter().copied())
+                    } else {
+                        vec![]
+                    },
+                    cards_in_hand: db.hand[viewed].len(),
+                    cards_in_library: db.all_players[viewed].library.cards().count(),
+                    graveyard: names(&mut db.graveyard[viewed].iter().copied()),
+                    exile: names(&mut db.exile[viewed].iter().copied()),
+                })
+                .collect_vec(),
+            stack: db
+                .stack
+                .entries
+                .values()
+                .map(|entry| entry.display(db))
+                .collect_vec(),
+            prompt: game
+                .pending_choice()
+                .filter(|choice| choice.player == player)
+                .map(|choice| choice.description),
+            actions: actions
+                .iter()
+                .map(|action| describe(game, action))
+                .collect_vec(),
+        }
+    }
+}
+
+/// Runs the game and applies the actions of both the local player and the client.
+pub struct Host {
+    game: Game,
+    local: Owner,
+    remote: Owner,
+    connection: Connection,
+    /// How many log entries have been described to each player.
+    local_logged: usize,
+    remote_logged: usize,
+}
+
+impl Host {
+    /// Waits for a client to connect and join, then starts a game between the host and the
+    /// client, with the host going first. An invalid deck is rejected, and the client is told
+    /// why.
+    pub fn accept(
+        listener: &TcpListener,
+        cards: &Cards,
+        name: String,
+        deck: DeckDefinition,
+    ) -> anyhow::Result<Self> {
+        let (stream, _) = listener.accept()?;
+        let mut connection = Connection::new(stream)?;
+        let ClientMessage::Join {
+            name: remote_name,
+            deck: remote_cards,
+        } = connection.recv()?
+        else {
+            connection.send(&ServerMessage::Rejected {
+                reason: "Expected to join the game".to_string(),
+            })?;
+            bail!("The client didn't join the game");
+        };
+
+        let mut remote_deck = DeckDefinition::default();
+        for (card, count) in remote_cards {
+            remote_deck.add_card(card, count);
+        }
+
+        let game = match Game::new(cards, [(name, deck), (remote_name, remote_deck)]) {
+            Ok(game) => game,
+            Err(e) => {
+                connection.send(&ServerMessage::Rejected {
+                    reason: e.to_string(),
+                })?;
+                return Err(e);
+            }
+        };
+
+        let [local, remote] = game.players()[..] else {
+            unreachable!()
+        };
+        let mut host = Self {
+            game,
+            local,
+            remote,
+            connection,
+            local_logged: 0,
+            remote_logged: 0,
+        };
+        host.send_update()?;
+
+        Ok(host)
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn local_player(&self) -> Owner {
+        self.local
+    }
+
+    pub fn remote_player(&self) -> Owner {
+        self.remote
+    }
+
+    /// What the local player can see, with the events since the last time this was called.
+    pub fn update(&mut self) -> Update {
+        let update = Update::new(&self.game, self.local, self.local_logged);
+        self.local_logged = self.game.db().log.entries.len();
+        update
+    }
+
+    /// Takes the action at `index` of the local player's [Update::actions] and sends the result to
+    /// the client.
+    pub fn choose(&mut self, index: usize) -> anyhow::Result<()> {
+        let actions = self.game.legal_actions(self.local);
+        let Some(action) = actions.get(index) else {
+            bail!("No action {}, expected one of 0..{}", index, actions.len());
+        };
+
+        self.game.apply(*action)?;
+        self.send_update()
+    }
+
+    /// If the client has to act, waits for its choice and applies it. Returns whether an action
+    /// was applied, which it isn't if the client doesn't have to act or chose an action which
+    /// wasn't offered.
+    pub fn poll_remote(&mut self) -> anyhow::Result<bool> {
+        if self.game.player_to_act() != self.remote {
+            return Ok(false);
+        }
+
+        match self.connection.recv()? {
+            ClientMessage::Choose(index) => {
+                let actions = self.game.legal_actions(self.remote);
+                if let Some(action) = actions.get(index) {
+                    self.game.apply(*action)?;
+                    self.send_update()?;
+                    Ok(true)
+                } else {
+                    self.connection.send(&ServerMessage::Rejected {
+                        reason: format!(
+                            "No action {}, expected one of 0..{}",
+                            index,
+                            actions.len()
+                        ),
+                    })?;
+                    Ok(false)
+                }
+            }
+            ClientMessage::Join { .. } => {
+                self.connection.send(&ServerMessage::Rejected {
+                    reason: "Already joined the game".to_string(),
+                })?;
+                Ok(false)
+            }
+        }
+    }
+
+    fn send_update(&mut self) -> anyhow::Result<()> {
+        let update = Update::new(&self.game, self.remote, self.remote_logged);
+        self.remote_logged = self.game.db().log.entries.len();
+        self.connection.send(&ServerMessage::Update(update))
+    }
+}
+
+/// Plays against a [Host] by choosing from the actions it offers.
+pub struct Client {
+    connection: Connection,
+}
+
+impl Client {
+    /// Connects to the host and joins its game with the deck.
+    pub fn connect(
+        address: impl ToSocketAddrs,
+        name: String,
+        deck: &DeckDefinition,
+    ) -> anyhow::Result<Self> {
+        let stream = TcpStream::connect(address).context("Connecting to the host")?;
+        let mut connection = Connection::new(stream)?;
+        connection.send(&ClientMessage::Join {
+            name,
+            deck: deck
+                .cards()
+                .map(|(card, count)| (card.to_string(), count))
+                .sorted()
+                .collect_vec(),
+        })?;
+
+        Ok(Self { connection })
+    }
+
+    /// Waits for the next message from the host.
+    pub fn recv(&mut self) -> anyhow::Result<ServerMessage> {
+        self.connection.recv()
+    }
+
+    /// Takes the action at `index` of the last [Update::actions] received.
+    pub fn choose(&mut self, index: usize) -> anyhow::Result<()> {
+        self.connection.send(&ClientMessage::Choose(index))
+    }
+}
+
+struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    fn new(stream: TcpStream) -> anyhow::Result<Self> {
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        })
+    }
+
+    fn send(&mut self, message: &impl Serialize) -> anyhow::Result<()> {
+        let mut line = serde_json::to_string(message)?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn recv<T: DeserializeOwned>(&mut self) -> anyhow::Result<T> {
+        let mut line = String::default();
+        if self.reader.read_line(&mut line)? == 0 {
+            bail!("The connection was closed");
+        }
+        serde_json::from_str(&line).context("Invalid message")
+    }
+}
+
+fn describe(game: &Game, action: &GameAction) -> String {
+    let db = game.db();
+    match action {
+        GameAction::Act(Action::Play(card)) => format!("Play {}", card.name(db)),
+        GameAction::Act(Action::Activate { card, index, .. }) => db[*card]
+            .abilities(db)
+            .get(*index)
+            .map(|(_, ability)| format!("{}: {}", card.name(db), ability.text(db)))
+            .unwrap_or_else(|| format!("Activate {}", card.name(db))),
+        GameAction::PassPriority => "Pass priority".to_string(),
+        GameAction::Choose(Some(index)) => game
+            .pending_choice()
+            .and_then(|choice| {
+                choice
+                    .options
+                    .into_iter()
+                    .find(|option| option.index == *index)
+            })
+            .map(|option| option.description)
+            .unwrap_or_else(|| format!("Option {}", index)),
+        GameAction::Choose(None) => "Done".to_string(),
+    }
+}
@@ -0,0 +1,120 @@
+//! Semantic cues about the game which frontends may want to turn into sounds or OS notifications.
+//! The engine only queues cues as the rules produce them, the frontend decides when and how to
+//! present them by calling [Notifications::drain].
+//!
+//! Turn changes, low life totals, and the end of the game are noticed by the engine as it runs.
+//! Who holds the current decision depends on the pending effects the frontend is holding, so the
+//! frontend reports them with [Notifications::decision] in the same way it does for
+//! [crate::clock::Clock::elapse].
+
+use std::collections::{HashSet, VecDeque};
+
+use crate::{effects::PendingEffects, in_play::Database, player::Owner};
+
+/// The life total at or below which [Notification::LifeLow] is sent by default.
+const DEFAULT_LOW_LIFE: i32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Notification {
+    /// The player's turn started.
+    YourTurn(Owner),
+    /// The player has priority or must make a choice before the game can continue.
+    ActionRequired(Owner),
+    /// The player's life total fell to or below the low life threshold.
+    LifeLow { player: Owner, life: i32 },
+    /// At most one player remains in the game.
+    GameOver { winner: Option<Owner> },
+}
+
+#[derive(Debug, Clone)]
+pub struct Notifications {
+    queue: VecDeque<Notification>,
+    low_life: i32,
+    /// Players who have already been warned about their life total. Cleared once they recover so
+    /// the warning is repeated if they fall low again.
+    warned: HashSet<Owner>,
+    deciding: Option<Owner>,
+    game_over: bool,
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Self {
+            queue: Default::default(),
+            low_life: DEFAULT_LOW_LIFE,
+            warned: Default::default(),
+            deciding: None,
+            game_over: false,
+        }
+    }
+}
+
+impl Notifications {
+    /// Removes and returns every queued notification, oldest first.
+    pub fn drain(db: &mut Database) -> Vec<Notification> {
+        db.notifications.queue.drain(..).collect()
+    }
+
+    pub fn set_low_life_threshold(db: &mut Database, life: i32) {
+        db.notifications.low_life = life;
+    }
+
+    /// Reports the pending effects the frontend is resolving, if any. Queues
+    /// [Notification::ActionRequired] when the decision passes to a different player, which is the
+    /// player with priority over the pending effects if there are any, otherwise the priority
+    /// player.
+    pub fn decision(db: &mut Database, pending: Option<&PendingEffects>) {
+        if db.notifications.game_over {
+            return;
+        }
+
+        let player = match pending {
+            Some(pending) if !pending.is_empty() => pending.priority(db),
+            _ => db.turn.priority_player(),
+        };
+
+        if db.notifications.deciding != Some(player) {
+            db.notifications.deciding = Some(player);
+            db.notifications
+                .queue
+                .push_back(Notification::ActionRequired(player));
+        }
+    }
+
+    pub(crate) fn new_turn(db: &mut Database, player: Owner) {
+        db.notifications
+            .queue
+            .push_back(Notification::YourTurn(player));
+    }
+
+    pub(crate) fn check_players(db: &mut Database) {
+        if db.notifications.game_over {
+            return;
+        }
+
+        let players = db.all_players.all_players();
+        for player in players.iter().copied() {
+            let life = db.all_players[player].life_total;
+            if db.all_players[player].lost || life > db.notifications.low_life {
+                db.notifications.warned.remove(&player);
+            } else if db.notifications.warned.insert(player) {
+                db.notifications
+                    .queue
+                    .push_back(Notification::LifeLow { player, life });
+            }
+        }
+
+        let remaining = players
+            .iter()
+            .copied()
+            .filter(|player| !db.all_players[*player].lost)
+            .collect::<Vec<_>>();
+        if remaining.len() <= 1 {
+            db.notifications.game_over = true;
+            db.notifications.queue.push_back(Notification::GameOver {
+                winner: remaining.first().copied(),
+            });
+        }
+    }
+}
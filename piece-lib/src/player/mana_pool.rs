@@ -15,7 +15,7 @@ use crate::{
 };
 
 impl Reason {
-    fn card(&self) -> Option<CardId> {
+    pub(crate) fn card(&self) -> Option<CardId> {
         match self {
             Reason::Activating(source) => Some(source.source.as_ref().cloned().unwrap().into()),
             Reason::Casting(card) => Some(card.card.as_ref().cloned().unwrap().into()),
@@ -24,9 +24,79 @@ impl Reason {
     }
 }
 
+/// The permanent which produced some mana, as it was when the mana was produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManaProducer {
+    pub card: CardId,
+    pub types: TypeSet,
+}
+
+impl ManaProducer {
+    pub(crate) fn new(db: &Database, card: CardId) -> Self {
+        Self {
+            card,
+            types: db[card].modified_types.clone(),
+        }
+    }
+
+    pub fn snow(&self) -> bool {
+        self.types.contains(&Type::SNOW)
+    }
+}
+
+/// An amount of unspent mana of one kind in a player's pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FloatingMana {
+    pub mana: Mana,
+    pub source: ManaSource,
+    pub restriction: ManaRestriction,
+    pub count: usize,
+}
+
+impl FloatingMana {
+    pub fn display(floating: &[FloatingMana]) -> Vec<String> {
+        display(floating.iter().map(|floating| {
+            (
+                floating.count,
+                floating.mana,
+                floating.source,
+                floating.restriction,
+            )
+        }))
+    }
+
+    /// The mana in `after` which wasn't in `before`.
+    pub(crate) fn added(before: &[FloatingMana], after: &[FloatingMana]) -> Vec<FloatingMana> {
+        after
+            .iter()
+            .filter_map(|floating| {
+                let previous = before
+                    .iter()
+                    .find(|previous| {
+                        previous.mana == floating.mana
+                            && previous.source == floating.source
+                            && previous.restriction == floating.restriction
+                    })
+                    .map(|previous| previous.count)
+                    .unwrap_or_default();
+
+                floating
+                    .count
+                    .checked_sub(previous)
+                    .filter(|added| *added > 0)
+                    .map(|count| FloatingMana { count, ..*floating })
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ManaPool {
     pub(crate) sourced: BTreeMap<Mana, BTreeMap<ManaSource, BTreeMap<ManaRestriction, usize>>>,
+    /// The producers of the mana in each pool in [ManaPool::sourced], most recently produced last.
+    /// Mana which is added without a producer isn't tracked here, so there may be fewer producers
+    /// than mana.
+    pub(crate) producers: BTreeMap<(Mana, ManaSource, ManaRestriction), Vec<ManaProducer>>,
 }
 
 impl Default for ManaPool {
@@ -44,13 +114,17 @@ impl Default for ManaPool {
                 .or_default() = 0;
         }
 
-        Self { sourced }
+        Self {
+            sourced,
+            producers: Default::default(),
+        }
     }
 }
 
 impl ManaPool {
     pub(crate) fn drain(&mut self) {
         self.sourced.clear();
+        self.producers.clear();
         for mana in Mana::iter() {
             *self
                 .sourced
@@ -75,6 +149,21 @@ impl ManaPool {
         *sourced = sourced.saturating_add(1);
     }
 
+    /// Add mana produced by a permanent, remembering the permanent as the mana's producer.
+    pub(crate) fn apply_produced(
+        &mut self,
+        mana: Mana,
+        source: ManaSource,
+        restriction: ManaRestriction,
+        producer: ManaProducer,
+    ) {
+        self.apply(mana, source, restriction);
+        self.producers
+            .entry((mana, source, restriction))
+            .or_default()
+            .push(producer);
+    }
+
     pub(crate) fn spend(
         &mut self,
         db: &Database,
@@ -82,6 +171,7 @@ impl ManaPool {
         source: ManaSource,
         reason: &Reason,
     ) -> (bool, ManaSource) {
+        let spending = mana;
         let mana = self.sourced.entry(mana).or_default();
         let mut ultimate_source = source;
         let mut sourced = mana
@@ -111,79 +201,44 @@ impl ManaPool {
         }
 
         if let Some(sourced) = sourced {
-            let card = reason.card();
-
-            if card.is_none() {
-                let restricted = sourced.get_mut(&ManaRestriction::NONE);
-                if let Some(restricted) = restricted {
-                    let Some(mana) = restricted.checked_sub(1) else {
-                        return (false, ManaSource::ANY);
-                    };
-
-                    *restricted = mana;
-                    return (true, ultimate_source);
-                } else {
-                    return (false, ManaSource::ANY);
-                }
-            }
-
-            let card = card.unwrap();
-            if card.types_intersect(db, &TypeSet::from([Type::ARTIFACT])) {
-                let restricted = if let Some(restricted) =
-                    sourced.get_mut(&ManaRestriction::ARTIFACT_SPELL_OR_ABILITY)
-                {
-                    Some(restricted)
-                } else if matches!(reason, Reason::Activating(_)) {
-                    if let Some(restricted) = sourced.get_mut(&ManaRestriction::ACTIVATE_ABILITY) {
-                        Some(restricted)
+            let restriction = match reason.card() {
+                None => ManaRestriction::NONE,
+                Some(card) if card.types_intersect(db, &TypeSet::from([Type::ARTIFACT])) => {
+                    if sourced.contains_key(&ManaRestriction::ARTIFACT_SPELL_OR_ABILITY) {
+                        ManaRestriction::ARTIFACT_SPELL_OR_ABILITY
+                    } else if matches!(reason, Reason::Activating(_))
+                        && sourced.contains_key(&ManaRestriction::ACTIVATE_ABILITY)
+                    {
+                        ManaRestriction::ACTIVATE_ABILITY
                     } else {
-                        sourced.get_mut(&ManaRestriction::NONE)
+                        ManaRestriction::NONE
                     }
-                } else {
-                    sourced.get_mut(&ManaRestriction::NONE)
-                };
-
-                if let Some(restricted) = restricted {
-                    let Some(mana) = restricted.checked_sub(1) else {
-                        return (false, ManaSource::ANY);
-                    };
-
-                    *restricted = mana;
-                    (true, ultimate_source)
-                } else {
-                    (false, ManaSource::ANY)
-                }
-            } else if matches!(reason, Reason::Activating(_)) {
-                let restricted =
-                    if let Some(restricted) = sourced.get_mut(&ManaRestriction::ACTIVATE_ABILITY) {
-                        Some(restricted)
-                    } else {
-                        sourced.get_mut(&ManaRestriction::NONE)
-                    };
-
-                if let Some(restricted) = restricted {
-                    let Some(mana) = restricted.checked_sub(1) else {
-                        return (false, ManaSource::ANY);
-                    };
-
-                    *restricted = mana;
-                    (true, ultimate_source)
-                } else {
-                    (false, ManaSource::ANY)
                 }
-            } else {
-                let restricted = sourced.get_mut(&ManaRestriction::NONE);
-                if let Some(restricted) = restricted {
-                    let Some(mana) = restricted.checked_sub(1) else {
-                        return (false, ManaSource::ANY);
-                    };
-
-                    *restricted = mana;
-                    (true, ultimate_source)
-                } else {
-                    (false, ManaSource::ANY)
+                Some(_)
+                    if matches!(reason, Reason::Activating(_))
+                        && sourced.contains_key(&ManaRestriction::ACTIVATE_ABILITY) =>
+                {
+                    ManaRestriction::ACTIVATE_ABILITY
                 }
+                Some(_) => ManaRestriction::NONE,
+            };
+
+            let Some(restricted) = sourced.get_mut(&restriction) else {
+                return (false, ManaSource::ANY);
+            };
+            let Some(remaining) = restricted.checked_sub(1) else {
+                return (false, ManaSource::ANY);
+            };
+            *restricted = remaining;
+
+            if let Some(producers) =
+                self.producers
+                    .get_mut(&(spending, ultimate_source, restriction))
+            {
+                producers.pop();
             }
+
+            (true, ultimate_source)
         } else {
             (false, ManaSource::ANY)
         }
@@ -196,54 +251,62 @@ impl ManaPool {
         source: ManaSource,
         reason: &Reason,
     ) -> bool {
-        let mut mana_pool = self.clone();
-        match cost {
-            ManaCost::WHITE => {
-                if let (false, _) = mana_pool.spend(db, Mana::WHITE, source, reason) {
-                    return false;
-                }
-            }
-            ManaCost::BLUE => {
-                if let (false, _) = mana_pool.spend(db, Mana::BLUE, source, reason) {
-                    return false;
-                }
-            }
-            ManaCost::BLACK => {
-                if let (false, _) = mana_pool.spend(db, Mana::BLACK, source, reason) {
-                    return false;
-                }
-            }
-            ManaCost::RED => {
-                if let (false, _) = mana_pool.spend(db, Mana::RED, source, reason) {
-                    return false;
-                }
-            }
-            ManaCost::GREEN => {
-                if let (false, _) = mana_pool.spend(db, Mana::GREEN, source, reason) {
-                    return false;
-                }
-            }
-            ManaCost::COLORLESS => {
-                if let (false, _) = mana_pool.spend(db, Mana::COLORLESS, source, reason) {
-                    return false;
-                }
-            }
-            ManaCost::GENERIC => {
-                if let Some(max) = mana_pool.max(db, reason) {
-                    if let (false, _) = mana_pool.spend(db, max, source, reason) {
-                        return false;
-                    }
-                } else {
-                    return false;
-                }
+        self.clone().spend_cost(db, cost, source, reason)
+    }
+
+    /// Spends the mana for a single symbol of a cost, returning whether it could be paid in full.
+    /// Nothing needs to be spent for X.
+    pub(crate) fn spend_cost(
+        &mut self,
+        db: &Database,
+        cost: ManaCost,
+        source: ManaSource,
+        reason: &Reason,
+    ) -> bool {
+        if matches!(cost, ManaCost::X | ManaCost::TWO_X) {
+            return true;
+        }
+
+        let Some(mana) = self.mana_for(db, cost, source, reason) else {
+            return false;
+        };
+        if let (false, _) = self.spend(db, mana, source, reason) {
+            return false;
+        }
+
+        if cost.two_hybrid().is_some_and(|two| two != mana) {
+            let Some(max) = self.max(db, reason) else {
+                return false;
+            };
+            if let (false, _) = self.spend(db, max, source, reason) {
+                return false;
             }
-            ManaCost::X => {}
-            ManaCost::TWO_X => {}
         }
 
         true
     }
 
+    /// The type of mana to spend on a symbol of a cost. Symbols which can be paid with several
+    /// types of mana use whichever is available, falling back to generic mana for {2/W}-style
+    /// symbols.
+    pub(crate) fn mana_for(
+        &self,
+        db: &Database,
+        cost: ManaCost,
+        source: ManaSource,
+        reason: &Reason,
+    ) -> Option<Mana> {
+        match cost {
+            ManaCost::X | ManaCost::TWO_X => None,
+            ManaCost::GENERIC => self.max(db, reason),
+            cost => cost
+                .mana_options()
+                .into_iter()
+                .find(|mana| self.clone().spend(db, *mana, source, reason).0)
+                .or_else(|| cost.two_hybrid().and_then(|_| self.max(db, reason))),
+        }
+    }
+
     pub(crate) fn all_mana(
         &self,
     ) -> impl Iterator<Item = (usize, Mana, ManaSource, ManaRestriction)> + std::fmt::Debug + '_
@@ -264,22 +327,67 @@ impl ManaPool {
     }
 
     pub(crate) fn max(&self, db: &Database, reason: &Reason) -> Option<Mana> {
-        self.available_mana()
-            .filter(|(_, _, _, restriction)| {
-                if *restriction == ManaRestriction::NONE {
-                    return true;
-                }
-
-                if let Some(card) = reason.card() {
-                    card.types_intersect(db, &TypeSet::from([Type::ARTIFACT]))
-                } else {
-                    false
-                }
-            })
+        self.spendable_mana(db, reason)
             .max_by_key(|(count, _, _, _)| *count)
             .map(|(_, mana, _, _)| mana)
     }
 
+    /// The total amount of mana which can be spent for the reason. Saturates rather than
+    /// overflowing when a player has infinite mana.
+    pub(crate) fn spendable_count(&self, db: &Database, reason: &Reason) -> usize {
+        self.spendable_mana(db, reason)
+            .map(|(count, _, _, _)| count)
+            .fold(0, usize::saturating_add)
+    }
+
+    fn spendable_mana<'a>(
+        &'a self,
+        db: &'a Database,
+        reason: &'a Reason,
+    ) -> impl Iterator<Item = (usize, Mana, ManaSource, ManaRestriction)> + 'a {
+        self.available_mana().filter(|(_, _, _, restriction)| {
+            if *restriction == ManaRestriction::NONE {
+                return true;
+            }
+
+            if let Some(card) = reason.card() {
+                card.types_intersect(db, &TypeSet::from([Type::ARTIFACT]))
+            } else {
+                false
+            }
+        })
+    }
+
+    /// The producers of the unspent mana of a given type.
+    pub fn producers(&self, mana: Mana) -> impl Iterator<Item = &ManaProducer> + '_ {
+        self.producers
+            .iter()
+            .filter(move |((produced, _, _), _)| *produced == mana)
+            .flat_map(|(_, producers)| producers.iter())
+    }
+
+    /// The amount of unspent mana which was produced by a permanent matching the predicate, e.g.
+    /// to find the mana produced by creatures or by snow permanents.
+    pub fn available_from(&self, predicate: impl Fn(&ManaProducer) -> bool) -> usize {
+        self.producers
+            .values()
+            .flat_map(|producers| producers.iter())
+            .filter(|producer| predicate(producer))
+            .count()
+    }
+
+    /// The unspent mana in the pool.
+    pub fn floating(&self) -> Vec<FloatingMana> {
+        self.available_mana()
+            .map(|(count, mana, source, restriction)| FloatingMana {
+                mana,
+                source,
+                restriction,
+                count,
+            })
+            .collect()
+    }
+
     pub fn available_pool_display(&self) -> Vec<String> {
         let available = self.available_mana();
 
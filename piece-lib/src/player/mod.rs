@@ -1,36 +1,54 @@
-pub(crate) mod mana_pool;
+pub mod mana_pool;
 
 use std::ops::{Index, IndexMut};
 
 use indexmap::IndexMap;
 use itertools::Itertools;
+use rand::Rng;
 use strum::IntoEnumIterator;
 use uuid::Uuid;
 
 use crate::{
+    abilities::Ability,
     battlefield::Battlefields,
+    config::{Cosmetics, GameConfig, Vanguard},
     effects::{EffectBundle, PendingEffects},
     in_play::{CardId, Database},
     library::Library,
-    log::{Log, LogEntry, LogId},
+    log::{Cause, Log, LogEntry, LogId},
     player::mana_pool::ManaPool,
     protogen::{
         self,
-        effects::{count::Fixed, Count, DrawCards, MoveToBattlefield},
+        effects::{
+            count::Fixed, pay_cost::PayMana, Count, DrawCards, MoveToBattlefield, MoveToHand,
+            PayCost, PayCosts,
+        },
         targets::{
             restriction::{self, EnteredBattlefieldThisTurn},
             Restriction,
         },
     },
     protogen::{
+        cost::ManaCost,
         effects::{static_ability, PopSelected},
         ids::UUID,
-        mana::{spend_reason::Reason, Mana, ManaRestriction, ManaSource},
+        mana::{
+            spend_reason::{Casting, Reason},
+            Mana, ManaRestriction, ManaSource, SpendReason,
+        },
         targets::Location,
     },
+    record::GameRecord,
     stack::{Selected, Stack, TargetType},
+    turns::{Phase, Turn},
 };
 
+/// The generic mana paid to put a companion into its owner's hand.
+const COMPANION_COST: usize = 3;
+
+/// The life paid rather than mana for a Phyrexian mana symbol.
+const PHYREXIAN_LIFE: u32 = 2;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct Owner(Uuid);
 
@@ -186,9 +204,15 @@ impl Owner {
                         return false;
                     }
                 }
+                restriction::Restriction::ExiledWith(_) => {
+                    return false;
+                }
                 restriction::Restriction::HasActivatedAbility(_) => {
                     return false;
                 }
+                restriction::Restriction::HasChosenName(_) => {
+                    return false;
+                }
                 restriction::Restriction::InGraveyard(_) => {
                     return false;
                 }
@@ -331,12 +355,36 @@ impl IndexMut<Controller> for AllPlayers {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct AllPlayers {
     players: IndexMap<Owner, Player>,
+    config: GameConfig,
 }
 
 impl AllPlayers {
+    pub fn new(config: GameConfig) -> Self {
+        Self {
+            players: Default::default(),
+            config,
+        }
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    /// Adds a player with the starting life and hand sizes from the [GameConfig], adjusted by
+    /// their vanguard.
+    #[must_use]
+    pub fn join(&mut self, name: String, vanguard: Vanguard) -> Owner {
+        let id = self.new_player(name, self.config.starting_life + vanguard.life);
+        let player = &mut self[id];
+        player.hand_size = vanguard.hand_size(player.hand_size);
+        player.starting_hand_size = vanguard.hand_size(player.starting_hand_size);
+
+        id
+    }
+
     #[must_use]
     pub fn new_player(&mut self, name: String, life_total: i32) -> Owner {
         let id = Owner(Uuid::new_v4());
@@ -346,31 +394,65 @@ impl AllPlayers {
                 name,
                 hexproof: false,
                 life_total,
-                hand_size: 7,
+                hand_size: self.config.max_hand_size,
+                starting_hand_size: self.config.starting_hand_size,
                 lands_played_this_turn: 0,
                 life_gained_this_turn: 0,
                 ban_attacking_this_turn: false,
                 mana_pool: Default::default(),
                 library: Library::empty(),
+                companion: None,
+                auto_pass: true,
+                mulligans: 0,
+                poison_counters: 0,
+                drew_from_empty_library: false,
                 lost: false,
+                cosmetics: Cosmetics::default(),
             },
         );
 
         id
     }
 
-    pub(crate) fn all_players(&self) -> Vec<Owner> {
+    pub fn all_players(&self) -> Vec<Owner> {
         self.players.keys().copied().collect_vec()
     }
+
+    /// The players who haven't lost, in turn order starting with `first`.
+    pub(crate) fn starting_with(&self, first: Owner) -> Vec<Owner> {
+        let start = self.players.get_index_of(&first).unwrap_or_default();
+        self.players
+            .iter()
+            .cycle()
+            .skip(start)
+            .take(self.players.len())
+            .filter(|(_, player)| !player.lost)
+            .map(|(owner, _)| *owner)
+            .collect_vec()
+    }
 }
 
-#[derive(Debug)]
+/// A choice available to a player with priority. See [Player::actions].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Cast a spell or play a land from hand, or from another zone as though it were in hand.
+    Play(CardId),
+    /// Activate the ability at `index` of the card.
+    Activate {
+        card: CardId,
+        index: usize,
+        mana: bool,
+    },
+}
+
+#[derive(Debug, Clone)]
 pub struct Player {
     pub name: String,
 
     #[allow(unused)]
     pub(crate) hexproof: bool,
     pub(crate) hand_size: usize,
+    pub(crate) starting_hand_size: usize,
     pub mana_pool: ManaPool,
 
     pub(crate) lands_played_this_turn: usize,
@@ -380,8 +462,21 @@ pub struct Player {
     pub life_total: i32,
 
     pub library: Library,
+    pub(crate) companion: Option<CardId>,
+
+    /// Automatically pass priority when [Player::should_auto_pass].
+    pub auto_pass: bool,
+
+    pub(crate) mulligans: usize,
+
+    pub poison_counters: u32,
+    /// Set when the player is asked to draw from an empty library, so that they lose the next
+    /// time state-based actions are checked.
+    pub(crate) drew_from_empty_library: bool,
 
     pub lost: bool,
+
+    pub cosmetics: Cosmetics,
 }
 
 impl Player {
@@ -400,16 +495,71 @@ impl Player {
     }
 
     pub fn draw_initial_hand(db: &mut Database, player: Owner) {
-        for _ in 0..7 {
+        for _ in 0..db.all_players[player].starting_hand_size {
             let card = db.all_players[player]
                 .library
                 .draw()
-                .expect("Decks should have at least 7 cards");
+                .expect("Decks should have enough cards for an opening hand");
 
             card.move_to_hand(db);
         }
     }
 
+    /// Shuffles the player's hand into their library and draws a new opening hand. The player
+    /// should then put [Player::cards_to_bottom] cards from their hand on the bottom of their
+    /// library using [Player::put_on_bottom].
+    pub fn mulligan(db: &mut Database, player: Owner, rng: &mut impl Rng) {
+        let description = format!(
+            "Mulligan {}",
+            db.hand[player].iter().map(|card| card.name(db)).join(", ")
+        );
+        GameRecord::decision(
+            db,
+            player,
+            "Mulligan".to_string(),
+            description,
+            vec![],
+            None,
+        );
+
+        for card in db.hand[player].iter().copied().collect_vec() {
+            Library::place_on_top(db, player, card);
+        }
+        Library::reshuffle(db, player, rng);
+        db.all_players[player].mulligans += 1;
+
+        Self::draw_initial_hand(db, player);
+    }
+
+    pub fn mulligans(db: &Database, player: Owner) -> usize {
+        db.all_players[player].mulligans
+    }
+
+    /// How many cards the player puts on the bottom of their library for the mulligans they have
+    /// taken, which is one fewer than [Player::mulligans] if the first mulligan is free.
+    pub fn cards_to_bottom(db: &Database, player: Owner) -> usize {
+        let mulligans = db.all_players[player].mulligans;
+        if db.all_players.config.free_mulligan {
+            mulligans.saturating_sub(1)
+        } else {
+            mulligans
+        }
+    }
+
+    pub fn put_on_bottom(db: &mut Database, player: Owner, card: CardId) {
+        assert!(db.hand[player].contains(&card));
+        let description = format!("Put {} on the bottom of library", card.name(db));
+        GameRecord::decision(
+            db,
+            player,
+            "PutOnBottom".to_string(),
+            description,
+            vec![],
+            None,
+        );
+        Library::place_on_bottom(db, player, card);
+    }
+
     pub fn draw(player: Owner, count: u32) -> PendingEffects {
         let mut results = PendingEffects::default();
         results.push_back(EffectBundle {
@@ -442,19 +592,25 @@ impl Player {
         results
     }
 
+    /// Cast the spell or play the land, either from the player's hand or from another zone a
+    /// static ability lets them play it from.
     pub fn play_card(db: &mut Database, player: Owner, card: CardId) -> PendingEffects {
-        assert!(db.hand[player].contains(&card));
+        assert!(db.hand[player].contains(&card) || card.play_permitted(db));
+        let from = card.location(db).unwrap();
 
         if card.is_land(db) && !Self::can_play_land(db, player) {
             return PendingEffects::default();
         }
 
         let mut db = scopeguard::guard(db, |db| db.stack.settle());
+        Log::set_cause(&mut db, Cause::Player(player));
         if card.is_land(&db) {
             db.all_players[player].lands_played_this_turn += 1;
+            db.turn.reset_passes();
+            Log::land_played(&mut db, player, card);
             return PendingEffects::from(EffectBundle {
                 push_on_enter: Some(vec![Selected {
-                    location: Some(Location::IN_HAND),
+                    location: Some(from),
                     target_type: TargetType::Card(card),
                     targeted: false,
                     restrictions: vec![],
@@ -467,9 +623,183 @@ impl Player {
             });
         }
 
+        Stack::move_card_to_stack_from(&mut db, card, from)
+    }
+
+    /// Cast the card as an aura for its bestow cost.
+    pub fn bestow(db: &mut Database, player: Owner, card: CardId) -> PendingEffects {
+        assert!(db.hand[player].contains(&card));
+        assert!(card.faceup_face(db).bestow.is_some());
+
+        let mut db = scopeguard::guard(db, |db| db.stack.settle());
+        Log::set_cause(&mut db, Cause::Player(player));
+        db[card].bestowed = true;
+        card.apply_modifiers_layered(&mut db);
+
         Stack::move_card_to_stack_from_hand(&mut db, card)
     }
 
+    /// Choose the card as the player's companion. Every card in the player's library is checked
+    /// against the companion's deckbuilding restriction, so this should be called once the
+    /// starting deck has been built. The companion stays outside the game until it is put into
+    /// its owner's hand.
+    pub fn set_companion(
+        db: &mut Database,
+        player: Owner,
+        companion: CardId,
+    ) -> anyhow::Result<()> {
+        let Some(restriction) = companion.faceup_face(db).companion.as_ref() else {
+            anyhow::bail!("{} is not a companion", companion.name(db));
+        };
+
+        let violations = db.all_players[player]
+            .library
+            .cards()
+            .filter(|card| {
+                card.passes_restrictions(db, LogId::current(db), companion, &restriction.applies_to)
+                    && !card.passes_restrictions(
+                        db,
+                        LogId::current(db),
+                        companion,
+                        &restriction.restrictions,
+                    )
+            })
+            .map(|card| card.name(db).clone())
+            .unique()
+            .collect_vec();
+        if !violations.is_empty() {
+            anyhow::bail!(
+                "{} cannot be a companion for this deck: {}",
+                companion.name(db),
+                violations.join(", ")
+            );
+        }
+
+        db.all_players[player].companion = Some(companion);
+        Ok(())
+    }
+
+    pub fn companion(db: &Database, player: Owner) -> Option<CardId> {
+        db.all_players[player].companion
+    }
+
+    /// Whether the player can pay {3} to put their companion into their hand. This is only
+    /// possible any time the player could cast a sorcery, and only once per game.
+    pub fn can_put_companion_in_hand(db: &Database, player: Owner) -> bool {
+        if db.all_players[player].companion.is_none()
+            || db.turn.active_player() != player
+            || !matches!(
+                db.turn.phase,
+                Phase::PreCombatMainPhase | Phase::PostCombatMainPhase
+            )
+            || !db.stack.is_empty()
+        {
+            return false;
+        }
+
+        let reason = Reason::Other(Default::default());
+        let mut mana_pool = db.all_players[player].mana_pool.clone();
+        for _ in 0..COMPANION_COST {
+            let Some(mana) = mana_pool.max(db, &reason) else {
+                return false;
+            };
+            if let (false, _) = mana_pool.spend(db, mana, ManaSource::ANY, &reason) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    pub fn put_companion_in_hand(db: &mut Database, player: Owner) -> PendingEffects {
+        let Some(companion) = db.all_players[player].companion.take() else {
+            return PendingEffects::default();
+        };
+
+        PendingEffects::from(EffectBundle {
+            push_on_enter: Some(vec![Selected {
+                location: None,
+                target_type: TargetType::Card(companion),
+                targeted: false,
+                restrictions: vec![],
+            }]),
+            effects: vec![
+                PayCosts {
+                    pay_costs: vec![PayCost {
+                        cost: Some(
+                            PayMana {
+                                paying: vec![ManaCost::GENERIC.into(); COMPANION_COST],
+                                reason: protobuf::MessageField::some(SpendReason {
+                                    reason: Some(Reason::Other(Default::default())),
+                                    ..Default::default()
+                                }),
+                                ..Default::default()
+                            }
+                            .into(),
+                        ),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }
+                .into(),
+                MoveToHand::default().into(),
+                PopSelected::default().into(),
+            ],
+            source: Some(companion),
+            ..Default::default()
+        })
+    }
+
+    /// Whether the player's mana pool can pay the mana cost of casting the card, ignoring any
+    /// cost reductions. Phyrexian symbols and symbols which static abilities let the player pay
+    /// with life count as payable if the player has enough life.
+    pub fn can_afford(db: &Database, player: Owner, card: CardId) -> bool {
+        let reason = Reason::Casting(Casting {
+            card: protobuf::MessageField::some(card.into()),
+            ..Default::default()
+        });
+
+        let mut mana_pool = db.all_players[player].mana_pool.clone();
+        let mut life = db.all_players[player].life_total;
+        for cost in db[card]
+            .modified_cost
+            .mana_cost
+            .iter()
+            .map(|cost| cost.enum_value().unwrap())
+            .sorted()
+        {
+            if matches!(cost, ManaCost::X | ManaCost::TWO_X) {
+                continue;
+            }
+
+            // Try the spend on a copy so a {2/W} symbol which can only be half paid doesn't use
+            // up mana needed for the rest of the cost.
+            let mut paid = mana_pool.clone();
+            if paid.spend_cost(db, cost, ManaSource::ANY, &reason) {
+                mana_pool = paid;
+                continue;
+            }
+
+            match Self::life_for_mana(db, player, Some(card), cost) {
+                Some(paying) if life >= paying as i32 => life -= paying as i32,
+                _ => return false,
+            }
+        }
+
+        let (min_x, _) = db[card].modified_cost.x.range(db, card);
+        let x_symbols = db[card]
+            .modified_cost
+            .mana_cost
+            .iter()
+            .map(|cost| match cost.enum_value().unwrap() {
+                ManaCost::X => 1,
+                ManaCost::TWO_X => 2,
+                _ => 0,
+            })
+            .sum::<usize>();
+        mana_pool.spendable_count(db, &reason) >= min_x as usize * x_symbols
+    }
+
     pub(crate) fn pool_post_pay(
         &self,
         db: &Database,
@@ -539,7 +869,7 @@ impl Player {
         }
     }
 
-    pub(crate) fn lands_per_turn(db: &mut Database, player: Owner) -> usize {
+    pub(crate) fn lands_per_turn(db: &Database, player: Owner) -> usize {
         1 + Battlefields::static_abilities(db)
             .into_iter()
             .filter_map(|(ability, card)| {
@@ -557,7 +887,134 @@ impl Player {
             .sum::<usize>()
     }
 
-    pub fn can_play_land(db: &mut Database, player: Owner) -> bool {
+    /// The mana which must be paid for each creature attacking the player.
+    pub(crate) fn attack_tax(db: &Database, player: Owner) -> Vec<ManaCost> {
+        Battlefields::static_abilities(db)
+            .into_iter()
+            .filter(|(_, card)| db[*card].controller == player)
+            .flat_map(|(ability, _)| match ability {
+                static_ability::Ability::AttackTax(tax) => tax
+                    .mana_cost
+                    .iter()
+                    .map(|cost| cost.enum_value().unwrap())
+                    .collect_vec(),
+                _ => vec![],
+            })
+            .collect_vec()
+    }
+
+    /// The life the player may pay rather than paying the mana symbol in a cost of `card`, if the
+    /// symbol is Phyrexian or a static ability they control allows it. When several do, the
+    /// cheapest is used.
+    pub(crate) fn life_for_mana(
+        db: &Database,
+        player: Owner,
+        card: Option<CardId>,
+        cost: ManaCost,
+    ) -> Option<u32> {
+        let phyrexian = if cost.is_phyrexian() {
+            Some(PHYREXIAN_LIFE)
+        } else {
+            None
+        };
+
+        Battlefields::static_abilities(db)
+            .into_iter()
+            .filter(|(_, source)| db[*source].controller == player)
+            .filter_map(|(ability, source)| match ability {
+                static_ability::Ability::PayLifeForMana(pay)
+                    if pay
+                        .mana_cost
+                        .iter()
+                        .any(|symbol| symbol.enum_value() == Ok(cost))
+                        && match card {
+                            Some(card) => card.passes_restrictions(
+                                db,
+                                LogId::current(db),
+                                source,
+                                &pay.restrictions,
+                            ),
+                            None => pay.restrictions.is_empty(),
+                        } =>
+                {
+                    Some(pay.life)
+                }
+                _ => None,
+            })
+            .chain(phyrexian)
+            .min()
+    }
+
+    pub fn can_play_land(db: &Database, player: Owner) -> bool {
         db.all_players[player].lands_played_this_turn < Self::lands_per_turn(db, player)
     }
+
+    /// Everything the player could currently do with priority, ignoring whether they can pay for
+    /// it. Abilities are indexed the same way as `db[card].abilities(db)`.
+    pub fn actions(db: &Database, player: Owner, pending: &Option<PendingEffects>) -> Vec<Action> {
+        let mut actions = vec![];
+        for (card, _) in Self::playable_cards(db, player) {
+            if Turn::can_cast(db, card) && (!card.is_land(db) || Self::can_play_land(db, player)) {
+                actions.push(Action::Play(card));
+            }
+        }
+
+        for card in db.hand[player]
+            .iter()
+            .chain(db.battlefield[player].iter())
+            .copied()
+        {
+            for (index, (_, ability)) in db[card].abilities(db).into_iter().enumerate() {
+                if ability.can_be_activated(db, card, player, pending) {
+                    actions.push(Action::Activate {
+                        card,
+                        index,
+                        mana: matches!(ability, Ability::Mana(_)),
+                    });
+                }
+            }
+        }
+
+        actions
+    }
+
+    /// Every card the player may play as though it were in their hand, along with the zone it is
+    /// in: their hand, followed by any cards in their graveyard or exile a static ability lets
+    /// them play. Whether each card can be played right now also depends on timing, see
+    /// [Turn::can_cast].
+    pub fn playable_cards(db: &Database, player: Owner) -> Vec<(CardId, Location)> {
+        db.with_restriction_cache(|db| {
+            db.hand[player]
+                .iter()
+                .map(|card| (*card, Location::IN_HAND))
+                .chain(
+                    db.graveyard[player]
+                        .iter()
+                        .map(|card| (*card, Location::IN_GRAVEYARD)),
+                )
+                .chain(
+                    db.exile[player]
+                        .iter()
+                        .map(|card| (*card, Location::IN_EXILE)),
+                )
+                .filter(|(card, location)| {
+                    *location == Location::IN_HAND || card.play_permitted(db)
+                })
+                .collect_vec()
+        })
+    }
+
+    /// Whether there is no point in the player holding priority: they have nothing they could
+    /// cast and no abilities to activate other than mana abilities.
+    pub fn should_auto_pass(
+        db: &Database,
+        player: Owner,
+        pending: &Option<PendingEffects>,
+    ) -> bool {
+        db.with_restriction_cache(|db| {
+            Self::actions(db, player, pending)
+                .into_iter()
+                .all(|action| matches!(action, Action::Activate { mana: true, .. }))
+        })
+    }
 }
@@ -0,0 +1,244 @@
+//! A full, uncensored record of a game for post-game review. Every decision is recorded as the
+//! game is played, including choices made against hidden information, but the record can only be
+//! exported once every player has finished the game with [GameRecord::finish] so it can't be used
+//! to look at an opponent's hand mid-game.
+
+use std::fmt::Write;
+
+use indexmap::IndexSet;
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::{
+    config::Cosmetics,
+    in_play::{CardId, Database},
+    log::LogEntry,
+    player::{Controller, Owner},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Decision {
+    pub turn: usize,
+    pub player: String,
+    /// The effect or action the decision was made for.
+    pub effect: String,
+    pub description: String,
+    /// Every option the player could choose from, with their indices.
+    pub options: Vec<(usize, String)>,
+    pub chosen: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlayerRecord {
+    pub name: String,
+    pub life_total: i32,
+    pub lost: bool,
+    pub mulligans: usize,
+    pub battlefield: Vec<String>,
+    pub hand: Vec<String>,
+    /// The library, starting from the top.
+    pub library: Vec<String>,
+    pub graveyard: Vec<String>,
+    pub exile: Vec<String>,
+    pub cosmetics: Cosmetics,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GameRecord {
+    pub turns: usize,
+    pub players: Vec<PlayerRecord>,
+    pub decisions: Vec<Decision>,
+}
+
+/// The public state of a player at the start of a turn.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlayerSnapshot {
+    pub name: String,
+    pub life_total: i32,
+    pub battlefield: Vec<String>,
+    pub hand: usize,
+    pub library: usize,
+    pub graveyard: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Snapshot {
+    pub turn: usize,
+    pub players: Vec<PlayerSnapshot>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Recording {
+    decisions: Vec<Decision>,
+    snapshots: Vec<Snapshot>,
+    finished: IndexSet<Owner>,
+}
+
+impl GameRecord {
+    /// Marks the player as done with the game, either because the game ended or because they left.
+    pub fn finish(db: &mut Database, player: Owner) {
+        db.record.finished.insert(player);
+    }
+
+    /// The full record of the game, or None if any player has not yet finished the game.
+    pub fn export(db: &Database) -> Option<GameRecord> {
+        let players = db.all_players.all_players();
+        if !players
+            .iter()
+            .all(|player| db.record.finished.contains(player))
+        {
+            return None;
+        }
+
+        let names = |cards: &mut dyn Iterator<Item = CardId>| {
+            cards.map(|card| card.name(db).clone()).collect_vec()
+        };
+
+        Some(GameRecord {
+            turns: db.turn.turn_count,
+            players: players
+                .into_iter()
+                .map(|player| PlayerRecord {
+                    name: db.all_players[player].name.clone(),
+                    life_total: db.all_players[player].life_total,
+                    lost: db.all_players[player].lost,
+                    mulligans: db.all_players[player].mulligans,
+                    battlefield: names(
+                        &mut db.battlefield.battlefields[&Controller::from(player)]
+                            .iter()
+                            .copied(),
+                    ),
+                    hand: names(&mut db.hand[player].iter().copied()),
+                    library: names(&mut db.all_players[player].library.cards()),
+                    graveyard: names(&mut db.graveyard[player].iter().copied()),
+                    exile: names(&mut db.exile[player].iter().copied()),
+                    cosmetics: db.all_players[player].cosmetics.clone(),
+                })
+                .collect_vec(),
+            decisions: db.record.decisions.clone(),
+        })
+    }
+
+    /// A readable account of the game built from the log, one line per event with a heading for
+    /// each turn, or None if any player has not yet finished the game. If `snapshots` is set, the
+    /// state of each player at the start of every turn and at the end of the game is included.
+    pub fn transcript(db: &Database, snapshots: bool) -> Option<String> {
+        if !db
+            .all_players
+            .all_players()
+            .iter()
+            .all(|player| db.record.finished.contains(player))
+        {
+            return None;
+        }
+
+        let mut transcript = String::default();
+        let mut turn = 0;
+        let first = db.all_players.all_players()[0];
+        writeln!(transcript, "Turn 1 — {}", db.all_players[first].name).unwrap();
+        for (_, entry) in db.log.entries.iter() {
+            let line = match entry {
+                LogEntry::NewTurn { player } => {
+                    turn += 1;
+                    writeln!(
+                        transcript,
+                        "\nTurn {} — {}",
+                        turn + 1,
+                        db.all_players[*player].name
+                    )
+                    .unwrap();
+                    if snapshots {
+                        if let Some(snapshot) = db
+                            .record
+                            .snapshots
+                            .iter()
+                            .find(|snapshot| snapshot.turn == turn)
+                        {
+                            write_snapshot(&mut transcript, snapshot);
+                        }
+                    }
+                    continue;
+                }
+                entry => match entry.describe(db) {
+                    Some(line) => line,
+                    None => continue,
+                },
+            };
+            writeln!(transcript, "  {}", line).unwrap();
+        }
+
+        if snapshots {
+            writeln!(transcript, "\nEnd of game").unwrap();
+            write_snapshot(&mut transcript, &Self::take_snapshot(db));
+        }
+
+        Some(transcript)
+    }
+
+    /// Records the state of each player at the start of the current turn.
+    pub(crate) fn snapshot(db: &mut Database) {
+        let snapshot = Self::take_snapshot(db);
+        db.record.snapshots.push(snapshot);
+    }
+
+    fn take_snapshot(db: &Database) -> Snapshot {
+        Snapshot {
+            turn: db.turn.turn_count,
+            players: db
+                .all_players
+                .all_players()
+                .into_iter()
+                .map(|player| PlayerSnapshot {
+                    name: db.all_players[player].name.clone(),
+                    life_total: db.all_players[player].life_total,
+                    battlefield: db.battlefield.battlefields[&Controller::from(player)]
+                        .iter()
+                        .map(|card| card.name(db).clone())
+                        .collect_vec(),
+                    hand: db.hand[player].len(),
+                    library: db.all_players[player].library.len(),
+                    graveyard: db.graveyard[player].len(),
+                })
+                .collect_vec(),
+        }
+    }
+
+    pub(crate) fn decision(
+        db: &mut Database,
+        player: Owner,
+        effect: String,
+        description: String,
+        options: Vec<(usize, String)>,
+        chosen: Option<usize>,
+    ) {
+        let decision = Decision {
+            turn: db.turn.turn_count,
+            player: db.all_players[player].name.clone(),
+            effect,
+            description,
+            options,
+            chosen,
+        };
+        db.record.decisions.push(decision);
+    }
+}
+
+fn write_snapshot(transcript: &mut String, snapshot: &Snapshot) {
+    for player in snapshot.players.iter() {
+        writeln!(
+            transcript,
+            "  [{}: {} life, {} in hand, {} in library, {} in graveyard, battlefield: {}]",
+            player.name,
+            player.life_total,
+            player.hand,
+            player.library,
+            player.graveyard,
+            if player.battlefield.is_empty() {
+                "empty".to_string()
+            } else {
+                player.battlefield.join(", ")
+            }
+        )
+        .unwrap();
+    }
+}
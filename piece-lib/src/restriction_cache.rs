@@ -0,0 +1,120 @@
+//! Memoizes [CardId::passes_restrictions] while the database can't change.
+//!
+//! Checks are only cached inside [Database::with_restriction_cache], which only has a shared
+//! reference to the database, so nothing a cached result depends on can change until the cache is
+//! dropped at the end of the outermost call. Entries are also keyed by the current log epoch, so a
+//! result recorded before an event was logged is never reused after it.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use crate::{
+    in_play::{CardId, Database},
+    log::LogId,
+    protogen::targets::Restriction,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Key {
+    card: CardId,
+    source: CardId,
+    log_session: LogId,
+    epoch: LogId,
+}
+
+/// The results for each set of restrictions checked for a key. Restrictions can't be hashed, and
+/// are usually only checked against a handful of sets, so they are compared one by one.
+type Results = Vec<(Vec<Restriction>, bool)>;
+
+#[derive(Debug, Default)]
+pub(crate) struct RestrictionCache {
+    /// A mutex rather than a cell so the database can be shared with other threads.
+    entries: Mutex<Option<HashMap<Key, Results>>>,
+}
+
+impl Clone for RestrictionCache {
+    /// A copy of the database starts without a cache, since the copy can be changed.
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl RestrictionCache {
+    pub(crate) fn get(
+        &self,
+        db: &Database,
+        card: CardId,
+        log_session: LogId,
+        source: CardId,
+        restrictions: &[Restriction],
+    ) -> Option<bool> {
+        let entries = self.entries.lock().unwrap();
+        let results = entries
+            .as_ref()?
+            .get(&Self::key(db, card, log_session, source))?;
+        results
+            .iter()
+            .find(|(cached, _)| cached == restrictions)
+            .map(|(_, passes)| *passes)
+    }
+
+    pub(crate) fn insert(
+        &self,
+        db: &Database,
+        card: CardId,
+        log_session: LogId,
+        source: CardId,
+        restrictions: &[Restriction],
+        passes: bool,
+    ) {
+        if let Some(entries) = self.entries.lock().unwrap().as_mut() {
+            entries
+                .entry(Self::key(db, card, log_session, source))
+                .or_default()
+                .push((restrictions.to_vec(), passes));
+        }
+    }
+
+    /// The number of results cached, or `None` if caching isn't enabled.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> Option<usize> {
+        self.entries
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|entries| entries.values().map(|results| results.len()).sum())
+    }
+
+    fn key(db: &Database, card: CardId, log_session: LogId, source: CardId) -> Key {
+        Key {
+            card,
+            source,
+            log_session,
+            epoch: LogId::current(db),
+        }
+    }
+}
+
+impl Database {
+    /// Runs `f` with restriction checks memoized, so checking the same card against the same
+    /// restrictions again is a lookup. Nested calls share the outermost call's cache.
+    pub fn with_restriction_cache<T>(&self, f: impl FnOnce(&Database) -> T) -> T {
+        let outermost = {
+            let mut entries = self.restriction_cache.entries.lock().unwrap();
+            if entries.is_none() {
+                *entries = Some(HashMap::default());
+                true
+            } else {
+                false
+            }
+        };
+
+        // Dropped even if `f` panics, so a caught panic doesn't leave stale results cached.
+        let _reset = scopeguard::guard(outermost, |outermost| {
+            if outermost {
+                *self.restriction_cache.entries.lock().unwrap() = None;
+            }
+        });
+
+        f(self)
+    }
+}
@@ -1,4 +1,4 @@
-use std::hash::Hash;
+use std::{collections::HashMap, hash::Hash};
 
 use indexmap::IndexMap;
 use itertools::Itertools;
@@ -7,22 +7,29 @@ use uuid::Uuid;
 
 use crate::{
     abilities::Ability,
+    battlefield::Battlefields,
     effects::{EffectBundle, PendingEffects, SelectedStack, SelectionResult},
     in_play::{CardId, CastFrom, Database},
-    log::{Log, LogId},
+    log::{Cause, Log, LogId},
     player::Owner,
     protogen::{
         effects::{
-            pay_cost::PayMana, ClearSelected, CompleteSpellResolution, Effect, MoveToStack,
-            PayCost, PayCosts, PushSelected, ReplacementEffect, TriggeredAbility,
+            count::Fixed, pay_cost::PayMana, static_ability, ClearSelected,
+            CompleteSpellResolution, Count, Effect, MoveToStack, PayCost, PayCosts, PushSelected,
+            ReplacementEffect, SelectTargets, TriggeredAbility,
         },
+        empty::Empty,
         keywords::Keyword,
         mana::{
             spend_reason::{Casting, Reason},
             SpendReason,
         },
-        targets::{Location, Restriction},
+        targets::{
+            restriction::{OfType, OnBattlefield},
+            Location, Restriction,
+        },
         triggers::TriggerSource,
+        types::Type,
     },
 };
 
@@ -43,16 +50,22 @@ impl std::fmt::Display for StackId {
 
 #[derive(Debug)]
 enum ResolutionType {
-    Card(CardId),
-    Ability(CardId),
+    Card,
+    Ability,
 }
 
 #[derive(Debug, Clone)]
 pub enum TargetType {
     Card(CardId),
     Stack(StackId),
-    Ability { source: CardId, ability: Ability },
-    ReplacementAbility(ReplacementEffect),
+    Ability {
+        source: CardId,
+        ability: Ability,
+    },
+    ReplacementAbility {
+        source: CardId,
+        replacement: ReplacementEffect,
+    },
     Player(Owner),
 }
 
@@ -66,11 +79,21 @@ pub struct Selected {
 }
 
 impl Selected {
+    /// An untargeted selection of the card in its current location.
+    pub(crate) fn card(db: &Database, card: CardId) -> Self {
+        Self {
+            location: card.location(db),
+            target_type: TargetType::Card(card),
+            targeted: false,
+            restrictions: vec![],
+        }
+    }
+
     pub(crate) fn display(&self, db: &Database) -> String {
         match &self.target_type {
             TargetType::Card(id) => id.name(db).clone(),
             TargetType::Stack(id) => db.stack.entries.get(id).unwrap().display(db),
-            TargetType::ReplacementAbility(effect) => effect
+            TargetType::ReplacementAbility { replacement, .. } => replacement
                 .effects
                 .iter()
                 .map(|effect| &effect.oracle_text)
@@ -91,7 +114,7 @@ impl Selected {
                 }
             }),
             TargetType::Ability { .. } => None,
-            TargetType::ReplacementAbility(_) => None,
+            TargetType::ReplacementAbility { .. } => None,
             TargetType::Player(_) => None,
         }
     }
@@ -102,6 +125,20 @@ impl Selected {
             _ => None,
         }
     }
+
+    /// Whether both selections are stack entries which are identical, see
+    /// [StackEntry::is_identical_to].
+    pub(crate) fn is_identical_stack_entry(&self, db: &Database, other: &Selected) -> bool {
+        match (&self.target_type, &other.target_type) {
+            (TargetType::Stack(lhs), TargetType::Stack(rhs)) => {
+                match (db.stack.entries.get(lhs), db.stack.entries.get(rhs)) {
+                    (Some(lhs), Some(rhs)) => lhs.is_identical_to(db, rhs),
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -130,9 +167,51 @@ impl StackEntry {
             }
         }
     }
+
+    /// Whether resolving this entry would do the same thing as resolving `other`, so the two can
+    /// be displayed and resolved as a group. Spells are never identical, and neither are abilities
+    /// with targets or modes, since those were chosen separately for each entry.
+    pub fn is_identical_to(&self, db: &Database, other: &StackEntry) -> bool {
+        match (&self.ty, &other.ty) {
+            (
+                Entry::Ability {
+                    source: lhs_source, ..
+                },
+                Entry::Ability {
+                    source: rhs_source, ..
+                },
+            ) => {
+                self.targets.is_empty()
+                    && other.targets.is_empty()
+                    && self.modes.is_empty()
+                    && other.modes.is_empty()
+                    && db[*lhs_source].controller == db[*rhs_source].controller
+                    && self.display(db) == other.display(db)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A run of identical entries which are next to each other on the stack.
+#[derive(Debug, Clone, Default)]
+pub struct StackGroup {
+    /// The entries in the group, from the bottom of the stack to the top.
+    pub ids: Vec<StackId>,
+    pub display: String,
+}
+
+impl StackGroup {
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct Stack {
     pub(crate) entries: IndexMap<StackId, StackEntry>,
 }
@@ -149,6 +228,24 @@ impl Stack {
             .map(|(id, _)| *id)
     }
 
+    /// The source of the triggered ability on top of the stack, if there is one.
+    pub(crate) fn top_trigger_source(&self) -> Option<CardId> {
+        match self.entries.last() {
+            Some((
+                _,
+                StackEntry {
+                    ty:
+                        Entry::Ability {
+                            source,
+                            ability: Ability::TriggeredAbility(_) | Ability::Etb(_),
+                        },
+                    ..
+                },
+            )) => Some(*source),
+            _ => None,
+        }
+    }
+
     pub(crate) fn split_second(&self, db: &Database) -> bool {
         if let Some((
             _,
@@ -198,6 +295,45 @@ impl Stack {
         self.entries.is_empty()
     }
 
+    /// The entries on the stack from bottom to top, with runs of identical entries grouped
+    /// together.
+    pub fn grouped(&self, db: &Database) -> Vec<StackGroup> {
+        let mut groups: Vec<(StackGroup, &StackEntry)> = vec![];
+        for (id, entry) in self.entries.iter() {
+            if let Some((group, last)) = groups.last_mut() {
+                if last.is_identical_to(db, entry) {
+                    group.ids.push(*id);
+                    *last = entry;
+                    continue;
+                }
+            }
+
+            groups.push((
+                StackGroup {
+                    ids: vec![*id],
+                    display: entry.display(db),
+                },
+                entry,
+            ));
+        }
+
+        groups.into_iter().map(|(group, _)| group).collect_vec()
+    }
+
+    /// How many entries at the top of the stack are identical to the top entry, including it.
+    pub fn identical_at_top(&self, db: &Database) -> usize {
+        let Some((_, top)) = self.entries.last() else {
+            return 0;
+        };
+
+        self.entries
+            .values()
+            .rev()
+            .take_while(|entry| entry.is_identical_to(db, top))
+            .count()
+            .max(1)
+    }
+
     pub(crate) fn settle(&mut self) {
         for entry in self.entries.values_mut() {
             entry.settled = true;
@@ -211,19 +347,21 @@ impl Stack {
 
         db.stack.settle();
 
+        match next.ty {
+            Entry::Card(card) => Log::set_cause(db, Cause::Spell(card)),
+            Entry::Ability { source, .. } => Log::set_cause(db, Cause::Ability(source)),
+        }
+
         let (effects, resolving_card, source, ty) = match next.ty {
             Entry::Card(card) => (
                 card.faceup_face(db).effects.clone(),
                 Some(card),
                 card,
-                ResolutionType::Card(card),
-            ),
-            Entry::Ability { source, ability } => (
-                ability.effects(db),
-                None,
-                source,
-                ResolutionType::Ability(source),
+                ResolutionType::Card,
             ),
+            Entry::Ability { source, ability } => {
+                (ability.effects(db), None, source, ResolutionType::Ability)
+            }
         };
 
         assert!(next.targets.len() <= 1);
@@ -272,7 +410,7 @@ impl Stack {
                 });
             }
 
-            if let ResolutionType::Ability(_) = ty {
+            if let ResolutionType::Ability = ty {
                 Log::ability_resolved(db, source);
             }
         }
@@ -280,11 +418,129 @@ impl Stack {
         pending
     }
 
+    /// Resolve the top of the stack and every identical entry directly beneath it, so a group of
+    /// identical triggers doesn't need to be passed through one at a time. Stops early and returns
+    /// the pending effects if resolving an entry or checking state-based actions needs a choice,
+    /// or if resolving an entry put something new on the stack.
+    pub fn resolve_identical(db: &mut Database) -> PendingEffects {
+        let remaining = db.stack.identical_at_top(db);
+        for _ in 0..remaining {
+            let Some((_, top)) = db.stack.entries.last() else {
+                break;
+            };
+            let top = top.clone();
+            let expected_len = db.stack.entries.len() - 1;
+
+            let mut pending = Self::resolve_1(db);
+            while !pending.wants_input(db) {
+                if let SelectionResult::Complete = pending.resolve(db, None) {
+                    break;
+                }
+            }
+            if !pending.is_empty() {
+                return pending;
+            }
+
+            let mut pending = Battlefields::check_sba(db);
+            while !pending.wants_input(db) {
+                if let SelectionResult::Complete = pending.resolve(db, None) {
+                    break;
+                }
+            }
+            if !pending.is_empty() {
+                return pending;
+            }
+
+            if db.stack.entries.len() != expected_len
+                || !db
+                    .stack
+                    .entries
+                    .last()
+                    .is_some_and(|(_, next)| next.is_identical_to(db, &top))
+            {
+                break;
+            }
+        }
+
+        PendingEffects::default()
+    }
+
+    /// Put the triggered ability on the stack once, plus once more for each static ability which
+    /// causes it to trigger an additional time.
     pub(crate) fn move_trigger_to_stack(
-        _db: &mut Database,
+        db: &mut Database,
         listener: CardId,
         trigger: TriggeredAbility,
-    ) -> EffectBundle {
+    ) -> Vec<EffectBundle> {
+        if db.triggers_suspended {
+            return vec![];
+        }
+
+        let mut times = Self::times_triggered(db, listener, &trigger);
+        if let Some(limit) = trigger.trigger.limit_per_turn {
+            times = times.min(remaining_fires(
+                &db.turn.limited_triggers_this_turn,
+                listener,
+                &trigger,
+                limit,
+            ));
+        }
+        if let Some(limit) = trigger.trigger.limit_per_game {
+            times = times.min(remaining_fires(
+                &db.limited_triggers_this_game,
+                listener,
+                &trigger,
+                limit,
+            ));
+        }
+
+        if trigger.trigger.limit_per_turn.is_some() {
+            db.turn
+                .limited_triggers_this_turn
+                .extend(std::iter::repeat_n((listener, trigger.clone()), times));
+        }
+        if trigger.trigger.limit_per_game.is_some() {
+            db.limited_triggers_this_game
+                .extend(std::iter::repeat_n((listener, trigger.clone()), times));
+        }
+
+        (0..times)
+            .map(|_| Self::trigger_bundle(listener, trigger.clone()))
+            .collect_vec()
+    }
+
+    /// Each static ability adds one trigger rather than multiplying the total, so several of them
+    /// don't compound, and the additional triggers are never themselves counted again.
+    fn times_triggered(db: &Database, listener: CardId, trigger: &TriggeredAbility) -> usize {
+        let Some(source) = trigger
+            .trigger
+            .as_ref()
+            .map(|trigger| trigger.source.enum_value().unwrap())
+        else {
+            return 1;
+        };
+
+        1 + Battlefields::static_abilities(db)
+            .into_iter()
+            .filter(|(ability, card)| match ability {
+                static_ability::Ability::TriggerAdditionalTime(additional) => {
+                    additional
+                        .sources
+                        .iter()
+                        .any(|additional| additional.enum_value() == Ok(source))
+                        && listener.passes_restrictions(
+                            db,
+                            LogId::current(db),
+                            *card,
+                            &additional.restrictions,
+                        )
+                }
+                _ => false,
+            })
+            .count()
+    }
+
+    fn trigger_bundle(listener: CardId, trigger: TriggeredAbility) -> EffectBundle {
         let mut to_trigger = vec![
             Effect::from(PushSelected::default()),
             Effect::from(ClearSelected::default()),
@@ -316,10 +572,24 @@ impl Stack {
     }
 
     pub(crate) fn move_card_to_stack_from_hand(db: &mut Database, card: CardId) -> PendingEffects {
-        db[card].cast_from = Some(CastFrom::Hand);
+        Self::move_card_to_stack_from(db, card, Location::IN_HAND)
+    }
+
+    /// Cast the card from `from`, which should be its hand or a zone its owner may cast it from as
+    /// though it were in their hand.
+    pub(crate) fn move_card_to_stack_from(
+        db: &mut Database,
+        card: CardId,
+        from: Location,
+    ) -> PendingEffects {
+        db[card].cast_from = Some(match from {
+            Location::IN_EXILE => CastFrom::Exile,
+            Location::IN_GRAVEYARD => CastFrom::Graveyard,
+            _ => CastFrom::Hand,
+        });
 
         let mut pending = PendingEffects::default();
-        pending.push_front(Stack::prepare_card_for_stack(db, card, true));
+        pending.push_front(Stack::prepare_card_for_stack(db, card, from, true));
 
         pending
     }
@@ -339,17 +609,31 @@ impl Stack {
                 modes: chosen_modes,
             },
         );
+        // The caster keeps priority, and every player has to pass in succession again before the
+        // spell resolves.
+        db.turn.reset_passes();
+
+        let caster = Owner::from(db[source].controller);
+        let cast_this_turn = {
+            let cast = db.turn.spells_cast_this_turn.entry(caster).or_default();
+            *cast += 1;
+            *cast
+        };
 
         let mut effects = vec![];
 
         for (listener, trigger) in db.active_triggers_of_source(TriggerSource::CAST) {
+            if trigger.trigger.first_each_turn && cast_this_turn != 1 {
+                continue;
+            }
+
             if source.passes_restrictions(
                 db,
                 LogId::current(db),
                 listener,
                 &trigger.trigger.restrictions,
             ) {
-                effects.push(Stack::move_trigger_to_stack(db, listener, trigger));
+                effects.extend(Stack::move_trigger_to_stack(db, listener, trigger));
             }
         }
 
@@ -364,7 +648,7 @@ impl Stack {
                             &trigger.trigger.restrictions,
                         )
                     {
-                        effects.push(Stack::move_trigger_to_stack(db, listener, trigger));
+                        effects.extend(Stack::move_trigger_to_stack(db, listener, trigger));
                     }
                 }
             }
@@ -379,6 +663,7 @@ impl Stack {
         ability: Ability,
         targets: Vec<Selected>,
     ) -> Vec<EffectBundle> {
+        let activated = matches!(ability, Ability::Activated(_));
         db.stack.entries.insert(
             StackId::new(),
             StackEntry {
@@ -388,6 +673,10 @@ impl Stack {
                 settled: true,
             },
         );
+        if activated {
+            // As with spells, the player who activated the ability keeps priority.
+            db.turn.reset_passes();
+        }
 
         let mut pending = vec![];
         for target in targets.into_iter() {
@@ -401,7 +690,7 @@ impl Stack {
                             &trigger.trigger.restrictions,
                         )
                     {
-                        pending.push(Stack::move_trigger_to_stack(db, listener, trigger));
+                        pending.extend(Stack::move_trigger_to_stack(db, listener, trigger));
                     }
                 }
             }
@@ -413,6 +702,7 @@ impl Stack {
     pub(crate) fn prepare_card_for_stack(
         db: &mut Database,
         card: CardId,
+        from: Location,
         pay_costs: bool,
     ) -> EffectBundle {
         let mut to_cast = vec![
@@ -428,7 +718,12 @@ impl Stack {
         if let Some(modes) = card.faceup_face(db).modes.as_ref() {
             to_cast.push(modes.clone().into());
         }
-        if let Some(target) = card.faceup_face(db).targets.as_ref() {
+        if db[card].bestowed {
+            to_cast.push(Effect {
+                effect: Some(Self::bestow_targets().into()),
+                ..Default::default()
+            });
+        } else if let Some(target) = card.faceup_face(db).targets.as_ref() {
             to_cast.push(target.clone().into());
         }
         to_cast.push(
@@ -439,19 +734,19 @@ impl Stack {
                 .into(),
         );
         if pay_costs {
+            let cost = if db[card].bestowed {
+                card.faceup_face(db).bestow.get_or_default()
+            } else {
+                &db[card].modified_cost
+            };
             to_cast.push(Effect {
                 effect: Some(
                     PayCosts {
                         pay_costs: vec![PayCost {
                             cost: Some(
                                 PayMana {
-                                    paying: db[card]
-                                        .modified_cost
-                                        .mana_cost
-                                        .iter()
-                                        .cloned()
-                                        .sorted()
-                                        .collect_vec(),
+                                    paying: cost.mana_cost.iter().cloned().sorted().collect_vec(),
+                                    x: cost.x.clone(),
                                     reducer: card.faceup_face(db).cost_reducer.clone(),
                                     reason: protobuf::MessageField::some(SpendReason {
                                         reason: Some(Reason::Casting(Casting {
@@ -481,7 +776,7 @@ impl Stack {
 
         EffectBundle {
             push_on_enter: Some(vec![Selected {
-                location: Some(Location::IN_HAND),
+                location: Some(from),
                 target_type: TargetType::Card(card),
                 targeted: false,
                 restrictions: vec![],
@@ -491,6 +786,53 @@ impl Stack {
             ..Default::default()
         }
     }
+
+    fn bestow_targets() -> SelectTargets {
+        SelectTargets {
+            count: protobuf::MessageField::some(Count {
+                count: Some(
+                    Fixed {
+                        count: 1,
+                        ..Default::default()
+                    }
+                    .into(),
+                ),
+                ..Default::default()
+            }),
+            restrictions: vec![
+                Restriction {
+                    restriction: Some(
+                        OfType {
+                            types: HashMap::from([(Type::CREATURE.value(), Empty::default())]),
+                            ..Default::default()
+                        }
+                        .into(),
+                    ),
+                    ..Default::default()
+                },
+                Restriction {
+                    restriction: Some(OnBattlefield::default().into()),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+/// How many more times `trigger` may fire for `listener`, given every time a limited trigger has
+/// fired so far.
+fn remaining_fires(
+    fired: &[(CardId, TriggeredAbility)],
+    listener: CardId,
+    trigger: &TriggeredAbility,
+    limit: u32,
+) -> usize {
+    let fired = fired
+        .iter()
+        .filter(|(card, fired)| *card == listener && fired == trigger)
+        .count();
+    (limit as usize).saturating_sub(fired)
 }
 
 #[cfg(test)]
@@ -526,8 +868,6 @@ mod tests {
 
         let mut results = Stack::resolve_1(&mut db);
 
-        let result = results.resolve(&mut db, None);
-        assert_eq!(result, SelectionResult::TryAgain);
         let result = results.resolve(&mut db, None);
         assert_eq!(result, SelectionResult::Complete);
 
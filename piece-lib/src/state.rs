@@ -0,0 +1,627 @@
+//! Saving a game to disk and resuming it later with [Database::serialize_state] and
+//! [Database::deserialize_state].
+//!
+//! The state is stored as JSON. Cards are stored by name and looked up in the loaded [Cards] when
+//! the game is resumed, except for tokens which are stored in full. Players, cards, and stack
+//! entries refer to each other by their index in the saved state. The format is versioned, and
+//! states saved with a different version are rejected rather than guessed at.
+//!
+//! Only the state of the board is saved: the cards in every zone with their counters, damage,
+//! attachments, and combat status, the stack, the turn, and each player's life, poison counters,
+//! and mana pool. Pending choices, the log, and anything tracked for the rest of the turn beyond
+//! lands played and life gained are not, so games should be saved while a player has priority.
+
+use anyhow::{anyhow, bail, Context};
+use indexmap::IndexSet;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    abilities::Ability,
+    config::GameConfig,
+    in_play::{CardId, CastFrom, Database},
+    library::Library,
+    player::{AllPlayers, Controller, Owner},
+    protogen::{
+        card::Card,
+        counters::Counter,
+        effects::{Duration, EtbAbility, TriggeredAbility},
+        mana::{Mana, ManaRestriction, ManaSource},
+        targets::{Location, Restriction},
+    },
+    stack::{Entry, Selected, StackEntry, StackId, TargetType},
+    turns::Phase,
+    Cards,
+};
+
+/// Bumped whenever the format changes in a way older states can't be read with.
+const STATE_VERSION: u32 = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GameState {
+    version: u32,
+    config: GameConfig,
+    turn: TurnState,
+    /// Every player, in turn order.
+    players: Vec<PlayerState>,
+    cards: Vec<CardState>,
+    /// The stack, from the bottom up.
+    stack: Vec<StackEntryState>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TurnState {
+    turn_count: usize,
+    phase: Phase,
+    active_player: usize,
+    priority_player: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlayerState {
+    name: String,
+    life_total: i32,
+    poison_counters: u32,
+    lost: bool,
+    mulligans: usize,
+    hand_size: usize,
+    starting_hand_size: usize,
+    lands_played_this_turn: usize,
+    life_gained_this_turn: u32,
+    mana_pool: Vec<ManaState>,
+    /// The permanents the player controls, in the order they entered the battlefield.
+    battlefield: Vec<usize>,
+    hand: Vec<usize>,
+    /// The library, starting from the top.
+    library: Vec<usize>,
+    graveyard: Vec<usize>,
+    exile: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManaState {
+    mana: Mana,
+    source: ManaSource,
+    restriction: ManaRestriction,
+    count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CardState {
+    name: String,
+    /// The full definition of the card if it is a token.
+    token: Option<Card>,
+    owner: usize,
+    controller: usize,
+    tapped: bool,
+    counters: Vec<(Counter, u32)>,
+    marked_damage: i32,
+    damaged_by_deathtouch: bool,
+    regeneration_shields: u32,
+    enchanting: Option<usize>,
+    /// The player the creature is attacking.
+    attacking: Option<usize>,
+    attacking_planeswalker: Option<usize>,
+    blocking: Option<usize>,
+    blocked_by: Vec<usize>,
+    blocked: bool,
+    facedown: bool,
+    transformed: bool,
+    manifested: bool,
+    phased_out: bool,
+    came_under_control_turn: Option<usize>,
+    entered_battlefield_turn: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StackEntryState {
+    entry: EntryState,
+    targets: Vec<TargetState>,
+    modes: Vec<usize>,
+    settled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+enum EntryState {
+    Spell {
+        card: usize,
+        cast_from: Option<CastFrom>,
+    },
+    /// An activated ability, by its index in the source's activated abilities.
+    Activated {
+        source: usize,
+        ability: usize,
+    },
+    Etb {
+        source: usize,
+        ability: EtbAbility,
+    },
+    Triggered {
+        source: usize,
+        ability: TriggeredAbility,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TargetState {
+    target: TargetTypeState,
+    location: Option<Location>,
+    targeted: bool,
+    restrictions: Vec<Restriction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+enum TargetTypeState {
+    Card(usize),
+    Player(usize),
+    /// An entry of the stack, by its index in [GameState::stack].
+    Stack(usize),
+}
+
+impl Database {
+    /// Saves the game as JSON, so it can be resumed with [Database::deserialize_state]. Returns an
+    /// error if the stack has an entry targeting an ability, which can't be saved.
+    pub fn serialize_state(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(&GameState::capture(self)?)?)
+    }
+
+    /// Resumes a game saved with [Database::serialize_state], looking up the saved cards in
+    /// `cards`. Returns an error if the state is malformed, was saved with a different version,
+    /// or has cards which aren't in `cards`.
+    pub fn deserialize_state(cards: &Cards, state: &str) -> anyhow::Result<Database> {
+        let state: GameState = serde_json::from_str(state).context("Invalid game state")?;
+        if state.version != STATE_VERSION {
+            bail!(
+                "Game state version {} is not supported, expected {}",
+                state.version,
+                STATE_VERSION
+            );
+        }
+
+        state.validate(cards)?;
+        state.restore(cards)
+    }
+}
+
+impl GameState {
+    fn capture(db: &Database) -> anyhow::Result<Self> {
+        let players = db.all_players.all_players();
+        let player_index = |player: Owner| players.iter().position(|p| *p == player).unwrap();
+
+        let mut ids = IndexSet::<CardId>::default();
+        let mut index = |card: CardId| ids.insert_full(card).0;
+
+        let mut player_states = vec![];
+        for player in players.iter().copied() {
+            let state = &db.all_players[player];
+            player_states.push(PlayerState {
+                name: state.name.clone(),
+                life_total: state.life_total,
+                poison_counters: state.poison_counters,
+                lost: state.lost,
+                mulligans: state.mulligans,
+                hand_size: state.hand_size,
+                starting_hand_size: state.starting_hand_size,
+                lands_played_this_turn: state.lands_played_this_turn,
+                life_gained_this_turn: state.life_gained_this_turn,
+                mana_pool: state
+                    .mana_pool
+                    .sourced
+                    .iter()
+                    .flat_map(|(mana, sources)| {
+                        sources.iter().flat_map(move |(source, restrictions)| {
+                            restrictions.iter().filter(|(_, count)| **count > 0).map(
+                                move |(restriction, count)| ManaState {
+                                    mana: *mana,
+                                    source: *source,
+                                    restriction: *restriction,
+                                    count: *count,
+                                },
+                            )
+                        })
+                    })
+                    .collect_vec(),
+                battlefield: db.battlefield.battlefields[&Controller::from(player)]
+                    .iter()
+                    .copied()
+                    .map(&mut index)
+                    .collect_vec(),
+                hand: db.hand[player]
+                    .iter()
+                    .copied()
+                    .map(&mut index)
+                    .collect_vec(),
+                library: state.library.cards().map(&mut index).collect_vec(),
+                graveyard: db.graveyard[player]
+                    .iter()
+                    .copied()
+                    .map(&mut index)
+                    .collect_vec(),
+                exile: db.exile[player]
+                    .iter()
+                    .copied()
+                    .map(&mut index)
+                    .collect_vec(),
+            });
+        }
+
+        let stack_ids = db.stack.entries.keys().copied().collect_vec();
+        let mut stack = vec![];
+        for entry in db.stack.entries.values() {
+            let state = match &entry.ty {
+                Entry::Card(card) => EntryState::Spell {
+                    card: index(*card),
+                    cast_from: db[*card].cast_from,
+                },
+                Entry::Ability { source, ability } => {
+                    let source_index = index(*source);
+                    match ability {
+                        Ability::Activated(ability) => EntryState::Activated {
+                            source: source_index,
+                            ability: db[*source]
+                                .modified_activated_abilities
+                                .get_index_of(ability)
+                                .ok_or_else(|| {
+                                    anyhow!("{} no longer has the ability", source.name(db))
+                                })?,
+                        },
+                        Ability::Etb(ability) => EntryState::Etb {
+                            source: source_index,
+                            ability: ability.clone(),
+                        },
+                        Ability::TriggeredAbility(ability) => EntryState::Triggered {
+                            source: source_index,
+                            ability: ability.clone(),
+                        },
+                        Ability::Mana(_) => bail!("Mana abilities can't be saved on the stack"),
+                    }
+                }
+            };
+
+            let mut targets = vec![];
+            for target in entry.targets.iter() {
+                let target_state = match &target.target_type {
+                    TargetType::Card(card) => TargetTypeState::Card(index(*card)),
+                    TargetType::Player(player) => TargetTypeState::Player(player_index(*player)),
+                    TargetType::Stack(id) => TargetTypeState::Stack(
+                        stack_ids
+                            .iter()
+                            .position(|entry| entry == id)
+                            .context("Targeted stack entry is missing")?,
+                    ),
+                    TargetType::Ability { .. } | TargetType::ReplacementAbility { .. } => {
+                        bail!("Stack entries targeting abilities can't be saved")
+                    }
+                };
+                targets.push(TargetState {
+                    target: target_state,
+                    location: target.location,
+                    targeted: target.targeted,
+                    restrictions: target.restrictions.clone(),
+                });
+            }
+
+            stack.push(StackEntryState {
+                entry: state,
+                targets,
+                modes: entry.modes.clone(),
+                settled: entry.settled,
+            });
+        }
+
+        // Cards which are referenced but aren't in a zone, such as the target of a spell which
+        // has left the game, are dropped from references rather than saved.
+        let card_index = |card: CardId| ids.get_index_of(&card);
+        let cards = ids
+            .iter()
+            .copied()
+            .map(|card| {
+                let in_play = &db[card];
+                CardState {
+                    name: in_play.card.name.clone(),
+                    token: in_play.token.then(|| in_play.card.clone()),
+                    owner: player_index(in_play.owner),
+                    controller: player_index(in_play.controller.into()),
+                    tapped: in_play.tapped,
+                    counters: in_play
+                        .counters
+                        .iter()
+                        .filter(|(_, count)| **count > 0)
+                        .map(|(counter, count)| (*counter, *count))
+                        .sorted()
+                        .collect_vec(),
+                    marked_damage: in_play.marked_damage,
+                    damaged_by_deathtouch: in_play.damaged_by_deathtouch,
+                    regeneration_shields: in_play.regeneration_shields,
+                    enchanting: in_play.enchanting.and_then(card_index),
+                    attacking: in_play.attacking.map(player_index),
+                    attacking_planeswalker: in_play.attacking_planeswalker.and_then(card_index),
+                    blocking: in_play.blocking.and_then(card_index),
+                    blocked_by: in_play
+                        .blocked_by
+                        .iter()
+                        .copied()
+                        .filter_map(card_index)
+                        .collect_vec(),
+                    blocked: in_play.blocked,
+                    facedown: in_play.facedown,
+                    transformed: in_play.transformed,
+                    manifested: in_play.manifested,
+                    phased_out: in_play.phased_out,
+                    came_under_control_turn: in_play.came_under_control_turn,
+                    entered_battlefield_turn: in_play.entered_battlefield_turn,
+                }
+            })
+            .collect_vec();
+
+        Ok(Self {
+            version: STATE_VERSION,
+            config: db.all_players.config().clone(),
+            turn: TurnState {
+                turn_count: db.turn.turn_count,
+                phase: db.turn.phase.clone(),
+                active_player: player_index(db.turn.active_player()),
+                priority_player: player_index(db.turn.priority_player()),
+            },
+            players: player_states,
+            cards,
+            stack,
+        })
+    }
+
+    /// Checks that every index refers to something in the state and that every card is known, so
+    /// restoring the state can't panic.
+    fn validate(&self, cards: &Cards) -> anyhow::Result<()> {
+        let players = self.players.len();
+        if players == 0 {
+            bail!("A game needs at least one player");
+        }
+
+        let check_player = |player: usize| -> anyhow::Result<()> {
+            if player >= players {
+                bail!("Unknown player {}", player);
+            }
+            Ok(())
+        };
+        let check_card = |card: usize| -> anyhow::Result<()> {
+            if card >= self.cards.len() {
+                bail!("Unknown card {}", card);
+            }
+            Ok(())
+        };
+
+        check_player(self.turn.active_player)?;
+        check_player(self.turn.priority_player)?;
+
+        for player in self.players.iter() {
+            for card in player
+                .battlefield
+                .iter()
+                .chain(player.hand.iter())
+                .chain(player.library.iter())
+                .chain(player.graveyard.iter())
+                .chain(player.exile.iter())
+            {
+                check_card(*card)?;
+            }
+        }
+
+        for card in self.cards.iter() {
+            if card.token.is_none() && !cards.contains_key(&card.name) {
+                bail!("Unknown card: {}", card.name);
+            }
+            check_player(card.owner)?;
+            check_player(card.controller)?;
+            if let Some(attacking) = card.attacking {
+                check_player(attacking)?;
+            }
+            for card in card
+                .enchanting
+                .iter()
+                .chain(card.attacking_planeswalker.iter())
+                .chain(card.blocking.iter())
+                .chain(card.blocked_by.iter())
+            {
+                check_card(*card)?;
+            }
+        }
+
+        for entry in self.stack.iter() {
+            match &entry.entry {
+                EntryState::Spell { card, .. } => check_card(*card)?,
+                EntryState::Activated { source, .. }
+                | EntryState::Etb { source, .. }
+                | EntryState::Triggered { source, .. } => check_card(*source)?,
+            }
+
+            for target in entry.targets.iter() {
+                match target.target {
+                    TargetTypeState::Card(card) => check_card(card)?,
+                    TargetTypeState::Player(player) => check_player(player)?,
+                    TargetTypeState::Stack(stack) => {
+                        if stack >= self.stack.len() {
+                            bail!("Unknown stack entry {}", stack);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn restore(self, cards: &Cards) -> anyhow::Result<Database> {
+        let mut all_players = AllPlayers::new(self.config);
+        let players = self
+            .players
+            .iter()
+            .map(|state| {
+                let player = all_players.new_player(state.name.clone(), state.life_total);
+                let restored = &mut all_players[player];
+                restored.poison_counters = state.poison_counters;
+                restored.lost = state.lost;
+                restored.mulligans = state.mulligans;
+                restored.hand_size = state.hand_size;
+                restored.starting_hand_size = state.starting_hand_size;
+                restored.lands_played_this_turn = state.lands_played_this_turn;
+                restored.life_gained_this_turn = state.life_gained_this_turn;
+                for mana in state.mana_pool.iter() {
+                    for _ in 0..mana.count {
+                        restored
+                            .mana_pool
+                            .apply(mana.mana, mana.source, mana.restriction);
+                    }
+                }
+                player
+            })
+            .collect_vec();
+
+        let mut db = Database::new(all_players);
+        db.set_card_names(cards);
+        db.turn.restore(
+            self.turn.turn_count,
+            self.turn.phase,
+            self.turn.active_player,
+            self.turn.priority_player,
+        );
+
+        let ids = self
+            .cards
+            .iter()
+            .map(|card| {
+                let owner = players[card.owner];
+                match &card.token {
+                    Some(token) => {
+                        CardId::upload_card_or_token(&mut db, owner, token.clone(), true)
+                    }
+                    None => CardId::upload(&mut db, cards, owner, &card.name),
+                }
+            })
+            .collect_vec();
+
+        db.with_suspended_triggers(|db| {
+            for (card, state) in ids.iter().copied().zip(self.cards.iter()) {
+                db[card].controller = Controller::from(players[state.controller]);
+            }
+
+            for (player, state) in players.iter().copied().zip(self.players.iter()) {
+                for card in state.battlefield.iter() {
+                    ids[*card].move_to_battlefield(db);
+                }
+                for card in state.hand.iter() {
+                    ids[*card].move_to_hand(db);
+                }
+                for card in state.library.iter().rev() {
+                    Library::place_on_top(db, player, ids[*card]);
+                }
+                for card in state.graveyard.iter() {
+                    ids[*card].move_to_graveyard(db);
+                }
+                for card in state.exile.iter() {
+                    let card = ids[*card];
+                    card.move_to_exile(db, card, None, Duration::PERMANENTLY);
+                }
+            }
+
+            for (card, state) in ids.iter().copied().zip(self.cards.iter()) {
+                let restored = &mut db[card];
+                restored.tapped = state.tapped;
+                restored.counters = state.counters.iter().copied().collect();
+                restored.marked_damage = state.marked_damage;
+                restored.damaged_by_deathtouch = state.damaged_by_deathtouch;
+                restored.regeneration_shields = state.regeneration_shields;
+                restored.attacking = state.attacking.map(|player| players[player]);
+                restored.attacking_planeswalker = state
+                    .attacking_planeswalker
+                    .map(|planeswalker| ids[planeswalker]);
+                restored.blocking = state.blocking.map(|blocking| ids[blocking]);
+                restored.blocked_by = state
+                    .blocked_by
+                    .iter()
+                    .map(|blocker| ids[*blocker])
+                    .collect_vec();
+                restored.blocked = state.blocked;
+                restored.facedown = state.facedown;
+                restored.transformed = state.transformed;
+                restored.manifested = state.manifested;
+                restored.phased_out = state.phased_out;
+                restored.came_under_control_turn = state.came_under_control_turn;
+                restored.entered_battlefield_turn = state.entered_battlefield_turn;
+            }
+
+            for (aura, state) in ids.iter().copied().zip(self.cards.iter()) {
+                if let Some(enchanting) = state.enchanting {
+                    ids[enchanting].apply_aura(db, aura);
+                }
+            }
+        });
+
+        let stack_ids = self.stack.iter().map(|_| StackId::new()).collect_vec();
+        for (id, state) in stack_ids.iter().copied().zip(self.stack) {
+            let ty = match state.entry {
+                EntryState::Spell { card, cast_from } => {
+                    let card = ids[card];
+                    db[card].location = Some(Location::IN_STACK);
+                    db[card].cast_from = cast_from;
+                    Entry::Card(card)
+                }
+                EntryState::Activated { source, ability } => {
+                    let source = ids[source];
+                    let ability = db[source]
+                        .modified_activated_abilities
+                        .get_index(ability)
+                        .copied()
+                        .with_context(|| {
+                            format!("{} has no ability {}", source.name(&db), ability)
+                        })?;
+                    Entry::Ability {
+                        source,
+                        ability: Ability::Activated(ability),
+                    }
+                }
+                EntryState::Etb { source, ability } => Entry::Ability {
+                    source: ids[source],
+                    ability: Ability::Etb(ability),
+                },
+                EntryState::Triggered { source, ability } => Entry::Ability {
+                    source: ids[source],
+                    ability: Ability::TriggeredAbility(ability),
+                },
+            };
+
+            let targets = state
+                .targets
+                .into_iter()
+                .map(|target| Selected {
+                    location: target.location,
+                    target_type: match target.target {
+                        TargetTypeState::Card(card) => TargetType::Card(ids[card]),
+                        TargetTypeState::Player(player) => TargetType::Player(players[player]),
+                        TargetTypeState::Stack(stack) => TargetType::Stack(stack_ids[stack]),
+                    },
+                    targeted: target.targeted,
+                    restrictions: target.restrictions,
+                })
+                .collect_vec();
+
+            db.stack.entries.insert(
+                id,
+                StackEntry {
+                    targets,
+                    ty,
+                    modes: state.modes,
+                    settled: state.settled,
+                },
+            );
+        }
+
+        Ok(db)
+    }
+}
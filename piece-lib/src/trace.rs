@@ -0,0 +1,131 @@
+//! Records how effect bundles resolve so that unexpected resolution orders can be inspected after
+//! the fact. Tracing is off by default and is enabled per-database with [Trace::start].
+
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::{effects::EffectBundle, in_play::Database};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum TraceEvent {
+    /// A bundle began resolving.
+    Enter {
+        source: Option<String>,
+        effects: Vec<String>,
+    },
+    /// A choice was made for an effect which wanted input.
+    Select {
+        effect: String,
+        option: Option<usize>,
+        complete: bool,
+    },
+    /// An effect was applied. Any bundles it produced are children of this event.
+    Apply { effect: String },
+    /// A bundle finished resolving.
+    Exit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceNode {
+    pub event: TraceEvent,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct Trace {
+    pub nodes: Vec<TraceNode>,
+}
+
+impl Trace {
+    /// Starts recording effect resolution into a new trace, discarding any existing trace.
+    pub fn start(db: &mut Database) {
+        db.trace = Some(Trace::default());
+    }
+
+    /// Stops recording and returns the recorded trace, if tracing was enabled.
+    pub fn take(db: &mut Database) -> Option<Trace> {
+        db.trace.take()
+    }
+
+    /// Bundles which were not produced by applying another effect.
+    pub fn roots(&self) -> impl Iterator<Item = usize> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.parent.is_none())
+            .map(|(idx, _)| idx)
+    }
+
+    /// Renders the trace as a graphviz digraph.
+    pub fn to_dot(&self) -> String {
+        let mut dot = "digraph trace {\n".to_string();
+        for (idx, node) in self.nodes.iter().enumerate() {
+            let label = match &node.event {
+                TraceEvent::Enter { source, effects } => format!(
+                    "Enter {}\\n{}",
+                    source.as_deref().unwrap_or_default(),
+                    effects.join(", ")
+                ),
+                TraceEvent::Select {
+                    effect,
+                    option,
+                    complete,
+                } => format!("Select {} {:?} complete={}", effect, option, complete),
+                TraceEvent::Apply { effect } => format!("Apply {}", effect),
+                TraceEvent::Exit => "Exit".to_string(),
+            };
+            writeln!(
+                dot,
+                "  n{} [label=\"{}\"];",
+                idx,
+                label.replace('"', "\\\"")
+            )
+            .unwrap();
+            for child in node.children.iter() {
+                writeln!(dot, "  n{} -> n{};", idx, child).unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    pub(crate) fn record(
+        db: &mut Database,
+        parent: Option<usize>,
+        event: TraceEvent,
+    ) -> Option<usize> {
+        let trace = db.trace.as_mut()?;
+        let idx = trace.nodes.len();
+        trace.nodes.push(TraceNode {
+            event,
+            parent,
+            children: vec![],
+        });
+        if let Some(parent) = parent {
+            trace.nodes[parent].children.push(idx);
+        }
+
+        Some(idx)
+    }
+
+    pub(crate) fn enter(db: &mut Database, bundle: &mut EffectBundle) {
+        if db.trace.is_none() || bundle.trace_node.is_some() {
+            return;
+        }
+
+        let source = bundle.source.map(|source| source.name(db).clone());
+        let effects = bundle
+            .effects
+            .iter()
+            .filter_map(|effect| effect.effect.as_ref())
+            .map(|effect| effect.as_ref().to_string())
+            .collect();
+        bundle.trace_node = Self::record(
+            db,
+            bundle.trace_parent,
+            TraceEvent::Enter { source, effects },
+        );
+    }
+}
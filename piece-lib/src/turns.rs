@@ -1,27 +1,49 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
+use anyhow::bail;
 use itertools::Itertools;
 
 use crate::{
     battlefield::Battlefields,
-    effects::{EffectBundle, PendingEffects},
+    clock::Clock,
+    combat,
+    counters::add_lore_counters,
+    effects::{EffectBundle, Options, PendingEffects, SelectionResult},
     in_play::{ActivatedAbilityId, CardId, Database},
-    log::{Log, LogId},
+    log::{Cause, Log, LogId},
+    notifications::Notifications,
     player::{AllPlayers, Owner, Player},
     protogen::{
         effects::{
             count::{self, Fixed},
-            ChooseAttackers, Count, Discard, PopSelected,
+            ChooseAttackers, ChooseBlockers, Count, Discard, PopSelected, TriggeredAbility,
         },
         targets::Location,
         triggers::TriggerSource,
         types::Type,
     },
+    record::GameRecord,
     stack::{Selected, Stack, TargetType},
     types::TypeSet,
 };
 
-#[derive(Debug, Default, PartialEq, Eq, strum::AsRefStr, Hash)]
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    strum::AsRefStr,
+    strum::EnumIter,
+    strum::EnumString,
+    Hash,
+)]
+#[strum(ascii_case_insensitive)]
+#[non_exhaustive]
 pub enum Phase {
     #[default]
     Untap,
@@ -33,22 +55,96 @@ pub enum Phase {
     DeclareBlockers,
     FirstStrike,
     Damage,
+    EndCombat,
     PostCombatMainPhase,
     EndStep,
     Cleanup,
 }
 
-#[derive(Debug, Default)]
+/// The most steps [Turn::advance_until] will take before giving up.
+const MAX_ADVANCE_STEPS: usize = 1_000;
+
+/// The most choices [AutoPolicy] will make while resolving a single set of pending effects.
+const MAX_AUTO_CHOICES: usize = 1_000;
+
+/// How choices are made for effects which resolve during [Turn::advance_until].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AutoPolicy {
+    /// Decline optional choices, use the default when there is one, and choose the first option
+    /// when a choice is mandatory.
+    #[default]
+    Decline,
+    /// Choose the first option whenever there is one.
+    First,
+}
+
+impl AutoPolicy {
+    /// Resolves the pending effects, returning the number of choices which were made.
+    fn resolve(self, db: &mut Database, pending: &mut PendingEffects) -> anyhow::Result<usize> {
+        let mut choices = 0;
+        for _ in 0..MAX_AUTO_CHOICES {
+            if pending.is_empty() {
+                return Ok(choices);
+            }
+
+            if pending.wants_input(db) {
+                choices += 1;
+            }
+
+            let choice = match pending.options(db) {
+                Options::MandatoryList(list) => list.first().map(|(idx, _)| *idx),
+                Options::OptionalList(list) => match self {
+                    AutoPolicy::Decline => None,
+                    AutoPolicy::First => list.first().map(|(idx, _)| *idx),
+                },
+                Options::ListWithDefault(_) => None,
+            };
+
+            if pending.resolve(db, choice) == SelectionResult::Complete {
+                return Ok(choices);
+            }
+        }
+
+        bail!(
+            "Pending effects did not resolve within {} choices",
+            MAX_AUTO_CHOICES
+        )
+    }
+}
+
+/// A triggered ability which [Turn::advance_until] resolved by making its controller's choices
+/// according to an [AutoPolicy], so the player can review what happened on their behalf.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AutoResolved {
+    pub source: CardId,
+    /// The number of choices made while resolving the trigger.
+    pub choices: usize,
+}
+
+impl AutoResolved {
+    pub fn warning(&self, db: &Database) -> String {
+        format!("{} trigger resolved automatically", self.source.name(db))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct Turn {
     pub turn_count: usize,
     pub phase: Phase,
     turn_order: Vec<Owner>,
     active_player: usize,
     priority_player: usize,
+    /// How many players have passed priority in succession since the last action or step.
     passed: usize,
 
     pub(crate) number_of_attackers_this_turn: usize,
     pub(crate) activated_abilities: HashSet<ActivatedAbilityId>,
+    pub(crate) spells_cast_this_turn: HashMap<Owner, usize>,
+    /// Every time a trigger with a per turn limit fired this turn, along with the card it fired
+    /// for.
+    pub(crate) limited_triggers_this_turn: Vec<(CardId, TriggeredAbility)>,
+    auto_resolved: Vec<AutoResolved>,
 }
 
 impl Turn {
@@ -65,6 +161,9 @@ impl Turn {
 
             number_of_attackers_this_turn: 0,
             activated_abilities: Default::default(),
+            spells_cast_this_turn: Default::default(),
+            limited_triggers_this_turn: Default::default(),
+            auto_resolved: Default::default(),
         }
     }
 
@@ -73,27 +172,64 @@ impl Turn {
         self.phase = phase;
     }
 
+    /// Puts the turn at the point a saved game was in, with `active_player` and `priority_player`
+    /// as indices into the turn order.
+    pub(crate) fn restore(
+        &mut self,
+        turn_count: usize,
+        phase: Phase,
+        active_player: usize,
+        priority_player: usize,
+    ) {
+        self.turn_count = turn_count;
+        self.phase = phase;
+        self.active_player = active_player;
+        self.priority_player = priority_player;
+        self.passed = 0;
+    }
+
+    pub(crate) fn skip_to(&mut self, phase: Phase) {
+        self.phase = phase;
+        self.priority_player = self.active_player;
+        self.passed = 0;
+    }
+
     pub fn step_priority(&mut self) {
         self.priority_player = (self.priority_player + 1) % self.turn_order.len();
         self.passed = 0;
     }
 
+    /// Passes priority to the next player in turn order. Casting a spell, activating an ability, or
+    /// playing a land keeps priority with the player who did it and starts the round over, so the
+    /// top of the stack only resolves once every player has passed in succession without acting.
+    /// See [Turn::passed_full_priority_round].
     pub fn pass_priority(&mut self) {
         self.priority_player = (self.priority_player + 1) % self.turn_order.len();
-        self.passed = (self.passed + 1) % self.turn_order.len();
+        self.passed += 1;
+    }
+
+    /// Keeps priority with the player who has it after they take an action, so every player has to
+    /// pass in succession again before the game moves on.
+    pub(crate) fn reset_passes(&mut self) {
+        self.passed = 0;
     }
 
     #[instrument(skip(db))]
     pub fn step(db: &mut Database) -> PendingEffects {
-        if db.turn.passed != 0 {
+        // Nothing resolves until every player has passed in succession. With an empty stack, a
+        // step nobody has passed in yet just moves on to the next phase.
+        if !db.turn.passed_full_priority_round() && (db.turn.passed != 0 || !db.stack.is_empty()) {
             return PendingEffects::default();
         }
 
+        db.turn.passed = 0;
+
         db.turn.priority_player = db.turn.active_player;
         if !db.stack.is_empty() {
             return Stack::resolve_1(db);
         }
 
+        Log::set_cause(db, Cause::TurnBasedActions);
         match db.turn.phase {
             Phase::Untap => {
                 for player in db.all_players.all_players() {
@@ -115,7 +251,7 @@ impl Turn {
                         continue;
                     }
 
-                    results.apply_result(Stack::move_trigger_to_stack(db, listener, trigger));
+                    results.apply_results(Stack::move_trigger_to_stack(db, listener, trigger));
                 }
 
                 results
@@ -126,7 +262,7 @@ impl Turn {
                 }
                 db.turn.phase = Phase::Draw;
                 let results = Self::delayed_triggers(db);
-                if db.turn.turn_count != 0 {
+                if db.turn.turn_count != 0 || db.all_players.config().starting_player_draws {
                     let player = db.turn.active_player();
                     return Player::draw(player, 1);
                 }
@@ -153,7 +289,16 @@ impl Turn {
                         continue;
                     }
 
-                    results.apply_result(Stack::move_trigger_to_stack(db, listener, trigger));
+                    results.apply_results(Stack::move_trigger_to_stack(db, listener, trigger));
+                }
+
+                for saga in db.battlefield[player]
+                    .iter()
+                    .copied()
+                    .filter(|card| card.final_chapter(db).is_some())
+                    .collect_vec()
+                {
+                    results.apply_results(add_lore_counters(db, saga, 1));
                 }
 
                 results
@@ -177,7 +322,7 @@ impl Turn {
                         continue;
                     }
 
-                    results.apply_result(Stack::move_trigger_to_stack(db, listener, trigger));
+                    results.apply_results(Stack::move_trigger_to_stack(db, listener, trigger));
                 }
                 results
             }
@@ -189,36 +334,36 @@ impl Turn {
                 let mut results = Self::delayed_triggers(db);
                 let player = db.turn.active_player();
 
+                let mut targets = db.all_players.all_players();
+                targets.retain(|target| *target != player);
+                let planeswalkers = targets
+                    .iter()
+                    .flat_map(|target| db.battlefield[*target].iter().copied())
+                    .filter(|card| card.is_planeswalker(db))
+                    .collect_vec();
+
                 results.push_back(EffectBundle {
                     push_on_enter: Some(
-                        db.battlefield[player]
-                            .iter()
-                            .copied()
-                            .filter(|card| card.can_attack(db))
+                        combat::attack_candidates(db, player)
+                            .into_iter()
                             .map(|card| Selected {
                                 location: Some(Location::ON_BATTLEFIELD),
                                 target_type: TargetType::Card(card),
                                 targeted: false,
                                 restrictions: vec![],
                             })
-                            .collect_vec(),
-                    ),
-                    ..Default::default()
-                });
-
-                let mut targets = db.all_players.all_players();
-                targets.retain(|target| *target != player);
-
-                results.push_back(EffectBundle {
-                    push_on_enter: Some(
-                        targets
-                            .into_iter()
-                            .map(|target| Selected {
+                            .chain(targets.into_iter().map(|target| Selected {
                                 location: None,
                                 target_type: TargetType::Player(target),
                                 targeted: false,
                                 restrictions: vec![],
-                            })
+                            }))
+                            .chain(planeswalkers.into_iter().map(|planeswalker| Selected {
+                                location: Some(Location::ON_BATTLEFIELD),
+                                target_type: TargetType::Card(planeswalker),
+                                targeted: false,
+                                restrictions: vec![],
+                            }))
                             .collect_vec(),
                     ),
                     effects: vec![
@@ -234,7 +379,25 @@ impl Turn {
                     db.all_players[player].mana_pool.drain();
                 }
                 db.turn.phase = Phase::DeclareBlockers;
-                Self::delayed_triggers(db)
+                let mut results = Self::delayed_triggers(db);
+
+                let attacked = db.battlefield[db.turn.active_player()]
+                    .iter()
+                    .filter_map(|card| db[*card].attacking)
+                    .unique()
+                    .collect_vec();
+                for defender in attacked {
+                    results.push_back(EffectBundle {
+                        effects: vec![ChooseBlockers {
+                            defender: protobuf::MessageField::some(defender.into()),
+                            ..Default::default()
+                        }
+                        .into()],
+                        ..Default::default()
+                    });
+                }
+
+                results
             }
             Phase::DeclareBlockers => {
                 for player in db.all_players.all_players() {
@@ -243,39 +406,7 @@ impl Turn {
                 db.turn.phase = Phase::FirstStrike;
 
                 let mut results = Self::delayed_triggers(db);
-
-                for (card, target) in db.battlefield[db.turn.active_player()]
-                    .iter()
-                    .filter_map(|card| {
-                        db[*card]
-                            .attacking
-                            .filter(|_| card.first_strike(db) || card.double_strike(db))
-                            .map(|attacking| (*card, attacking))
-                    })
-                    .collect_vec()
-                {
-                    if let Some(power) = card.power(db) {
-                        if power > 0 {
-                            db.all_players[target].life_total -= power;
-
-                            for (listener, trigger) in db.active_triggers_of_source(
-                                TriggerSource::DEALS_COMBAT_DAMAGE_TO_PLAYER,
-                            ) {
-                                if card.passes_restrictions(
-                                    db,
-                                    LogId::current(db),
-                                    listener,
-                                    &trigger.trigger.restrictions,
-                                ) {
-                                    results.apply_result(Stack::move_trigger_to_stack(
-                                        db, listener, trigger,
-                                    ));
-                                }
-                            }
-                        }
-                    }
-                }
-
+                results.extend(combat::deal_damage(db, true));
                 results
             }
             Phase::FirstStrike => {
@@ -285,40 +416,7 @@ impl Turn {
                 db.turn.phase = Phase::Damage;
 
                 let mut results = Self::delayed_triggers(db);
-
-                // TODO blocks
-                for (card, target) in db.battlefield[db.turn.active_player()]
-                    .iter()
-                    .filter_map(|card| {
-                        db[*card]
-                            .attacking
-                            .filter(|_| !card.first_strike(db))
-                            .map(|attacking| (*card, attacking))
-                    })
-                    .collect_vec()
-                {
-                    if let Some(power) = card.power(db) {
-                        if power > 0 {
-                            db.all_players[target].life_total -= power;
-
-                            for (listener, trigger) in db.active_triggers_of_source(
-                                TriggerSource::DEALS_COMBAT_DAMAGE_TO_PLAYER,
-                            ) {
-                                if card.passes_restrictions(
-                                    db,
-                                    LogId::current(db),
-                                    listener,
-                                    &trigger.trigger.restrictions,
-                                ) {
-                                    results.apply_result(Stack::move_trigger_to_stack(
-                                        db, listener, trigger,
-                                    ));
-                                }
-                            }
-                        }
-                    }
-                }
-
+                results.extend(combat::deal_damage(db, false));
                 results
             }
             Phase::Damage => {
@@ -326,10 +424,16 @@ impl Turn {
                     db.all_players[player].mana_pool.drain();
                 }
 
-                for card in db.battlefield[db.turn.active_player()].iter() {
-                    db.cards.get_mut(card).unwrap().attacking = None;
+                db.turn.phase = Phase::EndCombat;
+                Self::delayed_triggers(db)
+            }
+            Phase::EndCombat => {
+                for player in db.all_players.all_players() {
+                    db.all_players[player].mana_pool.drain();
                 }
 
+                Battlefields::end_combat(db);
+
                 db.turn.phase = Phase::PostCombatMainPhase;
                 Self::delayed_triggers(db)
             }
@@ -357,7 +461,7 @@ impl Turn {
                         continue;
                     }
 
-                    results.apply_result(Stack::move_trigger_to_stack(db, listener, trigger));
+                    results.apply_results(Stack::move_trigger_to_stack(db, listener, trigger));
                 }
 
                 results
@@ -409,6 +513,9 @@ impl Turn {
                 CardId::cleanup_tokens_in_limbo(db);
                 db.graveyard.descended_this_turn.clear();
                 db.turn.number_of_attackers_this_turn = 0;
+                db.turn.spells_cast_this_turn.clear();
+                db.turn.activated_abilities.clear();
+                db.turn.limited_triggers_this_turn.clear();
 
                 for player in db.all_players.all_players() {
                     let player = &mut db.all_players[player];
@@ -418,7 +525,7 @@ impl Turn {
                 }
 
                 for ability in db.gc_abilities.drain(..) {
-                    db.activated_abilities.remove(&ability);
+                    db.activated_abilities.swap_remove(&ability);
                 }
 
                 db.turn.phase = Phase::Untap;
@@ -429,7 +536,12 @@ impl Turn {
                 let results = Self::delayed_triggers(db);
 
                 Log::new_turn(db, db.turn.active_player());
+                GameRecord::snapshot(db);
+                Clock::new_turn(db, db.turn.active_player());
+                Notifications::new_turn(db, db.turn.active_player());
+                Battlefields::start_turn(db, db.turn.active_player());
 
+                Battlefields::phase(db, db.turn.active_player());
                 Battlefields::untap(db, db.turn.active_player());
                 results
             }
@@ -445,14 +557,55 @@ impl Turn {
             .remove(&db.turn.phase)
         {
             for (listener, trigger) in triggers {
-                results.apply_result(Stack::move_trigger_to_stack(db, listener, trigger));
+                results.apply_results(Stack::move_trigger_to_stack(db, listener, trigger));
             }
         }
 
         results
     }
 
+    /// Moves the game forward until it reaches `phase` of one of `player`'s turns with an empty
+    /// stack, as though every player passed priority at every opportunity. Spells on the stack
+    /// resolve along the way and any choices are made according to `policy`, and triggered
+    /// abilities which needed choices are recorded in [Turn::auto_resolved]. The game always
+    /// advances at least one step, so calling this during the requested phase advances to that
+    /// phase of the player's next turn.
+    pub fn advance_until(
+        db: &mut Database,
+        phase: Phase,
+        player: Owner,
+        policy: AutoPolicy,
+    ) -> anyhow::Result<()> {
+        for _ in 0..MAX_ADVANCE_STEPS {
+            db.turn.passed = db.turn.turn_order.len();
+            let resolving_trigger = db.stack.top_trigger_source();
+            let mut pending = Self::step(db);
+            let choices = policy.resolve(db, &mut pending)?;
+            if let Some(source) = resolving_trigger.filter(|_| choices > 0) {
+                db.turn.auto_resolved.push(AutoResolved { source, choices });
+            }
+
+            let mut pending = Battlefields::check_sba(db);
+            policy.resolve(db, &mut pending)?;
+
+            if db.turn.phase == phase && db.turn.active_player() == player && db.stack.is_empty() {
+                return Ok(());
+            }
+        }
+
+        bail!(
+            "Did not reach {} for {} within {} steps",
+            phase.as_ref(),
+            db.all_players[player].name,
+            MAX_ADVANCE_STEPS
+        )
+    }
+
     pub fn can_cast(db: &Database, card: CardId) -> bool {
+        if !card.is_land(db) && card.cast_prevented(db) {
+            return false;
+        }
+
         let instant_or_flash =
             card.types_intersect(db, &TypeSet::from([Type::INSTANT])) || card.has_flash(db);
         // TODO teferi like effects.
@@ -478,8 +631,25 @@ impl Turn {
         self.turn_order[self.active_player]
     }
 
+    /// The player whose turn comes after the active player's.
+    pub(crate) fn next_player(&self) -> Owner {
+        self.turn_order[(self.active_player + 1) % self.turn_order.len()]
+    }
+
+    /// The triggers [Turn::advance_until] has resolved automatically since they were last
+    /// cleared, oldest first.
+    pub fn auto_resolved(&self) -> &[AutoResolved] {
+        &self.auto_resolved
+    }
+
+    pub fn clear_auto_resolved(&mut self) {
+        self.auto_resolved.clear();
+    }
+
+    /// Whether every player has passed in succession since the last action, so [Turn::step] will
+    /// resolve the top of the stack or move on to the next phase.
     pub fn passed_full_priority_round(&self) -> bool {
-        self.passed == 0
+        self.passed >= self.turn_order.len()
     }
 
     pub fn turns_per_round(&self) -> usize {
@@ -5,7 +5,10 @@ use indexmap::IndexSet;
 use protobuf::Enum;
 
 use crate::protogen::{
+    cost::AbilityCost,
+    effects::{gain_mana, GainMana, GainManaAbility},
     empty::Empty,
+    mana::Mana,
     types::{Subtype, Type},
 };
 
@@ -100,6 +103,52 @@ impl<const C: usize> From<[Subtype; C]> for SubtypeSet {
 }
 
 impl Subtype {
+    /// The mana a land with this basic land type can tap for.
+    pub(crate) fn basic_land_mana(&self) -> Option<Mana> {
+        match self {
+            Subtype::PLAINS => Some(Mana::WHITE),
+            Subtype::ISLAND => Some(Mana::BLUE),
+            Subtype::SWAMP => Some(Mana::BLACK),
+            Subtype::MOUNTAIN => Some(Mana::RED),
+            Subtype::FOREST => Some(Mana::GREEN),
+            _ => None,
+        }
+    }
+
+    /// The mana ability a land has for having this basic land type, whether the type was printed
+    /// or gained from a modifier, so land cards don't need to list it.
+    pub(crate) fn intrinsic_mana_ability(&self) -> Option<GainManaAbility> {
+        let mana = self.basic_land_mana()?;
+        let symbol = match mana {
+            Mana::WHITE => "W",
+            Mana::BLUE => "U",
+            Mana::BLACK => "B",
+            Mana::RED => "R",
+            Mana::GREEN => "G",
+            Mana::COLORLESS => "C",
+        };
+
+        Some(GainManaAbility {
+            cost: protobuf::MessageField::some(AbilityCost {
+                tap: true,
+                ..Default::default()
+            }),
+            effects: vec![GainMana {
+                gain: Some(
+                    gain_mana::Specific {
+                        gain: vec![protobuf::EnumOrUnknown::new(mana)],
+                        ..Default::default()
+                    }
+                    .into(),
+                ),
+                ..Default::default()
+            }
+            .into()],
+            oracle_text: format!("{{T}}: Add {{{}}}.", symbol),
+            ..Default::default()
+        })
+    }
+
     pub(crate) fn is_creature_type(&self) -> bool {
         matches!(
             self,